@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests;
+
+/// Encodes an opaque ABGR16161616F image as an uncompressed scanline OpenEXR file.
+///
+/// The alpha channel is dropped. The R, G, and B channels are copied without conversion.
+pub fn abgr16161616f_encode_exr(bytes: &[u8], width: u32, height: u32, stride: u32) -> Vec<u8> {
+    const HALF: i32 = 1;
+
+    fn attr(res: &mut Vec<u8>, name: &str, ty: &str, value: &[u8]) {
+        res.extend_from_slice(name.as_bytes());
+        res.push(0);
+        res.extend_from_slice(ty.as_bytes());
+        res.push(0);
+        res.extend_from_slice(&(value.len() as i32).to_le_bytes());
+        res.extend_from_slice(value);
+    }
+
+    let mut res = vec![];
+    res.extend_from_slice(&[0x76, 0x2f, 0x31, 0x01]);
+    res.extend_from_slice(&2u32.to_le_bytes());
+    let mut channels = vec![];
+    for name in ["B", "G", "R"] {
+        channels.extend_from_slice(name.as_bytes());
+        channels.push(0);
+        channels.extend_from_slice(&HALF.to_le_bytes());
+        channels.extend_from_slice(&[0; 4]);
+        channels.extend_from_slice(&1i32.to_le_bytes());
+        channels.extend_from_slice(&1i32.to_le_bytes());
+    }
+    channels.push(0);
+    attr(&mut res, "channels", "chlist", &channels);
+    attr(&mut res, "compression", "compression", &[0]);
+    let mut window = vec![];
+    for v in [0, 0, width as i32 - 1, height as i32 - 1] {
+        window.extend_from_slice(&v.to_le_bytes());
+    }
+    attr(&mut res, "dataWindow", "box2i", &window);
+    attr(&mut res, "displayWindow", "box2i", &window);
+    attr(&mut res, "lineOrder", "lineOrder", &[0]);
+    attr(&mut res, "pixelAspectRatio", "float", &1.0f32.to_le_bytes());
+    attr(&mut res, "screenWindowCenter", "v2f", &[0; 8]);
+    attr(
+        &mut res,
+        "screenWindowWidth",
+        "float",
+        &1.0f32.to_le_bytes(),
+    );
+    res.push(0);
+
+    let line_size = width as usize * 3 * 2;
+    let chunk_size = 8 + line_size;
+    let table_start = res.len();
+    let data_start = table_start + height as usize * 8;
+    for y in 0..height as usize {
+        let offset = (data_start + y * chunk_size) as u64;
+        res.extend_from_slice(&offset.to_le_bytes());
+    }
+    res.reserve(height as usize * chunk_size);
+    for y in 0..height as usize {
+        res.extend_from_slice(&(y as i32).to_le_bytes());
+        res.extend_from_slice(&(line_size as i32).to_le_bytes());
+        let line = &bytes[y * stride as usize..][..width as usize * 8];
+        for channel in [2, 1, 0] {
+            for pixel in line.chunks_exact(8) {
+                res.extend_from_slice(&pixel[channel * 2..channel * 2 + 2]);
+            }
+        }
+    }
+    res
+}
+
+/// Converts the bits of an IEEE 754 half-precision float to an `f32`.
+///
+/// The conversion is exact.
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let sign = (bits as u32 >> 15) << 31;
+    let exp = (bits as u32 >> 10) & 0x1f;
+    let mantissa = bits as u32 & 0x3ff;
+    let bits = match exp {
+        0 => {
+            let value = mantissa as f32 / (1 << 24) as f32;
+            return if sign != 0 { -value } else { value };
+        }
+        0x1f => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exp + 112) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(bits)
+}
@@ -0,0 +1,149 @@
+use {
+    crate::exr::{abgr16161616f_encode_exr, f16_to_f32},
+    std::collections::HashMap,
+};
+
+/// Converts an `f32` that is exactly representable as a half-precision float.
+fn f32_to_f16(v: f32) -> u16 {
+    let bits = v.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    if exp == 0xff {
+        return sign | 0x7c00 | (mantissa >> 13) as u16;
+    }
+    if exp == 0 {
+        return sign;
+    }
+    let exp = exp - 127 + 15;
+    if exp <= 0 {
+        return sign | ((mantissa | 0x80_0000) >> (14 - exp)) as u16;
+    }
+    sign | ((exp as u16) << 10) | (mantissa >> 13) as u16
+}
+
+#[test]
+fn f16_known_values() {
+    assert_eq!(f16_to_f32(0x0000), 0.0);
+    assert_eq!(f16_to_f32(0x8000), 0.0);
+    assert!(f16_to_f32(0x8000).is_sign_negative());
+    assert_eq!(f16_to_f32(0x3c00), 1.0);
+    assert_eq!(f16_to_f32(0x3800), 0.5);
+    assert_eq!(f16_to_f32(0xc000), -2.0);
+    assert_eq!(f16_to_f32(0x3555), 0.333_251_95);
+    assert_eq!(f16_to_f32(0x7bff), 65504.0);
+    assert_eq!(f16_to_f32(0x0400), 2.0f32.powi(-14));
+    assert_eq!(f16_to_f32(0x0001), 2.0f32.powi(-24));
+    assert_eq!(f16_to_f32(0x83ff), -1023.0 * 2.0f32.powi(-24));
+    assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+    assert_eq!(f16_to_f32(0xfc00), f32::NEG_INFINITY);
+    assert!(f16_to_f32(0x7e00).is_nan());
+}
+
+#[test]
+fn f16_round_trip() {
+    for bits in 0..=u16::MAX {
+        let v = f16_to_f32(bits);
+        if bits & 0x7c00 == 0x7c00 && bits & 0x3ff != 0 {
+            assert!(v.is_nan());
+        } else {
+            assert_eq!(f32_to_f16(v), bits, "{bits:#06x}");
+        }
+    }
+}
+
+fn read_str(data: &[u8], pos: &mut usize) -> String {
+    let len = data[*pos..].iter().position(|&b| b == 0).unwrap();
+    let s = String::from_utf8(data[*pos..*pos + len].to_vec()).unwrap();
+    *pos += len + 1;
+    s
+}
+
+fn read_u32(data: &[u8], pos: &mut usize) -> u32 {
+    let v = u32::from_le_bytes(data[*pos..*pos + 4].try_into().unwrap());
+    *pos += 4;
+    v
+}
+
+fn read_u64(data: &[u8], pos: &mut usize) -> u64 {
+    let v = u64::from_le_bytes(data[*pos..*pos + 8].try_into().unwrap());
+    *pos += 8;
+    v
+}
+
+#[test]
+fn exr_round_trip() {
+    const WIDTH: usize = 3;
+    const HEIGHT: usize = 2;
+    const STRIDE: usize = WIDTH * 8 + 8;
+    let pixel = |x: usize, y: usize| -> [u16; 4] {
+        let base = (y * WIDTH + x) as u16;
+        [0x3c00 + base, 0x4000 + base, 0x4400 + base, 0x3c00]
+    };
+    let mut bytes = vec![0xff; STRIDE * HEIGHT];
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            for (c, v) in pixel(x, y).into_iter().enumerate() {
+                let offset = y * STRIDE + x * 8 + c * 2;
+                bytes[offset..offset + 2].copy_from_slice(&v.to_le_bytes());
+            }
+        }
+    }
+
+    let exr = abgr16161616f_encode_exr(&bytes, WIDTH as _, HEIGHT as _, STRIDE as _);
+
+    let mut pos = 0;
+    assert_eq!(read_u32(&exr, &mut pos), 20000630);
+    assert_eq!(read_u32(&exr, &mut pos), 2);
+    let mut attributes = HashMap::new();
+    loop {
+        let name = read_str(&exr, &mut pos);
+        if name.is_empty() {
+            break;
+        }
+        let ty = read_str(&exr, &mut pos);
+        let len = read_u32(&exr, &mut pos) as usize;
+        attributes.insert(name, (ty, exr[pos..pos + len].to_vec()));
+        pos += len;
+    }
+    let (ty, channels) = &attributes["channels"];
+    assert_eq!(ty, "chlist");
+    let mut cpos = 0;
+    let mut names = vec![];
+    loop {
+        let name = read_str(channels, &mut cpos);
+        if name.is_empty() {
+            break;
+        }
+        assert_eq!(read_u32(channels, &mut cpos), 1);
+        cpos += 12;
+        names.push(name);
+    }
+    assert_eq!(names, ["B", "G", "R"]);
+    assert_eq!(attributes["compression"].1, [0]);
+    let window: Vec<_> = [0i32, 0, WIDTH as i32 - 1, HEIGHT as i32 - 1]
+        .iter()
+        .flat_map(|v| v.to_le_bytes())
+        .collect();
+    assert_eq!(attributes["dataWindow"].1, window);
+    assert_eq!(attributes["displayWindow"].1, window);
+
+    let offsets: Vec<_> = (0..HEIGHT).map(|_| read_u64(&exr, &mut pos)).collect();
+    assert_eq!(offsets[0] as usize, pos);
+    let line_size = WIDTH * 3 * 2;
+    for (y, &offset) in offsets.iter().enumerate() {
+        let mut pos = offset as usize;
+        assert_eq!(read_u32(&exr, &mut pos), y as u32);
+        assert_eq!(read_u32(&exr, &mut pos) as usize, line_size);
+        for channel in [2, 1, 0] {
+            for x in 0..WIDTH {
+                let v = u16::from_le_bytes([exr[pos], exr[pos + 1]]);
+                pos += 2;
+                assert_eq!(v, pixel(x, y)[channel]);
+            }
+        }
+        if y == HEIGHT - 1 {
+            assert_eq!(pos, exr.len());
+        }
+    }
+}
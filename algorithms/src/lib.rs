@@ -5,6 +5,7 @@
     clippy::needless_lifetimes
 )]
 
+pub mod exr;
 pub mod qoi;
 pub mod rect;
 mod windows;
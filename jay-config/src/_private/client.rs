@@ -7,7 +7,8 @@ use {
             GenericCriterionIpc, PollableId, VERSION, WindowCriterionIpc,
             WindowCriterionStringField, WireMode, bincode_ops,
             ipc::{
-                ClientMessage, InitMessage, Response, ServerFeature, ServerMessage, WorkspaceSource,
+                ClientMessage, InitMessage, Response, SeatZoom, ServerFeature, ServerMessage,
+                WorkspaceSource,
             },
             logging,
         },
@@ -15,7 +16,8 @@ use {
         client::{Client, ClientCriterion, ClientMatcher, MatchedClient},
         exec::Command,
         input::{
-            FocusFollowsMouseMode, InputDevice, LayerDirection, Seat, SwitchEvent, Timeline,
+            FocusFollowsMouseMode, HotCorner, InputDevice, LayerDirection, MouseButton, ScreenEdge,
+            Seat, SwitchEvent, TabletPadElement, TabletPadEvent, Timeline,
             acceleration::AccelProfile, capability::Capability, clickmethod::ClickMethod,
         },
         keyboard::{
@@ -24,6 +26,7 @@ use {
             syms::KeySym,
         },
         logging::LogLevel,
+        portal::{ColorScheme, Contrast},
         tasks::{JoinHandle, JoinSlot},
         theme::{Color, colors::Colorable, sized::Resizable},
         timer::Timer,
@@ -33,10 +36,10 @@ use {
             connector_type::{CON_UNKNOWN, ConnectorType},
         },
         window::{
-            ContentType, MatchedWindow, TileState, Window, WindowCriterion, WindowMatcher,
-            WindowType,
+            ContentType, FloatPlacement, FloatSnap, MatchedWindow, TileState, Window,
+            WindowCriterion, WindowMatcher, WindowType,
         },
-        workspace::WorkspaceDisplayOrder,
+        workspace::{WorkspaceDisplayOrder, WorkspaceLayout},
         xwayland::XScalingMode,
     },
     bincode::Options,
@@ -109,6 +112,10 @@ pub(crate) struct ConfigClient {
     on_del_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_idle: RefCell<Option<Callback>>,
     on_switch_event: RefCell<HashMap<InputDevice, Callback<SwitchEvent>>>,
+    on_tablet_pad_event:
+        RefCell<HashMap<(InputDevice, TabletPadElement), Callback<TabletPadEvent>>>,
+    on_key_sequence_cancelled: RefCell<HashMap<Seat, Box<dyn FnOnce()>>>,
+    on_hot_corner_trigger: RefCell<HashMap<HotCorner, Callback<(Seat, Connector)>>>,
     on_unload: Cell<Option<OnDrop<Box<dyn FnOnce()>>>>,
     bufs: RefCell<Vec<Vec<u8>>>,
     reload: Cell<bool>,
@@ -254,6 +261,9 @@ pub unsafe extern "C" fn init(
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
         on_switch_event: Default::default(),
+        on_tablet_pad_event: Default::default(),
+        on_key_sequence_cancelled: Default::default(),
+        on_hot_corner_trigger: Default::default(),
         on_unload: Default::default(),
         bufs: Default::default(),
         reload: Cell::new(false),
@@ -380,6 +390,79 @@ impl ConfigClient {
         });
     }
 
+    pub fn seat_mouse_keys_motion(&self, seat: Seat, direction: Direction, active: bool) {
+        self.send(&ClientMessage::SeatMouseKeysMotion {
+            seat,
+            direction,
+            active,
+        });
+    }
+
+    pub fn seat_mouse_keys_select_button(&self, seat: Seat, button: MouseButton) {
+        self.send(&ClientMessage::SeatMouseKeysSelectButton { seat, button });
+    }
+
+    pub fn seat_mouse_keys_click(&self, seat: Seat, double: bool) {
+        self.send(&ClientMessage::SeatMouseKeysClick { seat, double });
+    }
+
+    pub fn seat_mouse_keys_press(&self, seat: Seat, pressed: bool) {
+        self.send(&ClientMessage::SeatMouseKeysPress { seat, pressed });
+    }
+
+    pub fn seat_set_mouse_keys_acceleration(
+        &self,
+        seat: Seat,
+        initial_speed: f64,
+        max_speed: f64,
+        time_to_max: Duration,
+    ) {
+        self.send(&ClientMessage::SeatSetMouseKeysAcceleration {
+            seat,
+            initial_speed,
+            max_speed,
+            time_to_max,
+        });
+    }
+
+    pub fn seat_zoom(&self, seat: Seat, zoom: SeatZoom) {
+        self.send(&ClientMessage::SeatZoom { seat, zoom });
+    }
+
+    pub fn seat_set_zoom_step(&self, seat: Seat, step: f64) {
+        self.send(&ClientMessage::SeatSetZoomStep { seat, step });
+    }
+
+    pub fn seat_set_zoom_scroll_modifiers(&self, seat: Seat, mods: Option<Modifiers>) {
+        self.send(&ClientMessage::SeatSetZoomScrollModifiers { seat, mods });
+    }
+
+    pub fn seat_begin_key_sequence<F: FnOnce() + 'static>(
+        &self,
+        seat: Seat,
+        prefix: &str,
+        timeout: Duration,
+        on_cancel: F,
+    ) {
+        self.on_key_sequence_cancelled
+            .borrow_mut()
+            .insert(seat, Box::new(on_cancel));
+        self.send(&ClientMessage::SeatBeginKeySequence {
+            seat,
+            prefix,
+            timeout_ms: timeout.as_millis() as u64,
+        });
+    }
+
+    pub fn seat_end_key_sequence(&self, seat: Seat) {
+        self.on_key_sequence_cancelled.borrow_mut().remove(&seat);
+        self.send(&ClientMessage::SeatEndKeySequence { seat });
+    }
+
+    pub fn set_magnifier_in_screencasts(&self, show: bool) {
+        self.send(&ClientMessage::SetMagnifierInScreencasts { show });
+    }
+
     pub fn seat_focus_layer_rel(&self, seat: Seat, direction: LayerDirection) {
         self.send(&ClientMessage::SeatFocusLayerRel { seat, direction });
     }
@@ -396,6 +479,38 @@ impl ConfigClient {
         self.send(&ClientMessage::SeatMove { seat, direction });
     }
 
+    pub fn seat_swap(&self, seat: Seat, direction: Direction) {
+        self.send(&ClientMessage::SeatSwap { seat, direction });
+    }
+
+    pub fn seat_toggle_overview(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatToggleOverview { seat });
+    }
+
+    pub fn seat_set_overview_swipe_enabled(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SeatSetOverviewSwipeEnabled { seat, enabled });
+    }
+
+    pub fn seat_set_overview_swipe_fingers(&self, seat: Seat, fingers: u32) {
+        self.send(&ClientMessage::SeatSetOverviewSwipeFingers { seat, fingers });
+    }
+
+    pub fn seat_release_input_capture(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatReleaseInputCapture { seat });
+    }
+
+    pub fn seat_window_switcher(&self, seat: Seat, timeline: Timeline) {
+        self.send(&ClientMessage::SeatWindowSwitcher { seat, timeline });
+    }
+
+    pub fn seat_window_switcher_set_thumbnails(&self, seat: Seat, thumbnails: bool) {
+        self.send(&ClientMessage::SeatWindowSwitcherSetThumbnails { seat, thumbnails });
+    }
+
+    pub fn window_swap(&self, window: Window, other: Window) {
+        self.send(&ClientMessage::WindowSwap { window, other });
+    }
+
     pub fn window_move(&self, window: Window, direction: Direction) {
         self.send(&ClientMessage::WindowMove { window, direction });
     }
@@ -827,6 +942,92 @@ impl ConfigClient {
             .insert(input_device, cb(f));
     }
 
+    pub fn set_tablet_output_area(
+        &self,
+        device: InputDevice,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) {
+        self.send(&ClientMessage::SetTabletOutputArea {
+            device,
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    pub fn set_tablet_pressure_curve(&self, device: InputDevice, curve: [f64; 4]) {
+        self.send(&ClientMessage::SetTabletPressureCurve { device, curve });
+    }
+
+    pub fn set_tablet_tool_button_mapping(
+        &self,
+        device: InputDevice,
+        button: u32,
+        mouse_button: Option<MouseButton>,
+    ) {
+        self.send(&ClientMessage::SetTabletToolButtonMapping {
+            device,
+            button,
+            mouse_button,
+        });
+    }
+
+    pub fn bind_tablet_pad<F: FnMut(TabletPadEvent) + 'static>(
+        &self,
+        device: InputDevice,
+        element: TabletPadElement,
+        f: F,
+    ) {
+        self.on_tablet_pad_event
+            .borrow_mut()
+            .insert((device, element), cb(f));
+        self.send(&ClientMessage::SetTabletPadBinding {
+            device,
+            element,
+            bound: true,
+        });
+    }
+
+    pub fn unbind_tablet_pad(&self, device: InputDevice, element: TabletPadElement) {
+        self.on_tablet_pad_event
+            .borrow_mut()
+            .remove(&(device, element));
+        self.send(&ClientMessage::SetTabletPadBinding {
+            device,
+            element,
+            bound: false,
+        });
+    }
+
+    pub fn create_hot_corner(&self, edge: ScreenEdge, dwell: Duration, pressure: f64) -> HotCorner {
+        let res = self.send_with_response(&ClientMessage::CreateHotCorner {
+            edge,
+            dwell,
+            pressure,
+        });
+        get_response!(res, HotCorner(0), CreateHotCorner { corner });
+        corner
+    }
+
+    pub fn on_hot_corner_trigger<F: FnMut(Seat, Connector) + 'static>(
+        &self,
+        corner: HotCorner,
+        mut f: F,
+    ) {
+        self.on_hot_corner_trigger
+            .borrow_mut()
+            .insert(corner, cb(move |(seat, connector)| f(seat, connector)));
+    }
+
+    pub fn remove_hot_corner(&self, corner: HotCorner) {
+        self.on_hot_corner_trigger.borrow_mut().remove(&corner);
+        self.send(&ClientMessage::RemoveHotCorner { corner });
+    }
+
     pub fn set_double_click_interval(&self, usec: u64) {
         self.send(&ClientMessage::SetDoubleClickIntervalUsec { usec });
     }
@@ -992,6 +1193,78 @@ impl ConfigClient {
         self.send(&ClientMessage::SetWorkspaceDisplayOrder { order });
     }
 
+    pub fn set_default_workspace_layout(&self, layout: WorkspaceLayout) {
+        self.send(&ClientMessage::SetDefaultWorkspaceLayout { layout });
+    }
+
+    pub fn set_workspace_layout(&self, workspace: Workspace, layout: WorkspaceLayout) {
+        self.send(&ClientMessage::SetWorkspaceLayout { workspace, layout });
+    }
+
+    pub fn get_workspace_layout(&self, workspace: Workspace) -> WorkspaceLayout {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceLayout { workspace });
+        get_response!(res, WorkspaceLayout::Manual, GetWorkspaceLayout { layout });
+        layout
+    }
+
+    pub fn set_workspace_master_count(&self, workspace: Workspace, count: u32) {
+        self.send(&ClientMessage::SetWorkspaceMasterCount { workspace, count });
+    }
+
+    pub fn get_workspace_master_count(&self, workspace: Workspace) -> u32 {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceMasterCount { workspace });
+        get_response!(res, 1, GetWorkspaceMasterCount { count });
+        count
+    }
+
+    pub fn set_workspace_master_ratio(&self, workspace: Workspace, ratio: f64) {
+        self.send(&ClientMessage::SetWorkspaceMasterRatio { workspace, ratio });
+    }
+
+    pub fn get_workspace_master_ratio(&self, workspace: Workspace) -> f64 {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceMasterRatio { workspace });
+        get_response!(res, 0.5, GetWorkspaceMasterRatio { ratio });
+        ratio
+    }
+
+    pub fn seat_cycle_column_width(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatCycleColumnWidth { seat });
+    }
+
+    pub fn seat_consume_into_column(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatConsumeIntoColumn { seat });
+    }
+
+    pub fn seat_expel_from_column(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatExpelFromColumn { seat });
+    }
+
+    pub fn rename_workspace(&self, workspace: Workspace, name: &str) {
+        self.send(&ClientMessage::RenameWorkspace { workspace, name });
+    }
+
+    pub fn set_workspace_persistent(&self, workspace: Workspace, persistent: bool) {
+        self.send(&ClientMessage::SetWorkspacePersistent {
+            workspace,
+            persistent,
+        });
+    }
+
+    pub fn set_workspace_preferred_outputs(&self, workspace: Workspace, outputs: &[&str]) {
+        self.send(&ClientMessage::SetWorkspacePreferredOutputs {
+            workspace,
+            outputs: outputs.iter().map(|o| o.to_string()).collect(),
+        });
+    }
+
+    pub fn set_workspace_default_split(&self, workspace: Workspace, axis: Axis) {
+        self.send(&ClientMessage::SetWorkspaceDefaultSplit { workspace, axis });
+    }
+
+    pub fn set_workspace_gap(&self, workspace: Workspace, gap: i32) {
+        self.send(&ClientMessage::SetWorkspaceGap { workspace, gap });
+    }
+
     pub fn seat_create_mark(&self, seat: Seat, kc: Option<u32>) {
         self.send(&ClientMessage::SeatCreateMark { seat, kc });
     }
@@ -1000,6 +1273,14 @@ impl ConfigClient {
         self.send(&ClientMessage::SeatJumpToMark { seat, kc });
     }
 
+    pub fn seat_swap_with_mark(&self, seat: Seat, kc: Option<u32>) {
+        self.send(&ClientMessage::SeatSwapWithMark { seat, kc });
+    }
+
+    pub fn seat_move_to_mark(&self, seat: Seat, kc: Option<u32>) {
+        self.send(&ClientMessage::SeatMoveToMark { seat, kc });
+    }
+
     pub fn seat_copy_mark(&self, seat: Seat, src: u32, dst: u32) {
         self.send(&ClientMessage::SeatCopyMark { seat, src, dst });
     }
@@ -1018,6 +1299,42 @@ impl ConfigClient {
         self.send(&ClientMessage::SetSeatFloatPinned { seat, pinned });
     }
 
+    pub fn get_sticky(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetSeatFloatSticky { seat });
+        get_response!(res, false, GetFloatSticky { sticky });
+        sticky
+    }
+
+    pub fn set_sticky(&self, seat: Seat, sticky: bool) {
+        self.send(&ClientMessage::SetSeatFloatSticky { seat, sticky });
+    }
+
+    pub fn get_window_sticky(&self, window: Window) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetWindowFloatSticky { window });
+        get_response!(res, false, GetFloatSticky { sticky });
+        sticky
+    }
+
+    pub fn set_window_sticky(&self, window: Window, sticky: bool) {
+        self.send(&ClientMessage::SetWindowFloatSticky { window, sticky });
+    }
+
+    pub fn set_float_placement(&self, placement: FloatPlacement) {
+        self.send(&ClientMessage::SetFloatPlacement { placement });
+    }
+
+    pub fn set_float_snap_threshold(&self, threshold: i32) {
+        self.send(&ClientMessage::SetFloatSnapThreshold { threshold });
+    }
+
+    pub fn seat_snap_float(&self, seat: Seat, snap: FloatSnap) {
+        self.send(&ClientMessage::SeatSnapFloat { seat, snap });
+    }
+
+    pub fn window_snap_float(&self, window: Window, snap: FloatSnap) {
+        self.send(&ClientMessage::WindowSnapFloat { window, snap });
+    }
+
     pub fn get_window_pinned(&self, window: Window) -> bool {
         let res = self.send_with_response(&ClientMessage::GetWindowFloatPinned { window });
         get_response!(res, false, GetWindowFloatPinned { pinned });
@@ -1114,6 +1431,28 @@ impl ConfigClient {
         self.send(&ClientMessage::SetXScalingMode { mode })
     }
 
+    pub fn set_color_scheme(&self, scheme: ColorScheme) {
+        self.send(&ClientMessage::SetColorScheme { scheme })
+    }
+
+    pub fn color_scheme(&self) -> ColorScheme {
+        let res = self.send_with_response(&ClientMessage::GetColorScheme);
+        get_response!(res, ColorScheme::DEFAULT, GetColorScheme { scheme });
+        scheme
+    }
+
+    pub fn set_accent_color(&self, color: Option<Color>) {
+        self.send(&ClientMessage::SetAccentColor { color })
+    }
+
+    pub fn set_contrast(&self, contrast: Contrast) {
+        self.send(&ClientMessage::SetContrast { contrast })
+    }
+
+    pub fn set_reduced_motion(&self, reduced: bool) {
+        self.send(&ClientMessage::SetReducedMotion { reduced })
+    }
+
     pub fn set_vrr_mode(&self, connector: Option<Connector>, mode: VrrMode) {
         self.send(&ClientMessage::SetVrrMode { connector, mode })
     }
@@ -1782,6 +2121,17 @@ impl ConfigClient {
         });
     }
 
+    pub fn set_window_matcher_swallow(&self, matcher: WindowMatcher, swallow: bool) {
+        self.send(&ClientMessage::SetWindowMatcherSwallow { matcher, swallow });
+    }
+
+    pub fn set_window_matcher_swallowable(&self, matcher: WindowMatcher, swallowable: bool) {
+        self.send(&ClientMessage::SetWindowMatcherSwallowable {
+            matcher,
+            swallowable,
+        });
+    }
+
     pub fn set_window_matcher_initial_tile_state(
         &self,
         matcher: WindowMatcher,
@@ -1982,6 +2332,9 @@ impl ConfigClient {
             }
             ServerMessage::DelInputDevice { device } => {
                 self.on_switch_event.borrow_mut().remove(&device);
+                self.on_tablet_pad_event
+                    .borrow_mut()
+                    .retain(|(d, _), _| *d != device);
                 let handler = self.on_input_device_removed.borrow_mut().clone();
                 if let Some(handler) = handler {
                     run_cb("input device removed", &handler, device);
@@ -2076,6 +2429,36 @@ impl ConfigClient {
                     run_cb("switch event", &cb, event);
                 }
             }
+            ServerMessage::TabletPadEvent {
+                input_device,
+                element,
+                event,
+            } => {
+                let cb = self
+                    .on_tablet_pad_event
+                    .borrow()
+                    .get(&(input_device, element))
+                    .cloned();
+                if let Some(cb) = cb {
+                    run_cb("tablet pad event", &cb, event);
+                }
+            }
+            ServerMessage::HotCornerTriggered {
+                corner,
+                seat,
+                connector,
+            } => {
+                let cb = self.on_hot_corner_trigger.borrow().get(&corner).cloned();
+                if let Some(cb) = cb {
+                    run_cb("hot corner", &cb, (seat, connector));
+                }
+            }
+            ServerMessage::KeySequenceCancelled { seat } => {
+                let cb = self.on_key_sequence_cancelled.borrow_mut().remove(&seat);
+                if let Some(cb) = cb {
+                    ignore_panic("key sequence cancelled", cb);
+                }
+            }
             ServerMessage::ClientMatcherMatched { matcher, client } => {
                 let cb = {
                     let handlers = self.client_match_handlers.borrow();
@@ -4,19 +4,23 @@ use {
         Axis, Direction, PciId, Workspace,
         client::{Client, ClientMatcher},
         input::{
-            FocusFollowsMouseMode, InputDevice, LayerDirection, Seat, SwitchEvent, Timeline,
+            FocusFollowsMouseMode, HotCorner, InputDevice, LayerDirection, MouseButton, ScreenEdge,
+            Seat, SwitchEvent, TabletPadElement, TabletPadEvent, Timeline,
             acceleration::AccelProfile, capability::Capability, clickmethod::ClickMethod,
         },
         keyboard::{Keymap, mods::Modifiers, syms::KeySym},
         logging::LogLevel,
+        portal::{ColorScheme, Contrast},
         theme::{Color, colors::Colorable, sized::Resizable},
         timer::Timer,
         video::{
             BlendSpace, ColorSpace, Connector, DrmDevice, Eotf, Format, GfxApi, TearingMode,
             Transform, VrrMode, connector_type::ConnectorType,
         },
-        window::{ContentType, TileState, Window, WindowMatcher, WindowType},
-        workspace::WorkspaceDisplayOrder,
+        window::{
+            ContentType, FloatPlacement, FloatSnap, TileState, Window, WindowMatcher, WindowType,
+        },
+        workspace::{WorkspaceDisplayOrder, WorkspaceLayout},
         xwayland::XScalingMode,
     },
     serde::{Deserialize, Serialize},
@@ -111,6 +115,19 @@ pub enum ServerMessage {
         matcher: WindowMatcher,
         window: Window,
     },
+    TabletPadEvent {
+        input_device: InputDevice,
+        element: TabletPadElement,
+        event: TabletPadEvent,
+    },
+    KeySequenceCancelled {
+        seat: Seat,
+    },
+    HotCornerTriggered {
+        corner: HotCorner,
+        seat: Seat,
+        connector: Connector,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -768,6 +785,228 @@ pub enum ClientMessage<'a> {
         connector: Connector,
         blend_space: BlendSpace,
     },
+    SeatMouseKeysMotion {
+        seat: Seat,
+        direction: Direction,
+        active: bool,
+    },
+    SeatMouseKeysSelectButton {
+        seat: Seat,
+        button: MouseButton,
+    },
+    SeatMouseKeysClick {
+        seat: Seat,
+        double: bool,
+    },
+    SeatMouseKeysPress {
+        seat: Seat,
+        pressed: bool,
+    },
+    SeatSetMouseKeysAcceleration {
+        seat: Seat,
+        initial_speed: f64,
+        max_speed: f64,
+        time_to_max: Duration,
+    },
+    SeatZoom {
+        seat: Seat,
+        zoom: SeatZoom,
+    },
+    SeatSetZoomStep {
+        seat: Seat,
+        step: f64,
+    },
+    SeatSetZoomScrollModifiers {
+        seat: Seat,
+        mods: Option<Modifiers>,
+    },
+    SetMagnifierInScreencasts {
+        show: bool,
+    },
+    SetTabletOutputArea {
+        device: InputDevice,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    SetTabletPressureCurve {
+        device: InputDevice,
+        curve: [f64; 4],
+    },
+    SetTabletToolButtonMapping {
+        device: InputDevice,
+        button: u32,
+        mouse_button: Option<MouseButton>,
+    },
+    SetTabletPadBinding {
+        device: InputDevice,
+        element: TabletPadElement,
+        bound: bool,
+    },
+    SeatBeginKeySequence {
+        seat: Seat,
+        prefix: &'a str,
+        timeout_ms: u64,
+    },
+    SeatEndKeySequence {
+        seat: Seat,
+    },
+    CreateHotCorner {
+        edge: ScreenEdge,
+        dwell: Duration,
+        pressure: f64,
+    },
+    RemoveHotCorner {
+        corner: HotCorner,
+    },
+    SetDefaultWorkspaceLayout {
+        layout: WorkspaceLayout,
+    },
+    SetWorkspaceLayout {
+        workspace: Workspace,
+        layout: WorkspaceLayout,
+    },
+    GetWorkspaceLayout {
+        workspace: Workspace,
+    },
+    SetWorkspaceMasterCount {
+        workspace: Workspace,
+        count: u32,
+    },
+    GetWorkspaceMasterCount {
+        workspace: Workspace,
+    },
+    SetWorkspaceMasterRatio {
+        workspace: Workspace,
+        ratio: f64,
+    },
+    GetWorkspaceMasterRatio {
+        workspace: Workspace,
+    },
+    SeatCycleColumnWidth {
+        seat: Seat,
+    },
+    SeatConsumeIntoColumn {
+        seat: Seat,
+    },
+    SeatExpelFromColumn {
+        seat: Seat,
+    },
+    RenameWorkspace {
+        workspace: Workspace,
+        name: &'a str,
+    },
+    SetWorkspacePersistent {
+        workspace: Workspace,
+        persistent: bool,
+    },
+    SetWorkspacePreferredOutputs {
+        workspace: Workspace,
+        outputs: Vec<String>,
+    },
+    SetWorkspaceDefaultSplit {
+        workspace: Workspace,
+        axis: Axis,
+    },
+    SetWorkspaceGap {
+        workspace: Workspace,
+        gap: i32,
+    },
+    SeatSwap {
+        seat: Seat,
+        direction: Direction,
+    },
+    SeatSwapWithMark {
+        seat: Seat,
+        kc: Option<u32>,
+    },
+    SeatMoveToMark {
+        seat: Seat,
+        kc: Option<u32>,
+    },
+    WindowSwap {
+        window: Window,
+        other: Window,
+    },
+    SetWindowMatcherSwallow {
+        matcher: WindowMatcher,
+        swallow: bool,
+    },
+    SetWindowMatcherSwallowable {
+        matcher: WindowMatcher,
+        swallowable: bool,
+    },
+    SeatToggleOverview {
+        seat: Seat,
+    },
+    SeatWindowSwitcher {
+        seat: Seat,
+        timeline: Timeline,
+    },
+    SeatWindowSwitcherSetThumbnails {
+        seat: Seat,
+        thumbnails: bool,
+    },
+    GetSeatFloatSticky {
+        seat: Seat,
+    },
+    SetSeatFloatSticky {
+        seat: Seat,
+        sticky: bool,
+    },
+    GetWindowFloatSticky {
+        window: Window,
+    },
+    SetWindowFloatSticky {
+        window: Window,
+        sticky: bool,
+    },
+    SetFloatPlacement {
+        placement: FloatPlacement,
+    },
+    SetFloatSnapThreshold {
+        threshold: i32,
+    },
+    SeatSnapFloat {
+        seat: Seat,
+        snap: FloatSnap,
+    },
+    WindowSnapFloat {
+        window: Window,
+        snap: FloatSnap,
+    },
+    SetColorScheme {
+        scheme: ColorScheme,
+    },
+    GetColorScheme,
+    SetAccentColor {
+        color: Option<Color>,
+    },
+    SetContrast {
+        contrast: Contrast,
+    },
+    SetReducedMotion {
+        reduced: bool,
+    },
+    SeatSetOverviewSwipeEnabled {
+        seat: Seat,
+        enabled: bool,
+    },
+    SeatSetOverviewSwipeFingers {
+        seat: Seat,
+        fingers: u32,
+    },
+    SeatReleaseInputCapture {
+        seat: Seat,
+    },
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug)]
+pub enum SeatZoom {
+    In,
+    Out,
+    Reset,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -1000,6 +1239,24 @@ pub enum Response {
     GetShowBar {
         show: bool,
     },
+    CreateHotCorner {
+        corner: HotCorner,
+    },
+    GetWorkspaceLayout {
+        layout: WorkspaceLayout,
+    },
+    GetWorkspaceMasterCount {
+        count: u32,
+    },
+    GetWorkspaceMasterRatio {
+        ratio: f64,
+    },
+    GetFloatSticky {
+        sticky: bool,
+    },
+    GetColorScheme {
+        scheme: ColorScheme,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -6,12 +6,15 @@ pub mod clickmethod;
 
 use {
     crate::{
-        _private::{DEFAULT_SEAT_NAME, ipc::WorkspaceSource},
+        _private::{
+            DEFAULT_SEAT_NAME,
+            ipc::{SeatZoom, WorkspaceSource},
+        },
         Axis, Direction, ModifiedKeySym, Workspace,
         input::{acceleration::AccelProfile, capability::Capability, clickmethod::ClickMethod},
         keyboard::{Keymap, mods::Modifiers, syms::KeySym},
         video::Connector,
-        window::Window,
+        window::{FloatSnap, Window},
     },
     serde::{Deserialize, Serialize},
     std::time::Duration,
@@ -180,6 +183,89 @@ impl InputDevice {
     pub fn remove_mapping(self) {
         get!().remove_input_mapping(self);
     }
+
+    /// Restricts a graphics tablet to a sub-area of its output.
+    ///
+    /// The values are fractions of the output the tablet is mapped to, or of the whole
+    /// desktop if the tablet is not mapped to an output. For example, `(0.5, 0.0, 0.5, 1.0)`
+    /// maps the tablet to the right half of the output.
+    ///
+    /// The default is `(0.0, 0.0, 1.0, 1.0)`.
+    pub fn set_tablet_output_area(self, x: f64, y: f64, width: f64, height: f64) {
+        get!().set_tablet_output_area(self, x, y, width, height);
+    }
+
+    /// Sets the pressure curve of the tools of a graphics tablet.
+    ///
+    /// The curve is a cubic bezier curve from `(0, 0)` to `(1, 1)` with the control points
+    /// `(x1, y1)` and `(x2, y2)`. All values must be between 0 and 1. The x axis is the
+    /// pressure reported by the hardware and the y axis is the pressure sent to
+    /// applications.
+    ///
+    /// The default is the linear curve `(0.0, 0.0, 1.0, 1.0)`.
+    pub fn set_tablet_pressure_curve(self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        get!().set_tablet_pressure_curve(self, [x1, y1, x2, y2]);
+    }
+
+    /// Makes a button of the tools of a graphics tablet emulate a mouse button.
+    ///
+    /// `button` is the evdev code of the tool button, e.g. `BTN_STYLUS` (`0x14b`) or
+    /// `BTN_STYLUS2` (`0x14c`). When the button is pressed, the pointer of the seat is
+    /// moved to the position of the tool and the mouse button is pressed instead. The
+    /// button event is then not sent to applications as a tablet event.
+    ///
+    /// If `mouse_button` is `None`, the mapping is removed.
+    pub fn set_tablet_tool_button_mapping(self, button: u32, mouse_button: Option<MouseButton>) {
+        get!().set_tablet_tool_button_mapping(self, button, mouse_button);
+    }
+
+    /// Binds an element of a tablet pad.
+    ///
+    /// Events of bound elements are no longer sent to applications. Instead, the callback
+    /// is invoked.
+    pub fn bind_tablet_pad<F: FnMut(TabletPadEvent) + 'static>(
+        self,
+        element: TabletPadElement,
+        f: F,
+    ) {
+        get!().bind_tablet_pad(self, element, f);
+    }
+
+    /// Unbinds an element of a tablet pad.
+    pub fn unbind_tablet_pad(self, element: TabletPadElement) {
+        get!().unbind_tablet_pad(self, element);
+    }
+}
+
+/// An element of a tablet pad.
+///
+/// The values are the indices of the elements as reported by libinput.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum TabletPadElement {
+    Button(u32),
+    Ring(u32),
+    Strip(u32),
+    Dial(u32),
+}
+
+/// An event generated by a bound element of a tablet pad.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum TabletPadEvent {
+    /// A button has been pressed or released.
+    Button { pressed: bool },
+    /// A ring has been rotated.
+    ///
+    /// Positive values are clockwise rotations.
+    Ring { degrees: f64 },
+    /// A finger has moved along a strip.
+    ///
+    /// The delta is a fraction of the length of the strip. Positive values are movements
+    /// downwards or to the right.
+    Strip { delta: f64 },
+    /// A dial has been rotated.
+    ///
+    /// A value of 120 is one logical detent. Positive values are clockwise rotations.
+    Dial { value120: i32 },
 }
 
 /// A direction in a timeline.
@@ -196,6 +282,14 @@ pub enum LayerDirection {
     Above,
 }
 
+/// A mouse button emulated by mouse keys.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
 /// A seat.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Seat(pub u64);
@@ -310,6 +404,28 @@ impl Seat {
         get!().seat_focus_history_set_same_workspace(self, same_workspace)
     }
 
+    /// Opens the window switcher or moves its selection.
+    ///
+    /// The window switcher shows the windows in the focus history as an on-screen list.
+    /// While the modifiers that were held when the switcher was opened remain pressed,
+    /// calling this function again moves the selection. Releasing any of them focuses the
+    /// selected window. Pressing escape closes the switcher without changing the focus.
+    ///
+    /// If no modifiers are held, the selected window is focused immediately.
+    ///
+    /// The switcher respects [`Seat::focus_history_set_only_visible`] and
+    /// [`Seat::focus_history_set_same_workspace`].
+    pub fn window_switcher(self, timeline: Timeline) {
+        get!().seat_window_switcher(self, timeline)
+    }
+
+    /// Configures whether the window switcher shows thumbnails of the windows.
+    ///
+    /// The default is `false`.
+    pub fn window_switcher_set_thumbnails(self, thumbnails: bool) {
+        get!().seat_window_switcher_set_thumbnails(self, thumbnails)
+    }
+
     /// Moves the keyboard focus of the seat to the layer above or below the current
     /// layer.
     pub fn focus_layer_rel(self, direction: LayerDirection) {
@@ -331,6 +447,14 @@ impl Seat {
         get!().seat_move(self, direction)
     }
 
+    /// Exchanges the focused window with the tiled window next to it in the specified
+    /// direction.
+    ///
+    /// Unlike [`Seat::move_`], this does not change the shape of the tree.
+    pub fn swap(self, direction: Direction) {
+        get!().seat_swap(self, direction)
+    }
+
     /// Sets the keymap of the seat.
     pub fn set_keymap(self, keymap: Keymap) {
         get!().seat_set_keymap(self, keymap)
@@ -394,6 +518,30 @@ impl Seat {
         get!().focus_seat_parent(self);
     }
 
+    /// Switches the width of the focused column to the next preset.
+    ///
+    /// The presets are 1/3, 1/2, and 2/3 of the output width.
+    ///
+    /// This only has an effect on workspaces using the `WorkspaceLayout::Scrolling` layout.
+    pub fn cycle_column_width(self) {
+        get!().seat_cycle_column_width(self);
+    }
+
+    /// Moves the first window of the column to the right of the focused column to the
+    /// bottom of the focused column.
+    ///
+    /// This only has an effect on workspaces using the `WorkspaceLayout::Scrolling` layout.
+    pub fn consume_into_column(self) {
+        get!().seat_consume_into_column(self);
+    }
+
+    /// Moves the focused window out of its column into a new column to the right.
+    ///
+    /// This only has an effect on workspaces using the `WorkspaceLayout::Scrolling` layout.
+    pub fn expel_from_column(self) {
+        get!().seat_expel_from_column(self);
+    }
+
     /// Requests the currently focused window to be closed.
     pub fn close(self) {
         get!().seat_close(self);
@@ -543,6 +691,31 @@ impl Seat {
         self.set_float_pinned(!self.float_pinned());
     }
 
+    /// Gets whether the currently focused window is sticky.
+    ///
+    /// A sticky floating window is pinned and additionally follows the pointer to
+    /// whichever output it moves to.
+    pub fn sticky(self) -> bool {
+        get!(false).get_sticky(self)
+    }
+
+    /// Sets whether the currently focused window is sticky.
+    pub fn set_sticky(self, sticky: bool) {
+        get!().set_sticky(self, sticky);
+    }
+
+    /// Toggles whether the currently focused window is sticky.
+    pub fn toggle_sticky(self) {
+        self.set_sticky(!self.sticky());
+    }
+
+    /// Moves and resizes the currently focused window to cover a region of its output.
+    ///
+    /// This has no effect if the window is not floating.
+    pub fn snap_float(self, snap: FloatSnap) {
+        get!().seat_snap_float(self, snap);
+    }
+
     /// Returns the focused window.
     ///
     /// If no window is focused, [`Window::exists`] returns false.
@@ -587,6 +760,22 @@ impl Seat {
         get!().seat_jump_to_mark(self, kc);
     }
 
+    /// Exchanges the focused window with the window identified by a mark.
+    ///
+    /// Both windows must be tiled. See [`Seat::create_mark`] for information about the
+    /// `kc` parameter.
+    pub fn swap_with_mark(self, kc: Option<u32>) {
+        get!().seat_swap_with_mark(self, kc);
+    }
+
+    /// Moves the focused window next to the window identified by a mark.
+    ///
+    /// Both windows must be tiled. See [`Seat::create_mark`] for information about the
+    /// `kc` parameter.
+    pub fn move_to_mark(self, kc: Option<u32>) {
+        get!().seat_move_to_mark(self, kc);
+    }
+
     /// Copies a mark from one keycode to another.
     ///
     /// If the `src` keycode identifies a mark before this function is called, the `dst`
@@ -594,6 +783,164 @@ impl Seat {
     pub fn copy_mark(self, src: u32, dst: u32) {
         get!().seat_copy_mark(self, src, dst);
     }
+
+    /// Starts moving the pointer in a direction via mouse keys.
+    ///
+    /// The pointer keeps moving, and accelerating, until
+    /// [`Seat::mouse_keys_stop_motion`] is called for the same direction. Calling this
+    /// function for two orthogonal directions moves the pointer diagonally.
+    ///
+    /// This is usually called from a key-press shortcut that uses [`Seat::latch`] to
+    /// stop the motion when the key is released.
+    pub fn mouse_keys_start_motion(self, direction: Direction) {
+        get!().seat_mouse_keys_motion(self, direction, true);
+    }
+
+    /// Stops moving the pointer in a direction via mouse keys.
+    pub fn mouse_keys_stop_motion(self, direction: Direction) {
+        get!().seat_mouse_keys_motion(self, direction, false);
+    }
+
+    /// Sets the button used by [`Seat::mouse_keys_click`], [`Seat::mouse_keys_press`],
+    /// and [`Seat::mouse_keys_release`].
+    ///
+    /// The default is [`MouseButton::Left`].
+    pub fn mouse_keys_select_button(self, button: MouseButton) {
+        get!().seat_mouse_keys_select_button(self, button);
+    }
+
+    /// Emulates a click of the selected mouse keys button.
+    ///
+    /// If `double` is `true`, the button is clicked twice.
+    pub fn mouse_keys_click(self, double: bool) {
+        get!().seat_mouse_keys_click(self, double);
+    }
+
+    /// Emulates a press of the selected mouse keys button.
+    ///
+    /// The button stays pressed until [`Seat::mouse_keys_release`] is called. This can
+    /// be used to drag windows or select text.
+    pub fn mouse_keys_press(self) {
+        get!().seat_mouse_keys_press(self, true);
+    }
+
+    /// Releases all buttons pressed via [`Seat::mouse_keys_press`].
+    pub fn mouse_keys_release(self) {
+        get!().seat_mouse_keys_press(self, false);
+    }
+
+    /// Configures the acceleration of mouse keys motion.
+    ///
+    /// `initial_speed` and `max_speed` are in logical pixels per second. The speed grows
+    /// linearly from `initial_speed` to `max_speed` over `time_to_max`.
+    ///
+    /// The defaults are 100, 1200, and 1 second respectively.
+    pub fn set_mouse_keys_acceleration(
+        self,
+        initial_speed: f64,
+        max_speed: f64,
+        time_to_max: Duration,
+    ) {
+        get!().seat_set_mouse_keys_acceleration(self, initial_speed, max_speed, time_to_max);
+    }
+
+    /// Increases the magnification of the output containing the cursor.
+    ///
+    /// The output is magnified around the cursor position. Each call multiplies the
+    /// zoom factor by the step configured with [`Seat::set_zoom_step`].
+    pub fn zoom_in(self) {
+        get!().seat_zoom(self, SeatZoom::In);
+    }
+
+    /// Decreases the magnification of the output containing the cursor.
+    pub fn zoom_out(self) {
+        get!().seat_zoom(self, SeatZoom::Out);
+    }
+
+    /// Disables the magnification of the output containing the cursor.
+    pub fn zoom_reset(self) {
+        get!().seat_zoom(self, SeatZoom::Reset);
+    }
+
+    /// Opens or closes the overview of the output containing the cursor.
+    ///
+    /// The overview shows all workspaces of the output side by side. Clicking a window
+    /// or workspace switches to it. Windows can be dragged between workspaces. The arrow
+    /// keys select a window, enter switches to it, and escape closes the overview.
+    pub fn toggle_overview(self) {
+        get!().seat_toggle_overview(self);
+    }
+
+    /// Sets whether swiping vertically on a touchpad opens and closes the overview.
+    ///
+    /// Swiping up opens the overview and swiping down closes it. Swipes with this many
+    /// fingers are then not sent to clients. See also
+    /// [`Seat::set_overview_swipe_fingers`].
+    ///
+    /// The default is `true`.
+    pub fn set_overview_swipe_enabled(self, enabled: bool) {
+        get!().seat_set_overview_swipe_enabled(self, enabled);
+    }
+
+    /// Sets the number of fingers of the touchpad swipe that opens and closes the
+    /// overview.
+    ///
+    /// The number must be at least 3. The default is 4.
+    pub fn set_overview_swipe_fingers(self, fingers: u32) {
+        get!().seat_set_overview_swipe_fingers(self, fingers);
+    }
+
+    /// Ends an input capture of this seat.
+    ///
+    /// While an application such as Input Leap captures the input of the seat, keyboard
+    /// and pointer input is sent to that application instead of the focused window.
+    /// Shortcuts are still processed, so this function can be bound to a shortcut to take
+    /// the input back if the application does not release it.
+    pub fn release_input_capture(self) {
+        get!().seat_release_input_capture(self);
+    }
+
+    /// Sets the factor by which [`Seat::zoom_in`] and [`Seat::zoom_out`] change the
+    /// magnification.
+    ///
+    /// The step must be larger than 1. The default is 1.25.
+    pub fn set_zoom_step(self, step: f64) {
+        get!().seat_set_zoom_step(self, step);
+    }
+
+    /// Sets the modifiers that turn vertical scrolling into zooming.
+    ///
+    /// While exactly these modifiers are pressed, scrolling up zooms in and scrolling
+    /// down zooms out. Scroll events consumed this way are not sent to clients.
+    ///
+    /// If this is `None`, which is the default, scrolling never zooms.
+    pub fn set_zoom_scroll_modifiers(self, mods: Option<Modifiers>) {
+        get!().seat_set_zoom_scroll_modifiers(self, mods);
+    }
+
+    /// Starts or continues a key sequence.
+    ///
+    /// While a key sequence is pending, `prefix` is shown in the bar and key presses that
+    /// do not trigger a shortcut are discarded. Such a key press cancels the sequence.
+    /// The sequence is also cancelled if no shortcut is triggered within `timeout`. If
+    /// `timeout` is zero, the sequence does not time out.
+    ///
+    /// `on_cancel` is invoked if the compositor cancels the sequence. It is not invoked
+    /// if the sequence is ended with [`Seat::end_key_sequence`] or replaced by another
+    /// call to this function.
+    pub fn begin_key_sequence<F: FnOnce() + 'static>(
+        self,
+        prefix: &str,
+        timeout: Duration,
+        on_cancel: F,
+    ) {
+        get!().seat_begin_key_sequence(self, prefix, timeout, on_cancel);
+    }
+
+    /// Ends the pending key sequence.
+    pub fn end_key_sequence(self) {
+        get!().seat_end_key_sequence(self);
+    }
 }
 
 /// A focus-follows-mouse mode.
@@ -715,3 +1062,49 @@ pub enum SwitchEvent {
 pub fn set_libei_socket_enabled(enabled: bool) {
     get!().set_ei_socket_enabled(enabled);
 }
+
+/// A corner or edge of an output.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum ScreenEdge {
+    TopLeft,
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+}
+
+/// A hot corner.
+///
+/// Hot corners are created with [`create_hot_corner`].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct HotCorner(pub u64);
+
+/// Creates a hot corner.
+///
+/// The hot corner triggers when the pointer stays in `edge` of an output for `dwell`
+/// or when the pointer is pushed against the edge by a total of `pressure` logical
+/// pixels. Only the outer edges of the desktop are considered. If `pressure` is `0`,
+/// the hot corner does not trigger on pressure.
+///
+/// A hot corner triggers at most once until the pointer leaves the edge.
+pub fn create_hot_corner(edge: ScreenEdge, dwell: Duration, pressure: f64) -> HotCorner {
+    get!(HotCorner(0)).create_hot_corner(edge, dwell, pressure)
+}
+
+impl HotCorner {
+    /// Sets the function to be executed when the hot corner triggers.
+    ///
+    /// The function receives the seat whose pointer triggered the hot corner and the
+    /// connector of the output.
+    pub fn on_trigger<F: FnMut(Seat, Connector) + 'static>(self, f: F) {
+        get!().on_hot_corner_trigger(self, f);
+    }
+
+    /// Removes the hot corner.
+    pub fn remove(self) {
+        get!().remove_hot_corner(self);
+    }
+}
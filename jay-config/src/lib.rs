@@ -48,7 +48,11 @@ use crate::input::Seat;
 
 use {
     crate::{
-        _private::ipc::WorkspaceSource, keyboard::ModifiedKeySym, video::Connector, window::Window,
+        _private::ipc::WorkspaceSource,
+        keyboard::ModifiedKeySym,
+        video::Connector,
+        window::{FloatPlacement, Window},
+        workspace::WorkspaceLayout,
     },
     serde::{Deserialize, Serialize},
     std::{
@@ -68,6 +72,7 @@ pub mod input;
 pub mod io;
 pub mod keyboard;
 pub mod logging;
+pub mod portal;
 pub mod status;
 pub mod tasks;
 pub mod theme;
@@ -186,6 +191,99 @@ impl Workspace {
     pub fn window(self) -> Window {
         get!(Window(0)).get_workspace_window(self)
     }
+
+    /// Renames this workspace.
+    ///
+    /// The windows, output, and capture settings of the workspace are preserved. After
+    /// this call, this object refers to the workspace under its new name.
+    ///
+    /// This has no effect if the workspace does not exist or if another workspace with
+    /// the new name exists.
+    pub fn rename(self, name: &str) {
+        get!().rename_workspace(self, name);
+    }
+
+    /// Sets the layout of this workspace.
+    ///
+    /// The layout is remembered and applied whenever a workspace with this name is
+    /// created.
+    ///
+    /// When switching from an automatic layout back to `WorkspaceLayout::Manual`, the
+    /// container tree created by the automatic layout is kept.
+    pub fn set_layout(self, layout: WorkspaceLayout) {
+        get!().set_workspace_layout(self, layout);
+    }
+
+    /// Returns the layout of this workspace.
+    pub fn layout(self) -> WorkspaceLayout {
+        get!(WorkspaceLayout::Manual).get_workspace_layout(self)
+    }
+
+    /// Sets the number of windows in the master area of the `MasterStack` layout.
+    ///
+    /// The value is remembered if the workspace is destroyed and re-created.
+    ///
+    /// The default is `1`.
+    pub fn set_master_count(self, count: u32) {
+        get!().set_workspace_master_count(self, count);
+    }
+
+    /// Returns the number of windows in the master area of the `MasterStack` layout.
+    pub fn master_count(self) -> u32 {
+        get!(1).get_workspace_master_count(self)
+    }
+
+    /// Sets the share of the workspace width occupied by the master area of the
+    /// `MasterStack` layout.
+    ///
+    /// The value is clamped to the range `[0.1, 0.9]`. The value is remembered if the
+    /// workspace is destroyed and re-created.
+    ///
+    /// The default is `0.5`.
+    pub fn set_master_ratio(self, ratio: f64) {
+        get!().set_workspace_master_ratio(self, ratio);
+    }
+
+    /// Returns the share of the workspace width occupied by the master area of the
+    /// `MasterStack` layout.
+    pub fn master_ratio(self) -> f64 {
+        get!(0.5).get_workspace_master_ratio(self)
+    }
+
+    /// Sets whether this workspace is persistent.
+    ///
+    /// Persistent workspaces are created as soon as an output is available and are not
+    /// destroyed when they become empty.
+    ///
+    /// The default is `false`.
+    pub fn set_persistent(self, persistent: bool) {
+        get!().set_workspace_persistent(self, persistent);
+    }
+
+    /// Sets the outputs this workspace prefers, identified by their connector names, e.g.
+    /// `eDP-1`.
+    ///
+    /// The workspace is placed on the first of these outputs that is connected. If the
+    /// output is disconnected, the workspace moves to the next connected output in the
+    /// list and moves back once a more preferred output is connected again.
+    pub fn set_preferred_outputs(self, outputs: &[&str]) {
+        get!().set_workspace_preferred_outputs(self, outputs);
+    }
+
+    /// Sets the split of containers created in this workspace when the first window is
+    /// mapped.
+    ///
+    /// The default is `Axis::Horizontal`.
+    pub fn set_default_split(self, axis: Axis) {
+        get!().set_workspace_default_split(self, axis);
+    }
+
+    /// Sets the gap between the edges of the workspace and the tiled windows.
+    ///
+    /// The default is `0`.
+    pub fn set_gap(self, gap: i32) {
+        get!().set_workspace_gap(self, gap);
+    }
 }
 
 /// Returns the workspace with the given name.
@@ -318,6 +416,25 @@ pub fn set_show_float_pin_icon(show: bool) {
     get!().set_show_float_pin_icon(show);
 }
 
+/// Sets how newly mapped floating windows are placed.
+///
+/// This only affects windows that don't request a position themselves.
+///
+/// The default is `FloatPlacement::Center`.
+pub fn set_float_placement(placement: FloatPlacement) {
+    get!().set_float_placement(placement);
+}
+
+/// Sets the distance in pixels at which dragged floating windows snap to the edges of
+/// the output and of other floating windows.
+///
+/// Setting this to `0` disables snapping.
+///
+/// The default is `0`.
+pub fn set_float_snap_threshold(threshold: i32) {
+    get!().set_float_snap_threshold(threshold);
+}
+
 /// Sets whether the built-in bar is shown.
 ///
 /// The default is `true`.
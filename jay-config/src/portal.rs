@@ -0,0 +1,69 @@
+//! Tools for configuring the settings exposed by the desktop portal.
+//!
+//! These settings are exposed to applications via the `org.freedesktop.appearance`
+//! namespace of the `org.freedesktop.portal.Settings` interface.
+
+use {
+    crate::theme::Color,
+    serde::{Deserialize, Serialize},
+};
+
+/// The color scheme preferred by the user.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct ColorScheme(pub u32);
+
+impl ColorScheme {
+    /// The user has no preference.
+    pub const DEFAULT: Self = Self(0);
+    /// The user prefers a dark appearance.
+    pub const PREFER_DARK: Self = Self(1);
+    /// The user prefers a light appearance.
+    pub const PREFER_LIGHT: Self = Self(2);
+}
+
+/// The contrast preferred by the user.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub struct Contrast(pub u32);
+
+impl Contrast {
+    /// The user has no preference.
+    pub const NORMAL: Self = Self(0);
+    /// The user prefers higher contrast.
+    pub const HIGH: Self = Self(1);
+}
+
+/// Sets the color scheme preferred by the user.
+///
+/// The default is `ColorScheme::DEFAULT`.
+pub fn set_color_scheme(scheme: ColorScheme) {
+    get!().set_color_scheme(scheme)
+}
+
+/// Returns the color scheme preferred by the user.
+pub fn color_scheme() -> ColorScheme {
+    get!(ColorScheme::DEFAULT).color_scheme()
+}
+
+/// Sets the accent color preferred by the user.
+///
+/// The alpha component of the color is ignored. If this is `None`, applications use
+/// their own accent color.
+///
+/// The default is `None`.
+pub fn set_accent_color(color: Option<Color>) {
+    get!().set_accent_color(color)
+}
+
+/// Sets the contrast preferred by the user.
+///
+/// The default is `Contrast::NORMAL`.
+pub fn set_contrast(contrast: Contrast) {
+    get!().set_contrast(contrast)
+}
+
+/// Sets whether the user prefers reduced motion.
+///
+/// The default is `false`.
+pub fn set_reduced_motion(reduced: bool) {
+    get!().set_reduced_motion(reduced)
+}
@@ -595,6 +595,14 @@ pub fn set_direct_scanout_enabled(enabled: bool) {
     get!().set_direct_scanout_enabled(None, enabled);
 }
 
+/// Sets whether screencasts and screenshots show the magnified or the regular contents
+/// of magnified outputs.
+///
+/// The default is `true`.
+pub fn set_magnifier_in_screencasts(show: bool) {
+    get!().set_magnifier_in_screencasts(show);
+}
+
 /// A transformation.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
 pub enum Transform {
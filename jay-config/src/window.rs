@@ -66,6 +66,43 @@ pub enum TileState {
     Floating,
 }
 
+/// The placement of newly mapped floating windows.
+///
+/// This is used when the client does not request a position for the window.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+pub enum FloatPlacement {
+    /// The window is centered on the output.
+    #[default]
+    Center,
+    /// The window is centered under the cursor.
+    UnderCursor,
+    /// The window is placed where it overlaps the fewest other floating windows.
+    Smart,
+    /// The window is placed below and to the right of the previous floating window.
+    Cascade,
+}
+
+/// A region of the output that a floating window can be snapped to.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum FloatSnap {
+    /// The left half of the output.
+    Left,
+    /// The right half of the output.
+    Right,
+    /// The top half of the output.
+    Top,
+    /// The bottom half of the output.
+    Bottom,
+    /// The top-left quarter of the output.
+    TopLeft,
+    /// The top-right quarter of the output.
+    TopRight,
+    /// The bottom-left quarter of the output.
+    BottomLeft,
+    /// The bottom-right quarter of the output.
+    BottomRight,
+}
+
 /// A window created by a client.
 ///
 /// This is the same as `XDG_TOPLEVEL | X_WINDOW`.
@@ -172,6 +209,13 @@ impl Window {
         get!().create_window_split(self, axis);
     }
 
+    /// Exchanges the positions of this window and another window.
+    ///
+    /// Both windows must be tiled. The shape of the tree is not changed.
+    pub fn swap(self, other: Window) {
+        get!().window_swap(self, other);
+    }
+
     /// Requests the window to be closed.
     pub fn close(self) {
         get!().close_window(self);
@@ -236,6 +280,34 @@ impl Window {
     pub fn toggle_float_pinned(self) {
         self.set_float_pinned(!self.float_pinned());
     }
+
+    /// Gets whether the window is sticky.
+    ///
+    /// A sticky floating window is pinned and additionally follows the pointer to
+    /// whichever output it moves to.
+    pub fn sticky(self) -> bool {
+        get!(false).get_window_sticky(self)
+    }
+
+    /// Sets whether the window is sticky.
+    ///
+    /// Making a window sticky also pins it. Unpinning a sticky window makes it
+    /// non-sticky.
+    pub fn set_sticky(self, sticky: bool) {
+        get!().set_window_sticky(self, sticky);
+    }
+
+    /// Toggles whether the window is sticky.
+    pub fn toggle_sticky(self) {
+        self.set_sticky(!self.sticky());
+    }
+
+    /// Moves and resizes the window to cover a region of its output.
+    ///
+    /// This has no effect if the window is not floating.
+    pub fn snap_float(self, snap: FloatSnap) {
+        get!().window_snap_float(self, snap);
+    }
 }
 
 /// A window matcher.
@@ -349,6 +421,29 @@ impl WindowCriterion<'_> {
     pub fn set_initial_tile_state(self, tile_state: TileState) {
         self.to_matcher().set_initial_tile_state(tile_state);
     }
+
+    /// Sets whether windows that match this criterion swallow windows spawned by their
+    /// process.
+    ///
+    /// This is usually enabled for terminals. When a tiled window is mapped whose process
+    /// is a descendant of the process of such a window, the new window replaces the tile
+    /// of the swallowing window. The swallowing window is restored when the new window is
+    /// closed.
+    ///
+    /// This leaks the matcher.
+    pub fn set_swallow(self, swallow: bool) {
+        self.to_matcher().set_swallow(swallow);
+    }
+
+    /// Sets whether windows that match this criterion can be swallowed.
+    ///
+    /// If a window matches any criterion for which this is false, the window will not
+    /// replace the window of its parent process.
+    ///
+    /// This leaks the matcher.
+    pub fn set_swallowable(self, swallowable: bool) {
+        self.to_matcher().set_swallowable(swallowable);
+    }
 }
 
 impl WindowMatcher {
@@ -382,6 +477,25 @@ impl WindowMatcher {
     pub fn set_initial_tile_state(self, tile_state: TileState) {
         get!().set_window_matcher_initial_tile_state(self, tile_state);
     }
+
+    /// Sets whether windows that match this matcher swallow windows spawned by their
+    /// process.
+    ///
+    /// This is usually enabled for terminals. When a tiled window is mapped whose process
+    /// is a descendant of the process of such a window, the new window replaces the tile
+    /// of the swallowing window. The swallowing window is restored when the new window is
+    /// closed.
+    pub fn set_swallow(self, swallow: bool) {
+        get!().set_window_matcher_swallow(self, swallow);
+    }
+
+    /// Sets whether windows that match this matcher can be swallowed.
+    ///
+    /// If a window matches any matcher for which this is false, the window will not
+    /// replace the window of its parent process.
+    pub fn set_swallowable(self, swallowable: bool) {
+        get!().set_window_matcher_swallowable(self, swallowable);
+    }
 }
 
 impl MatchedWindow {
@@ -17,3 +17,38 @@ pub enum WorkspaceDisplayOrder {
 pub fn set_workspace_display_order(order: WorkspaceDisplayOrder) {
     get!().set_workspace_display_order(order);
 }
+
+/// The layout of the tiled windows of a workspace.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+pub enum WorkspaceLayout {
+    /// Windows are arranged manually in a tree of containers.
+    #[default]
+    Manual,
+    /// The first windows are shown in a master area on the left and the remaining windows
+    /// are stacked on the right.
+    ///
+    /// The number of windows in the master area and the share of the workspace it
+    /// occupies are configured with [`Workspace::set_master_count`](crate::Workspace::set_master_count) and
+    /// [`Workspace::set_master_ratio`](crate::Workspace::set_master_ratio).
+    MasterStack,
+    /// Each window splits the space of the previous window in half, alternating between
+    /// horizontal and vertical splits.
+    Dwindle,
+    /// Windows are arranged in a grid with as many columns as rows.
+    Grid,
+    /// Windows are arranged in columns on an infinite horizontal strip.
+    ///
+    /// Only the part of the strip that fits on the output is shown. The view scrolls to
+    /// keep the focused column visible and can be scrolled with touchpad swipes.
+    Scrolling,
+}
+
+/// Sets the layout of workspaces that don't have a layout set with
+/// [`Workspace::set_layout`](crate::Workspace::set_layout).
+///
+/// This only affects workspaces created after this call.
+///
+/// The default is `WorkspaceLayout::Manual`.
+pub fn set_default_workspace_layout(layout: WorkspaceLayout) {
+    get!().set_default_workspace_layout(layout);
+}
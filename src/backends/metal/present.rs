@@ -38,6 +38,7 @@ struct Latched {
     damage_count: u64,
     damage: Region,
     locked: bool,
+    magnified: bool,
 }
 
 #[derive(Debug)]
@@ -595,7 +596,9 @@ impl MetalConnector {
         };
         let render_hw_cursor = !self.cursor_enabled.get();
         let mode = node.global.mode.get();
-        let pass = create_render_pass(
+        node.update_overview_thumbnails();
+        node.update_window_switcher_thumbnails();
+        let mut pass = create_render_pass(
             (mode.width, mode.height),
             &**node,
             &self.state,
@@ -608,11 +611,17 @@ impl MetalConnector {
             node.global.persistent.transform.get(),
             Some(&self.state.damage_visualizer),
         );
+        let magnified = node.magnify_render_pass(&mut pass);
+        let damage = match magnified || node.overview.is_some() || node.has_color_picker_lens() {
+            true => buffer.render_fb().full_region(),
+            false => damage,
+        };
         Some(Latched {
             pass,
             damage_count,
             damage,
             locked: self.state.lock.locked.get(),
+            magnified,
         })
     }
 
@@ -814,6 +823,7 @@ impl MetalConnector {
     ) -> Result<PresentFb, MetalError> {
         self.trim_scanout_cache();
         let try_direct_scanout = try_direct_scanout
+            && !latched.magnified
             && self.direct_scanout_enabled()
             // at least on AMD, using a FB on a different device for rendering will fail
             // and destroy the render context. it's possible to work around this by waiting
@@ -7,8 +7,10 @@ mod generate;
 mod idle;
 mod input;
 mod log;
+mod pick_color;
 mod quit;
 mod randr;
+mod record;
 mod reexec;
 mod run_privileged;
 pub mod screenshot;
@@ -17,6 +19,7 @@ mod set_log_level;
 mod tree;
 mod unlock;
 mod version;
+mod workspace;
 mod xwayland;
 
 use {
@@ -24,12 +27,13 @@ use {
         cli::{
             clients::ClientsArgs, color_management::ColorManagementArgs,
             damage_tracking::DamageTrackingArgs, idle::IdleCmd, input::InputArgs, randr::RandrArgs,
-            reexec::ReexecArgs, tree::TreeArgs, xwayland::XwaylandArgs,
+            reexec::ReexecArgs, tree::TreeArgs, workspace::WorkspaceArgs, xwayland::XwaylandArgs,
         },
         compositor::start_compositor,
         format::{Format, ref_formats},
         portal,
         pr_caps::drop_all_pr_caps,
+        rect::Rect,
     },
     ::log::Level,
     clap::{Args, Parser, Subcommand, ValueEnum, ValueHint, builder::PossibleValue},
@@ -68,6 +72,10 @@ pub enum Cmd {
     Unlock,
     /// Take a screenshot.
     Screenshot(ScreenshotArgs),
+    /// Record an output, window, or region.
+    Record(RecordArgs),
+    /// Print the color of a pixel on the screen.
+    PickColor(PickColorArgs),
     /// Inspect/modify the idle (screensaver) settings.
     Idle(IdleArgs),
     /// Run a privileged program.
@@ -94,6 +102,8 @@ pub enum Cmd {
     Clients(ClientsArgs),
     /// Inspect the surface tree.
     Tree(TreeArgs),
+    /// Modify workspaces.
+    Workspace(WorkspaceArgs),
     /// Prints the Jay version and exits.
     Version,
     #[cfg(feature = "it")]
@@ -126,6 +136,16 @@ pub enum ScreenshotFormat {
     Png,
     /// The QOI image format.
     Qoi,
+    /// The PNG image format with 16 bits per channel.
+    ///
+    /// The image uses BT.2020 primaries and the PQ transfer function, which is signaled
+    /// with a cICP chunk. HDR content is preserved.
+    Png16,
+    /// The OpenEXR image format.
+    ///
+    /// The image contains linear half-float values with sRGB primaries where 1.0 is
+    /// 80 cd/m^2. HDR content is preserved.
+    Exr,
 }
 
 #[derive(Args, Debug)]
@@ -133,16 +153,143 @@ pub struct ScreenshotArgs {
     /// The format to use for the image.
     #[clap(value_enum, long, default_value_t)]
     pub format: ScreenshotFormat,
+    /// Capture only the output with this name, e.g. `DP-1`.
+    #[clap(long, conflicts_with_all = ["window", "region", "select"])]
+    pub output: Option<String>,
+    /// Capture only a window.
+    ///
+    /// The value is the ID of the window as printed by `jay tree query`. If no ID is given,
+    /// the window can be selected by clicking on it.
+    #[clap(long, num_args = 0..=1, default_missing_value = "", conflicts_with_all = ["region", "select"])]
+    pub window: Option<String>,
+    /// Capture only a region given as `x,y,width,height` in compositor coordinates.
+    #[clap(long, value_parser = parse_region, conflicts_with = "select")]
+    pub region: Option<Rect>,
+    /// Capture only a region that is selected by dragging with the pointer.
+    ///
+    /// Right-click to cancel the selection.
+    #[clap(long)]
+    pub select: bool,
+    /// Do not draw the cursor into the screenshot.
+    #[clap(long)]
+    pub no_cursor: bool,
+    /// Tone-map HDR content into the SDR range instead of clipping it.
+    ///
+    /// This can only be used with the png and qoi formats.
+    #[clap(long)]
+    pub tone_map: bool,
+    /// Copy the screenshot to the clipboard instead of saving it.
+    ///
+    /// The command keeps running until another application takes over the clipboard.
+    #[clap(long, conflicts_with = "filename")]
+    pub clipboard: bool,
     /// The filename of the saved screenshot
     ///
     /// If no filename is given, the screenshot will be saved under %Y-%m-%d-%H%M%S_jay.<ext>
-    /// in the current directory.
+    /// in the current directory. If the filename is `-`, the screenshot is written to
+    /// stdout.
     ///
     /// The filename can contain the usual strftime parameters.
     #[clap(value_hint = ValueHint::FilePath)]
     pub filename: Option<String>,
 }
 
+#[derive(ValueEnum, Debug, Copy, Clone, Hash, Default, PartialEq)]
+pub enum RecordFormat {
+    /// An uncompressed YUV4MPEG2 stream with full-range BT.601 4:4:4 pixels.
+    ///
+    /// The stream has a constant frame rate. Frames are repeated while the content does
+    /// not change. If the size of the recorded area changes, frames are cropped or padded
+    /// with black to the initial size.
+    #[default]
+    Y4m,
+    /// A directory of QOI images.
+    ///
+    /// An image is only written when the content changes. The filename of each image
+    /// contains the frame number and the number of milliseconds since the start of the
+    /// recording.
+    Qoi,
+}
+
+#[derive(Args, Debug)]
+pub struct RecordArgs {
+    /// The format to use for the recording.
+    #[clap(value_enum, long, default_value_t)]
+    pub format: RecordFormat,
+    /// Record the output with this name, e.g. `DP-1`.
+    ///
+    /// This is optional if there is only one output.
+    #[clap(long, conflicts_with_all = ["window", "region", "select"])]
+    pub output: Option<String>,
+    /// Record a window.
+    ///
+    /// The value is the ID of the window as printed by `jay tree query`. If no ID is given,
+    /// the window can be selected by clicking on it.
+    #[clap(long, num_args = 0..=1, default_missing_value = "", conflicts_with_all = ["region", "select"])]
+    pub window: Option<String>,
+    /// Record a region given as `x,y,width,height` in compositor coordinates.
+    ///
+    /// The region is clipped to the output that contains most of it.
+    #[clap(long, value_parser = parse_region, conflicts_with = "select")]
+    pub region: Option<Rect>,
+    /// Record a region that is selected by dragging with the pointer.
+    ///
+    /// Right-click to cancel the selection.
+    #[clap(long)]
+    pub select: bool,
+    /// Do not draw the cursor into the recording.
+    #[clap(long)]
+    pub no_cursor: bool,
+    /// The frame rate of y4m recordings.
+    #[clap(long, default_value_t = 60, value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub fps: u32,
+    /// Stop the recording after this many seconds.
+    ///
+    /// Otherwise the recording runs until the process is interrupted or the recorded
+    /// window or output disappears.
+    #[clap(long)]
+    pub duration: Option<f64>,
+    /// The file (y4m) or directory (qoi) to write the recording to.
+    ///
+    /// If no filename is given, the recording will be saved under
+    /// %Y-%m-%d-%H%M%S_jay.y4m or %Y-%m-%d-%H%M%S_jay in the current directory. If the
+    /// filename is `-`, y4m recordings are written to stdout, e.g. to pipe them into an
+    /// encoder.
+    ///
+    /// The filename can contain the usual strftime parameters.
+    #[clap(value_hint = ValueHint::AnyPath)]
+    pub filename: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct PickColorArgs {
+    /// Sample the pixel at `x,y` in compositor coordinates.
+    ///
+    /// Otherwise the pixel can be selected by clicking on it. A lens shows a magnified
+    /// view of the area around the pointer. Right-click to cancel.
+    #[clap(long, value_parser = parse_position)]
+    pub position: Option<(i32, i32)>,
+}
+
+fn parse_position(s: &str) -> Result<(i32, i32), String> {
+    let parts: Vec<_> = s.split(',').map(|p| p.trim().parse::<i32>()).collect();
+    let [Ok(x), Ok(y)] = parts[..] else {
+        return Err("expected two integers `x,y`".to_string());
+    };
+    Ok((x, y))
+}
+
+fn parse_region(s: &str) -> Result<Rect, String> {
+    let parts: Vec<_> = s.split(',').map(|p| p.trim().parse::<i32>()).collect();
+    let [Ok(x), Ok(y), Ok(width), Ok(height)] = parts[..] else {
+        return Err("expected four integers `x,y,width,height`".to_string());
+    };
+    match Rect::new_sized(x, y, width, height) {
+        Some(r) if !r.is_empty() => Ok(r),
+        _ => Err("the region must have a positive width and height".to_string()),
+    }
+}
+
 #[derive(Args, Debug, Default)]
 pub struct RunArgs {
     /// The backends to try.
@@ -247,6 +394,8 @@ pub fn main() {
         Cmd::Quit => quit::main(cli.global),
         Cmd::SetLogLevel(a) => set_log_level::main(cli.global, a),
         Cmd::Screenshot(a) => screenshot::main(cli.global, a),
+        Cmd::Record(a) => record::main(cli.global, a),
+        Cmd::PickColor(a) => pick_color::main(cli.global, a),
         Cmd::Idle(a) => idle::main(cli.global, a),
         Cmd::Unlock => unlock::main(cli.global),
         Cmd::RunPrivileged(a) => run_privileged::main(cli.global, a),
@@ -259,6 +408,7 @@ pub fn main() {
         Cmd::ColorManagement(a) => color_management::main(cli.global, a),
         Cmd::Clients(a) => clients::main(cli.global, a),
         Cmd::Tree(a) => tree::main(cli.global, a),
+        Cmd::Workspace(a) => workspace::main(cli.global, a),
         Cmd::Version => version::main(cli.global),
         #[cfg(feature = "it")]
         Cmd::RunTests => crate::it::run_tests(),
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        cli::{GlobalArgs, PickColorArgs},
+        tools::tool_client::{Handle, ToolClient, with_tool_client},
+        utils::asyncevent::AsyncEvent,
+        wire::{
+            WlSeatId,
+            jay_compositor::{PickColor, SampleColor},
+            jay_pick_color::{Cancelled, Color, Failed},
+        },
+    },
+    std::{cell::RefCell, rc::Rc},
+};
+
+pub fn main(global: GlobalArgs, args: PickColorArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let picker = Rc::new(Picker {
+            tc: tc.clone(),
+            args,
+        });
+        run(picker).await;
+    });
+}
+
+struct Picker {
+    tc: Rc<ToolClient>,
+    args: PickColorArgs,
+}
+
+async fn run(picker: Rc<Picker>) {
+    let tc = &picker.tc;
+    let comp = tc.jay_compositor().await;
+    let id = tc.id();
+    match picker.args.position {
+        Some((x, y)) => tc.send(SampleColor {
+            self_id: comp,
+            id,
+            x,
+            y,
+        }),
+        None => tc.send(PickColor {
+            self_id: comp,
+            id,
+            seat: WlSeatId::NONE,
+        }),
+    }
+    let ae = Rc::new(AsyncEvent::default());
+    let res = Rc::new(RefCell::new(None));
+    Cancelled::handle(tc, id, ae.clone(), |ae, _event| {
+        ae.trigger();
+    });
+    Failed::handle(tc, id, (ae.clone(), res.clone()), |(ae, res), event| {
+        *res.borrow_mut() = Some(Err(event.msg.to_string()));
+        ae.trigger();
+    });
+    Color::handle(tc, id, (ae.clone(), res.clone()), |(ae, res), event| {
+        *res.borrow_mut() = Some(Ok(format!(
+            "position: {},{} ({})\n\
+             srgb:     #{:02x}{:02x}{:02x}\n\
+             native:   #{:02x}{:02x}{:02x} (color space: {}, eotf: {})",
+            event.x,
+            event.y,
+            event.output,
+            event.srgb_r,
+            event.srgb_g,
+            event.srgb_b,
+            event.r,
+            event.g,
+            event.b,
+            event.color_space,
+            event.eotf,
+        )));
+        ae.trigger();
+    });
+    ae.triggered().await;
+    match res.take() {
+        Some(Ok(color)) => println!("{}", color),
+        Some(Err(msg)) => fatal!("Could not pick a color: {}", msg),
+        None => fatal!("Color picking was cancelled"),
+    }
+}
@@ -0,0 +1,593 @@
+#[cfg(test)]
+mod tests;
+
+use {
+    crate::{
+        allocator::{Allocator, BO_USE_RENDERING, BufferObject},
+        async_engine::SpawnedFuture,
+        cli::{GlobalArgs, RecordArgs, RecordFormat, screenshot::allocators},
+        format::XRGB8888,
+        ifs::jay_screencast::{CURSOR_MODE_EMBEDDED, CURSOR_MODE_HIDDEN},
+        time::Time,
+        tools::tool_client::{Handle, ToolClient, with_tool_client},
+        utils::{buf::TypedBuf, errorfmt::ErrorFmt, oserror::OsError, queue::AsyncQueue},
+        video::{LINEAR_MODIFIER, dmabuf::DmaBufIds},
+        wire::{
+            JayCompositorId, JayOutputId, JayRenderCtxId, JayScreencastId, WlBufferId,
+            ZwpLinuxBufferParamsV1Id, ZwpLinuxDmabufV1, ZwpLinuxDmabufV1Id,
+            jay_compositor::{CreateScreencast, GetOutput, GetRenderCtx},
+            jay_render_ctx, jay_screencast, wl_buffer, wl_registry, zwp_linux_buffer_params_v1,
+            zwp_linux_dmabuf_v1,
+        },
+    },
+    chrono::Local,
+    jay_algorithms::qoi::xrgb8888_encode_qoi,
+    std::{
+        cell::{Cell, RefCell},
+        fs::File,
+        io::{BufWriter, Write},
+        path::PathBuf,
+        rc::Rc,
+    },
+    uapi::{OwnedFd, c},
+};
+
+const NUM_BUFFERS: usize = 3;
+
+pub fn main(global: GlobalArgs, args: RecordArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        run(&tc, args).await;
+    });
+}
+
+enum RecordEvent {
+    Config {
+        serial: u32,
+        width: i32,
+        height: i32,
+    },
+    Ready {
+        idx: u32,
+        time: Time,
+    },
+    Stop,
+}
+
+async fn run(tc: &Rc<ToolClient>, args: RecordArgs) {
+    let mut sink = create_sink(&args);
+    let comp = tc.jay_compositor().await;
+    let sc = create_screencast(tc, comp, &args).await;
+    let events = Rc::new(AsyncQueue::new());
+    let size = Rc::new(Cell::new((0, 0)));
+    jay_screencast::ConfigSize::handle(tc, sc, size.clone(), |size, ev| {
+        size.set((ev.width, ev.height));
+    });
+    jay_screencast::ConfigDone::handle(tc, sc, (size, events.clone()), |(size, events), ev| {
+        let (width, height) = size.get();
+        events.push(RecordEvent::Config {
+            serial: ev.serial,
+            width,
+            height,
+        });
+    });
+    jay_screencast::Ready::handle(tc, sc, events.clone(), |events, ev| {
+        events.push(RecordEvent::Ready {
+            idx: ev.idx,
+            time: Time::now_unchecked(),
+        });
+    });
+    jay_screencast::Destroyed::handle(tc, sc, events.clone(), |events, _| {
+        log::info!("The recorded output or window is no longer available");
+        events.push(RecordEvent::Stop);
+    });
+    let _signals = handle_signals(tc, &events);
+    let _timeout = args.duration.map(|duration| {
+        let wheel = tc.wheel.clone();
+        let events = events.clone();
+        tc.eng.spawn("record timeout", async move {
+            let _ = wheel.timeout((duration * 1000.0) as u64).await;
+            events.push(RecordEvent::Stop);
+        })
+    });
+    let mut recorder = Recorder {
+        tc: tc.clone(),
+        sc,
+        linux_dmabuf: bind_linux_dmabuf(tc).await,
+        drm_dev: render_device(tc, comp).await,
+        allocator: None,
+        dma_buf_ids: Default::default(),
+        buffers: vec![],
+        width: 0,
+        height: 0,
+    };
+    loop {
+        match events.pop().await {
+            RecordEvent::Config {
+                serial,
+                width,
+                height,
+            } => {
+                recorder.configure(serial, width, height);
+            }
+            RecordEvent::Ready { idx, time } => {
+                let pixels = recorder.read_buffer(idx as usize);
+                tc.send(jay_screencast::ReleaseBuffer { self_id: sc, idx });
+                if let Some(pixels) = pixels {
+                    sink.frame(time, recorder.width as _, recorder.height as _, &pixels);
+                }
+            }
+            RecordEvent::Stop => break,
+        }
+    }
+    sink.finish(Time::now_unchecked());
+}
+
+async fn create_screencast(
+    tc: &Rc<ToolClient>,
+    comp: JayCompositorId,
+    args: &RecordArgs,
+) -> JayScreencastId {
+    let toplevel = match args.window.as_deref() {
+        Some("") => Some(tc.select_toplevel().await),
+        Some(id) => Some(tc.get_toplevel(id).await),
+        None => None,
+    };
+    let sc: JayScreencastId = tc.id();
+    tc.send(CreateScreencast {
+        self_id: comp,
+        id: sc,
+    });
+    if let Some(toplevel) = toplevel {
+        if toplevel.is_none() {
+            fatal!("Could not find the window");
+        }
+        tc.send(jay_screencast::SetToplevel {
+            self_id: sc,
+            id: toplevel,
+        });
+    } else {
+        let region = match args.select {
+            true => match tc.select_region().await {
+                Some(r) => Some(r),
+                _ => fatal!("Region selection failed"),
+            },
+            false => args.region,
+        };
+        let outputs = tc.outputs().await;
+        let output = if let Some(name) = &args.output {
+            match outputs.iter().find(|o| &o.name == name) {
+                Some(o) => o,
+                _ => fatal!("There is no output named `{}`", name),
+            }
+        } else if let Some(region) = region {
+            let area = |rect: &_| {
+                let rect = region.intersect(*rect);
+                rect.width() as i64 * rect.height() as i64
+            };
+            match outputs.iter().max_by_key(|o| area(&o.rect)) {
+                Some(o) if area(&o.rect) > 0 => o,
+                _ => fatal!("The region does not intersect any output"),
+            }
+        } else {
+            match &outputs[..] {
+                [o] => o,
+                [] => fatal!("There are no outputs"),
+                _ => fatal!("There are multiple outputs. Select one with --output"),
+            }
+        };
+        let jay_output: JayOutputId = tc.id();
+        tc.send(GetOutput {
+            self_id: comp,
+            id: jay_output,
+            output: output.id,
+        });
+        match region {
+            Some(region) => {
+                let clipped = region.intersect(output.rect);
+                if clipped != region {
+                    log::warn!("The region has been clipped to output {}", output.name);
+                }
+                let rect = clipped.move_(-output.rect.x1(), -output.rect.y1());
+                tc.send(jay_screencast::SetRegion {
+                    self_id: sc,
+                    output: jay_output,
+                    x: rect.x1(),
+                    y: rect.y1(),
+                    width: rect.width(),
+                    height: rect.height(),
+                });
+            }
+            None => {
+                tc.send(jay_screencast::SetOutput {
+                    self_id: sc,
+                    output: jay_output,
+                });
+            }
+        }
+    }
+    tc.send(jay_screencast::SetAllowAllWorkspaces {
+        self_id: sc,
+        allow_all: 1,
+    });
+    tc.send(jay_screencast::SetCursorMode {
+        self_id: sc,
+        mode: match args.no_cursor {
+            true => CURSOR_MODE_HIDDEN,
+            false => CURSOR_MODE_EMBEDDED,
+        },
+    });
+    tc.send(jay_screencast::SetRunning {
+        self_id: sc,
+        running: 1,
+    });
+    tc.send(jay_screencast::Configure { self_id: sc });
+    sc
+}
+
+async fn bind_linux_dmabuf(tc: &Rc<ToolClient>) -> ZwpLinuxDmabufV1Id {
+    let s = tc.singletons().await;
+    let Some((name, version)) = s.zwp_linux_dmabuf else {
+        fatal!(
+            "Compositor does not provide the {} global",
+            ZwpLinuxDmabufV1.name()
+        );
+    };
+    if version < 2 {
+        fatal!(
+            "Compositor only supports version {} of the {} global",
+            version,
+            ZwpLinuxDmabufV1.name()
+        );
+    }
+    let id: ZwpLinuxDmabufV1Id = tc.id();
+    tc.send(wl_registry::Bind {
+        self_id: s.registry,
+        name,
+        interface: ZwpLinuxDmabufV1.name(),
+        version: 2,
+        id: id.into(),
+    });
+    id
+}
+
+async fn render_device(tc: &Rc<ToolClient>, comp: JayCompositorId) -> Option<Rc<OwnedFd>> {
+    let id: JayRenderCtxId = tc.id();
+    tc.send(GetRenderCtx { self_id: comp, id });
+    let dev = Rc::new(RefCell::new(None));
+    jay_render_ctx::Device::handle(tc, id, dev.clone(), |dev, ev| {
+        *dev.borrow_mut() = Some(ev.fd);
+    });
+    tc.round_trip().await;
+    tc.send(jay_render_ctx::Destroy { self_id: id });
+    dev.take()
+}
+
+fn handle_signals(
+    tc: &Rc<ToolClient>,
+    events: &Rc<AsyncQueue<RecordEvent>>,
+) -> Option<SpawnedFuture<()>> {
+    let mut set: c::sigset_t = uapi::pod_zeroed();
+    uapi::sigaddset(&mut set, c::SIGINT).unwrap();
+    uapi::sigaddset(&mut set, c::SIGTERM).unwrap();
+    if let Err(e) = uapi::pthread_sigmask(c::SIG_BLOCK, Some(&set), None) {
+        log::warn!("Could not block signals: {}", ErrorFmt(OsError::from(e)));
+        return None;
+    }
+    let fd = match uapi::signalfd_new(&set, c::SFD_CLOEXEC) {
+        Ok(fd) => Rc::new(fd),
+        Err(e) => {
+            log::warn!(
+                "Could not create a signalfd: {}",
+                ErrorFmt(OsError::from(e))
+            );
+            return None;
+        }
+    };
+    let ring = tc.ring.clone();
+    let events = events.clone();
+    let future = tc.eng.spawn("record signals", async move {
+        let mut buf = TypedBuf::<c::signalfd_siginfo>::new();
+        if let Err(e) = ring.read(&fd, buf.buf()).await {
+            log::error!("Could not read from signal fd: {}", ErrorFmt(e));
+            return;
+        }
+        events.push(RecordEvent::Stop);
+    });
+    Some(future)
+}
+
+struct Recorder {
+    tc: Rc<ToolClient>,
+    sc: JayScreencastId,
+    linux_dmabuf: ZwpLinuxDmabufV1Id,
+    drm_dev: Option<Rc<OwnedFd>>,
+    allocator: Option<Rc<dyn Allocator>>,
+    dma_buf_ids: DmaBufIds,
+    buffers: Vec<Rc<dyn BufferObject>>,
+    width: i32,
+    height: i32,
+}
+
+impl Recorder {
+    fn configure(&mut self, serial: u32, width: i32, height: i32) {
+        let tc = self.tc.clone();
+        if (width, height) != (self.width, self.height) && width > 0 && height > 0 {
+            self.width = width;
+            self.height = height;
+            self.buffers.clear();
+            tc.send(jay_screencast::ClearBuffers { self_id: self.sc });
+            for _ in 0..NUM_BUFFERS {
+                let bo = self.create_bo();
+                let buffer = self.import(&*bo);
+                tc.send(jay_screencast::AddBuffer {
+                    self_id: self.sc,
+                    buffer,
+                });
+                tc.send(wl_buffer::Destroy { self_id: buffer });
+                self.buffers.push(bo);
+            }
+        }
+        tc.send(jay_screencast::AckConfig {
+            self_id: self.sc,
+            serial,
+        });
+        tc.send(jay_screencast::Configure { self_id: self.sc });
+    }
+
+    fn create_bo(&mut self) -> Rc<dyn BufferObject> {
+        let create = |allocator: &Rc<dyn Allocator>| {
+            allocator.create_bo(
+                &self.dma_buf_ids,
+                self.width,
+                self.height,
+                XRGB8888,
+                &[LINEAR_MODIFIER],
+                BO_USE_RENDERING,
+            )
+        };
+        if let Some(allocator) = &self.allocator {
+            match create(allocator) {
+                Ok(bo) => return bo,
+                Err(e) => fatal!("Could not allocate a buffer: {}", ErrorFmt(e)),
+            }
+        }
+        for allocator in allocators(self.drm_dev.as_ref()) {
+            let allocator = match allocator() {
+                Ok(a) => a,
+                Err(e) => {
+                    log::error!("Could not create allocator: {}", ErrorFmt(e));
+                    continue;
+                }
+            };
+            match create(&allocator) {
+                Ok(bo) => {
+                    self.allocator = Some(allocator);
+                    return bo;
+                }
+                Err(e) => {
+                    log::error!("Could not allocate a buffer: {}", ErrorFmt(e));
+                }
+            }
+        }
+        fatal!("Could not allocate a buffer with any allocator");
+    }
+
+    fn import(&self, bo: &dyn BufferObject) -> WlBufferId {
+        let tc = &self.tc;
+        let dmabuf = bo.dmabuf();
+        let params: ZwpLinuxBufferParamsV1Id = tc.id();
+        tc.send(zwp_linux_dmabuf_v1::CreateParams {
+            self_id: self.linux_dmabuf,
+            params_id: params,
+        });
+        for (idx, plane) in dmabuf.planes.iter().enumerate() {
+            tc.send(zwp_linux_buffer_params_v1::Add {
+                self_id: params,
+                fd: plane.fd.clone(),
+                plane_idx: idx as _,
+                offset: plane.offset,
+                stride: plane.stride,
+                modifier: dmabuf.modifier,
+            });
+        }
+        let buffer: WlBufferId = tc.id();
+        tc.send(zwp_linux_buffer_params_v1::CreateImmed {
+            self_id: params,
+            buffer_id: buffer,
+            width: dmabuf.width,
+            height: dmabuf.height,
+            format: dmabuf.format.drm,
+            flags: 0,
+        });
+        zwp_linux_buffer_params_v1::Failed::handle(tc, params, (), |_, _| {
+            fatal!("The compositor could not import a buffer");
+        });
+        tc.send(zwp_linux_buffer_params_v1::Destroy { self_id: params });
+        buffer
+    }
+
+    fn read_buffer(&self, idx: usize) -> Option<Vec<u8>> {
+        let bo = self.buffers.get(idx)?;
+        let map = match bo.clone().map_read() {
+            Ok(m) => m,
+            Err(e) => fatal!("Could not map a buffer: {}", ErrorFmt(e)),
+        };
+        let data = unsafe { map.data() };
+        let stride = map.stride() as usize;
+        let row = self.width as usize * 4;
+        let mut pixels = Vec::with_capacity(row * self.height as usize);
+        for line in data.chunks(stride).take(self.height as usize) {
+            pixels.extend_from_slice(&line[..row]);
+        }
+        Some(pixels)
+    }
+}
+
+trait FrameSink {
+    fn frame(&mut self, time: Time, width: usize, height: usize, pixels: &[u8]);
+    fn finish(&mut self, time: Time);
+}
+
+fn create_sink(args: &RecordArgs) -> Box<dyn FrameSink> {
+    match args.format {
+        RecordFormat::Y4m => {
+            let out: Box<dyn Write> = match args.filename.as_deref() {
+                Some("-") => Box::new(BufWriter::new(std::io::stdout())),
+                filename => {
+                    let filename = filename.unwrap_or("%Y-%m-%d-%H%M%S_jay.y4m");
+                    let filename = Local::now().format(filename).to_string();
+                    match File::create(&filename) {
+                        Ok(f) => Box::new(BufWriter::new(f)),
+                        Err(e) => fatal!("Could not create `{}`: {}", filename, ErrorFmt(e)),
+                    }
+                }
+            };
+            Box::new(Y4m {
+                out,
+                fps: args.fps,
+                start: None,
+                size: (0, 0),
+                frame_size: (0, 0),
+                frames: 0,
+                frame: vec![],
+            })
+        }
+        RecordFormat::Qoi => {
+            let dir = match args.filename.as_deref() {
+                Some("-") => fatal!("qoi recordings cannot be written to stdout"),
+                dir => dir.unwrap_or("%Y-%m-%d-%H%M%S_jay"),
+            };
+            let dir = Local::now().format(dir).to_string();
+            if let Err(e) = std::fs::create_dir_all(&dir) {
+                fatal!("Could not create `{}`: {}", dir, ErrorFmt(e));
+            }
+            Box::new(QoiSequence {
+                dir: dir.into(),
+                start: None,
+                frames: 0,
+            })
+        }
+    }
+}
+
+struct Y4m {
+    out: Box<dyn Write>,
+    fps: u32,
+    start: Option<Time>,
+    size: (usize, usize),
+    frame_size: (usize, usize),
+    frames: u64,
+    frame: Vec<u8>,
+}
+
+impl Y4m {
+    fn slot(&self, time: Time) -> u64 {
+        let Some(start) = self.start else {
+            return 0;
+        };
+        ((time - start).as_nanos() * self.fps as u128 / 1_000_000_000) as u64
+    }
+
+    fn write(&mut self, data: &[u8]) {
+        if let Err(e) = self.out.write_all(data) {
+            fatal!("Could not write the recording: {}", ErrorFmt(e));
+        }
+    }
+
+    /// Repeats the most recent frame until `slot` frames have been written.
+    fn write_frames(&mut self, slot: u64) {
+        let frame = std::mem::take(&mut self.frame);
+        while self.frames < slot {
+            self.write(b"FRAME\n");
+            self.write(&frame);
+            self.frames += 1;
+        }
+        self.frame = frame;
+    }
+}
+
+impl FrameSink for Y4m {
+    fn frame(&mut self, time: Time, width: usize, height: usize, pixels: &[u8]) {
+        if self.start.is_none() {
+            self.start = Some(time);
+            self.size = (width, height);
+            let header = format!(
+                "YUV4MPEG2 W{width} H{height} F{}:1 Ip A1:1 C444 XCOLORRANGE=FULL\n",
+                self.fps,
+            );
+            self.write(header.as_bytes());
+        } else {
+            self.write_frames(self.slot(time));
+        }
+        if self.frame_size != (width, height) {
+            self.frame_size = (width, height);
+            if self.size != (width, height) {
+                log::warn!(
+                    "The frame size changed to {width}x{height}. Frames are cropped or padded to {}x{}.",
+                    self.size.0,
+                    self.size.1,
+                );
+            }
+        }
+        xrgb8888_to_yuv444(pixels, width, height, self.size, &mut self.frame);
+    }
+
+    fn finish(&mut self, time: Time) {
+        if self.start.is_none() {
+            return;
+        }
+        self.write_frames(self.slot(time).max(self.frames + 1));
+        if let Err(e) = self.out.flush() {
+            fatal!("Could not write the recording: {}", ErrorFmt(e));
+        }
+    }
+}
+
+/// Converts XRGB8888 pixels to planar full-range BT.601 YUV 4:4:4.
+///
+/// The image is cropped or padded with black to the size of the stream.
+fn xrgb8888_to_yuv444(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    (stream_width, stream_height): (usize, usize),
+    out: &mut Vec<u8>,
+) {
+    let plane_size = stream_width * stream_height;
+    out.clear();
+    out.resize(plane_size, 0);
+    out.resize(3 * plane_size, 128);
+    let (y_plane, uv) = out.split_at_mut(plane_size);
+    let (u_plane, v_plane) = uv.split_at_mut(plane_size);
+    for y in 0..height.min(stream_height) {
+        for x in 0..width.min(stream_width) {
+            let pixel = &pixels[(y * width + x) * 4..];
+            let [b, g, r] = [pixel[0], pixel[1], pixel[2]].map(|c| c as i32);
+            let idx = y * stream_width + x;
+            y_plane[idx] = ((77 * r + 150 * g + 29 * b + 128) >> 8) as u8;
+            u_plane[idx] = (((-43 * r - 85 * g + 128 * b + 128) >> 8) + 128).min(255) as u8;
+            v_plane[idx] = (((128 * r - 107 * g - 21 * b + 128) >> 8) + 128).min(255) as u8;
+        }
+    }
+}
+
+struct QoiSequence {
+    dir: PathBuf,
+    start: Option<Time>,
+    frames: u64,
+}
+
+impl FrameSink for QoiSequence {
+    fn frame(&mut self, time: Time, width: usize, height: usize, pixels: &[u8]) {
+        let start = *self.start.get_or_insert(time);
+        let msec = (time - start).as_millis();
+        let path = self.dir.join(format!("{:06}_{:08}.qoi", self.frames, msec));
+        let data = xrgb8888_encode_qoi(pixels, width as _, height as _, width as u32 * 4);
+        if let Err(e) = std::fs::write(&path, data) {
+            fatal!("Could not write `{}`: {}", path.display(), ErrorFmt(e));
+        }
+        self.frames += 1;
+    }
+
+    fn finish(&mut self, _time: Time) {}
+}
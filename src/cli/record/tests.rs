@@ -0,0 +1,169 @@
+use {
+    crate::{
+        cli::record::{FrameSink, Y4m, xrgb8888_to_yuv444},
+        time::Time,
+    },
+    std::{cell::RefCell, io::Write, rc::Rc, time::Duration},
+    uapi::c,
+};
+
+fn xrgb(pixels: &[[u8; 3]]) -> Vec<u8> {
+    pixels
+        .iter()
+        .flat_map(|&[r, g, b]| [b, g, r, 255])
+        .collect()
+}
+
+fn yuv(pixels: &[[u8; 3]], width: usize, height: usize, size: (usize, usize)) -> Vec<[u8; 3]> {
+    let mut out = vec![];
+    xrgb8888_to_yuv444(&xrgb(pixels), width, height, size, &mut out);
+    let plane = size.0 * size.1;
+    assert_eq!(out.len(), 3 * plane);
+    (0..plane)
+        .map(|i| [out[i], out[plane + i], out[2 * plane + i]])
+        .collect()
+}
+
+#[test]
+fn known_colors() {
+    let pixels = [
+        [0, 0, 0],
+        [255, 255, 255],
+        [255, 0, 0],
+        [0, 255, 0],
+        [0, 0, 255],
+    ];
+    let expected = [
+        [0, 128, 128],
+        [255, 128, 128],
+        [77, 85, 255],
+        [149, 43, 21],
+        [29, 255, 107],
+    ];
+    assert_eq!(yuv(&pixels, 5, 1, (5, 1)), expected);
+}
+
+#[test]
+fn crop_and_pad() {
+    const W: [u8; 3] = [255, 255, 255];
+    const BLACK: [u8; 3] = [0, 128, 128];
+    const WHITE: [u8; 3] = [255, 128, 128];
+    let pixels = [W, W, W, W];
+    assert_eq!(yuv(&pixels, 2, 2, (3, 1)), [WHITE, WHITE, BLACK]);
+    assert_eq!(yuv(&pixels, 2, 2, (1, 3)), [WHITE, WHITE, BLACK]);
+    assert_eq!(yuv(&pixels, 4, 1, (2, 2)), [WHITE, WHITE, BLACK, BLACK]);
+}
+
+#[derive(Clone, Default)]
+struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn y4m(fps: u32) -> (Y4m, SharedBuf) {
+    let buf = SharedBuf::default();
+    let y4m = Y4m {
+        out: Box::new(buf.clone()),
+        fps,
+        start: None,
+        size: (0, 0),
+        frame_size: (0, 0),
+        frames: 0,
+        frame: vec![],
+    };
+    (y4m, buf)
+}
+
+fn time(msec: u64) -> Time {
+    Time(c::timespec {
+        tv_sec: 1000,
+        tv_nsec: 0,
+    }) + Duration::from_millis(msec)
+}
+
+/// Splits the output into the header and the frames.
+fn frames(buf: &SharedBuf, frame_size: usize) -> (String, Vec<Vec<u8>>) {
+    let data = buf.0.borrow();
+    let header_len = data.iter().position(|&b| b == b'\n').unwrap() + 1;
+    let header = String::from_utf8(data[..header_len].to_vec()).unwrap();
+    let mut frames = vec![];
+    let mut rest = &data[header_len..];
+    while !rest.is_empty() {
+        assert!(rest.starts_with(b"FRAME\n"));
+        frames.push(rest[6..6 + frame_size].to_vec());
+        rest = &rest[6 + frame_size..];
+    }
+    (header, frames)
+}
+
+#[test]
+fn slot() {
+    let (mut y4m, _) = y4m(10);
+    assert_eq!(y4m.slot(time(500)), 0);
+    y4m.start = Some(time(0));
+    assert_eq!(y4m.slot(time(0)), 0);
+    assert_eq!(y4m.slot(time(99)), 0);
+    assert_eq!(y4m.slot(time(100)), 1);
+    assert_eq!(y4m.slot(time(250)), 2);
+    assert_eq!(y4m.slot(time(2000)), 20);
+}
+
+#[test]
+fn frames_are_repeated() {
+    let (mut y4m, buf) = y4m(10);
+    let black = xrgb(&[[0, 0, 0]]);
+    let white = xrgb(&[[255, 255, 255]]);
+    y4m.frame(time(0), 1, 1, &black);
+    y4m.frame(time(350), 1, 1, &white);
+    y4m.finish(time(350));
+    let (header, frames) = frames(&buf, 3);
+    assert_eq!(
+        header,
+        "YUV4MPEG2 W1 H1 F10:1 Ip A1:1 C444 XCOLORRANGE=FULL\n"
+    );
+    let black = vec![0, 128, 128];
+    let white = vec![255, 128, 128];
+    assert_eq!(frames, [black.clone(), black.clone(), black, white]);
+}
+
+#[test]
+fn finish_writes_a_frame() {
+    let (mut y4m, buf) = y4m(60);
+    y4m.frame(time(0), 1, 1, &xrgb(&[[255, 255, 255]]));
+    y4m.finish(time(0));
+    let (_, frames) = frames(&buf, 3);
+    assert_eq!(frames, [vec![255, 128, 128]]);
+}
+
+#[test]
+fn finish_without_frames() {
+    let (mut y4m, buf) = y4m(60);
+    y4m.finish(time(100));
+    assert!(buf.0.borrow().is_empty());
+}
+
+#[test]
+fn frame_size_change() {
+    let (mut y4m, buf) = y4m(10);
+    let white = [255, 255, 255];
+    y4m.frame(time(0), 2, 1, &xrgb(&[white, white]));
+    y4m.frame(time(100), 1, 1, &xrgb(&[white]));
+    y4m.finish(time(100));
+    let (header, frames) = frames(&buf, 6);
+    assert!(header.starts_with("YUV4MPEG2 W2 H1 "));
+    assert_eq!(
+        frames,
+        [
+            vec![255, 255, 128, 128, 128, 128],
+            vec![255, 0, 128, 128, 128, 128],
+        ]
+    );
+}
@@ -1,27 +1,53 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
         allocator::{Allocator, AllocatorError, BufferUsage, MappedBuffer},
         cli::{GlobalArgs, ScreenshotArgs, ScreenshotFormat},
-        format::XRGB8888,
+        cmm::{
+            cmm_description::ColorDescription,
+            cmm_eotf::Eotf,
+            cmm_luminance::Luminance,
+            cmm_manager::ColorManager,
+            cmm_primaries::{NamedPrimaries, Primaries},
+        },
+        format::{ABGR16161616F, XRGB8888},
         gfx_apis,
+        rect::Rect,
+        theme::Color,
         tools::tool_client::{Handle, ToolClient, with_tool_client},
         udmabuf::{Udmabuf, UdmabufError},
-        utils::{errorfmt::ErrorFmt, queue::AsyncQueue, windows::WindowsExt},
+        utils::{
+            asyncevent::AsyncEvent, errorfmt::ErrorFmt, nonblock::set_block, ordered_float::F64,
+            queue::AsyncQueue, windows::WindowsExt,
+        },
         video::{
             dmabuf::{DmaBuf, DmaBufIds, DmaBufPlane, PlaneVec},
             drm::{Drm, DrmError},
             gbm::{GbmDevice, GbmError},
         },
         wire::{
-            jay_compositor::TakeScreenshot,
+            ExtDataControlDeviceV1Id, ExtDataControlManagerV1, ExtDataControlManagerV1Id,
+            ExtDataControlSourceV1Id, WlSeat, WlSeatId, ext_data_control_device_v1,
+            ext_data_control_manager_v1, ext_data_control_source_v1,
+            jay_compositor::{
+                SetScreenshotFormat, TakeOutputScreenshot, TakeRegionScreenshot, TakeScreenshot2,
+                TakeToplevelScreenshot,
+            },
             jay_screenshot::{Dmabuf, Dmabuf2, DrmDev, Error, Plane},
+            wl_registry,
         },
     },
     chrono::Local,
-    jay_algorithms::qoi::xrgb8888_encode_qoi,
-    png::{BitDepth, ColorType, Encoder, SrgbRenderingIntent},
+    jay_algorithms::{
+        exr::{abgr16161616f_encode_exr, f16_to_f32},
+        qoi::xrgb8888_encode_qoi,
+    },
+    png::{BitDepth, ColorType, Encoder, EncodingError, SrgbRenderingIntent, chunk::ChunkType},
     std::{
         cell::{Cell, RefCell},
+        io::Write,
         rc::Rc,
     },
     thiserror::Error,
@@ -45,12 +71,85 @@ struct Screenshot {
 
 async fn run(screenshot: Rc<Screenshot>) {
     let tc = &screenshot.tc;
+    let args = &screenshot.args;
     let comp = tc.jay_compositor().await;
+    let include_cursor = !args.no_cursor as u32;
+    let wide = match args.format {
+        ScreenshotFormat::Png | ScreenshotFormat::Qoi => args.tone_map,
+        ScreenshotFormat::Png16 | ScreenshotFormat::Exr => {
+            if args.tone_map {
+                fatal!("--tone-map can only be used with the png and qoi formats");
+            }
+            true
+        }
+    };
+    let capture_format = match wide {
+        true => ABGR16161616F,
+        false => XRGB8888,
+    };
+    let output = match &args.output {
+        Some(name) => {
+            let outputs = tc.outputs().await;
+            match outputs.iter().find(|o| &o.name == name) {
+                Some(o) => Some(o.id),
+                _ => fatal!("There is no output named `{}`", name),
+            }
+        }
+        None => None,
+    };
+    let toplevel_id = match args.window.as_deref() {
+        Some("") => match tc.select_toplevel_id().await {
+            Some(id) => Some(id),
+            _ => fatal!("Window selection failed"),
+        },
+        Some(id) => Some(id.to_string()),
+        None => None,
+    };
+    let region = match args.select {
+        true => match tc.select_region().await {
+            Some(r) => Some(r),
+            _ => fatal!("Region selection failed"),
+        },
+        false => args.region,
+    };
+    if wide {
+        tc.send(SetScreenshotFormat {
+            self_id: comp,
+            format: capture_format.drm,
+        });
+    }
     let sid = tc.id();
-    tc.send(TakeScreenshot {
-        self_id: comp,
-        id: sid,
-    });
+    if let Some(output) = output {
+        tc.send(TakeOutputScreenshot {
+            self_id: comp,
+            id: sid,
+            output,
+            include_cursor,
+        });
+    } else if let Some(toplevel_id) = &toplevel_id {
+        tc.send(TakeToplevelScreenshot {
+            self_id: comp,
+            id: sid,
+            toplevel_id,
+            include_cursor,
+        });
+    } else if let Some(region) = region {
+        tc.send(TakeRegionScreenshot {
+            self_id: comp,
+            id: sid,
+            x: region.x1(),
+            y: region.y1(),
+            width: region.width(),
+            height: region.height(),
+            include_cursor,
+        });
+    } else {
+        tc.send(TakeScreenshot2 {
+            self_id: comp,
+            id: sid,
+            include_cursor,
+        });
+    }
     let result = Rc::new(AsyncQueue::new());
     Error::handle(tc, sid, result.clone(), |res, err| {
         res.push(Err(err.msg.to_owned()));
@@ -66,7 +165,7 @@ async fn run(screenshot: Rc<Screenshot>) {
             id: DmaBufIds::default().next(),
             width: ev.width as _,
             height: ev.height as _,
-            format: XRGB8888,
+            format: capture_format,
             modifier: ev.modifier,
             planes,
         };
@@ -93,7 +192,7 @@ async fn run(screenshot: Rc<Screenshot>) {
                 id: DmaBufIds::default().next(),
                 width: ev.width,
                 height: ev.height,
-                format: XRGB8888,
+                format: capture_format,
                 modifier: ev.modifier,
                 planes: planes.take(),
             };
@@ -106,17 +205,33 @@ async fn run(screenshot: Rc<Screenshot>) {
             fatal!("Could not take a screenshot: {}", e);
         }
     };
-    let format = screenshot.args.format;
+    let format = args.format;
     let data = match buf_to_bytes(drm_dev.as_ref(), &buf, format) {
         Ok(d) => d,
-        Err(e) => fatal!("{}", ErrorFmt(e)),
+        Err(e) => fatal!("Could not convert the screenshot: {}", ErrorFmt(e)),
     };
-    let filename = match &screenshot.args.filename {
+    if args.clipboard {
+        let mime_type = match format {
+            ScreenshotFormat::Png | ScreenshotFormat::Png16 => "image/png",
+            ScreenshotFormat::Qoi => "image/qoi",
+            ScreenshotFormat::Exr => "image/x-exr",
+        };
+        copy_to_clipboard(tc, mime_type, data).await;
+        return;
+    }
+    if args.filename.as_deref() == Some("-") {
+        if let Err(e) = std::io::stdout().write_all(&data) {
+            fatal!("Could not write to stdout: {}", ErrorFmt(e));
+        }
+        return;
+    }
+    let filename = match &args.filename {
         Some(f) => f.clone(),
         _ => {
             let ext = match format {
-                ScreenshotFormat::Png => "png",
+                ScreenshotFormat::Png | ScreenshotFormat::Png16 => "png",
                 ScreenshotFormat::Qoi => "qoi",
+                ScreenshotFormat::Exr => "exr",
             };
             format!("%Y-%m-%d-%H%M%S_jay.{ext}")
         }
@@ -127,6 +242,68 @@ async fn run(screenshot: Rc<Screenshot>) {
     }
 }
 
+async fn copy_to_clipboard(tc: &Rc<ToolClient>, mime_type: &str, data: Vec<u8>) {
+    let s = tc.singletons().await;
+    let Some(manager_name) = s.ext_data_control_manager else {
+        fatal!(
+            "Compositor does not provide the {} global",
+            ExtDataControlManagerV1.name()
+        );
+    };
+    let Some((seat_name, _)) = s.wl_seat else {
+        fatal!("Compositor does not have any seats");
+    };
+    let manager: ExtDataControlManagerV1Id = tc.id();
+    tc.send(wl_registry::Bind {
+        self_id: s.registry,
+        name: manager_name,
+        interface: ExtDataControlManagerV1.name(),
+        version: 1,
+        id: manager.into(),
+    });
+    let seat: WlSeatId = tc.id();
+    tc.send(wl_registry::Bind {
+        self_id: s.registry,
+        name: seat_name,
+        interface: WlSeat.name(),
+        version: 1,
+        id: seat.into(),
+    });
+    let source: ExtDataControlSourceV1Id = tc.id();
+    tc.send(ext_data_control_manager_v1::CreateDataSource {
+        self_id: manager,
+        id: source,
+    });
+    tc.send(ext_data_control_source_v1::Offer {
+        self_id: source,
+        mime_type,
+    });
+    let device: ExtDataControlDeviceV1Id = tc.id();
+    tc.send(ext_data_control_manager_v1::GetDataDevice {
+        self_id: manager,
+        id: device,
+        seat,
+    });
+    tc.send(ext_data_control_device_v1::SetSelection {
+        self_id: device,
+        source,
+    });
+    ext_data_control_source_v1::Send::handle(tc, source, data, |data, event| {
+        if let Err(e) = set_block(event.fd.raw()) {
+            log::error!("Could not make the pipe blocking: {}", ErrorFmt(e));
+            return;
+        }
+        if let Err(e) = event.fd.borrow().write_all(data) {
+            log::error!("Could not send the screenshot: {}", ErrorFmt(e));
+        }
+    });
+    let cancelled = Rc::new(AsyncEvent::default());
+    ext_data_control_source_v1::Cancelled::handle(tc, source, cancelled.clone(), |c, _| {
+        c.trigger();
+    });
+    cancelled.triggered().await;
+}
+
 #[derive(Debug, Error)]
 pub enum ScreenshotError {
     #[error("Could not open the drm device")]
@@ -143,6 +320,12 @@ pub enum ScreenshotError {
     CreateVulkanAllocator(#[source] AllocatorError),
     #[error("Could not map the dmabuf with any allocator")]
     MapDmabufAny,
+    #[error("The selected region is empty")]
+    EmptyRegion,
+    #[error("The {0:?} format requires a screenshot with more than 8 bits per channel")]
+    NotWide(ScreenshotFormat),
+    #[error("Could not encode the png image")]
+    EncodePng(#[from] EncodingError),
 }
 
 fn map(
@@ -161,8 +344,205 @@ pub fn buf_to_bytes(
     buf: &DmaBuf,
     format: ScreenshotFormat,
 ) -> Result<Vec<u8>, ScreenshotError> {
-    let mut allocators =
-        Vec::<Box<dyn FnOnce() -> Result<Rc<dyn Allocator>, ScreenshotError>>>::new();
+    buf_region_to_bytes(drm_dev, buf, None, format)
+}
+
+pub fn buf_region_to_bytes(
+    drm_dev: Option<&Rc<OwnedFd>>,
+    buf: &DmaBuf,
+    region: Option<Rect>,
+    format: ScreenshotFormat,
+) -> Result<Vec<u8>, ScreenshotError> {
+    let bo_map = map_buf(drm_dev, buf)?;
+    let data = unsafe { bo_map.data() };
+    let stride = bo_map.stride() as usize;
+    let full = Rect::new_sized_unchecked(0, 0, buf.width, buf.height);
+    let region = match region {
+        Some(r) => r.intersect(full),
+        None => full,
+    };
+    if region.is_empty() {
+        return Err(ScreenshotError::EmptyRegion);
+    }
+    let bpp = match buf.format.drm == ABGR16161616F.drm {
+        true => 8,
+        false => 4,
+    };
+    let width = region.width() as usize;
+    let height = region.height() as usize;
+    let data = &data[region.y1() as usize * stride + region.x1() as usize * bpp..];
+    let mut pixels = Vec::with_capacity(width * height * bpp);
+    for line in data.chunks(stride).take(height) {
+        pixels.extend_from_slice(&line[..width * bpp]);
+    }
+    if bpp == 8 {
+        return wide_pixels_to_bytes(&pixels, width, height, format);
+    }
+    encode_xrgb8888(&pixels, width, height, format)
+}
+
+fn encode_xrgb8888(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    format: ScreenshotFormat,
+) -> Result<Vec<u8>, ScreenshotError> {
+    match format {
+        ScreenshotFormat::Png | ScreenshotFormat::Qoi => {}
+        ScreenshotFormat::Png16 | ScreenshotFormat::Exr => {
+            return Err(ScreenshotError::NotWide(format));
+        }
+    }
+    if format == ScreenshotFormat::Qoi {
+        return Ok(xrgb8888_encode_qoi(
+            pixels,
+            width as _,
+            height as _,
+            width as u32 * 4,
+        ));
+    }
+
+    let mut out = vec![];
+    {
+        let mut image_data = Vec::with_capacity(width * height * 4);
+        for pixel in pixels.array_chunks_ext::<4>() {
+            image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
+        }
+        let mut encoder = Encoder::new(&mut out, width as _, height as _);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        encoder.set_source_srgb(SrgbRenderingIntent::Perceptual);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&image_data)?;
+    }
+    Ok(out)
+}
+
+/// Encodes scRGB pixels in the ABGR16161616F format.
+///
+/// The png and qoi formats tone-map the pixels to sRGB.
+fn wide_pixels_to_bytes(
+    pixels: &[u8],
+    width: usize,
+    height: usize,
+    format: ScreenshotFormat,
+) -> Result<Vec<u8>, ScreenshotError> {
+    if format == ScreenshotFormat::Exr {
+        return Ok(abgr16161616f_encode_exr(
+            pixels,
+            width as _,
+            height as _,
+            width as u32 * 8,
+        ));
+    }
+    let cm = ColorManager::new();
+    let target = match format {
+        ScreenshotFormat::Png16 => cm.get_description(
+            Some(NamedPrimaries::Bt2020),
+            Primaries::BT2020,
+            Luminance::ST2084_PQ,
+            Eotf::St2084Pq,
+            Primaries::BT2020,
+            Luminance::ST2084_PQ.to_target(),
+            None,
+            None,
+        ),
+        _ => cm.srgb_gamma22().clone(),
+    };
+    let matrix = cm.windows_scrgb().linear.color_transform(&target.linear);
+    let mut linear = Vec::with_capacity(width * height);
+    for pixel in pixels.array_chunks_ext::<8>() {
+        let [r, g, b] = [0, 2, 4].map(|i| f16_to_f32(u16::from_le_bytes([pixel[i], pixel[i + 1]])));
+        let c = matrix * Color::new(Eotf::Linear, r, g, b);
+        let [r, g, b, _] = c.to_array(Eotf::Linear);
+        linear.push([r, g, b]);
+    }
+    if format == ScreenshotFormat::Png16 {
+        let mut image_data = Vec::with_capacity(width * height * 6);
+        for &[r, g, b] in &linear {
+            let c = Color::new(Eotf::Linear, r, g, b).to_array(Eotf::St2084Pq);
+            for c in &c[..3] {
+                let c = (c.clamp(0.0, 1.0) * 65535.0).round() as u16;
+                image_data.extend_from_slice(&c.to_be_bytes());
+            }
+        }
+        let mut out = vec![];
+        {
+            let mut encoder = Encoder::new(&mut out, width as _, height as _);
+            encoder.set_color(ColorType::Rgb);
+            encoder.set_depth(BitDepth::Sixteen);
+            let mut writer = encoder.write_header()?;
+            writer.write_chunk(ChunkType(*b"cICP"), &[9, 16, 0, 1])?;
+            writer.write_image_data(&image_data)?;
+        }
+        return Ok(out);
+    }
+    tone_map(&cm, &target, &mut linear);
+    let mut xrgb = Vec::with_capacity(width * height * 4);
+    for &[r, g, b] in &linear {
+        let [r, g, b, _] = Color::new(Eotf::Linear, r, g, b).to_array(target.eotf);
+        let [r, g, b] = [r, g, b].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+        xrgb.extend_from_slice(&[b, g, r, 255]);
+    }
+    encode_xrgb8888(&xrgb, width, height, format)
+}
+
+/// Maps linear pixels in the SDR color description `sdr` into the `[0, 1]` range.
+///
+/// If the brightest channel exceeds the SDR range, the pixels are converted to a
+/// description whose maximum luminance is that of the brightest channel.
+fn tone_map(cm: &Rc<ColorManager>, sdr: &ColorDescription, linear: &mut [[f32; 3]]) {
+    let peak = linear.iter().flatten().fold(1.0f32, |peak, &c| peak.max(c));
+    if peak <= 1.0 {
+        return;
+    }
+    let sdr_luminance = sdr.linear.luminance;
+    let luminance = Luminance {
+        max: sdr_luminance.min + (sdr_luminance.max - sdr_luminance.min) * F64(peak as f64),
+        ..sdr_luminance
+    };
+    let mapped = cm.get_description(
+        sdr.named_primaries,
+        sdr.linear.primaries,
+        luminance,
+        sdr.eotf,
+        sdr.linear.target_primaries,
+        luminance.to_target(),
+        None,
+        None,
+    );
+    let matrix = sdr.linear.color_transform(&mapped.linear);
+    for [r, g, b] in linear {
+        let c = matrix * Color::new(Eotf::Linear, *r, *g, *b);
+        let [nr, ng, nb, _] = c.to_array(Eotf::Linear);
+        [*r, *g, *b] = [nr, ng, nb];
+    }
+}
+
+pub fn buf_pixel(
+    drm_dev: Option<&Rc<OwnedFd>>,
+    buf: &DmaBuf,
+    x: i32,
+    y: i32,
+) -> Result<[u8; 3], ScreenshotError> {
+    if x < 0 || y < 0 || x >= buf.width || y >= buf.height {
+        return Err(ScreenshotError::EmptyRegion);
+    }
+    let bo_map = map_buf(drm_dev, buf)?;
+    let data = unsafe { bo_map.data() };
+    let offset = y as usize * bo_map.stride() as usize + x as usize * 4;
+    let pixel = &data[offset..offset + 4];
+    Ok([pixel[2], pixel[1], pixel[0]])
+}
+
+pub type AllocatorFactory<'a> =
+    Box<dyn FnOnce() -> Result<Rc<dyn Allocator>, ScreenshotError> + 'a>;
+
+/// Returns constructors for the allocators that can access buffers of the drm device.
+///
+/// The allocators should be tried in order.
+pub fn allocators(drm_dev: Option<&Rc<OwnedFd>>) -> Vec<AllocatorFactory<'_>> {
+    let mut allocators = Vec::<AllocatorFactory>::new();
     match drm_dev {
         Some(drm_dev) => {
             let drm = || Drm::reopen(drm_dev.raw(), false).map_err(ScreenshotError::OpenDrmDevice);
@@ -187,51 +567,28 @@ pub fn buf_to_bytes(
             allocators.push(udmabuf);
         }
     }
-    let bo_map = 'create_bo_map: {
-        for allocator in allocators {
-            let allocator = match allocator() {
-                Ok(a) => a,
-                Err(e) => {
-                    log::error!("Could not create allocator: {}", ErrorFmt(e));
-                    continue;
-                }
-            };
-            match map(allocator, buf) {
-                Ok(m) => break 'create_bo_map m,
-                Err(e) => {
-                    log::error!("Could not map dmabuf: {}", ErrorFmt(e));
-                    continue;
-                }
-            };
-        }
-        return Err(ScreenshotError::MapDmabufAny);
-    };
-    let data = unsafe { bo_map.data() };
-    if format == ScreenshotFormat::Qoi {
-        return Ok(xrgb8888_encode_qoi(
-            data,
-            buf.width as _,
-            buf.height as _,
-            bo_map.stride() as u32,
-        ));
-    }
+    allocators
+}
 
-    let mut out = vec![];
-    {
-        let mut image_data = Vec::with_capacity((buf.width * buf.height * 4) as usize);
-        let lines = data[..(buf.height as usize * bo_map.stride() as usize)]
-            .chunks_exact(bo_map.stride() as usize);
-        for line in lines {
-            for pixel in line[..(buf.width as usize * 4)].array_chunks_ext::<4>() {
-                image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
+fn map_buf(
+    drm_dev: Option<&Rc<OwnedFd>>,
+    buf: &DmaBuf,
+) -> Result<Box<dyn MappedBuffer>, ScreenshotError> {
+    for allocator in allocators(drm_dev) {
+        let allocator = match allocator() {
+            Ok(a) => a,
+            Err(e) => {
+                log::error!("Could not create allocator: {}", ErrorFmt(e));
+                continue;
             }
-        }
-        let mut encoder = Encoder::new(&mut out, buf.width as _, buf.height as _);
-        encoder.set_color(ColorType::Rgba);
-        encoder.set_depth(BitDepth::Eight);
-        encoder.set_source_srgb(SrgbRenderingIntent::Perceptual);
-        let mut writer = encoder.write_header().unwrap();
-        writer.write_image_data(&image_data).unwrap();
+        };
+        match map(allocator, buf) {
+            Ok(m) => return Ok(m),
+            Err(e) => {
+                log::error!("Could not map dmabuf: {}", ErrorFmt(e));
+                continue;
+            }
+        };
     }
-    Ok(out)
+    Err(ScreenshotError::MapDmabufAny)
 }
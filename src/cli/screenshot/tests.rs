@@ -0,0 +1,30 @@
+use crate::{cli::screenshot::tone_map, cmm::cmm_manager::ColorManager};
+
+fn assert_close(actual: &[[f32; 3]], expected: &[[f32; 3]]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        for (a, e) in a.iter().zip(e) {
+            assert!((a - e).abs() < 1e-4, "{actual:?} != {expected:?}");
+        }
+    }
+}
+
+#[test]
+fn sdr_is_unchanged() {
+    let cm = ColorManager::new();
+    let mut pixels = vec![[0.0, 0.5, 1.0], [0.25, 0.25, 0.25]];
+    let expected = pixels.clone();
+    tone_map(&cm, cm.srgb_gamma22(), &mut pixels);
+    assert_close(&pixels, &expected);
+}
+
+#[test]
+fn peak_maps_to_one() {
+    let cm = ColorManager::new();
+    let mut pixels = vec![[4.0, 2.0, 0.0], [1.0, 1.0, 1.0], [0.0, 0.0, 0.0]];
+    tone_map(&cm, cm.srgb_gamma22(), &mut pixels);
+    assert_close(
+        &pixels,
+        &[[1.0, 0.5, 0.0], [0.25, 0.25, 0.25], [0.0, 0.0, 0.0]],
+    );
+}
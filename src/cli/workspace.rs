@@ -0,0 +1,66 @@
+use {
+    crate::{
+        cli::GlobalArgs,
+        tools::tool_client::{Handle, ToolClient, with_tool_client},
+        wire::jay_compositor,
+    },
+    clap::{Args, Subcommand},
+    std::{cell::RefCell, rc::Rc},
+};
+
+#[derive(Args, Debug)]
+pub struct WorkspaceArgs {
+    #[clap(subcommand)]
+    pub command: WorkspaceCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WorkspaceCmd {
+    /// Rename a workspace.
+    Rename(RenameArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RenameArgs {
+    /// The current name of the workspace.
+    pub old_name: String,
+    /// The new name of the workspace.
+    pub new_name: String,
+}
+
+pub fn main(global: GlobalArgs, args: WorkspaceArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let workspace = Workspace { tc: tc.clone() };
+        workspace.run(args).await;
+    });
+}
+
+struct Workspace {
+    tc: Rc<ToolClient>,
+}
+
+impl Workspace {
+    async fn run(self, args: WorkspaceArgs) {
+        match args.command {
+            WorkspaceCmd::Rename(args) => self.rename(args).await,
+        }
+    }
+
+    async fn rename(self, args: RenameArgs) {
+        let tc = &self.tc;
+        let comp = tc.jay_compositor().await;
+        let error = Rc::new(RefCell::new(None));
+        jay_compositor::RenameWorkspaceFailed::handle(tc, comp, error.clone(), |e, msg| {
+            *e.borrow_mut() = Some(msg.msg.to_string());
+        });
+        tc.send(jay_compositor::RenameWorkspace {
+            self_id: comp,
+            old_name: &args.old_name,
+            new_name: &args.new_name,
+        });
+        tc.round_trip().await;
+        if let Some(msg) = error.borrow_mut().take() {
+            fatal!("Could not rename the workspace: {}", msg);
+        }
+    }
+}
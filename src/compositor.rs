@@ -55,9 +55,10 @@ use {
         tasks::{self, handle_const_40hz_latch, idle},
         tracy::enable_profiler,
         tree::{
-            DisplayNode, NodeIds, OutputNode, TearingMode, VrrMode, WorkspaceNode,
+            ContainerSplit, DisplayNode, NodeIds, OutputNode, TearingMode, VrrMode, WorkspaceNode,
             container_layout, container_render_positions, container_render_titles, float_layout,
-            float_titles, output_render_data, placeholder_render_textures,
+            float_titles, output_render_data, placeholder_render_textures, scrolling_layout,
+            workspace_layout,
         },
         user_session::import_environment,
         utils::{
@@ -85,7 +86,15 @@ use {
         video::{GfxApi, Transform},
         workspace::WorkspaceDisplayOrder,
     },
-    std::{cell::Cell, env, future::Future, ops::Deref, rc::Rc, sync::Arc, time::Duration},
+    std::{
+        cell::{Cell, RefCell},
+        env,
+        future::Future,
+        ops::Deref,
+        rc::Rc,
+        sync::Arc,
+        time::Duration,
+    },
     thiserror::Error,
     uapi::c,
 };
@@ -223,6 +232,7 @@ fn start_compositor2(
         globals: Globals::new(),
         connector_ids: Default::default(),
         root: Rc::new(DisplayNode::new(node_ids.next())),
+        sticky_floats: Default::default(),
         workspaces: Default::default(),
         dummy_output: Default::default(),
         node_ids,
@@ -241,6 +251,8 @@ fn start_compositor2(
         pending_container_render_title: Default::default(),
         pending_output_render_data: Default::default(),
         pending_float_layout: Default::default(),
+        pending_workspace_layout: Default::default(),
+        pending_scrolling_layout: Default::default(),
         pending_float_titles: Default::default(),
         pending_input_popup_positioning: Default::default(),
         pending_toplevel_screencasts: Default::default(),
@@ -295,6 +307,7 @@ fn start_compositor2(
         scales,
         cursor_sizes: Default::default(),
         hardware_tick_cursor: Default::default(),
+        hardware_cursor_image_serial: Default::default(),
         testers: Default::default(),
         render_ctx_watchers: Default::default(),
         workspace_watchers: Default::default(),
@@ -345,6 +358,12 @@ fn start_compositor2(
         float_above_fullscreen: Cell::new(false),
         icons: Default::default(),
         show_pin_icon: Cell::new(false),
+        float_placement: Default::default(),
+        float_snap_threshold: Cell::new(0),
+        portal_settings: Default::default(),
+        global_shortcuts: Default::default(),
+        global_shortcut_capture: Default::default(),
+        input_captures: Default::default(),
         cl_matcher_manager: ClMatcherManager::new(&crit_ids),
         tl_matcher_manager: TlMatcherManager::new(&crit_ids),
         caps_thread,
@@ -356,6 +375,11 @@ fn start_compositor2(
         head_managers: Default::default(),
         head_managers_async: Default::default(),
         show_bar: Cell::new(true),
+        magnifier_in_screencasts: Cell::new(true),
+        hot_corners: Default::default(),
+        default_workspace_layout: Default::default(),
+        workspace_layouts: Default::default(),
+        workspace_definitions: Default::default(),
         enable_primary_selection: Cell::new(true),
         xdg_surface_configure_events: Default::default(),
         workspace_display_order: Cell::new(WorkspaceDisplayOrder::Manual),
@@ -490,6 +514,16 @@ fn start_global_event_handlers(state: &Rc<State>) -> Vec<SpawnedFuture<()>> {
             wlr_output_manager_done(state.clone()),
         ),
         eng.spawn2("float layout", Phase::Layout, float_layout(state.clone())),
+        eng.spawn2(
+            "workspace layout",
+            Phase::Layout,
+            workspace_layout(state.clone()),
+        ),
+        eng.spawn2(
+            "scrolling layout",
+            Phase::Layout,
+            scrolling_layout(state.clone()),
+        ),
         eng.spawn2(
             "float titles",
             Phase::PostLayout,
@@ -760,6 +794,11 @@ fn create_dummy_output(state: &Rc<State>) {
         ext_workspace_groups: Default::default(),
         pinned: Default::default(),
         tearing: Default::default(),
+        zoom: Cell::new(1.0),
+        unmagnified: Default::default(),
+        cursorless: Default::default(),
+        overview: Default::default(),
+        window_switcher: Default::default(),
     });
     let dummy_workspace = Rc::new(WorkspaceNode {
         id: state.node_ids.next(),
@@ -769,9 +808,10 @@ fn create_dummy_output(state: &Rc<State>) {
         output_id: Cell::new(dummy_output.id),
         position: Default::default(),
         container: Default::default(),
+        scrolling: Default::default(),
         stacked: Default::default(),
         seat_state: Default::default(),
-        name: "dummy".to_string(),
+        name: RefCell::new("dummy".to_string()),
         output_link: Default::default(),
         visible: Default::default(),
         fullscreen: Default::default(),
@@ -785,6 +825,12 @@ fn create_dummy_output(state: &Rc<State>) {
         render_highlight: Default::default(),
         ext_workspaces: Default::default(),
         opt: Default::default(),
+        layout: Default::default(),
+        master_count: Cell::new(1),
+        master_ratio: Cell::new(0.5),
+        layout_scheduled: Cell::new(false),
+        split: Cell::new(ContainerSplit::Horizontal),
+        gap: Cell::new(0),
     });
     *dummy_workspace.output_link.borrow_mut() =
         Some(dummy_output.workspaces.add_last(dummy_workspace.clone()));
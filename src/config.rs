@@ -25,7 +25,7 @@ use {
             ConfigEntry, VERSION, bincode_ops,
             ipc::{InitMessage, ServerFeature, ServerMessage, V1InitMessage},
         },
-        input::{InputDevice, Seat, SwitchEvent},
+        input::{HotCorner, InputDevice, Seat, SwitchEvent, TabletPadElement, TabletPadEvent},
         keyboard::{mods::Modifiers, syms::KeySym},
         video::{Connector, DrmDevice},
         window::{self, TileState},
@@ -161,6 +161,33 @@ impl ConfigProxy {
         });
     }
 
+    pub fn tablet_pad_event(
+        &self,
+        input_device: InputDeviceId,
+        element: TabletPadElement,
+        event: TabletPadEvent,
+    ) {
+        self.send(&ServerMessage::TabletPadEvent {
+            input_device: InputDevice(input_device.raw() as _),
+            element,
+            event,
+        });
+    }
+
+    pub fn hot_corner_triggered(&self, corner: u64, seat: SeatId, connector: ConnectorId) {
+        self.send(&ServerMessage::HotCornerTriggered {
+            corner: HotCorner(corner),
+            seat: Seat(seat.raw() as _),
+            connector: Connector(connector.raw() as _),
+        });
+    }
+
+    pub fn key_sequence_cancelled(&self, seat: SeatId) {
+        self.send(&ServerMessage::KeySequenceCancelled {
+            seat: Seat(seat.raw() as _),
+        });
+    }
+
     pub fn toplevel_removed(&self, id: ToplevelIdentifier) {
         let Some(handler) = self.handler.get() else {
             return;
@@ -170,6 +197,12 @@ impl ConfigProxy {
         }
     }
 
+    pub fn workspace_renamed(&self, old: &str, new: &str) {
+        if let Some(handler) = self.handler.get() {
+            handler.workspace_renamed(old, new);
+        }
+    }
+
     pub fn auto_focus(&self, data: &ToplevelData) -> bool {
         let Some(handler) = self.handler.get() else {
             return true;
@@ -180,6 +213,20 @@ impl ConfigProxy {
     pub fn initial_tile_state(&self, data: &ToplevelData) -> Option<TileState> {
         self.handler.get()?.initial_tile_state(data)
     }
+
+    pub fn swallow(&self, data: &ToplevelData) -> bool {
+        let Some(handler) = self.handler.get() else {
+            return false;
+        };
+        handler.swallow(data)
+    }
+
+    pub fn swallowable(&self, data: &ToplevelData) -> bool {
+        let Some(handler) = self.handler.get() else {
+            return true;
+        };
+        handler.swallowable(data)
+    }
 }
 
 impl Drop for ConfigProxy {
@@ -245,6 +292,8 @@ impl ConfigProxy {
             window_matcher_std_kinds: state.tl_matcher_manager.kind(window::CLIENT_WINDOW),
             window_matcher_no_auto_focus: Default::default(),
             window_matcher_initial_tile_state: Default::default(),
+            window_matcher_swallow: Default::default(),
+            window_matcher_no_swallowable: Default::default(),
         });
         let init_msg = bincode_ops()
             .serialize(&InitMessage::V1(V1InitMessage {}))
@@ -18,7 +18,12 @@ use {
         format::config_formats,
         ifs::{
             wl_output::BlendSpace,
-            wl_seat::{SeatId, WlSeatGlobal},
+            wl_seat::{
+                SeatId, WlSeatGlobal,
+                hot_corner::HotCorner,
+                mouse_button_code,
+                tablet::{PressureCurve, TabletOutputArea},
+            },
             wp_content_type_v1::ContentTypeExt,
         },
         io_uring::TaskResultExt,
@@ -28,10 +33,11 @@ use {
         state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
         theme::{Color, ThemeSized},
         tree::{
-            ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase, OutputNode,
-            TearingMode, ToplevelData, ToplevelNode, VrrMode, WorkspaceNode, WsMoveConfig,
-            move_ws_to_output, toplevel_create_split, toplevel_parent_container,
-            toplevel_set_floating, toplevel_set_workspace,
+            ContainerNode, ContainerSplit, FloatNode, MAX_MASTER_RATIO, MIN_MASTER_RATIO, Node,
+            NodeVisitorBase, OutputNode, ScrollingNode, TearingMode, ToplevelData, ToplevelNode,
+            VrrMode, WorkspaceNode, WorkspaceRenameError, WsMoveConfig, move_ws_to_output,
+            toplevel_create_split, toplevel_parent_container, toplevel_set_floating,
+            toplevel_set_workspace, toplevel_swap,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -50,12 +56,13 @@ use {
         _private::{
             ClientCriterionIpc, ClientCriterionStringField, GenericCriterionIpc, PollableId,
             WindowCriterionIpc, WindowCriterionStringField, WireMode, bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage, WorkspaceSource},
+            ipc::{ClientMessage, Response, SeatZoom, ServerMessage, WorkspaceSource},
         },
         Axis, Direction, Workspace,
         client::{Client as ConfigClient, ClientMatcher},
         input::{
-            FocusFollowsMouseMode, InputDevice, LayerDirection, Seat, Timeline,
+            FocusFollowsMouseMode, HotCorner as ConfigHotCorner, InputDevice, LayerDirection,
+            MouseButton, ScreenEdge, Seat, TabletPadElement, Timeline,
             acceleration::{ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_FLAT, AccelProfile},
             capability::{
                 CAP_GESTURE, CAP_KEYBOARD, CAP_POINTER, CAP_SWITCH, CAP_TABLET_PAD,
@@ -67,6 +74,7 @@ use {
         },
         keyboard::{Keymap, mods::Modifiers, syms::KeySym},
         logging::LogLevel,
+        portal::{ColorScheme, Contrast},
         theme::{colors::Colorable, sized::Resizable},
         timer::Timer as JayTimer,
         video::{
@@ -74,8 +82,8 @@ use {
             Format as ConfigFormat, GfxApi, TearingMode as ConfigTearingMode, Transform,
             VrrMode as ConfigVrrMode,
         },
-        window::{TileState, Window, WindowMatcher},
-        workspace::WorkspaceDisplayOrder,
+        window::{FloatPlacement, FloatSnap, TileState, Window, WindowMatcher},
+        workspace::{WorkspaceDisplayOrder, WorkspaceLayout},
         xwayland::XScalingMode,
     },
     kbvm::Keycode,
@@ -143,6 +151,10 @@ pub(super) struct ConfigProxyHandler {
             TileState,
         ),
     >,
+    pub window_matcher_swallow:
+        CopyHashMap<WindowMatcher, Rc<CachedCriterion<WindowCriterionIpc, ToplevelData>>>,
+    pub window_matcher_no_swallowable:
+        CopyHashMap<WindowMatcher, Rc<CachedCriterion<WindowCriterionIpc, ToplevelData>>>,
 }
 
 pub struct Pollable {
@@ -207,6 +219,10 @@ impl ConfigProxyHandler {
         self.window_matcher_leafs.clear();
         self.window_matchers.clear();
 
+        self.state.hot_corners.clear();
+        self.state.workspace_layouts.clear();
+        self.state.workspace_definitions.clear();
+
         if let Some(path) = &self.path
             && let Err(e) = uapi::unlink(path.as_str())
         {
@@ -389,6 +405,13 @@ impl ConfigProxyHandler {
             for seat in self.state.globals.seats.lock().values() {
                 seat.clear_shortcuts();
             }
+            for dev in self.state.input_device_handlers.borrow().values() {
+                let data = &dev.data;
+                data.tablet_output_area.set(Default::default());
+                data.tablet_pressure_curve.set(None);
+                data.tablet_tool_buttons.clear();
+                data.tablet_pad_bindings.clear();
+            }
         }
         config.configure(true);
         self.state.config.set(Some(Rc::new(config)));
@@ -537,7 +560,7 @@ impl ConfigProxyHandler {
     fn handle_get_workspaces(&self) {
         let mut workspaces = vec![];
         for ws in self.state.workspaces.lock().values() {
-            workspaces.push(self.get_workspace_by_name(&ws.name));
+            workspaces.push(self.get_workspace_by_name(&ws.name.borrow()));
         }
         self.respond(Response::GetWorkspaces { workspaces });
     }
@@ -621,6 +644,54 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_swap(&self, seat: Seat, direction: Direction) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.swap(direction.into());
+        Ok(())
+    }
+
+    fn handle_seat_toggle_overview(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.toggle_overview();
+        Ok(())
+    }
+
+    fn handle_seat_set_overview_swipe_enabled(
+        &self,
+        seat: Seat,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_overview_swipe_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_seat_set_overview_swipe_fingers(
+        &self,
+        seat: Seat,
+        fingers: u32,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        if fingers < 3 {
+            return Err(CphError::InvalidSwipeFingers(fingers));
+        }
+        seat.set_overview_swipe_fingers(fingers);
+        Ok(())
+    }
+
+    fn handle_seat_release_input_capture(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.deactivate_input_capture(None);
+        Ok(())
+    }
+
+    fn handle_window_swap(&self, window: Window, other: Window) -> Result<(), CphError> {
+        let window = self.get_window(window)?;
+        let other = self.get_window(other)?;
+        toplevel_swap(window, other);
+        Ok(())
+    }
+
     fn handle_window_move(&self, window: Window, direction: Direction) -> Result<(), CphError> {
         let window = self.get_window(window)?;
         if let Some(c) = toplevel_parent_container(&*window) {
@@ -786,6 +857,86 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_tablet_output_area(
+        &self,
+        device: InputDevice,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let valid = [x, y, width, height].iter().all(|v| v.is_finite())
+            && x >= 0.0
+            && y >= 0.0
+            && width > 0.0
+            && height > 0.0
+            && x + width <= 1.0
+            && y + height <= 1.0;
+        if !valid {
+            return Err(CphError::InvalidTabletOutputArea);
+        }
+        dev.tablet_output_area.set(TabletOutputArea {
+            x,
+            y,
+            width,
+            height,
+        });
+        Ok(())
+    }
+
+    fn handle_set_tablet_pressure_curve(
+        &self,
+        device: InputDevice,
+        curve: [f64; 4],
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        if curve.iter().any(|v| !(0.0..=1.0).contains(v)) {
+            return Err(CphError::InvalidPressureCurve);
+        }
+        let [x1, y1, x2, y2] = curve;
+        let curve = PressureCurve { x1, y1, x2, y2 };
+        let linear = x1 == y1 && x2 == y2;
+        dev.tablet_pressure_curve.set((!linear).then_some(curve));
+        Ok(())
+    }
+
+    fn handle_set_tablet_tool_button_mapping(
+        &self,
+        device: InputDevice,
+        button: u32,
+        mouse_button: Option<MouseButton>,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        match mouse_button {
+            Some(mb) => {
+                dev.tablet_tool_buttons.set(button, mouse_button_code(mb));
+            }
+            None => {
+                dev.tablet_tool_buttons.remove(&button);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_set_tablet_pad_binding(
+        &self,
+        device: InputDevice,
+        element: TabletPadElement,
+        bound: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        match bound {
+            true => {
+                dev.tablet_pad_bindings.set(element, ());
+            }
+            false => {
+                dev.tablet_pad_bindings.remove(&element);
+            }
+        }
+        Ok(())
+    }
+
     fn handle_set_tap_enabled(&self, device: InputDevice, enabled: bool) -> Result<(), CphError> {
         let dev = self.get_device_handler_data(device)?;
         dev.device.set_tap_enabled(enabled);
@@ -923,6 +1074,54 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_color_scheme(&self, scheme: ColorScheme) -> Result<(), CphError> {
+        match scheme {
+            ColorScheme::DEFAULT | ColorScheme::PREFER_DARK | ColorScheme::PREFER_LIGHT => {}
+            _ => return Err(CphError::UnknownColorScheme(scheme)),
+        }
+        let settings = &self.state.portal_settings;
+        if settings.color_scheme.replace(scheme) != scheme {
+            settings.changed();
+        }
+        Ok(())
+    }
+
+    fn handle_get_color_scheme(&self) {
+        self.respond(Response::GetColorScheme {
+            scheme: self.state.portal_settings.color_scheme.get(),
+        });
+    }
+
+    fn handle_set_accent_color(&self, color: Option<jay_config::theme::Color>) {
+        let color = color.map(|c| {
+            let [r, g, b, _] = c.to_f32_straight();
+            [r as f64, g as f64, b as f64]
+        });
+        let settings = &self.state.portal_settings;
+        if settings.accent_color.replace(color) != color {
+            settings.changed();
+        }
+    }
+
+    fn handle_set_contrast(&self, contrast: Contrast) -> Result<(), CphError> {
+        match contrast {
+            Contrast::NORMAL | Contrast::HIGH => {}
+            _ => return Err(CphError::UnknownContrast(contrast)),
+        }
+        let settings = &self.state.portal_settings;
+        if settings.contrast.replace(contrast) != contrast {
+            settings.changed();
+        }
+        Ok(())
+    }
+
+    fn handle_set_reduced_motion(&self, reduced: bool) {
+        let settings = &self.state.portal_settings;
+        if settings.reduced_motion.replace(reduced) != reduced {
+            settings.changed();
+        }
+    }
+
     fn handle_set_ui_drag_enabled(&self, enabled: bool) {
         self.state.ui_drag_enabled.set(enabled);
     }
@@ -973,7 +1172,7 @@ impl ConfigProxyHandler {
         if !output.is_dummy
             && let Some(ws) = output.workspace.get()
         {
-            workspace = self.get_workspace_by_name(&ws.name);
+            workspace = self.get_workspace_by_name(&ws.name.borrow());
         }
         self.respond(Response::GetSeatWorkspace { workspace });
         Ok(())
@@ -986,7 +1185,7 @@ impl ConfigProxyHandler {
             && !output.is_dummy
             && let Some(ws) = output.workspace.get()
         {
-            workspace = self.get_workspace_by_name(&ws.name);
+            workspace = self.get_workspace_by_name(&ws.name.borrow());
         }
         self.respond(Response::GetSeatKeyboardWorkspace { workspace });
         Ok(())
@@ -1004,7 +1203,11 @@ impl ConfigProxyHandler {
         let name = self.get_workspace(ws)?;
         let workspace = match self.state.workspaces.get(name.deref()) {
             Some(ws) => ws,
-            _ => seat.get_output().create_workspace(name.deref()),
+            _ => self
+                .state
+                .preferred_workspace_output(&name)
+                .unwrap_or_else(|| seat.get_output())
+                .create_workspace(name.deref()),
         };
         seat.set_workspace(&workspace);
         Ok(())
@@ -1015,7 +1218,11 @@ impl ConfigProxyHandler {
         let name = self.get_workspace(ws)?;
         let workspace = match self.state.workspaces.get(name.deref()) {
             Some(ws) => ws,
-            _ => match window.node_output() {
+            _ => match self
+                .state
+                .preferred_workspace_output(&name)
+                .or_else(|| window.node_output())
+            {
                 Some(o) => o.create_workspace(name.deref()),
                 _ => return Ok(()),
             },
@@ -1065,14 +1272,19 @@ impl ConfigProxyHandler {
                 _ => return Ok(()),
             },
         };
+        self.move_ws_to_output_node(&ws, &output);
+        Ok(())
+    }
+
+    fn move_ws_to_output_node(&self, ws: &Rc<WorkspaceNode>, output: &Rc<OutputNode>) {
         if ws.is_dummy || output.is_dummy {
-            return Ok(());
+            return;
         }
         if ws.output.get().id == output.id {
-            return Ok(());
+            return;
         }
         let link = match &*ws.output_link.borrow() {
-            None => return Ok(()),
+            None => return,
             Some(l) => l.to_ref(),
         };
         let config = WsMoveConfig {
@@ -1081,10 +1293,9 @@ impl ConfigProxyHandler {
             source_is_destroyed: false,
             before: None,
         };
-        move_ws_to_output(&link, &output, config);
+        move_ws_to_output(&link, output, config);
         ws.desired_output.set(output.global.output_id.clone());
         self.state.tree_changed();
-        Ok(())
     }
 
     fn handle_set_idle(&self, timeout: Duration) {
@@ -1405,6 +1616,56 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_seat_float_sticky(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::GetFloatSticky {
+            sticky: seat.sticky(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_seat_float_sticky(&self, seat: Seat, sticky: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_sticky(sticky);
+        Ok(())
+    }
+
+    fn handle_get_window_float_sticky(&self, window: Window) -> Result<(), CphError> {
+        let window = self.get_window(window)?;
+        self.respond(Response::GetFloatSticky {
+            sticky: window.tl_sticky(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_window_float_sticky(&self, window: Window, sticky: bool) -> Result<(), CphError> {
+        let window = self.get_window(window)?;
+        window.tl_set_sticky(true, sticky);
+        Ok(())
+    }
+
+    fn handle_set_float_placement(&self, placement: FloatPlacement) {
+        self.state.float_placement.set(placement);
+    }
+
+    fn handle_set_float_snap_threshold(&self, threshold: i32) {
+        self.state.float_snap_threshold.set(threshold.max(0));
+    }
+
+    fn handle_seat_snap_float(&self, seat: Seat, snap: FloatSnap) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.snap_float(snap);
+        Ok(())
+    }
+
+    fn handle_window_snap_float(&self, window: Window, snap: FloatSnap) -> Result<(), CphError> {
+        let window = self.get_window(window)?;
+        if let Some(float) = window.tl_data().float.get() {
+            float.snap(snap);
+        }
+        Ok(())
+    }
+
     fn handle_set_vrr_mode(
         &self,
         connector: Option<Connector>,
@@ -1527,10 +1788,9 @@ impl ConfigProxyHandler {
 
     fn handle_get_connector_active_workspace(&self, connector: Connector) -> Result<(), CphError> {
         let output = self.get_output_node(connector)?;
-        let workspace = output
-            .workspace
-            .get()
-            .map_or(Workspace(0), |ws| self.get_workspace_by_name(&ws.name));
+        let workspace = output.workspace.get().map_or(Workspace(0), |ws| {
+            self.get_workspace_by_name(&ws.name.borrow())
+        });
         self.respond(Response::GetConnectorActiveWorkspace { workspace });
         Ok(())
     }
@@ -1540,7 +1800,7 @@ impl ConfigProxyHandler {
         let workspaces = output
             .workspaces
             .iter()
-            .map(|ws| self.get_workspace_by_name(&ws.name))
+            .map(|ws| self.get_workspace_by_name(&ws.name.borrow()))
             .collect::<Vec<_>>();
         self.respond(Response::GetConnectorWorkspaces { workspaces });
         Ok(())
@@ -1756,6 +2016,42 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_cycle_column_width(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.cycle_column_width();
+        Ok(())
+    }
+
+    fn handle_seat_consume_into_column(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.consume_into_column();
+        Ok(())
+    }
+
+    fn handle_seat_expel_from_column(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.expel_from_column();
+        Ok(())
+    }
+
+    fn handle_rename_workspace(&self, workspace: Workspace, name: &str) -> Result<(), CphError> {
+        let old = self.get_workspace(workspace)?;
+        let Some(ws) = self.state.workspaces.get(&*old) else {
+            return Err(CphError::WorkspaceDoesNotExist(workspace));
+        };
+        ws.rename(name).map_err(CphError::RenameWorkspace)?;
+        Ok(())
+    }
+
+    pub fn workspace_renamed(&self, old: &str, new: &str) {
+        let Some(id) = self.workspaces_by_name.remove(&old.to_string()) else {
+            return;
+        };
+        let new = Rc::new(new.to_string());
+        self.workspaces_by_name.set(new.clone(), id);
+        self.workspaces_by_id.set(id, new);
+    }
+
     fn handle_quit(&self) {
         log::info!("Quitting");
         self.state.ring.stop();
@@ -2122,6 +2418,8 @@ impl ConfigProxyHandler {
         self.window_matcher_leafs.remove(&matcher);
         self.window_matcher_no_auto_focus.remove(&matcher);
         self.window_matcher_initial_tile_state.remove(&matcher);
+        self.window_matcher_swallow.remove(&matcher);
+        self.window_matcher_no_swallowable.remove(&matcher);
     }
 
     fn handle_enable_window_matcher_events(
@@ -2165,6 +2463,34 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_window_matcher_swallow(
+        &self,
+        matcher: WindowMatcher,
+        swallow: bool,
+    ) -> Result<(), CphError> {
+        if swallow {
+            let m = self.get_window_matcher(matcher)?;
+            self.window_matcher_swallow.set(matcher, m);
+        } else {
+            self.window_matcher_swallow.remove(&matcher);
+        }
+        Ok(())
+    }
+
+    fn handle_set_window_matcher_swallowable(
+        &self,
+        matcher: WindowMatcher,
+        swallowable: bool,
+    ) -> Result<(), CphError> {
+        if swallowable {
+            self.window_matcher_no_swallowable.remove(&matcher);
+        } else {
+            let m = self.get_window_matcher(matcher)?;
+            self.window_matcher_no_swallowable.set(matcher, m);
+        }
+        Ok(())
+    }
+
     fn handle_set_window_matcher_initial_tile_state(
         &self,
         matcher: WindowMatcher,
@@ -2210,6 +2536,25 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_window_switcher(&self, seat: Seat, timeline: Timeline) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        match timeline {
+            Timeline::Older => seat.window_switcher_prev(),
+            Timeline::Newer => seat.window_switcher_next(),
+        }
+        Ok(())
+    }
+
+    fn handle_seat_window_switcher_set_thumbnails(
+        &self,
+        seat: Seat,
+        thumbnails: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.window_switcher_set_thumbnails(thumbnails);
+        Ok(())
+    }
+
     fn handle_seat_focus_layer_rel(
         &self,
         seat: Seat,
@@ -2253,12 +2598,319 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_swap_with_mark(&self, seat: Seat, kc: Option<u32>) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        if let Some(kc) = kc {
+            seat.swap_with_mark(Keycode::from_evdev(kc));
+        } else {
+            seat.swap_with_mark_interactive();
+        }
+        Ok(())
+    }
+
+    fn handle_seat_move_to_mark(&self, seat: Seat, kc: Option<u32>) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        if let Some(kc) = kc {
+            seat.move_to_mark(Keycode::from_evdev(kc));
+        } else {
+            seat.move_to_mark_interactive();
+        }
+        Ok(())
+    }
+
     fn handle_seat_copy_mark(&self, seat: Seat, src: u32, dst: u32) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.copy_mark(Keycode::from_evdev(src), Keycode::from_evdev(dst));
         Ok(())
     }
 
+    fn handle_seat_mouse_keys_motion(
+        &self,
+        seat: Seat,
+        direction: Direction,
+        active: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.mouse_keys_set_motion(direction.into(), active);
+        Ok(())
+    }
+
+    fn handle_seat_mouse_keys_select_button(
+        &self,
+        seat: Seat,
+        button: MouseButton,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.mouse_keys_select_button(button);
+        Ok(())
+    }
+
+    fn handle_seat_mouse_keys_click(&self, seat: Seat, double: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.mouse_keys_click(double);
+        Ok(())
+    }
+
+    fn handle_seat_mouse_keys_press(&self, seat: Seat, pressed: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        match pressed {
+            true => seat.mouse_keys_press(),
+            false => seat.mouse_keys_release(),
+        }
+        Ok(())
+    }
+
+    fn handle_seat_set_mouse_keys_acceleration(
+        &self,
+        seat: Seat,
+        initial_speed: f64,
+        max_speed: f64,
+        time_to_max: Duration,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        for speed in [initial_speed, max_speed] {
+            if !speed.is_finite() || speed < 0.0 {
+                return Err(CphError::InvalidMouseKeysSpeed);
+            }
+        }
+        seat.mouse_keys_set_acceleration(initial_speed, max_speed, time_to_max.as_micros() as u64);
+        Ok(())
+    }
+
+    fn handle_seat_zoom(&self, seat: Seat, zoom: SeatZoom) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        match zoom {
+            SeatZoom::In => seat.zoom_in(),
+            SeatZoom::Out => seat.zoom_out(),
+            SeatZoom::Reset => seat.zoom_reset(),
+        }
+        Ok(())
+    }
+
+    fn handle_seat_set_zoom_step(&self, seat: Seat, step: f64) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        if !step.is_finite() || step <= 1.0 {
+            return Err(CphError::InvalidZoomStep(step));
+        }
+        seat.set_zoom_step(step);
+        Ok(())
+    }
+
+    fn handle_seat_set_zoom_scroll_modifiers(
+        &self,
+        seat: Seat,
+        mods: Option<Modifiers>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_zoom_scroll_mods(mods);
+        Ok(())
+    }
+
+    fn handle_seat_begin_key_sequence(
+        &self,
+        seat: Seat,
+        prefix: &str,
+        timeout_ms: u64,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.begin_key_sequence(prefix, timeout_ms);
+        Ok(())
+    }
+
+    fn handle_seat_end_key_sequence(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.end_key_sequence();
+        Ok(())
+    }
+
+    fn handle_create_hot_corner(
+        &self,
+        edge: ScreenEdge,
+        dwell: Duration,
+        pressure: f64,
+    ) -> Result<(), CphError> {
+        if !pressure.is_finite() || pressure < 0.0 {
+            return Err(CphError::InvalidHotCornerPressure(pressure));
+        }
+        let id = self.id();
+        self.state.hot_corners.set(
+            id,
+            Rc::new(HotCorner {
+                id,
+                edge,
+                dwell_usec: dwell.as_micros() as u64,
+                pressure,
+            }),
+        );
+        self.respond(Response::CreateHotCorner {
+            corner: ConfigHotCorner(id),
+        });
+        Ok(())
+    }
+
+    fn handle_remove_hot_corner(&self, corner: ConfigHotCorner) {
+        self.state.hot_corners.remove(&corner.0);
+    }
+
+    fn handle_set_default_workspace_layout(&self, layout: WorkspaceLayout) {
+        self.state.default_workspace_layout.set(layout);
+    }
+
+    fn handle_set_workspace_layout(
+        &self,
+        workspace: Workspace,
+        layout: WorkspaceLayout,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        self.state.workspace_layouts.set(name.to_string(), layout);
+        if let Some(ws) = self.state.workspaces.get(&*name) {
+            ws.set_layout(layout);
+        }
+        Ok(())
+    }
+
+    fn handle_set_workspace_persistent(
+        &self,
+        workspace: Workspace,
+        persistent: bool,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        self.state
+            .workspace_definition(&name)
+            .persistent
+            .set(persistent);
+        self.state.create_persistent_workspaces();
+        Ok(())
+    }
+
+    fn handle_set_workspace_preferred_outputs(
+        &self,
+        workspace: Workspace,
+        outputs: Vec<String>,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        *self.state.workspace_definition(&name).outputs.borrow_mut() = outputs;
+        if let Some(ws) = self.state.workspaces.get(&*name)
+            && let Some(output) = self.state.preferred_workspace_output(&name)
+        {
+            self.move_ws_to_output_node(&ws, &output);
+        }
+        Ok(())
+    }
+
+    fn handle_set_workspace_default_split(
+        &self,
+        workspace: Workspace,
+        axis: Axis,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        self.state
+            .workspace_definition(&name)
+            .split
+            .set(axis.into());
+        if let Some(ws) = self.state.workspaces.get(&*name) {
+            ws.split.set(axis.into());
+        }
+        Ok(())
+    }
+
+    fn handle_set_workspace_gap(&self, workspace: Workspace, gap: i32) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        let gap = gap.max(0);
+        self.state.workspace_definition(&name).gap.set(gap);
+        if let Some(ws) = self.state.workspaces.get(&*name) {
+            ws.set_gap(gap);
+        }
+        Ok(())
+    }
+
+    fn handle_get_workspace_layout(&self, workspace: Workspace) -> Result<(), CphError> {
+        let layout = match self.get_existing_workspace(workspace)? {
+            Some(ws) => ws.layout.get(),
+            None => {
+                let name = self.get_workspace(workspace)?;
+                self.state
+                    .workspace_layouts
+                    .get(&*name)
+                    .unwrap_or(self.state.default_workspace_layout.get())
+            }
+        };
+        self.respond(Response::GetWorkspaceLayout { layout });
+        Ok(())
+    }
+
+    fn handle_set_workspace_master_count(
+        &self,
+        workspace: Workspace,
+        count: u32,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        self.state
+            .workspace_definition(&name)
+            .master_count
+            .set(count);
+        if let Some(ws) = self.state.workspaces.get(&*name) {
+            ws.set_master_count(count);
+        }
+        Ok(())
+    }
+
+    fn handle_get_workspace_master_count(&self, workspace: Workspace) -> Result<(), CphError> {
+        let count = match self.get_existing_workspace(workspace)? {
+            Some(ws) => ws.master_count.get(),
+            None => {
+                let name = self.get_workspace(workspace)?;
+                self.state
+                    .workspace_definitions
+                    .get(&*name)
+                    .map(|d| d.master_count.get())
+                    .unwrap_or(1)
+            }
+        };
+        self.respond(Response::GetWorkspaceMasterCount { count });
+        Ok(())
+    }
+
+    fn handle_set_workspace_master_ratio(
+        &self,
+        workspace: Workspace,
+        ratio: f64,
+    ) -> Result<(), CphError> {
+        if !ratio.is_finite() {
+            return Err(CphError::InvalidMasterRatio(ratio));
+        }
+        let name = self.get_workspace(workspace)?;
+        let ratio = ratio.clamp(MIN_MASTER_RATIO, MAX_MASTER_RATIO);
+        self.state
+            .workspace_definition(&name)
+            .master_ratio
+            .set(ratio);
+        if let Some(ws) = self.state.workspaces.get(&*name) {
+            ws.set_master_ratio(ratio);
+        }
+        Ok(())
+    }
+
+    fn handle_get_workspace_master_ratio(&self, workspace: Workspace) -> Result<(), CphError> {
+        let ratio = match self.get_existing_workspace(workspace)? {
+            Some(ws) => ws.master_ratio.get(),
+            None => {
+                let name = self.get_workspace(workspace)?;
+                self.state
+                    .workspace_definitions
+                    .get(&*name)
+                    .map(|d| d.master_ratio.get())
+                    .unwrap_or(0.5)
+            }
+        };
+        self.respond(Response::GetWorkspaceMasterRatio { ratio });
+        Ok(())
+    }
+
+    fn handle_set_magnifier_in_screencasts(&self, show: bool) {
+        self.state.magnifier_in_screencasts.set(show);
+    }
+
     fn spaces_change(&self) {
         struct V;
         impl NodeVisitorBase for V {
@@ -2274,6 +2926,10 @@ impl ConfigProxyHandler {
                 node.on_spaces_changed();
                 node.node_visit_children(self);
             }
+            fn visit_scrolling(&mut self, node: &Rc<ScrollingNode>) {
+                node.on_spaces_changed();
+                node.node_visit_children(self);
+            }
         }
         self.state.root.clone().node_visit(&mut V);
         self.state.damage(self.state.root.extents.get());
@@ -2437,7 +3093,10 @@ impl ConfigProxyHandler {
     fn handle_get_workspace_window(&self, ws: Workspace) -> Result<(), CphError> {
         let window = self
             .get_existing_workspace(ws)?
-            .and_then(|ws| ws.container.get())
+            .and_then(|ws| match ws.scrolling.get() {
+                Some(s) => s.active_column(),
+                _ => ws.container.get().map(|c| c as Rc<dyn ToplevelNode>),
+            })
             .map(|c| self.tl_to_window(&*c))
             .unwrap_or(Window(0));
         self.respond(Response::GetWorkspaceWindow { window });
@@ -2545,7 +3204,7 @@ impl ConfigProxyHandler {
             .tl_data()
             .workspace
             .get()
-            .map(|ws| self.get_workspace_by_name(&ws.name))
+            .map(|ws| self.get_workspace_by_name(&ws.name.borrow()))
             .unwrap_or(Workspace(0));
         self.respond(Response::GetWindowWorkspace { workspace });
         Ok(())
@@ -2937,6 +3596,15 @@ impl ConfigProxyHandler {
             ClientMessage::SetXScalingMode { mode } => self
                 .handle_set_x_scaling_mode(mode)
                 .wrn("set_x_scaling_mode")?,
+            ClientMessage::SetColorScheme { scheme } => self
+                .handle_set_color_scheme(scheme)
+                .wrn("set_color_scheme")?,
+            ClientMessage::GetColorScheme => self.handle_get_color_scheme(),
+            ClientMessage::SetAccentColor { color } => self.handle_set_accent_color(color),
+            ClientMessage::SetContrast { contrast } => {
+                self.handle_set_contrast(contrast).wrn("set_contrast")?
+            }
+            ClientMessage::SetReducedMotion { reduced } => self.handle_set_reduced_motion(reduced),
             ClientMessage::SetIdleGracePeriod { period } => {
                 self.handle_set_idle_grace_period(period)
             }
@@ -3087,6 +3755,15 @@ impl ConfigProxyHandler {
             } => self
                 .handle_set_window_matcher_initial_tile_state(matcher, tile_state)
                 .wrn("set_window_matcher_initial_tile_state")?,
+            ClientMessage::SetWindowMatcherSwallow { matcher, swallow } => self
+                .handle_set_window_matcher_swallow(matcher, swallow)
+                .wrn("set_window_matcher_swallow")?,
+            ClientMessage::SetWindowMatcherSwallowable {
+                matcher,
+                swallowable,
+            } => self
+                .handle_set_window_matcher_swallowable(matcher, swallowable)
+                .wrn("set_window_matcher_swallowable")?,
             ClientMessage::SetPointerRevertKey { seat, key } => self
                 .handle_set_pointer_revert_key(seat, key)
                 .wrn("set_pointer_revert_key")?,
@@ -3140,6 +3817,193 @@ impl ConfigProxyHandler {
             } => self
                 .handle_connector_set_blend_space(connector, blend_space)
                 .wrn("connector_set_blend_space")?,
+            ClientMessage::SeatMouseKeysMotion {
+                seat,
+                direction,
+                active,
+            } => self
+                .handle_seat_mouse_keys_motion(seat, direction, active)
+                .wrn("seat_mouse_keys_motion")?,
+            ClientMessage::SeatMouseKeysSelectButton { seat, button } => self
+                .handle_seat_mouse_keys_select_button(seat, button)
+                .wrn("seat_mouse_keys_select_button")?,
+            ClientMessage::SeatMouseKeysClick { seat, double } => self
+                .handle_seat_mouse_keys_click(seat, double)
+                .wrn("seat_mouse_keys_click")?,
+            ClientMessage::SeatMouseKeysPress { seat, pressed } => self
+                .handle_seat_mouse_keys_press(seat, pressed)
+                .wrn("seat_mouse_keys_press")?,
+            ClientMessage::SeatSetMouseKeysAcceleration {
+                seat,
+                initial_speed,
+                max_speed,
+                time_to_max,
+            } => self
+                .handle_seat_set_mouse_keys_acceleration(
+                    seat,
+                    initial_speed,
+                    max_speed,
+                    time_to_max,
+                )
+                .wrn("seat_set_mouse_keys_acceleration")?,
+            ClientMessage::SeatZoom { seat, zoom } => {
+                self.handle_seat_zoom(seat, zoom).wrn("seat_zoom")?
+            }
+            ClientMessage::SeatSetZoomStep { seat, step } => self
+                .handle_seat_set_zoom_step(seat, step)
+                .wrn("seat_set_zoom_step")?,
+            ClientMessage::SeatSetZoomScrollModifiers { seat, mods } => self
+                .handle_seat_set_zoom_scroll_modifiers(seat, mods)
+                .wrn("seat_set_zoom_scroll_modifiers")?,
+            ClientMessage::SetMagnifierInScreencasts { show } => {
+                self.handle_set_magnifier_in_screencasts(show)
+            }
+            ClientMessage::SetTabletOutputArea {
+                device,
+                x,
+                y,
+                width,
+                height,
+            } => self
+                .handle_set_tablet_output_area(device, x, y, width, height)
+                .wrn("set_tablet_output_area")?,
+            ClientMessage::SetTabletPressureCurve { device, curve } => self
+                .handle_set_tablet_pressure_curve(device, curve)
+                .wrn("set_tablet_pressure_curve")?,
+            ClientMessage::SetTabletToolButtonMapping {
+                device,
+                button,
+                mouse_button,
+            } => self
+                .handle_set_tablet_tool_button_mapping(device, button, mouse_button)
+                .wrn("set_tablet_tool_button_mapping")?,
+            ClientMessage::SetTabletPadBinding {
+                device,
+                element,
+                bound,
+            } => self
+                .handle_set_tablet_pad_binding(device, element, bound)
+                .wrn("set_tablet_pad_binding")?,
+            ClientMessage::SeatBeginKeySequence {
+                seat,
+                prefix,
+                timeout_ms,
+            } => self
+                .handle_seat_begin_key_sequence(seat, prefix, timeout_ms)
+                .wrn("seat_begin_key_sequence")?,
+            ClientMessage::SeatEndKeySequence { seat } => self
+                .handle_seat_end_key_sequence(seat)
+                .wrn("seat_end_key_sequence")?,
+            ClientMessage::CreateHotCorner {
+                edge,
+                dwell,
+                pressure,
+            } => self
+                .handle_create_hot_corner(edge, dwell, pressure)
+                .wrn("create_hot_corner")?,
+            ClientMessage::RemoveHotCorner { corner } => self.handle_remove_hot_corner(corner),
+            ClientMessage::SetDefaultWorkspaceLayout { layout } => {
+                self.handle_set_default_workspace_layout(layout)
+            }
+            ClientMessage::SetWorkspaceLayout { workspace, layout } => self
+                .handle_set_workspace_layout(workspace, layout)
+                .wrn("set_workspace_layout")?,
+            ClientMessage::GetWorkspaceLayout { workspace } => self
+                .handle_get_workspace_layout(workspace)
+                .wrn("get_workspace_layout")?,
+            ClientMessage::SetWorkspaceMasterCount { workspace, count } => self
+                .handle_set_workspace_master_count(workspace, count)
+                .wrn("set_workspace_master_count")?,
+            ClientMessage::GetWorkspaceMasterCount { workspace } => self
+                .handle_get_workspace_master_count(workspace)
+                .wrn("get_workspace_master_count")?,
+            ClientMessage::SetWorkspaceMasterRatio { workspace, ratio } => self
+                .handle_set_workspace_master_ratio(workspace, ratio)
+                .wrn("set_workspace_master_ratio")?,
+            ClientMessage::GetWorkspaceMasterRatio { workspace } => self
+                .handle_get_workspace_master_ratio(workspace)
+                .wrn("get_workspace_master_ratio")?,
+            ClientMessage::SeatCycleColumnWidth { seat } => self
+                .handle_seat_cycle_column_width(seat)
+                .wrn("seat_cycle_column_width")?,
+            ClientMessage::SeatConsumeIntoColumn { seat } => self
+                .handle_seat_consume_into_column(seat)
+                .wrn("seat_consume_into_column")?,
+            ClientMessage::SeatExpelFromColumn { seat } => self
+                .handle_seat_expel_from_column(seat)
+                .wrn("seat_expel_from_column")?,
+            ClientMessage::RenameWorkspace { workspace, name } => self
+                .handle_rename_workspace(workspace, name)
+                .wrn("rename_workspace")?,
+            ClientMessage::SetWorkspacePersistent {
+                workspace,
+                persistent,
+            } => self
+                .handle_set_workspace_persistent(workspace, persistent)
+                .wrn("set_workspace_persistent")?,
+            ClientMessage::SetWorkspacePreferredOutputs { workspace, outputs } => self
+                .handle_set_workspace_preferred_outputs(workspace, outputs)
+                .wrn("set_workspace_preferred_outputs")?,
+            ClientMessage::SetWorkspaceDefaultSplit { workspace, axis } => self
+                .handle_set_workspace_default_split(workspace, axis)
+                .wrn("set_workspace_default_split")?,
+            ClientMessage::SetWorkspaceGap { workspace, gap } => self
+                .handle_set_workspace_gap(workspace, gap)
+                .wrn("set_workspace_gap")?,
+            ClientMessage::SeatSwap { seat, direction } => {
+                self.handle_seat_swap(seat, direction).wrn("seat_swap")?
+            }
+            ClientMessage::SeatSwapWithMark { seat, kc } => self
+                .handle_seat_swap_with_mark(seat, kc)
+                .wrn("seat_swap_with_mark")?,
+            ClientMessage::SeatMoveToMark { seat, kc } => self
+                .handle_seat_move_to_mark(seat, kc)
+                .wrn("seat_move_to_mark")?,
+            ClientMessage::WindowSwap { window, other } => {
+                self.handle_window_swap(window, other).wrn("window_swap")?
+            }
+            ClientMessage::SeatToggleOverview { seat } => self
+                .handle_seat_toggle_overview(seat)
+                .wrn("seat_toggle_overview")?,
+            ClientMessage::SeatSetOverviewSwipeEnabled { seat, enabled } => self
+                .handle_seat_set_overview_swipe_enabled(seat, enabled)
+                .wrn("seat_set_overview_swipe_enabled")?,
+            ClientMessage::SeatSetOverviewSwipeFingers { seat, fingers } => self
+                .handle_seat_set_overview_swipe_fingers(seat, fingers)
+                .wrn("seat_set_overview_swipe_fingers")?,
+            ClientMessage::SeatReleaseInputCapture { seat } => self
+                .handle_seat_release_input_capture(seat)
+                .wrn("seat_release_input_capture")?,
+            ClientMessage::SeatWindowSwitcher { seat, timeline } => self
+                .handle_seat_window_switcher(seat, timeline)
+                .wrn("seat_window_switcher")?,
+            ClientMessage::SeatWindowSwitcherSetThumbnails { seat, thumbnails } => self
+                .handle_seat_window_switcher_set_thumbnails(seat, thumbnails)
+                .wrn("seat_window_switcher_set_thumbnails")?,
+            ClientMessage::GetSeatFloatSticky { seat } => self
+                .handle_get_seat_float_sticky(seat)
+                .wrn("get_seat_float_sticky")?,
+            ClientMessage::SetSeatFloatSticky { seat, sticky } => self
+                .handle_set_seat_float_sticky(seat, sticky)
+                .wrn("set_seat_float_sticky")?,
+            ClientMessage::GetWindowFloatSticky { window } => self
+                .handle_get_window_float_sticky(window)
+                .wrn("get_window_float_sticky")?,
+            ClientMessage::SetWindowFloatSticky { window, sticky } => self
+                .handle_set_window_float_sticky(window, sticky)
+                .wrn("set_window_float_sticky")?,
+            ClientMessage::SetFloatPlacement { placement } => {
+                self.handle_set_float_placement(placement)
+            }
+            ClientMessage::SetFloatSnapThreshold { threshold } => {
+                self.handle_set_float_snap_threshold(threshold)
+            }
+            ClientMessage::SeatSnapFloat { seat, snap } => self
+                .handle_seat_snap_float(seat, snap)
+                .wrn("seat_snap_float")?,
+            ClientMessage::WindowSnapFloat { window, snap } => self
+                .handle_window_snap_float(window, snap)
+                .wrn("window_snap_float")?,
         }
         Ok(())
     }
@@ -3161,6 +4025,24 @@ impl ConfigProxyHandler {
         }
         None
     }
+
+    pub fn swallow(&self, data: &ToplevelData) -> bool {
+        for matcher in self.window_matcher_swallow.lock().values() {
+            if matcher.node.pull(data) {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn swallowable(&self, data: &ToplevelData) -> bool {
+        for matcher in self.window_matcher_no_swallowable.lock().values() {
+            if matcher.node.pull(data) {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 #[derive(Debug, Error)]
@@ -3201,6 +4083,8 @@ enum CphError {
     DrmDeviceDoesNotExist(DrmDevice),
     #[error("Workspace {0:?} does not exist")]
     WorkspaceDoesNotExist(Workspace),
+    #[error("Could not rename the workspace")]
+    RenameWorkspace(#[source] WorkspaceRenameError),
     #[error("Keyboard {0:?} does not exist")]
     KeyboardDoesNotExist(InputDevice),
     #[error("Colorable element {0} is not known")]
@@ -3231,6 +4115,10 @@ enum CphError {
     UnknownFormat(ConfigFormat),
     #[error("Unknown x scaling mode {0:?}")]
     UnknownXScalingMode(XScalingMode),
+    #[error("Unknown color scheme {0:?}")]
+    UnknownColorScheme(ColorScheme),
+    #[error("Unknown contrast {0:?}")]
+    UnknownContrast(Contrast),
     #[error("Unknown color space {0:?}")]
     UnknownColorSpace(ColorSpace),
     #[error("Unknown EOTF {0:?}")]
@@ -3251,6 +4139,20 @@ enum CphError {
     ModifyConnectorState(#[source] BackendConnectorTransactionError),
     #[error("Unknown blend space {0:?}")]
     UnknownBlendSpace(ConfigBlendSpace),
+    #[error("Mouse keys speeds must be non-negative and finite")]
+    InvalidMouseKeysSpeed,
+    #[error("Hot corner pressure must be non-negative and finite: {0}")]
+    InvalidHotCornerPressure(f64),
+    #[error("Master ratio must be finite: {0}")]
+    InvalidMasterRatio(f64),
+    #[error("Zoom step {0} is not larger than 1")]
+    InvalidZoomStep(f64),
+    #[error("Swipe gestures need at least 3 fingers but {0} were requested")]
+    InvalidSwipeFingers(u32),
+    #[error("Tablet output area must be a non-empty sub-area of the unit square")]
+    InvalidTabletOutputArea,
+    #[error("Pressure curve control points must be between 0 and 1")]
+    InvalidPressureCurve,
 }
 
 trait WithRequestName {
@@ -99,7 +99,7 @@ impl StringAccess<ToplevelData> for RoleAccess {
 impl StringAccess<ToplevelData> for WorkspaceAccess {
     fn with_string(data: &ToplevelData, f: impl FnOnce(&str) -> bool) -> bool {
         if let Some(ws) = data.workspace.get() {
-            return f(&ws.name);
+            return f(&ws.name.borrow());
         }
         false
     }
@@ -425,6 +425,10 @@ impl CursorUser {
         }
         self.pos.set((x, y));
         self.update_hardware_cursor_(false);
+        let output = self.output.get();
+        if output.is_magnified() {
+            self.group.state.damage(output.global.pos.get());
+        }
         (x, y)
     }
 
@@ -445,6 +449,9 @@ impl CursorUser {
         if !self.hardware_cursor() {
             return;
         }
+        if render {
+            self.group.state.hardware_cursor_image_serial.fetch_add(1);
+        }
         let cursor = self.cursor.get();
         self.group.state.hardware_tick_cursor.push(cursor);
         for output in self.group.state.root.outputs.lock().values() {
@@ -68,6 +68,13 @@ impl EiSeat {
         self.context() == EiContext::Sender
     }
 
+    fn receives_input(&self) -> bool {
+        if self.is_sender() {
+            return false;
+        }
+        !self.client.input_capture.get() || self.seat.is_input_captured_by(self.client.id)
+    }
+
     pub fn regions_changed(self: &Rc<Self>) {
         if self.touchscreen.is_none() && self.pointer_absolute.is_none() {
             return;
@@ -117,7 +124,7 @@ impl EiSeat {
         state: KeyState,
         kb_state: &KeyboardState,
     ) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         let old_id = self.kb_state_id.get();
@@ -131,7 +138,7 @@ impl EiSeat {
     }
 
     pub fn handle_motion_abs(&self, time_usec: u64, x: Fixed, y: Fixed) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(v) = self.pointer_absolute.get() {
@@ -141,7 +148,7 @@ impl EiSeat {
     }
 
     pub fn handle_motion(&self, time_usec: u64, dx: Fixed, dy: Fixed) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(v) = self.pointer.get() {
@@ -151,7 +158,7 @@ impl EiSeat {
     }
 
     pub fn handle_button(&self, time_usec: u64, button: u32, state: KeyState) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(b) = self.button.get() {
@@ -161,7 +168,7 @@ impl EiSeat {
     }
 
     pub fn handle_pending_scroll(&self, time_usec: u64, ps: &PendingScroll) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(b) = self.scroll.get() {
@@ -182,7 +189,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_down(&self, id: u32, x: Fixed, y: Fixed) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -191,7 +198,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_motion(&self, id: u32, x: Fixed, y: Fixed) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -200,7 +207,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_up(&self, id: u32) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -209,7 +216,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_cancel(&self, id: u32) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -222,7 +229,7 @@ impl EiSeat {
     }
 
     pub fn handle_touch_frame(&self, time_usec: u64) {
-        if self.is_sender() {
+        if !self.receives_input() {
             return;
         }
         if let Some(b) = self.touchscreen.get() {
@@ -382,7 +389,13 @@ impl EiSeat {
         device.send_done();
         device.send_resumed(self.client.serial());
         if self.context() == EiContext::Receiver {
-            device.send_start_emulating(self.client.serial(), 1);
+            if !self.client.input_capture.get() {
+                device.send_start_emulating(self.client.serial(), 1);
+            } else if let Some(ic) = self.seat.input_capture()
+                && ic.ei_client_id.get() == Some(self.client.id)
+            {
+                device.send_start_emulating(self.client.serial(), ic.activation_id.get());
+            }
         }
         if let Some(kb) = self.keyboard.get() {
             kb.send_modifiers(kb_state);
@@ -45,6 +45,26 @@ pub struct GfxRenderPass {
     pub clear_cd: Rc<LinearColorDescription>,
 }
 
+impl GfxRenderPass {
+    /// Scales all operations by `zoom` around the point `(x, y)`.
+    ///
+    /// The point is given in normalized framebuffer coordinates, before the output
+    /// transform has been applied.
+    pub fn magnify(&mut self, zoom: f32, x: f32, y: f32) {
+        for opt in &mut self.ops {
+            let rect = match opt {
+                GfxApiOpt::Sync => continue,
+                GfxApiOpt::FillRect(fr) => &mut fr.rect,
+                GfxApiOpt::CopyTexture(ct) => &mut ct.target,
+            };
+            rect.x1 = (rect.x1 - x) * zoom + x;
+            rect.x2 = (rect.x2 - x) * zoom + x;
+            rect.y1 = (rect.y1 - y) * zoom + y;
+            rect.y2 = (rect.y2 - y) * zoom + y;
+        }
+    }
+}
+
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub struct SampleRect {
     pub x1: f32,
@@ -541,10 +561,7 @@ impl dyn GfxFramebuffer {
         blend_buffer: Option<&Rc<dyn GfxBlendBuffer>>,
         blend_cd: &Rc<ColorDescription>,
     ) -> Result<Option<SyncFile>, GfxError> {
-        self.render_node(
-            acquire_sync,
-            release_sync,
-            cd,
+        let mut pass = self.create_render_pass(
             node,
             state,
             cursor_rect,
@@ -554,6 +571,15 @@ impl dyn GfxFramebuffer {
             node.has_fullscreen(),
             fill_black_in_grace_period,
             node.global.persistent.transform.get(),
+            None,
+        );
+        node.magnify_render_pass(&mut pass);
+        self.perform_render_pass(
+            acquire_sync,
+            release_sync,
+            cd,
+            &pass,
+            &self.full_region(),
             blend_buffer,
             blend_cd,
         )
@@ -898,18 +924,22 @@ pub fn create_render_pass(
             clear_cd: state.color_manager.srgb_gamma22().linear.clone(),
         };
     }
+    let pos = node.node_absolute_position();
+    let extents = cursor_rect.unwrap_or(pos);
     let mut ops = vec![];
     let mut renderer = Renderer {
         base: renderer_base(physical_size, &mut ops, scale, transform),
         state,
-        logical_extents: node.node_absolute_position().at_point(0, 0),
+        logical_extents: extents.at_point(0, 0),
         pixel_extents: {
             let (width, height) = logical_size(physical_size, transform);
             Rect::new(0, 0, width, height).unwrap()
         },
         icons: state.icons.get(state, scale),
     };
-    node.node_render(&mut renderer, 0, 0, None);
+    let (x, y) = extents.translate(pos.x1(), pos.y1());
+    node.node_render(&mut renderer, x, y, None);
+    let scene = renderer.base.ops.len();
     if let Some(rect) = cursor_rect {
         let seats = state.globals.lock_seats();
         for seat in seats.values() {
@@ -935,6 +965,15 @@ pub fn create_render_pass(
             if let Some(dnd_icon) = seat.dnd_icon() {
                 dnd_icon.render(&mut renderer, &rect, x, y);
             }
+            if let Some(lens) = seat.color_picker_lens() {
+                let (x, y) = seat.pointer_cursor().position();
+                renderer.render_color_picker_lens(
+                    scene,
+                    &lens.move_(-rect.x1(), -rect.y1()),
+                    x - Fixed::from_int(rect.x1()),
+                    y - Fixed::from_int(rect.y1()),
+                );
+            }
             if render_cursor {
                 let cursor_user_group = seat.cursor_group();
                 if (render_hardware_cursor || !cursor_user_group.hardware_cursor())
@@ -17,17 +17,22 @@ pub mod jay_compositor;
 pub mod jay_damage_tracking;
 pub mod jay_ei_session;
 pub mod jay_ei_session_builder;
+pub mod jay_global_shortcuts;
 pub mod jay_idle;
 pub mod jay_input;
+pub mod jay_input_capture;
 pub mod jay_log_file;
 pub mod jay_output;
+pub mod jay_pick_color;
 pub mod jay_pointer;
+pub mod jay_portal_settings;
 pub mod jay_randr;
 pub mod jay_reexec;
 pub mod jay_render_ctx;
 pub mod jay_screencast;
 pub mod jay_screenshot;
 pub mod jay_seat_events;
+pub mod jay_select_region;
 pub mod jay_select_toplevel;
 pub mod jay_select_workspace;
 pub mod jay_toplevel;
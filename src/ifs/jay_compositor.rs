@@ -2,22 +2,29 @@ use {
     crate::{
         cli::CliLogLevel,
         client::{CAP_JAY_COMPOSITOR, Client, ClientCaps, ClientError, ClientId},
+        fixed::Fixed,
+        format::{Format, XRGB8888, formats},
         globals::{Global, GlobalName},
         ifs::{
             jay_client_query::JayClientQuery,
             jay_color_management::JayColorManagement,
             jay_ei_session_builder::JayEiSessionBuilder,
+            jay_global_shortcuts::JayGlobalShortcuts,
             jay_idle::JayIdle,
             jay_input::JayInput,
+            jay_input_capture::JayInputCapture,
             jay_log_file::JayLogFile,
             jay_output::JayOutput,
+            jay_pick_color::{JayColorPicker, JayPickColor},
             jay_pointer::JayPointer,
+            jay_portal_settings::JayPortalSettings,
             jay_randr::JayRandr,
             jay_reexec::JayReexec,
             jay_render_ctx::JayRenderCtx,
             jay_screencast::JayScreencast,
             jay_screenshot::JayScreenshot,
             jay_seat_events::JaySeatEvents,
+            jay_select_region::{JayRegionSelector, JaySelectRegion},
             jay_select_toplevel::{JaySelectToplevel, JayToplevelSelector},
             jay_select_workspace::{JaySelectWorkspace, JayWorkspaceSelector},
             jay_tree_query::JayTreeQuery,
@@ -26,7 +33,11 @@ use {
         },
         leaks::Tracker,
         object::{Object, Version},
-        screenshoter::take_screenshot,
+        rect::Rect,
+        screenshoter::{
+            ScreenshooterError, Screenshot, screenshot_color_description, take_output_screenshot,
+            take_region_screenshot, take_screenshot, take_toplevel_screenshot,
+        },
         utils::{errorfmt::ErrorFmt, toplevel_identifier::ToplevelIdentifier},
         wire::{
             JayCompositorId, JayScreenshotId,
@@ -42,6 +53,10 @@ use {
 pub const CREATE_EI_SESSION_SINCE: Version = Version(5);
 pub const SCREENSHOT_SPLITUP_SINCE: Version = Version(6);
 pub const GET_TOPLEVEL_SINCE: Version = Version(12);
+pub const TAKE_OUTPUT_SCREENSHOT_SINCE: Version = Version(23);
+pub const GET_PORTAL_SETTINGS_SINCE: Version = Version(24);
+pub const GET_GLOBAL_SHORTCUTS_SINCE: Version = Version(25);
+pub const CREATE_INPUT_CAPTURE_SINCE: Version = Version(26);
 
 pub struct JayCompositorGlobal {
     name: GlobalName,
@@ -63,6 +78,7 @@ impl JayCompositorGlobal {
             client: client.clone(),
             tracker: Default::default(),
             version,
+            screenshot_format: Cell::new(XRGB8888),
         });
         track!(client, obj);
         client.add_client_obj(&obj)?;
@@ -79,7 +95,7 @@ impl Global for JayCompositorGlobal {
     }
 
     fn version(&self) -> u32 {
-        21
+        31
     }
 
     fn required_caps(&self) -> ClientCaps {
@@ -94,6 +110,7 @@ pub struct JayCompositor {
     client: Rc<Client>,
     tracker: Tracker<Self>,
     version: Version,
+    screenshot_format: Cell<&'static Format>,
 }
 
 pub struct Cap;
@@ -112,10 +129,29 @@ impl JayCompositor {
         });
     }
 
+    fn send_rename_workspace_failed(&self, msg: &str) {
+        self.client.event(RenameWorkspaceFailed {
+            self_id: self.id,
+            msg,
+        });
+    }
+
     fn take_screenshot_impl(
         &self,
         id: JayScreenshotId,
         include_cursor: bool,
+    ) -> Result<(), JayCompositorError> {
+        let format = self.screenshot_format.get();
+        self.send_screenshot(
+            id,
+            take_screenshot(&self.client.state, include_cursor, format),
+        )
+    }
+
+    fn send_screenshot(
+        &self,
+        id: JayScreenshotId,
+        res: Result<Screenshot, ScreenshooterError>,
     ) -> Result<(), JayCompositorError> {
         let ss = Rc::new(JayScreenshot {
             id,
@@ -124,7 +160,7 @@ impl JayCompositor {
         });
         track!(self.client, ss);
         self.client.add_client_obj(&ss)?;
-        match take_screenshot(&self.client.state, include_cursor) {
+        match res {
             Ok(s) => {
                 let dmabuf = s.bo.dmabuf();
                 if self.version < SCREENSHOT_SPLITUP_SINCE {
@@ -419,6 +455,76 @@ impl JayCompositorRequestHandler for JayCompositor {
         Ok(())
     }
 
+    fn select_region(&self, req: SelectRegion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JaySelectRegion {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            destroyed: Cell::new(false),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        let selector = JayRegionSelector {
+            rect: Default::default(),
+            jsr: obj.clone(),
+        };
+        let seat = if req.seat.is_none() {
+            match self.client.state.seat_queue.last() {
+                Some(s) => s.deref().clone(),
+                None => return Ok(()),
+            }
+        } else {
+            self.client.lookup(req.seat)?.global.clone()
+        };
+        seat.select_region(selector);
+        Ok(())
+    }
+
+    fn pick_color(&self, req: PickColor, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayPickColor::new(req.id, &self.client));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        let picker = JayColorPicker {
+            picked: Default::default(),
+            jpc: obj.clone(),
+        };
+        let seat = if req.seat.is_none() {
+            match self.client.state.seat_queue.last() {
+                Some(s) => s.deref().clone(),
+                None => return Ok(()),
+            }
+        } else {
+            self.client.lookup(req.seat)?.global.clone()
+        };
+        seat.pick_color(picker);
+        Ok(())
+    }
+
+    fn sample_color(&self, req: SampleColor, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayPickColor::new(req.id, &self.client));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.sample(Fixed::from_int(req.x), Fixed::from_int(req.y));
+        Ok(())
+    }
+
+    fn take_region_screenshot(
+        &self,
+        req: TakeRegionScreenshot,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let res = match Rect::new_sized(req.x, req.y, req.width, req.height) {
+            Some(rect) => take_region_screenshot(
+                &self.client.state,
+                rect,
+                req.include_cursor != 0,
+                self.screenshot_format.get(),
+            ),
+            None => Err(ScreenshooterError::EmptyRegion),
+        };
+        self.send_screenshot(req.id, res)
+    }
+
     fn create_ei_session(&self, req: CreateEiSession, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         let obj = Rc::new(JayEiSessionBuilder {
             id: req.id,
@@ -515,6 +621,154 @@ impl JayCompositorRequestHandler for JayCompositor {
         self.client.add_client_obj(&obj)?;
         Ok(())
     }
+
+    fn rename_workspace(
+        &self,
+        req: RenameWorkspace<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let Some(ws) = self.client.state.workspaces.get(req.old_name) else {
+            self.send_rename_workspace_failed(&format!(
+                "Workspace {} does not exist",
+                req.old_name
+            ));
+            return Ok(());
+        };
+        if let Err(e) = ws.rename(req.new_name) {
+            self.send_rename_workspace_failed(&ErrorFmt(e).to_string());
+        }
+        Ok(())
+    }
+
+    fn take_output_screenshot(
+        &self,
+        req: TakeOutputScreenshot,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?;
+        let res = match output.global.node() {
+            Some(node) => take_output_screenshot(
+                &self.client.state,
+                &node,
+                req.include_cursor != 0,
+                self.screenshot_format.get(),
+            ),
+            None => Err(ScreenshooterError::EmptyDisplay),
+        };
+        self.send_screenshot(req.id, res)
+    }
+
+    fn take_toplevel_screenshot(
+        &self,
+        req: TakeToplevelScreenshot<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let tl = match ToplevelIdentifier::from_str(req.toplevel_id) {
+            Ok(id) => self
+                .client
+                .state
+                .toplevels
+                .get(&id)
+                .and_then(|w| w.upgrade()),
+            Err(e) => {
+                log::error!("Could not parse toplevel id: {}", ErrorFmt(e));
+                None
+            }
+        };
+        let res = match tl {
+            Some(tl) => take_toplevel_screenshot(
+                &self.client.state,
+                &*tl,
+                req.include_cursor != 0,
+                self.screenshot_format.get(),
+            ),
+            None => Err(ScreenshooterError::InvisibleWindow),
+        };
+        self.send_screenshot(req.id, res)
+    }
+
+    fn set_screenshot_format(
+        &self,
+        req: SetScreenshotFormat,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let format = formats()
+            .get(&req.format)
+            .copied()
+            .filter(|f| screenshot_color_description(&self.client.state, f).is_some());
+        let Some(format) = format else {
+            return Err(JayCompositorError::UnsupportedScreenshotFormat(req.format));
+        };
+        self.screenshot_format.set(format);
+        Ok(())
+    }
+
+    fn get_portal_settings(
+        &self,
+        req: GetPortalSettings,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayPortalSettings {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        let settings = &self.client.state.portal_settings;
+        settings.watchers.set((self.client.id, req.id), obj.clone());
+        obj.send_settings(settings);
+        Ok(())
+    }
+
+    fn get_global_shortcuts(
+        &self,
+        req: GetGlobalShortcuts,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayGlobalShortcuts {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            bindings: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        self.client
+            .state
+            .global_shortcuts
+            .set((self.client.id, req.id), obj);
+        Ok(())
+    }
+
+    fn create_input_capture(
+        &self,
+        req: CreateInputCapture,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayInputCapture {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            barriers: Default::default(),
+            enabled: Default::default(),
+            ei_client_id: Default::default(),
+            next_activation_id: Default::default(),
+            activation_id: Default::default(),
+            seat: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.send_zones();
+        self.client
+            .state
+            .input_captures
+            .set((self.client.id, req.id), obj);
+        Ok(())
+    }
 }
 
 object_base! {
@@ -532,5 +786,7 @@ pub enum JayCompositorError {
     ClientError(Box<ClientError>),
     #[error("Unknown log level {0}")]
     UnknownLogLevel(u32),
+    #[error("Screenshots cannot be taken in format {0}")]
+    UnsupportedScreenshotFormat(u32),
 }
 efrom!(JayCompositorError, ClientError);
@@ -0,0 +1,113 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::copyhashmap::CopyHashMap,
+        wire::{JayGlobalShortcutsId, jay_global_shortcuts::*},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct JayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub bindings: CopyHashMap<u32, (u32, u32)>,
+}
+
+impl JayGlobalShortcuts {
+    pub fn matches(&self, mods: u32, keysym: u32) -> Vec<u32> {
+        self.bindings
+            .lock()
+            .iter()
+            .filter(|(_, b)| **b == (mods, keysym))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    pub fn send_activated(&self, id: u32, time_usec: u64) {
+        self.client.event(Activated {
+            self_id: self.id,
+            id,
+            time_usec,
+        });
+    }
+
+    pub fn send_deactivated(&self, id: u32, time_usec: u64) {
+        self.client.event(Deactivated {
+            self_id: self.id,
+            id,
+            time_usec,
+        });
+    }
+
+    pub fn send_trigger_captured(&self, mods: u32, keysym: u32) {
+        self.client.event(TriggerCaptured {
+            self_id: self.id,
+            mods,
+            keysym,
+        });
+    }
+
+    fn remove_from_state(&self) {
+        let state = &self.client.state;
+        state.global_shortcuts.remove(&(self.client.id, self.id));
+        if let Some(capture) = state.global_shortcut_capture.get()
+            && capture.id == self.id
+            && capture.client.id == self.client.id
+        {
+            state.global_shortcut_capture.take();
+        }
+    }
+}
+
+impl JayGlobalShortcutsRequestHandler for JayGlobalShortcuts {
+    type Error = JayGlobalShortcutsError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn bind(&self, req: Bind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.bindings.set(req.id, (req.mods, req.keysym));
+        Ok(())
+    }
+
+    fn unbind(&self, req: Unbind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.bindings.remove(&req.id);
+        Ok(())
+    }
+
+    fn capture_trigger(&self, _req: CaptureTrigger, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client
+            .state
+            .global_shortcut_capture
+            .set(Some(slf.clone()));
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayGlobalShortcuts;
+    version = self.version;
+}
+
+impl Object for JayGlobalShortcuts {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayGlobalShortcuts);
+
+#[derive(Debug, Error)]
+pub enum JayGlobalShortcutsError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayGlobalShortcutsError, ClientError);
@@ -0,0 +1,256 @@
+use {
+    crate::{
+        client::{Client, ClientError, ClientId},
+        ei::ei_client::EiClientError,
+        fixed::Fixed,
+        ifs::wl_seat::WlSeatGlobal,
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::Node,
+        utils::{
+            cell_ext::CellExt, clonecell::CloneCell, errorfmt::ErrorFmt, numcell::NumCell,
+            oserror::OsError,
+        },
+        wire::{JayInputCaptureId, jay_input_capture::*},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+    uapi::{OwnedFd, c},
+};
+
+#[derive(Copy, Clone, Debug)]
+pub struct InputBarrier {
+    pub id: u32,
+    pub x1: i32,
+    pub y1: i32,
+    pub x2: i32,
+    pub y2: i32,
+}
+
+pub struct JayInputCapture {
+    pub id: JayInputCaptureId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub barriers: RefCell<Vec<InputBarrier>>,
+    pub enabled: Cell<bool>,
+    pub ei_client_id: Cell<Option<ClientId>>,
+    pub next_activation_id: NumCell<u32>,
+    pub activation_id: Cell<u32>,
+    pub seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
+}
+
+impl InputBarrier {
+    fn crossed(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
+        if self.x1 == self.x2 {
+            let (min, max) = (self.y1.min(self.y2), self.y1.max(self.y2));
+            (x1 < self.x1) != (x2 < self.x1) && y1 >= min && y1 <= max
+        } else if self.y1 == self.y2 {
+            let (min, max) = (self.x1.min(self.x2), self.x1.max(self.x2));
+            (y1 < self.y1) != (y2 < self.y1) && x1 >= min && x1 <= max
+        } else {
+            false
+        }
+    }
+}
+
+impl JayInputCapture {
+    pub fn crossed_barrier(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> Option<u32> {
+        if !self.enabled.get() || self.seat.is_some() {
+            return None;
+        }
+        self.barriers
+            .borrow()
+            .iter()
+            .find(|b| b.crossed(x1, y1, x2, y2))
+            .map(|b| b.id)
+    }
+
+    pub fn send_zones(&self) {
+        for output in self.client.state.root.outputs.lock().values() {
+            let rect = output.node_absolute_position();
+            self.client.event(Zone {
+                self_id: self.id,
+                x: rect.x1(),
+                y: rect.y1(),
+                width: rect.width(),
+                height: rect.height(),
+            });
+        }
+        self.client.event(ZonesDone { self_id: self.id });
+    }
+
+    pub fn zones_changed(&self) {
+        self.deactivate(None);
+        self.barriers.borrow_mut().clear();
+        self.send_zones();
+    }
+
+    fn send_ei_created(&self, fd: &Rc<OwnedFd>) {
+        self.client.event(EiCreated {
+            self_id: self.id,
+            fd: fd.clone(),
+        });
+    }
+
+    fn send_ei_failed(&self, reason: &str) {
+        self.client.event(EiFailed {
+            self_id: self.id,
+            reason,
+        });
+    }
+
+    pub fn send_activated(&self, activation_id: u32, barrier_id: u32, x: Fixed, y: Fixed) {
+        self.client.event(Activated {
+            self_id: self.id,
+            activation_id,
+            barrier_id,
+            x,
+            y,
+        });
+    }
+
+    pub fn send_deactivated(&self, activation_id: u32, x: Fixed, y: Fixed) {
+        self.client.event(Deactivated {
+            self_id: self.id,
+            activation_id,
+            x,
+            y,
+        });
+    }
+
+    fn send_disabled(&self) {
+        self.client.event(Disabled { self_id: self.id });
+    }
+
+    pub fn deactivate(&self, warp: Option<(Fixed, Fixed)>) {
+        if let Some(seat) = self.seat.get() {
+            seat.deactivate_input_capture(warp);
+        }
+    }
+
+    fn spawn_ei_client(
+        &self,
+        app_id: &str,
+    ) -> Result<(ClientId, Rc<OwnedFd>), JayInputCaptureError> {
+        let state = &self.client.state;
+        let (server, client) = uapi::socketpair(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
+            .map_err(|e| JayInputCaptureError::SocketPair(e.into()))?;
+        let ei_client = state
+            .ei_clients
+            .spawn2(state, Rc::new(server), None, Some(app_id.to_string()))
+            .map_err(JayInputCaptureError::SpawnClient)?;
+        ei_client.input_capture.set(true);
+        Ok((ei_client.id, Rc::new(client)))
+    }
+
+    fn kill(&self) {
+        self.deactivate(None);
+        self.client
+            .state
+            .input_captures
+            .remove(&(self.client.id, self.id));
+        if let Some(id) = self.ei_client_id.take() {
+            self.client.state.ei_clients.shutdown(id);
+        }
+    }
+}
+
+impl JayInputCaptureRequestHandler for JayInputCapture {
+    type Error = JayInputCaptureError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.kill();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn connect_ei(&self, req: ConnectEi<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.ei_client_id.is_some() {
+            return Err(JayInputCaptureError::AlreadyConnected);
+        }
+        match self.spawn_ei_client(req.app_id) {
+            Ok((id, fd)) => {
+                self.ei_client_id.set(Some(id));
+                self.send_ei_created(&fd);
+            }
+            Err(e) => {
+                let e = format!("Could not spawn client: {}", ErrorFmt(e));
+                log::error!("{}", e);
+                self.send_ei_failed(&e);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_barrier(&self, req: AddBarrier, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.x1 != req.x2 && req.y1 != req.y2 {
+            return Err(JayInputCaptureError::DiagonalBarrier);
+        }
+        self.barriers.borrow_mut().push(InputBarrier {
+            id: req.id,
+            x1: req.x1,
+            y1: req.y1,
+            x2: req.x2,
+            y2: req.y2,
+        });
+        Ok(())
+    }
+
+    fn clear_barriers(&self, _req: ClearBarriers, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.barriers.borrow_mut().clear();
+        Ok(())
+    }
+
+    fn enable(&self, _req: Enable, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.enabled.set(true);
+        Ok(())
+    }
+
+    fn disable(&self, _req: Disable, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.enabled.replace(false) {
+            self.deactivate(None);
+            self.send_disabled();
+        }
+        Ok(())
+    }
+
+    fn release(&self, req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.seat.is_some() && self.activation_id.get() == req.activation_id {
+            let warp = (req.warp != 0).then_some((req.x, req.y));
+            self.deactivate(warp);
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayInputCapture;
+    version = self.version;
+}
+
+impl Object for JayInputCapture {
+    fn break_loops(&self) {
+        self.kill();
+    }
+}
+
+simple_add_obj!(JayInputCapture);
+
+#[derive(Debug, Error)]
+pub enum JayInputCaptureError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Could not create a socketpair")]
+    SocketPair(#[source] OsError),
+    #[error("Could not spawn a new client")]
+    SpawnClient(#[source] EiClientError),
+    #[error("The input capture is already connected to an EI client")]
+    AlreadyConnected,
+    #[error("Barriers must be horizontal or vertical")]
+    DiagonalBarrier,
+}
+efrom!(JayInputCaptureError, ClientError);
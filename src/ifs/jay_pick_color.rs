@@ -0,0 +1,288 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        cmm::cmm_description::ColorDescription,
+        fixed::Fixed,
+        format::ARGB8888,
+        gfx_api::{
+            AcquireSync, AsyncShmGfxTextureCallback, GfxError, GfxFramebuffer, PendingShmTransfer,
+            ReleaseSync, STAGING_DOWNLOAD,
+        },
+        ifs::wl_seat::ColorPicker,
+        leaks::Tracker,
+        object::{Object, Version},
+        rect::{Rect, Region},
+        tree::OutputNode,
+        utils::errorfmt::ErrorFmt,
+        wire::{JayPickColorId, jay_pick_color::*},
+    },
+    jay_config::video::Transform,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub struct JayPickColor {
+    pub id: JayPickColorId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub destroyed: Cell<bool>,
+    sample: RefCell<Option<Sample>>,
+}
+
+struct Sample {
+    x: i32,
+    y: i32,
+    output: Rc<OutputNode>,
+    srgb: Cell<Option<[u8; 3]>>,
+    native: Cell<Option<[u8; 3]>>,
+    pending: Vec<PendingShmTransfer>,
+}
+
+pub struct JayColorPicker {
+    pub picked: Cell<bool>,
+    pub jpc: Rc<JayPickColor>,
+}
+
+impl ColorPicker for JayColorPicker {
+    fn pick(&self, x: Fixed, y: Fixed) {
+        self.picked.set(true);
+        self.jpc.sample(x, y);
+    }
+}
+
+impl Drop for JayColorPicker {
+    fn drop(&mut self) {
+        if self.jpc.destroyed.get() || self.picked.get() {
+            return;
+        }
+        self.jpc.send_cancelled();
+        let _ = self.jpc.client.remove_obj(&*self.jpc);
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Target {
+    Srgb,
+    Native,
+}
+
+struct PixelDownload {
+    jpc: Rc<JayPickColor>,
+    target: Target,
+    mem: Rc<Vec<Cell<u8>>>,
+    offset: usize,
+}
+
+impl AsyncShmGfxTextureCallback for PixelDownload {
+    fn completed(self: Rc<Self>, res: Result<(), GfxError>) {
+        let jpc = &self.jpc;
+        if jpc.destroyed.get() {
+            return;
+        }
+        if let Err(e) = res {
+            jpc.fail(JayPickColorError::Download(e));
+            return;
+        }
+        let [b, g, r] = [0, 1, 2].map(|i| self.mem[self.offset + i].get());
+        let complete = {
+            let sample = jpc.sample.borrow();
+            let Some(sample) = &*sample else {
+                return;
+            };
+            match self.target {
+                Target::Srgb => sample.srgb.set(Some([r, g, b])),
+                Target::Native => sample.native.set(Some([r, g, b])),
+            }
+            sample.srgb.get().is_some() && sample.native.get().is_some()
+        };
+        if complete {
+            jpc.complete();
+        }
+    }
+}
+
+impl JayPickColor {
+    pub fn new(id: JayPickColorId, client: &Rc<Client>) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            destroyed: Cell::new(false),
+            sample: Default::default(),
+        }
+    }
+
+    pub fn sample(self: &Rc<Self>, x: Fixed, y: Fixed) {
+        if self.destroyed.get() {
+            return;
+        }
+        if let Err(e) = self.try_sample(x, y) {
+            self.fail(e);
+        }
+    }
+
+    fn try_sample(self: &Rc<Self>, x: Fixed, y: Fixed) -> Result<(), JayPickColorError> {
+        let state = &self.client.state;
+        let (xi, yi) = (x.round_down(), y.round_down());
+        let output = state
+            .root
+            .outputs
+            .lock()
+            .values()
+            .find(|o| o.global.pos.get().contains(xi, yi))
+            .cloned();
+        let Some(output) = output else {
+            return Err(JayPickColorError::NoOutput);
+        };
+        let Some(ctx) = state.render_ctx.get() else {
+            return Err(JayPickColorError::NoRenderContext);
+        };
+        // Only the logical pixel under the position is rendered. At scales above 1 it
+        // covers several physical pixels of which the one under the position is used.
+        let rect = Rect::new_sized_unchecked(xi, yi, 1, 1);
+        let scale = output.global.persistent.scale.get();
+        let [width, height] = scale.pixel_size([1, 1]).map(|v| v.max(1));
+        let stride = width * 4;
+        let scalef = scale.to_f64();
+        let px = ((x.to_f64() - xi as f64) * scalef) as i32;
+        let py = ((y.to_f64() - yi as f64) * scalef) as i32;
+        let (px, py) = (px.clamp(0, width - 1), py.clamp(0, height - 1));
+        *self.sample.borrow_mut() = Some(Sample {
+            x: xi,
+            y: yi,
+            output: output.clone(),
+            srgb: Default::default(),
+            native: Default::default(),
+            pending: vec![],
+        });
+        let targets: [(Target, Rc<ColorDescription>); 2] = [
+            (Target::Srgb, state.color_manager.srgb_gamma22().clone()),
+            (Target::Native, output.global.color_description.get()),
+        ];
+        for (target, cd) in targets {
+            if self.sample.borrow().is_none() {
+                break;
+            }
+            let fb = ctx.clone().create_internal_fb(
+                &state.cpu_worker,
+                width,
+                height,
+                stride,
+                ARGB8888,
+            )?;
+            (fb.clone() as Rc<dyn GfxFramebuffer>).render_node(
+                AcquireSync::Unnecessary,
+                ReleaseSync::None,
+                &cd,
+                &*output,
+                state,
+                Some(rect),
+                scale,
+                false,
+                false,
+                false,
+                false,
+                Transform::None,
+                None,
+                state.color_manager.srgb_linear(),
+            )?;
+            let staging = ctx.create_staging_buffer(fb.staging_size(), STAGING_DOWNLOAD);
+            let mem = Rc::new(vec![Cell::new(0u8); (stride * height) as usize]);
+            let download = Rc::new(PixelDownload {
+                jpc: self.clone(),
+                target,
+                mem: mem.clone(),
+                offset: (py * stride + px * 4) as usize,
+            });
+            let pending = fb.download(
+                &staging,
+                download,
+                mem,
+                Region::new(Rect::new_sized_unchecked(px, py, 1, 1)),
+            )?;
+            if let Some(pending) = pending
+                && let Some(sample) = &mut *self.sample.borrow_mut()
+            {
+                sample.pending.push(pending);
+            }
+        }
+        Ok(())
+    }
+
+    fn complete(&self) {
+        let Some(sample) = self.sample.take() else {
+            return;
+        };
+        let global = &sample.output.global;
+        let [srgb_r, srgb_g, srgb_b] = sample.srgb.get().unwrap_or_default();
+        let [r, g, b] = sample.native.get().unwrap_or_default();
+        self.client.event(Color {
+            self_id: self.id,
+            x: sample.x,
+            y: sample.y,
+            output: &global.connector.name,
+            srgb_r: srgb_r as u32,
+            srgb_g: srgb_g as u32,
+            srgb_b: srgb_b as u32,
+            color_space: global.bcs.get().name(),
+            eotf: global.btf.get().name(),
+            r: r as u32,
+            g: g as u32,
+            b: b as u32,
+        });
+        let _ = self.client.remove_obj(self);
+    }
+
+    fn fail(&self, e: JayPickColorError) {
+        self.sample.take();
+        self.send_failed(&ErrorFmt(e).to_string());
+        let _ = self.client.remove_obj(self);
+    }
+
+    pub fn send_cancelled(&self) {
+        self.client.event(Cancelled { self_id: self.id });
+    }
+
+    fn send_failed(&self, msg: &str) {
+        self.client.event(Failed {
+            self_id: self.id,
+            msg,
+        });
+    }
+}
+
+impl JayPickColorRequestHandler for JayPickColor {
+    type Error = JayPickColorError;
+}
+
+object_base! {
+    self = JayPickColor;
+    version = Version(1);
+}
+
+impl Object for JayPickColor {
+    fn break_loops(&self) {
+        self.destroyed.set(true);
+        self.sample.take();
+    }
+}
+
+simple_add_obj!(JayPickColor);
+
+#[derive(Debug, Error)]
+pub enum JayPickColorError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("There is no output at this position")]
+    NoOutput,
+    #[error("There is no render context")]
+    NoRenderContext,
+    #[error("Could not render the output")]
+    Render(#[from] GfxError),
+    #[error("Could not download the pixel")]
+    Download(#[source] GfxError),
+}
+efrom!(JayPickColorError, ClientError);
@@ -0,0 +1,101 @@
+use {
+    crate::{
+        client::{Client, ClientError, ClientId},
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::copyhashmap::CopyHashMap,
+        wire::{JayPortalSettingsId, jay_portal_settings::*},
+    },
+    jay_config::portal::{ColorScheme as ConfigColorScheme, Contrast as ConfigContrast},
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+#[derive(Default)]
+pub struct PortalSettings {
+    pub color_scheme: Cell<ConfigColorScheme>,
+    pub accent_color: Cell<Option<[f64; 3]>>,
+    pub contrast: Cell<ConfigContrast>,
+    pub reduced_motion: Cell<bool>,
+    pub watchers: CopyHashMap<(ClientId, JayPortalSettingsId), Rc<JayPortalSettings>>,
+}
+
+impl PortalSettings {
+    pub fn changed(&self) {
+        for watcher in self.watchers.lock().values() {
+            watcher.send_settings(self);
+        }
+    }
+}
+
+pub struct JayPortalSettings {
+    pub id: JayPortalSettingsId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl JayPortalSettings {
+    pub fn send_settings(&self, settings: &PortalSettings) {
+        self.client.event(ColorScheme {
+            self_id: self.id,
+            scheme: settings.color_scheme.get().0,
+        });
+        match settings.accent_color.get() {
+            Some([r, g, b]) => self.client.event(AccentColor {
+                self_id: self.id,
+                r,
+                g,
+                b,
+            }),
+            None => self.client.event(NoAccentColor { self_id: self.id }),
+        }
+        self.client.event(Contrast {
+            self_id: self.id,
+            contrast: settings.contrast.get().0,
+        });
+        self.client.event(ReducedMotion {
+            self_id: self.id,
+            reduced: settings.reduced_motion.get() as u32,
+        });
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .portal_settings
+            .watchers
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl JayPortalSettingsRequestHandler for JayPortalSettings {
+    type Error = JayPortalSettingsError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayPortalSettings;
+    version = self.version;
+}
+
+impl Object for JayPortalSettings {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayPortalSettings);
+
+#[derive(Debug, Error)]
+pub enum JayPortalSettingsError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayPortalSettingsError, ClientError);
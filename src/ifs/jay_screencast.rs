@@ -3,13 +3,17 @@ use {
         allocator::{AllocatorError, BO_USE_LINEAR, BO_USE_RENDERING, BufferObject},
         client::{Client, ClientError},
         cmm::cmm_description::ColorDescription,
-        format::XRGB8888,
+        cursor::Cursor,
+        fixed::Fixed,
+        format::{ARGB8888, XRGB8888},
         gfx_api::{
-            AcquireSync, BufferResv, GfxContext, GfxError, GfxFramebuffer, GfxTexture, ReleaseSync,
+            AcquireSync, AsyncShmGfxTextureCallback, BufferResv, GfxContext, GfxError,
+            GfxFramebuffer, GfxTexture, PendingShmTransfer, ReleaseSync, STAGING_DOWNLOAD,
         },
         ifs::{jay_output::JayOutput, jay_toplevel::JayToplevel, wl_buffer::WlBufferStorage},
         leaks::Tracker,
         object::{Object, Version},
+        rect::{Rect, Region},
         scale::Scale,
         state::State,
         tree::{LatchListener, OutputNode, ToplevelNode, WorkspaceNode, WorkspaceNodeId},
@@ -19,6 +23,7 @@ use {
             event_listener::EventListener,
             numcell::NumCell,
             option_ext::OptionExt,
+            oserror::OsError,
         },
         video::{INVALID_MODIFIER, LINEAR_MODIFIER, dmabuf::DmaBuf},
         wire::{JayScreencastId, jay_screencast::*},
@@ -27,10 +32,12 @@ use {
     jay_config::video::Transform,
     std::{
         cell::{Cell, RefCell},
+        io::Write,
         ops::DerefMut,
         rc::{Rc, Weak},
     },
     thiserror::Error,
+    uapi::c,
 };
 
 pub async fn perform_toplevel_screencasts(state: Rc<State>) {
@@ -59,6 +66,19 @@ pub async fn perform_screencast_realloc(state: Rc<State>) {
 }
 
 pub const CLIENT_BUFFERS_SINCE: Version = Version(7);
+pub const CURSOR_MODE_SINCE: Version = Version(27);
+pub const REGION_SINCE: Version = Version(28);
+
+pub const CURSOR_MODE_EMBEDDED: u32 = 0;
+pub const CURSOR_MODE_HIDDEN: u32 = 1;
+pub const CURSOR_MODE_METADATA: u32 = 2;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CursorMode {
+    Embedded,
+    Hidden,
+    Metadata,
+}
 
 pub struct JayScreencast {
     pub id: JayScreencastId,
@@ -81,11 +101,16 @@ pub struct JayScreencast {
     need_realloc_or_reconfigure: Cell<bool>,
     realloc_or_reconfigure_scheduled: Cell<bool>,
     latch_listener: EventListener<dyn LatchListener>,
+    cursor_mode: Cell<CursorMode>,
+    cursor_position: Cell<Option<(bool, i32, i32)>>,
+    cursor_image: Cell<Option<(u64, Rect)>>,
+    pending_cursor_download: Cell<Option<PendingShmTransfer>>,
 }
 
 #[derive(Clone)]
 enum Target {
     Output(Rc<OutputNode>),
+    Region(Rc<OutputNode>, Rect),
     Toplevel(Rc<dyn ToplevelNode>),
 }
 
@@ -99,6 +124,7 @@ unsafe impl UnsafeCellCloneSafe for Target {}
 
 enum PendingTarget {
     Output(Rc<JayOutput>),
+    Region(Rc<JayOutput>, Rect),
     Toplevel(Rc<JayToplevel>),
 }
 
@@ -109,6 +135,7 @@ struct Pending {
     target: Cell<Option<Option<PendingTarget>>>,
     show_all: Cell<Option<bool>>,
     show_workspaces: RefCell<Option<AHashSet<WorkspaceNodeId>>>,
+    cursor_mode: Cell<Option<CursorMode>>,
     clear_buffers: Cell<bool>,
     buffers: RefCell<Vec<Rc<dyn GfxFramebuffer>>>,
 }
@@ -160,9 +187,17 @@ impl JayScreencast {
             need_realloc_or_reconfigure: Cell::new(false),
             realloc_or_reconfigure_scheduled: Cell::new(false),
             latch_listener: EventListener::new(slf.clone()),
+            cursor_mode: Cell::new(CursorMode::Embedded),
+            cursor_position: Default::default(),
+            cursor_image: Default::default(),
+            pending_cursor_download: Default::default(),
         }
     }
 
+    pub fn includes_cursor(&self) -> bool {
+        self.cursor_mode.get() == CursorMode::Embedded
+    }
+
     fn schedule_toplevel_screencast(self: &Rc<Self>) {
         if !self.running.get() {
             return;
@@ -173,7 +208,7 @@ impl JayScreencast {
             .push(self.clone());
     }
 
-    fn perform_toplevel_screencast(&self) {
+    fn perform_toplevel_screencast(self: &Rc<Self>) {
         if self.destroyed.get() || !self.running.get() {
             return;
         }
@@ -199,7 +234,7 @@ impl JayScreencast {
                     &self.client.state,
                     Some(tl.node_absolute_position()),
                     scale,
-                    true,
+                    self.includes_cursor(),
                     true,
                     false,
                     false,
@@ -209,6 +244,7 @@ impl JayScreencast {
                 );
                 match res {
                     Ok(_) => {
+                        self.send_cursor(tl.node_absolute_position(), scale);
                         self.client.event(Ready {
                             self_id: self.id,
                             idx: idx as _,
@@ -272,7 +308,7 @@ impl JayScreencast {
                     height,
                 });
             }
-            if let Target::Output(output) = target {
+            if let Target::Output(output) | Target::Region(output, _) = target {
                 self.client.event(ConfigOutput {
                     self_id: self.id,
                     linear_id: output.id.raw(),
@@ -304,7 +340,7 @@ impl JayScreencast {
     }
 
     pub fn copy_texture(
-        &self,
+        self: &Rc<Self>,
         on: &OutputNode,
         texture: &Rc<dyn GfxTexture>,
         cd: &Rc<ColorDescription>,
@@ -328,6 +364,16 @@ impl JayScreencast {
                 return;
             }
         }
+        let (mut x_off, mut y_off) = (x_off, y_off);
+        let mut cursor_rect = on.global.pos.get();
+        if let Some(Target::Region(_, region)) = self.target.get() {
+            let pixel_rect = region_pixel_rect(on, region);
+            x_off -= pixel_rect.x1();
+            y_off -= pixel_rect.y1();
+            cursor_rect = region
+                .intersect(on.global.pos.get().at_point(0, 0))
+                .move_(cursor_rect.x1(), cursor_rect.y1());
+        }
         let mut buffer = self.buffers.borrow_mut();
         for (idx, buffer) in buffer.deref_mut().iter_mut().enumerate() {
             if buffer.free {
@@ -343,7 +389,7 @@ impl JayScreencast {
                     Transform::None,
                     self.client.state.color_manager.srgb_gamma22(),
                     on.global.pos.get(),
-                    render_hardware_cursors,
+                    render_hardware_cursors && self.includes_cursor(),
                     x_off,
                     y_off,
                     size,
@@ -352,6 +398,7 @@ impl JayScreencast {
                 );
                 match res {
                     Ok(_) => {
+                        self.send_cursor(cursor_rect, on.global.persistent.scale.get());
                         self.client.event(Ready {
                             self_id: self.id,
                             idx: idx as _,
@@ -370,11 +417,101 @@ impl JayScreencast {
         self.client.event(MissedFrame { self_id: self.id })
     }
 
+    fn send_cursor(self: &Rc<Self>, rect: Rect, scale: Scale) {
+        if self.cursor_mode.get() != CursorMode::Metadata {
+            return;
+        }
+        let state = &self.client.state;
+        let cursor = state
+            .cursor_user_group_hardware_cursor
+            .get()
+            .and_then(|g| g.active())
+            .and_then(|u| u.get().map(|c| (u, c)));
+        let mut position = (false, 0, 0);
+        if let Some((user, cursor)) = cursor {
+            let (x, y) = user.position();
+            if rect.contains(x.round_down(), y.round_down()) {
+                let scalef = scale.to_f64();
+                let x = ((x - Fixed::from_int(rect.x1())).to_f64() * scalef).round() as i32;
+                let y = ((y - Fixed::from_int(rect.y1())).to_f64() * scalef).round() as i32;
+                position = (true, x, y);
+                let extents = cursor.extents_at_scale(scale);
+                let image = (state.hardware_cursor_image_serial.get(), extents);
+                if self.cursor_image.get() != Some(image) {
+                    self.cursor_image.set(Some(image));
+                    if let Err(e) = self.download_cursor(&*cursor, extents, scale) {
+                        log::error!("Could not download cursor image: {}", ErrorFmt(e));
+                    }
+                }
+            }
+        }
+        if self.cursor_position.replace(Some(position)) == Some(position) {
+            return;
+        }
+        let (visible, x, y) = position;
+        self.client.event(CursorPosition {
+            self_id: self.id,
+            visible: visible as _,
+            x,
+            y,
+        });
+    }
+
+    fn download_cursor(
+        self: &Rc<Self>,
+        cursor: &dyn Cursor,
+        extents: Rect,
+        scale: Scale,
+    ) -> Result<(), JayScreencastError> {
+        self.pending_cursor_download.take();
+        if extents.is_empty() {
+            return Ok(());
+        }
+        let state = &self.client.state;
+        let Some(ctx) = state.render_ctx.get() else {
+            return Ok(());
+        };
+        let (width, height) = (extents.width(), extents.height());
+        let stride = width * 4;
+        let fb =
+            ctx.clone()
+                .create_internal_fb(&state.cpu_worker, width, height, stride, ARGB8888)?;
+        (fb.clone() as Rc<dyn GfxFramebuffer>).render_hardware_cursor(
+            AcquireSync::Unnecessary,
+            ReleaseSync::None,
+            cursor,
+            state,
+            scale,
+            Transform::None,
+            state.color_manager.srgb_gamma22(),
+        )?;
+        let staging = ctx.create_staging_buffer(fb.staging_size(), STAGING_DOWNLOAD);
+        let mem = Rc::new(vec![Cell::new(0u8); (stride * height) as usize]);
+        let download = Rc::new(CursorDownload {
+            screencast: self.clone(),
+            mem: mem.clone(),
+            width,
+            height,
+            stride,
+            hotspot_x: -extents.x1(),
+            hotspot_y: -extents.y1(),
+        });
+        let pending = fb.download(
+            &staging,
+            download,
+            mem,
+            Region::new(Rect::new_sized_unchecked(0, 0, width, height)),
+        )?;
+        self.pending_cursor_download.set(pending);
+        Ok(())
+    }
+
     fn detach(&self) {
+        self.pending_cursor_download.take();
         self.latch_listener.detach();
         if let Some(target) = self.target.take() {
             match target {
-                Target::Output(output) => {
+                Target::Output(output) | Target::Region(output, _) => {
                     output.remove_screencast(self);
                 }
                 Target::Toplevel(tl) => {
@@ -392,6 +529,12 @@ impl JayScreencast {
         self.client.event(Destroyed { self_id: self.id });
     }
 
+    pub fn output_scale_changed(self: &Rc<Self>) {
+        if let Some(Target::Region(..)) = self.target.get() {
+            self.schedule_realloc_or_reconfigure();
+        }
+    }
+
     pub fn schedule_realloc_or_reconfigure(self: &Rc<Self>) {
         self.need_realloc_or_reconfigure.set(true);
         if !self.realloc_or_reconfigure_scheduled.replace(true) {
@@ -471,10 +614,19 @@ impl JayScreencast {
         Ok(())
     }
 
+    #[cfg(feature = "it")]
+    pub fn allocate_buffers(&self, ctx: &Rc<dyn GfxContext>) -> Result<(), JayScreencastError> {
+        self.do_realloc(ctx)
+    }
+
     fn damage(&self) {
         if let Some(target) = self.target.get() {
             let rect = match target {
                 Target::Output(o) => o.global.pos.get(),
+                Target::Region(o, region) => {
+                    let pos = o.global.pos.get();
+                    region.move_(pos.x1(), pos.y1()).intersect(pos)
+                }
                 Target::Toplevel(t) => {
                     if !t.node_visible() {
                         return;
@@ -601,6 +753,14 @@ impl JayScreencastRequestHandler for JayScreencast {
                         o.add_screencast(slf);
                         new_target = Some(Target::Output(o));
                     }
+                    PendingTarget::Region(o, region) => {
+                        let Some(o) = o.output.node() else {
+                            self.do_destroy();
+                            return Ok(());
+                        };
+                        o.add_screencast(slf);
+                        new_target = Some(Target::Region(o, region));
+                    }
                     PendingTarget::Toplevel(t) => {
                         if t.destroyed.get() {
                             self.do_destroy();
@@ -650,12 +810,21 @@ impl JayScreencastRequestHandler for JayScreencast {
         if let Some(running) = self.pending.running.take() {
             self.running.set(running);
         }
+        if let Some(cursor_mode) = self.pending.cursor_mode.take()
+            && self.cursor_mode.replace(cursor_mode) != cursor_mode
+        {
+            self.cursor_position.take();
+            self.cursor_image.take();
+            self.pending_cursor_download.take();
+        }
 
         if need_realloc_or_reconfigure {
             slf.schedule_realloc_or_reconfigure();
         }
 
-        if capture_rules_changed && let Some(Target::Output(o)) = self.target.get() {
+        if capture_rules_changed
+            && let Some(Target::Output(o) | Target::Region(o, _)) = self.target.get()
+        {
             o.screencast_changed();
         }
 
@@ -748,6 +917,82 @@ impl JayScreencastRequestHandler for JayScreencast {
         }
         Err(JayScreencastError::NotDmabuf)
     }
+
+    fn set_region(&self, req: SetRegion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?;
+        let Some(region) = Rect::new_sized(req.x, req.y, req.width, req.height) else {
+            return Err(JayScreencastError::InvalidRegion);
+        };
+        if region.is_empty() {
+            return Err(JayScreencastError::InvalidRegion);
+        }
+        if self.destroyed.get() {
+            return Ok(());
+        }
+        self.pending
+            .target
+            .set(Some(Some(PendingTarget::Region(output, region))));
+        Ok(())
+    }
+
+    fn set_cursor_mode(&self, req: SetCursorMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mode = match req.mode {
+            CURSOR_MODE_EMBEDDED => CursorMode::Embedded,
+            CURSOR_MODE_HIDDEN => CursorMode::Hidden,
+            CURSOR_MODE_METADATA => CursorMode::Metadata,
+            _ => return Err(JayScreencastError::UnknownCursorMode(req.mode)),
+        };
+        if self.destroyed.get() {
+            return Ok(());
+        }
+        self.pending.cursor_mode.set(Some(mode));
+        Ok(())
+    }
+}
+
+struct CursorDownload {
+    screencast: Rc<JayScreencast>,
+    mem: Rc<Vec<Cell<u8>>>,
+    width: i32,
+    height: i32,
+    stride: i32,
+    hotspot_x: i32,
+    hotspot_y: i32,
+}
+
+impl AsyncShmGfxTextureCallback for CursorDownload {
+    fn completed(self: Rc<Self>, res: Result<(), GfxError>) {
+        let sc = &self.screencast;
+        sc.pending_cursor_download.take();
+        if sc.destroyed.get() {
+            return;
+        }
+        if let Err(e) = res {
+            log::error!("Could not download cursor image: {}", ErrorFmt(e));
+            return;
+        }
+        let mut fd = match uapi::memfd_create("cursor", c::MFD_CLOEXEC) {
+            Ok(fd) => fd,
+            Err(e) => {
+                log::error!("Could not create memfd: {}", ErrorFmt(OsError::from(e)));
+                return;
+            }
+        };
+        let bytes: Vec<u8> = self.mem.iter().map(|b| b.get()).collect();
+        if let Err(e) = fd.write_all(&bytes) {
+            log::error!("Could not write cursor image: {}", ErrorFmt(e));
+            return;
+        }
+        sc.client.event(CursorImage {
+            self_id: sc.id,
+            fd: Rc::new(fd),
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+            hotspot_x: self.hotspot_x,
+            hotspot_y: self.hotspot_y,
+        });
+    }
 }
 
 object_base! {
@@ -781,6 +1026,10 @@ pub enum JayScreencastError {
     Modifier,
     #[error("Buffer is not a dmabuf")]
     NotDmabuf,
+    #[error("Unknown cursor mode {0}")]
+    UnknownCursorMode(u32),
+    #[error("The region is empty")]
+    InvalidRegion,
 }
 efrom!(JayScreencastError, ClientError);
 
@@ -788,8 +1037,22 @@ fn target_size(target: Option<&Target>) -> (i32, i32) {
     if let Some(target) = target {
         return match target {
             Target::Output(o) => o.global.pixel_size(),
+            Target::Region(o, region) => {
+                let rect = region_pixel_rect(o, *region);
+                (rect.width(), rect.height())
+            }
             Target::Toplevel(t) => t.tl_data().desired_pixel_size(),
         };
     }
     (0, 0)
 }
+
+fn region_pixel_rect(output: &OutputNode, region: Rect) -> Rect {
+    let scale = output.global.persistent.scale.get().to_f64();
+    let (width, height) = output.global.pixel_size();
+    let x1 = (region.x1() as f64 * scale).round() as i32;
+    let y1 = (region.y1() as f64 * scale).round() as i32;
+    let x2 = (region.x2() as f64 * scale).round() as i32;
+    let y2 = (region.y2() as f64 * scale).round() as i32;
+    Rect::new_unchecked(x1, y1, x2, y2).intersect(Rect::new_sized_unchecked(0, 0, width, height))
+}
@@ -0,0 +1,83 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::RegionSelector,
+        leaks::Tracker,
+        object::{Object, Version},
+        rect::Rect,
+        wire::{JaySelectRegionId, jay_select_region::*},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct JaySelectRegion {
+    pub id: JaySelectRegionId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub destroyed: Cell<bool>,
+}
+
+pub struct JayRegionSelector {
+    pub rect: Cell<Option<Rect>>,
+    pub jsr: Rc<JaySelectRegion>,
+}
+
+impl RegionSelector for JayRegionSelector {
+    fn set(&self, rect: Rect) {
+        self.rect.set(Some(rect));
+    }
+}
+
+impl Drop for JayRegionSelector {
+    fn drop(&mut self) {
+        if self.jsr.destroyed.get() {
+            return;
+        }
+        match self.rect.take() {
+            None => self.jsr.send_cancelled(),
+            Some(rect) => self.jsr.send_selected(rect),
+        }
+        let _ = self.jsr.client.remove_obj(&*self.jsr);
+    }
+}
+
+impl JaySelectRegion {
+    pub fn send_cancelled(&self) {
+        self.client.event(Cancelled { self_id: self.id });
+    }
+
+    fn send_selected(&self, rect: Rect) {
+        self.client.event(Selected {
+            self_id: self.id,
+            x: rect.x1(),
+            y: rect.y1(),
+            width: rect.width(),
+            height: rect.height(),
+        });
+    }
+}
+
+impl JaySelectRegionRequestHandler for JaySelectRegion {
+    type Error = JaySelectRegionError;
+}
+
+object_base! {
+    self = JaySelectRegion;
+    version = Version(1);
+}
+
+impl Object for JaySelectRegion {
+    fn break_loops(&self) {
+        self.destroyed.set(true);
+    }
+}
+
+simple_add_obj!(JaySelectRegion);
+
+#[derive(Debug, Error)]
+pub enum JaySelectRegionError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JaySelectRegionError, ClientError);
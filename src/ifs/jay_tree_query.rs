@@ -156,7 +156,7 @@ impl JayTreeQuery {
             title: &data.title.borrow(),
         });
         if let Some(w) = data.workspace.get() {
-            self.send_workspace_name(&w.name);
+            self.send_workspace_name(&w.name.borrow());
         }
         match &data.kind {
             ToplevelType::Container => {}
@@ -227,7 +227,7 @@ impl JayTreeQuery {
         if let Some(ws) = data.workspace.get() {
             self.client.event(Workspace {
                 self_id: self.id,
-                name: &ws.name,
+                name: &ws.name.borrow(),
             });
         }
         if self.version >= CONTENT_TYPE_SINCE
@@ -409,7 +409,7 @@ impl tree::NodeVisitorBase for Visitor<'_> {
         let s = self.0;
         s.send_start(TREE_TY_WORKSPACE);
         s.send_node_position(&**node);
-        s.send_workspace_name(&node.name);
+        s.send_workspace_name(&node.name.borrow());
         s.send_output_name(&node.output.get().global.connector.name);
         for stacked in node.stacked.iter() {
             if stacked.stacked_is_xdg_popup() {
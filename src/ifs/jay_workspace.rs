@@ -37,7 +37,7 @@ impl JayWorkspace {
     pub fn send_name(&self, ws: &WorkspaceNode) {
         self.client.event(Name {
             self_id: self.id,
-            name: &ws.name,
+            name: &ws.name.borrow(),
         });
     }
 
@@ -2,11 +2,16 @@ mod event_handling;
 pub mod ext_transient_seat_manager_v1;
 pub mod ext_transient_seat_v1;
 mod gesture_owner;
+pub mod hot_corner;
+mod input_capture;
 mod kb_owner;
+mod key_sequence;
+mod mouse_keys;
 mod pointer_owner;
 pub mod tablet;
 pub mod text_input;
 mod touch_owner;
+pub mod window_switcher;
 pub mod wl_keyboard;
 pub mod wl_pointer;
 pub mod wl_touch;
@@ -24,7 +29,7 @@ pub mod zwp_virtual_keyboard_v1;
 use {
     crate::{
         async_engine::SpawnedFuture,
-        backend::{KeyState, Leds},
+        backend::{KeyState, Leds, ScrollAxis},
         client::{Client, ClientError, ClientId},
         cursor_user::{CursorUser, CursorUserGroup, CursorUserOwner},
         ei::ei_ifs::ei_seat::EiSeat,
@@ -44,11 +49,16 @@ use {
                 },
                 zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
             },
+            jay_global_shortcuts::JayGlobalShortcuts,
+            jay_input_capture::JayInputCapture,
             wl_output::WlOutputGlobal,
             wl_seat::{
                 event_handling::FocusHistoryData,
                 gesture_owner::GestureOwnerHolder,
+                hot_corner::HotCornerSeatData,
                 kb_owner::KbOwnerHolder,
+                key_sequence::KeySequenceSeatData,
+                mouse_keys::MouseKeysSeatData,
                 pointer_owner::PointerOwnerHolder,
                 tablet::TabletSeatData,
                 text_input::{
@@ -56,8 +66,9 @@ use {
                     zwp_input_method_v2::ZwpInputMethodV2, zwp_text_input_v3::ZwpTextInputV3,
                 },
                 touch_owner::TouchOwnerHolder,
+                window_switcher::WindowSwitcherSeatData,
                 wl_keyboard::{REPEAT_INFO_SINCE, WlKeyboard, WlKeyboardError},
-                wl_pointer::WlPointer,
+                wl_pointer::{PendingScroll, WlPointer},
                 wl_touch::WlTouch,
                 zwp_pointer_constraints_v1::{SeatConstraint, SeatConstraintStatus},
                 zwp_pointer_gesture_hold_v1::ZwpPointerGestureHoldV1,
@@ -83,8 +94,9 @@ use {
         tree::{
             ContainerNode, ContainerSplit, Direction, FoundNode, Node, NodeId, NodeLayer,
             NodeLayerLink, NodeLocation, OutputNode, StackedNode, ToplevelNode, WorkspaceNode,
-            generic_node_visitor, toplevel_create_split, toplevel_parent_container,
-            toplevel_set_floating, toplevel_set_workspace,
+            generic_node_visitor, scrolling_column, toplevel_create_split, toplevel_in_direction,
+            toplevel_parent_container, toplevel_set_floating, toplevel_set_workspace,
+            toplevel_swap,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -106,7 +118,14 @@ use {
         wire_ei::EiSeatId,
     },
     ahash::AHashMap,
-    jay_config::keyboard::syms::{KeySym, SYM_Escape},
+    jay_config::{
+        input::MouseButton,
+        keyboard::{
+            mods::{CAPS, Modifiers, NUM},
+            syms::{KeySym, SYM_Escape},
+        },
+        window::FloatSnap,
+    },
     kbvm::Keycode,
     smallvec::SmallVec,
     std::{
@@ -120,7 +139,7 @@ use {
 };
 pub use {
     event_handling::NodeSeatState,
-    pointer_owner::{ToplevelSelector, WorkspaceSelector},
+    pointer_owner::{ColorPicker, RegionSelector, ToplevelSelector, WorkspaceSelector},
 };
 
 pub const POINTER: u32 = 1;
@@ -132,6 +151,7 @@ const MISSING_CAPABILITY: u32 = 0;
 
 pub const BTN_LEFT: u32 = 0x110;
 pub const BTN_RIGHT: u32 = 0x111;
+pub const BTN_MIDDLE: u32 = 0x112;
 
 pub const SEAT_NAME_SINCE: Version = Version(2);
 
@@ -226,6 +246,7 @@ pub struct WlSeatGlobal {
     tablet: TabletSeatData,
     ei_seats: CopyHashMap<(ClientId, EiSeatId), Rc<EiSeat>>,
     ui_drag_highlight: Cell<Option<Rect>>,
+    color_picker_lens: Cell<Option<Rect>>,
     keyboard_node_serial: Cell<u64>,
     tray_popups: CopyHashMap<(TrayItemId, XdgPopupId), Rc<dyn DynTrayItem>>,
     revert_key: Cell<KeySym>,
@@ -235,7 +256,17 @@ pub struct WlSeatGlobal {
     focus_history_visible_only: Cell<bool>,
     focus_history_same_workspace: Cell<bool>,
     mark_mode: Cell<Option<MarkMode>>,
+    active_global_shortcuts: RefCell<AHashMap<u32, Vec<(Rc<JayGlobalShortcuts>, u32)>>>,
+    input_capture: CloneCell<Option<Rc<JayInputCapture>>>,
     marks: CopyHashMap<Keycode, Rc<dyn Node>>,
+    mouse_keys: MouseKeysSeatData,
+    key_sequence: KeySequenceSeatData,
+    hot_corners: HotCornerSeatData,
+    window_switcher: WindowSwitcherSeatData,
+    zoom_step: Cell<f64>,
+    zoom_scroll_mods: Cell<Option<Modifiers>>,
+    overview_swipe_enabled: Cell<bool>,
+    overview_swipe_fingers: Cell<u32>,
     modifiers_listener: EventListener<dyn LedsListener>,
     modifiers_forward: EventSource<dyn LedsListener>,
 }
@@ -244,6 +275,8 @@ pub struct WlSeatGlobal {
 enum MarkMode {
     Mark,
     Jump,
+    Swap,
+    Move,
 }
 
 const CHANGE_CURSOR_MOVED: u32 = 1 << 0;
@@ -316,6 +349,7 @@ impl WlSeatGlobal {
             tablet: Default::default(),
             ei_seats: Default::default(),
             ui_drag_highlight: Default::default(),
+            color_picker_lens: Default::default(),
             tray_popups: Default::default(),
             revert_key: Cell::new(SYM_Escape),
             last_focus_location: Default::default(),
@@ -324,7 +358,17 @@ impl WlSeatGlobal {
             focus_history_visible_only: Cell::new(false),
             focus_history_same_workspace: Cell::new(false),
             mark_mode: Default::default(),
+            active_global_shortcuts: Default::default(),
+            input_capture: Default::default(),
             marks: Default::default(),
+            mouse_keys: Default::default(),
+            key_sequence: Default::default(),
+            hot_corners: Default::default(),
+            window_switcher: Default::default(),
+            zoom_step: Cell::new(1.25),
+            zoom_scroll_mods: Default::default(),
+            overview_swipe_enabled: Cell::new(true),
+            overview_swipe_fingers: Cell::new(4),
             modifiers_listener: EventListener::new(slf.clone()),
             modifiers_forward: Default::default(),
         });
@@ -383,6 +427,10 @@ impl WlSeatGlobal {
         self.ui_drag_highlight.get()
     }
 
+    pub fn color_picker_lens(&self) -> Option<Rect> {
+        self.color_picker_lens.get()
+    }
+
     pub fn add_data_device(&self, device: &Rc<WlDataDevice>) {
         let mut dd = self.data_devices.borrow_mut();
         dd.entry(device.client.id)
@@ -712,8 +760,10 @@ impl WlSeatGlobal {
         if direction == Direction::Down && tl.node_is_container() {
             tl.node_do_focus(self, direction);
         } else if let Some(p) = tl.tl_data().parent.get() {
-            if let Some(c) = p.node_into_container() {
+            if let Some(c) = p.clone().node_into_container() {
                 c.move_focus_from_child(self, tl.deref(), direction);
+            } else if let Some(s) = p.node_into_scrolling() {
+                s.move_focus_from_column(self, tl.deref(), direction);
             }
         }
     }
@@ -722,9 +772,44 @@ impl WlSeatGlobal {
         let kb_node = self.keyboard_node.get();
         if let Some(tl) = kb_node.node_toplevel()
             && let Some(parent) = tl.tl_data().parent.get()
-            && let Some(c) = parent.node_into_container()
         {
-            c.move_child(tl, direction);
+            if let Some(c) = parent.clone().node_into_container() {
+                c.move_child(tl, direction);
+            } else if let Some(s) = parent.node_into_scrolling() {
+                s.move_column(&*tl, direction);
+            }
+        }
+    }
+
+    pub fn swap(self: &Rc<Self>, direction: Direction) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel()
+            && let Some(other) = toplevel_in_direction(&*tl, direction)
+        {
+            toplevel_swap(tl, other);
+        }
+    }
+
+    pub fn cycle_column_width(self: &Rc<Self>) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel()
+            && let Some((scrolling, column, _)) = scrolling_column(tl)
+        {
+            scrolling.cycle_column_width(&*column);
+        }
+    }
+
+    pub fn consume_into_column(self: &Rc<Self>) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel()
+            && let Some((scrolling, column, _)) = scrolling_column(tl)
+        {
+            scrolling.consume_into_column(&*column);
+        }
+    }
+
+    pub fn expel_from_column(self: &Rc<Self>) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel()
+            && let Some((scrolling, column, child)) = scrolling_column(tl)
+        {
+            scrolling.expel_from_column(&*column, child);
         }
     }
 
@@ -741,12 +826,12 @@ impl WlSeatGlobal {
         }
     }
 
-    fn get_focus_history(
+    /// Returns a function that filters focus-history entries according to the
+    /// `only-visible` and `same-workspace` settings.
+    fn focus_history_filter(
         &self,
-        next: impl Fn(&NodeRef<FocusHistoryData>) -> Option<NodeRef<FocusHistoryData>>,
-        first: impl FnOnce(&LinkedList<FocusHistoryData>) -> Option<NodeRef<FocusHistoryData>>,
-    ) -> Option<(Rc<dyn Node>, bool)> {
-        let original = self.keyboard_node.get();
+        original: &Rc<dyn Node>,
+    ) -> impl Fn(&FocusHistoryData) -> Option<(Rc<dyn Node>, bool)> + '_ {
         let mut output = None;
         let mut workspace = None;
         if let Some(old) = original.node_location() {
@@ -779,7 +864,7 @@ impl WlSeatGlobal {
         {
             workspace = Some(ws.id);
         }
-        let matches = |node: &FocusHistoryData| {
+        move |node: &FocusHistoryData| {
             let visible = node.visible.get();
             if self.focus_history_visible_only.get() && !visible {
                 return None;
@@ -801,7 +886,16 @@ impl WlSeatGlobal {
                 }
             }
             Some((node, visible))
-        };
+        }
+    }
+
+    fn get_focus_history(
+        &self,
+        next: impl Fn(&NodeRef<FocusHistoryData>) -> Option<NodeRef<FocusHistoryData>>,
+        first: impl FnOnce(&LinkedList<FocusHistoryData>) -> Option<NodeRef<FocusHistoryData>>,
+    ) -> Option<(Rc<dyn Node>, bool)> {
+        let original = self.keyboard_node.get();
+        let matches = self.focus_history_filter(&original);
         let node = original.node_seat_state().get_focus_history(self);
         if let Some(mut node) = node {
             loop {
@@ -946,10 +1040,10 @@ impl WlSeatGlobal {
                 NodeLayer::Layer1 => handle_layer_shell(&output.layers[1]),
                 NodeLayer::Output => None,
                 NodeLayer::Workspace => None,
-                NodeLayer::Tiled => ws
-                    .as_ref()
-                    .and_then(|w| w.container.get())
-                    .map(|n| n as Rc<dyn Node>),
+                NodeLayer::Tiled => ws.as_ref().and_then(|w| match w.scrolling.get() {
+                    Some(s) => Some(s as Rc<dyn Node>),
+                    _ => w.container.get().map(|n| n as Rc<dyn Node>),
+                }),
                 NodeLayer::Fullscreen => ws
                     .as_ref()
                     .and_then(|w| w.fullscreen.get())
@@ -1012,8 +1106,9 @@ impl WlSeatGlobal {
         };
         let node = match ws.fullscreen.get() {
             Some(fs) => fs as Rc<dyn Node>,
-            _ => match ws.container.get() {
-                Some(c) => c,
+            _ => match (ws.scrolling.get(), ws.container.get()) {
+                (Some(s), _) => s as Rc<dyn Node>,
+                (_, Some(c)) => c,
                 _ => return,
             },
         };
@@ -1247,6 +1342,12 @@ impl WlSeatGlobal {
         self.tablet_clear();
         self.ei_seats.clear();
         self.marks.clear();
+        self.active_global_shortcuts.borrow_mut().clear();
+        self.input_capture.take();
+        self.mouse_keys_clear();
+        self.clear_key_sequence();
+        self.hot_corners_clear();
+        self.close_window_switcher(false);
     }
 
     pub fn id(&self) -> SeatId {
@@ -1333,6 +1434,14 @@ impl WlSeatGlobal {
         self.pointer_owner.select_workspace(self, selector);
     }
 
+    pub fn select_region(self: &Rc<Self>, selector: impl RegionSelector) {
+        self.pointer_owner.select_region(self, selector);
+    }
+
+    pub fn pick_color(self: &Rc<Self>, picker: impl ColorPicker) {
+        self.pointer_owner.pick_color(self, picker);
+    }
+
     pub fn set_focus_follows_mouse(&self, focus_follows_mouse: bool) {
         self.focus_follows_mouse.set(focus_follows_mouse);
     }
@@ -1450,13 +1559,88 @@ impl WlSeatGlobal {
         tl.tl_set_pinned(true, pinned);
     }
 
+    pub fn sticky(&self) -> bool {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return false;
+        };
+        tl.tl_sticky()
+    }
+
+    pub fn set_sticky(&self, sticky: bool) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        tl.tl_set_sticky(true, sticky);
+    }
+
+    pub fn snap_float(&self, snap: FloatSnap) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        if let Some(float) = tl.tl_data().float.get() {
+            float.snap(snap);
+        }
+    }
+
     pub fn set_pointer_revert_key(&self, key: KeySym) {
         self.revert_key.set(key);
     }
+
+    pub fn zoom_in(&self) {
+        let output = self.pointer_cursor.output();
+        output.set_zoom(output.zoom.get() * self.zoom_step.get());
+    }
+
+    pub fn zoom_out(&self) {
+        let output = self.pointer_cursor.output();
+        output.set_zoom(output.zoom.get() / self.zoom_step.get());
+    }
+
+    pub fn zoom_reset(&self) {
+        self.pointer_cursor.output().set_zoom(1.0);
+    }
+
+    pub fn toggle_overview(self: &Rc<Self>) {
+        self.pointer_cursor.output().toggle_overview(self);
+    }
+
+    pub fn set_overview_swipe_enabled(&self, enabled: bool) {
+        self.overview_swipe_enabled.set(enabled);
+    }
+
+    pub fn set_overview_swipe_fingers(&self, fingers: u32) {
+        self.overview_swipe_fingers.set(fingers);
+    }
+
+    pub fn set_zoom_step(&self, step: f64) {
+        self.zoom_step.set(step);
+    }
+
+    pub fn set_zoom_scroll_mods(&self, mods: Option<Modifiers>) {
+        self.zoom_scroll_mods.set(mods);
+    }
+
+    fn handle_zoom_scroll(&self, pending: &PendingScroll, px_per_scroll_wheel: f64) -> bool {
+        let Some(zoom_mods) = self.zoom_scroll_mods.get() else {
+            return false;
+        };
+        let mods = self.latest_kb_state.get().borrow().mods.mods.0 & !(CAPS.0 | NUM.0);
+        if mods != zoom_mods.0 {
+            return false;
+        }
+        let Some(px) = pending.px[ScrollAxis::Vertical as usize].get() else {
+            return false;
+        };
+        let steps = px.to_f64() / px_per_scroll_wheel;
+        let output = self.pointer_cursor.output();
+        output.set_zoom(output.zoom.get() * self.zoom_step.get().powf(-steps));
+        true
+    }
 }
 
 impl CursorUserOwner for WlSeatGlobal {
     fn output_changed(&self, output: &Rc<OutputNode>) {
+        self.state.move_sticky_floats(output);
         if let Some(dnd) = self.pointer_owner.dnd_icon() {
             dnd.surface()
                 .set_output(output, NodeLocation::Output(output.id));
@@ -1637,6 +1821,14 @@ pub enum WlSeatError {
 efrom!(WlSeatError, ClientError);
 efrom!(WlSeatError, WlKeyboardError);
 
+pub fn mouse_button_code(button: MouseButton) -> u32 {
+    match button {
+        MouseButton::Left => BTN_LEFT,
+        MouseButton::Middle => BTN_MIDDLE,
+        MouseButton::Right => BTN_RIGHT,
+    }
+}
+
 pub fn collect_kb_foci2(node: Rc<dyn Node>, seats: &mut SmallVec<[Rc<WlSeatGlobal>; 3]>) {
     node.node_visit(&mut generic_node_visitor(|node| {
         node.node_seat_state().for_each_kb_focus(|s| seats.push(s));
@@ -1737,6 +1929,18 @@ impl DeviceHandlerData {
         }
         state.root.extents.get()
     }
+
+    pub fn get_tablet_rect(&self, state: &State) -> Rect {
+        let rect = self.get_rect(state);
+        let area = self.tablet_output_area.get();
+        let width = rect.width() as f64;
+        let height = rect.height() as f64;
+        let x1 = rect.x1() + (width * area.x).round() as i32;
+        let y1 = rect.y1() + (height * area.y).round() as i32;
+        let width = (width * area.width).round() as i32;
+        let height = (height * area.height).round() as i32;
+        Rect::new_sized(x1, y1, width, height).unwrap_or(rect)
+    }
 }
 
 impl LedsListener for DeviceHandlerData {
@@ -38,7 +38,7 @@ use {
         object::Version,
         rect::Rect,
         state::DeviceHandlerData,
-        tree::{Direction, Node, ToplevelNode},
+        tree::{Direction, Node, ToplevelNode, toplevel_move_next_to, toplevel_swap},
         utils::{
             bitflags::BitflagsExt,
             hash_map_ext::HashMapExt,
@@ -494,13 +494,25 @@ impl WlSeatGlobal {
                 time_usec,
                 id,
                 changes: change,
-            } => self.tablet_event_tool_changes(id, time_usec, dev.get_rect(&self.state), &change),
+            } => self.tablet_event_tool_changes(
+                id,
+                time_usec,
+                dev.get_tablet_rect(&self.state),
+                dev.tablet_pressure_curve.get(),
+                &change,
+            ),
             InputEvent::TabletToolButton {
                 time_usec,
                 id,
                 button,
                 state,
-            } => self.tablet_event_tool_button(id, time_usec, button, state),
+            } => self.tablet_event_tool_button(
+                id,
+                time_usec,
+                button,
+                dev.tablet_tool_buttons.get(&button),
+                state,
+            ),
             InputEvent::TabletToolRemoved { time_usec, id } => {
                 self.tablet_handle_remove_tool(time_usec, id)
             }
@@ -509,7 +521,7 @@ impl WlSeatGlobal {
                 id,
                 button,
                 state,
-            } => self.tablet_event_pad_button(id, time_usec, button, state),
+            } => self.tablet_event_pad_button(dev, id, time_usec, button, state),
             InputEvent::TabletPadModeSwitch {
                 time_usec,
                 pad,
@@ -522,20 +534,20 @@ impl WlSeatGlobal {
                 ring,
                 source,
                 angle,
-            } => self.tablet_event_pad_ring(pad, ring, source, angle, time_usec),
+            } => self.tablet_event_pad_ring(dev, pad, ring, source, angle, time_usec),
             InputEvent::TabletPadStrip {
                 time_usec,
                 pad,
                 strip,
                 source,
                 position,
-            } => self.tablet_event_pad_strip(pad, strip, source, position, time_usec),
+            } => self.tablet_event_pad_strip(dev, pad, strip, source, position, time_usec),
             InputEvent::TabletPadDial {
                 time_usec,
                 pad,
                 dial,
                 value120,
-            } => self.tablet_event_pad_dial(pad, dial, value120, time_usec),
+            } => self.tablet_event_pad_dial(dev, pad, dial, value120, time_usec),
             InputEvent::TouchDown {
                 time_usec,
                 id,
@@ -554,7 +566,7 @@ impl WlSeatGlobal {
         }
     }
 
-    fn set_pointer_cursor_position(&self, x: Fixed, y: Fixed) -> (Fixed, Fixed) {
+    pub(super) fn set_pointer_cursor_position(&self, x: Fixed, y: Fixed) -> (Fixed, Fixed) {
         let dnd_icon = self.pointer_owner.dnd_icon();
         if let Some(dnd_icon) = &dnd_icon {
             let (x_old, y_old) = self.pointer_cursor.position_int();
@@ -593,12 +605,20 @@ impl WlSeatGlobal {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_motion_abs(time_usec, x, y);
         });
+        if self.input_captured() {
+            return;
+        }
+        let old_pos = self.pointer_cursor.position();
+        if self.input_capture_motion(old_pos, (x, y)) {
+            return;
+        }
         let (x, y) = self.set_pointer_cursor_position(x, y);
         if let Some(c) = self.constraint.get()
             && (c.ty == ConstraintType::Lock || !c.contains(x.round_down(), y.round_down()))
         {
             c.deactivate(false);
         }
+        self.hot_corners_motion(x, y, Fixed(0), Fixed(0));
         self.state.for_each_seat_tester(|t| {
             t.send_pointer_abs(self.id, time_usec, x, y);
         });
@@ -616,6 +636,9 @@ impl WlSeatGlobal {
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_motion(time_usec, dx, dy);
         });
+        if self.input_captured() {
+            return;
+        }
         self.pointer_owner.relative_motion(
             self,
             time_usec,
@@ -630,6 +653,7 @@ impl WlSeatGlobal {
             _ => false,
         };
         let (mut x, mut y) = self.pointer_cursor.position();
+        let old_pos = (x, y);
         if !locked {
             x += dx;
             y += dy;
@@ -656,7 +680,13 @@ impl WlSeatGlobal {
                 dy_unaccelerated,
             );
         });
-        self.set_pointer_cursor_position(x, y);
+        if !locked && self.input_capture_motion(old_pos, (x, y)) {
+            return;
+        }
+        let (x_new, y_new) = self.set_pointer_cursor_position(x, y);
+        if !locked {
+            self.hot_corners_motion(x_new, y_new, x - x_new, y - y_new);
+        }
         self.cursor_moved(time_usec, false);
     }
 
@@ -679,6 +709,9 @@ impl WlSeatGlobal {
         self.state.for_each_seat_tester(|t| {
             t.send_button(self.id, time_usec, button, state);
         });
+        if self.input_captured() {
+            return;
+        }
         self.pointer_owner.button(self, time_usec, button, state);
     }
 
@@ -917,10 +950,32 @@ impl WlSeatGlobal {
                         self.jump_to_mark(kc);
                         kbvm_state = kbvm_state_rc.borrow_mut();
                     }
+                    MarkMode::Swap => {
+                        drop(kbvm_state);
+                        self.swap_with_mark(kc);
+                        kbvm_state = kbvm_state_rc.borrow_mut();
+                    }
+                    MarkMode::Move => {
+                        drop(kbvm_state);
+                        self.move_to_mark(kc);
+                        kbvm_state = kbvm_state_rc.borrow_mut();
+                    }
                 }
                 continue;
             }
+            if self.window_switcher_active() {
+                let mods = kbvm_state.kb_state.mods.mods.0;
+                drop(kbvm_state);
+                let consumed = self.window_switcher_key(kc, key_state == KeyState::Pressed, mods);
+                kbvm_state = kbvm_state_rc.borrow_mut();
+                if consumed {
+                    update_pressed_keys(&mut kbvm_state);
+                    continue;
+                }
+            }
             shortcuts.clear();
+            let mut syms = SmallVec::<[u32; 1]>::new();
+            let mut is_modifier = false;
             {
                 let mut mods = kbvm_state.kb_state.mods.mods.0 & !(CAPS.0 | NUM.0);
                 if key_state == KeyState::Released {
@@ -934,7 +989,9 @@ impl WlSeatGlobal {
                 );
                 let mut revert_pointer_to_default = false;
                 for props in keysyms {
+                    is_modifier |= props.keysym().is_modifier();
                     let sym = props.keysym().0;
+                    syms.push(props.keysym().to_lowercase().0);
                     if sym == self.revert_key.get().0 && mods == 0 {
                         revert_pointer_to_default = true;
                     }
@@ -961,6 +1018,41 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_key(self.id, time_usec, kc.to_evdev(), key_state);
             });
+            if key_state == KeyState::Released
+                && self.take_swallowed_key_sequence_key(kc.to_evdev())
+            {
+                update_pressed_keys(&mut kbvm_state);
+                continue;
+            }
+            if (shortcuts.is_empty() || key_state == KeyState::Released)
+                && self.handle_global_shortcuts(
+                    time_usec,
+                    kc,
+                    key_state,
+                    kbvm_state.kb_state.mods.mods.0,
+                    is_modifier,
+                    &syms,
+                )
+            {
+                update_pressed_keys(&mut kbvm_state);
+                continue;
+            }
+            if shortcuts.is_empty()
+                && key_state == KeyState::Pressed
+                && !is_modifier
+                && self.key_sequence_pending()
+            {
+                drop(kbvm_state);
+                self.swallow_key_sequence_key(kc.to_evdev());
+                self.cancel_key_sequence();
+                kbvm_state = kbvm_state_rc.borrow_mut();
+                update_pressed_keys(&mut kbvm_state);
+                if kbvm_state.kb_state.id != self.latest_kb_state_id.get() {
+                    kbvm_state.apply_events(events);
+                    return;
+                }
+                continue;
+            }
             if shortcuts.is_not_empty() {
                 self.forward.set(key_state == KeyState::Released);
                 if let Some(config) = self.state.config.get() {
@@ -980,6 +1072,13 @@ impl WlSeatGlobal {
                     continue;
                 }
             }
+            if self.input_captured() {
+                self.for_each_ei_seat(|ei_seat| {
+                    ei_seat.handle_key(time_usec, kc.to_evdev(), key_state, &kbvm_state.kb_state);
+                });
+                update_pressed_keys(&mut kbvm_state);
+                continue;
+            }
             self.send_components(&mut components_changed, &kbvm_state);
             match self.input_method_grab.get() {
                 Some(g) => g.on_key(time_usec, kc.to_evdev(), key_state, &kbvm_state.kb_state),
@@ -999,6 +1098,59 @@ impl WlSeatGlobal {
         self.send_components(&mut components_changed, &kbvm_state);
     }
 
+    fn handle_global_shortcuts(
+        &self,
+        time_usec: u64,
+        kc: Keycode,
+        key_state: KeyState,
+        mods: u32,
+        is_modifier: bool,
+        syms: &[u32],
+    ) -> bool {
+        if key_state == KeyState::Released {
+            let Some(active) = self
+                .active_global_shortcuts
+                .borrow_mut()
+                .remove(&kc.to_evdev())
+            else {
+                return false;
+            };
+            for (gs, id) in active {
+                gs.send_deactivated(id, time_usec);
+            }
+            return true;
+        }
+        if is_modifier || self.state.lock.locked.get() {
+            return false;
+        }
+        let mods = mods & !(CAPS.0 | NUM.0);
+        if let Some(capture) = self.state.global_shortcut_capture.take() {
+            capture.send_trigger_captured(mods, syms.first().copied().unwrap_or(0));
+            self.active_global_shortcuts
+                .borrow_mut()
+                .insert(kc.to_evdev(), vec![]);
+            return true;
+        }
+        let mut active = vec![];
+        for gs in self.state.global_shortcuts.lock().values() {
+            for &sym in syms {
+                for id in gs.matches(mods, sym) {
+                    active.push((gs.clone(), id));
+                }
+            }
+        }
+        if active.is_empty() {
+            return false;
+        }
+        for (gs, id) in &active {
+            gs.send_activated(*id, time_usec);
+        }
+        self.active_global_shortcuts
+            .borrow_mut()
+            .insert(kc.to_evdev(), active);
+        true
+    }
+
     pub fn create_mark_interactive(&self) {
         self.mark_mode.set(Some(MarkMode::Mark));
     }
@@ -1047,6 +1199,38 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn swap_with_mark_interactive(&self) {
+        self.mark_mode.set(Some(MarkMode::Swap));
+    }
+
+    pub fn swap_with_mark(self: &Rc<Self>, kc: Keycode) {
+        if let Some(node) = self.marks.get(&kc)
+            && let Some(target) = node.node_toplevel()
+            && let Some(tl) = self.keyboard_node.get().node_toplevel()
+        {
+            toplevel_swap(tl.clone(), target);
+            if !tl.node_visible() {
+                tl.node_make_visible();
+            }
+        }
+    }
+
+    pub fn move_to_mark_interactive(&self) {
+        self.mark_mode.set(Some(MarkMode::Move));
+    }
+
+    pub fn move_to_mark(self: &Rc<Self>, kc: Keycode) {
+        if let Some(node) = self.marks.get(&kc)
+            && let Some(target) = node.node_toplevel()
+            && let Some(tl) = self.keyboard_node.get().node_toplevel()
+        {
+            toplevel_move_next_to(tl.clone(), target);
+            if !tl.node_visible() {
+                tl.node_make_visible();
+            }
+        }
+    }
+
     pub fn copy_mark(self: &Rc<Self>, src: Keycode, dst: Keycode) {
         if let Some(node) = self.marks.get(&src) {
             self.create_mark_(dst, node);
@@ -1248,7 +1432,7 @@ impl WlSeatGlobal {
         });
     }
 
-    fn cursor_moved(self: &Rc<Self>, time_usec: u64, defer: bool) {
+    pub(super) fn cursor_moved(self: &Rc<Self>, time_usec: u64, defer: bool) {
         self.pos_time_usec.set(time_usec);
         self.changes.or_assign(CHANGE_CURSOR_MOVED);
         if defer {
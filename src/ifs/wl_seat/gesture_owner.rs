@@ -1,8 +1,16 @@
 use {
-    crate::{fixed::Fixed, ifs::wl_seat::WlSeatGlobal, tree::Node, utils::clonecell::CloneCell},
-    std::rc::Rc,
+    crate::{
+        fixed::Fixed,
+        ifs::wl_seat::WlSeatGlobal,
+        tree::{Node, ScrollingNode},
+        utils::clonecell::CloneCell,
+    },
+    std::{cell::Cell, rc::Rc},
 };
 
+const SCROLLING_SWIPE_FINGERS: u32 = 3;
+const OVERVIEW_SWIPE_DISTANCE: f64 = 100.0;
+
 pub struct GestureOwnerHolder {
     default: Rc<NoGesture>,
     owner: CloneCell<Rc<dyn GestureOwner>>,
@@ -143,6 +151,26 @@ impl GestureOwner for NoGesture {
     }
 
     fn swipe_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+        if finger_count == SCROLLING_SWIPE_FINGERS
+            && let Some(ws) = seat.get_output().workspace.get()
+            && ws.fullscreen.is_none()
+            && let Some(scrolling) = ws.scrolling.get()
+        {
+            let (x, y) = seat.pointer_cursor().position_int();
+            if ws.position.get().contains(x, y) {
+                scrolling.swipe_begin();
+                seat.gesture_owner
+                    .owner
+                    .set(Rc::new(ScrollingSwipeGesture { scrolling }));
+                return;
+            }
+        }
+        if seat.overview_swipe_enabled.get() && finger_count == seat.overview_swipe_fingers.get() {
+            seat.gesture_owner
+                .owner
+                .set(Rc::new(OverviewSwipeGesture { dy: Cell::new(0.0) }));
+            return;
+        }
         let Some(node) = seat.pointer_node() else {
             return;
         };
@@ -190,6 +218,53 @@ impl GestureOwner for SwipeGesture {
     }
 }
 
+struct ScrollingSwipeGesture {
+    scrolling: Rc<ScrollingNode>,
+}
+
+impl GestureOwner for ScrollingSwipeGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        self.swipe_end(seat, seat.state.now_usec(), true);
+    }
+
+    fn swipe_update(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, dx: Fixed, _dy: Fixed) {
+        self.scrolling.swipe_update(dx.to_f64());
+    }
+
+    fn swipe_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        self.scrolling.swipe_end(seat, cancelled);
+        seat.gesture_owner.set_default_owner();
+    }
+}
+
+struct OverviewSwipeGesture {
+    dy: Cell<f64>,
+}
+
+impl GestureOwner for OverviewSwipeGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        self.swipe_end(seat, seat.state.now_usec(), true);
+    }
+
+    fn swipe_update(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, _dx: Fixed, dy: Fixed) {
+        self.dy.set(self.dy.get() + dy.to_f64());
+    }
+
+    fn swipe_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        seat.gesture_owner.set_default_owner();
+        if cancelled {
+            return;
+        }
+        let output = seat.pointer_cursor().output();
+        let dy = self.dy.get();
+        if dy <= -OVERVIEW_SWIPE_DISTANCE {
+            output.open_overview(seat);
+        } else if dy >= OVERVIEW_SWIPE_DISTANCE {
+            output.close_overview(seat);
+        }
+    }
+}
+
 struct PinchGesture {
     node: Rc<dyn Node>,
 }
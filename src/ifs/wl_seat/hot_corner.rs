@@ -0,0 +1,164 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture, backend::ConnectorId, fixed::Fixed,
+        ifs::wl_seat::WlSeatGlobal, tree::OutputNode, utils::errorfmt::ErrorFmt,
+    },
+    jay_config::input::ScreenEdge,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct HotCorner {
+    pub id: u64,
+    pub edge: ScreenEdge,
+    pub dwell_usec: u64,
+    pub pressure: f64,
+}
+
+#[derive(Default)]
+pub struct HotCornerSeatData {
+    location: Cell<Option<(ConnectorId, ScreenEdge)>>,
+    pressure: Cell<f64>,
+    triggered: RefCell<Vec<u64>>,
+    dwell: RefCell<Vec<SpawnedFuture<()>>>,
+}
+
+impl WlSeatGlobal {
+    pub fn hot_corners_motion(self: &Rc<Self>, x: Fixed, y: Fixed, dx: Fixed, dy: Fixed) {
+        let hc = &self.hot_corners;
+        if self.state.hot_corners.is_empty() {
+            if hc.location.take().is_some() {
+                self.hot_corners_reset();
+            }
+            return;
+        }
+        let output = self.pointer_cursor.output();
+        let location = self
+            .screen_edge(&output, x.round_down(), y.round_down())
+            .map(|edge| (output.global.connector.connector.id(), edge));
+        if location != hc.location.get() {
+            hc.location.set(location);
+            self.hot_corners_reset();
+            let Some((_, edge)) = location else {
+                return;
+            };
+            let corners: Vec<_> = self
+                .state
+                .hot_corners
+                .lock()
+                .values()
+                .filter(|c| c.edge == edge)
+                .cloned()
+                .collect();
+            for corner in corners {
+                if corner.dwell_usec == 0 {
+                    if corner.pressure <= 0.0 {
+                        self.trigger_hot_corner(corner.id, &output);
+                    }
+                    continue;
+                }
+                let seat = self.clone();
+                let output = output.clone();
+                let id = corner.id;
+                let timeout_ms = corner.dwell_usec.div_ceil(1000);
+                let future = self.state.eng.spawn("hot corner dwell", async move {
+                    if let Err(e) = seat.state.wheel.timeout(timeout_ms).await {
+                        log::error!("Could not wait for hot corner dwell time: {}", ErrorFmt(e));
+                        return;
+                    }
+                    seat.trigger_hot_corner(id, &output);
+                });
+                hc.dwell.borrow_mut().push(future);
+            }
+            return;
+        }
+        let Some((_, edge)) = location else {
+            return;
+        };
+        let dx = dx.to_f64().abs();
+        let dy = dy.to_f64().abs();
+        let push = match edge {
+            ScreenEdge::Left | ScreenEdge::Right => dx,
+            ScreenEdge::Top | ScreenEdge::Bottom => dy,
+            _ => dx.max(dy),
+        };
+        if push <= 0.0 {
+            return;
+        }
+        let pressure = hc.pressure.get() + push;
+        hc.pressure.set(pressure);
+        let corners: Vec<_> = self
+            .state
+            .hot_corners
+            .lock()
+            .values()
+            .filter(|c| c.edge == edge && c.pressure > 0.0 && pressure >= c.pressure)
+            .map(|c| c.id)
+            .collect();
+        for id in corners {
+            self.trigger_hot_corner(id, &output);
+        }
+    }
+
+    fn screen_edge(&self, output: &OutputNode, x: i32, y: i32) -> Option<ScreenEdge> {
+        if output.is_dummy {
+            return None;
+        }
+        let pos = output.global.pos.get();
+        if pos.is_empty() || !pos.contains(x, y) {
+            return None;
+        }
+        let outside = |x: i32, y: i32| {
+            !self
+                .state
+                .root
+                .outputs
+                .lock()
+                .values()
+                .any(|o| o.global.pos.get().contains(x, y))
+        };
+        let left = x == pos.x1() && outside(x - 1, y);
+        let right = x == pos.x2() - 1 && outside(x + 1, y);
+        let top = y == pos.y1() && outside(x, y - 1);
+        let bottom = y == pos.y2() - 1 && outside(x, y + 1);
+        let edge = match (left, right, top, bottom) {
+            (true, _, true, _) => ScreenEdge::TopLeft,
+            (_, true, true, _) => ScreenEdge::TopRight,
+            (true, _, _, true) => ScreenEdge::BottomLeft,
+            (_, true, _, true) => ScreenEdge::BottomRight,
+            (true, _, _, _) => ScreenEdge::Left,
+            (_, true, _, _) => ScreenEdge::Right,
+            (_, _, true, _) => ScreenEdge::Top,
+            (_, _, _, true) => ScreenEdge::Bottom,
+            _ => return None,
+        };
+        Some(edge)
+    }
+
+    fn trigger_hot_corner(&self, id: u64, output: &OutputNode) {
+        {
+            let triggered = &mut *self.hot_corners.triggered.borrow_mut();
+            if triggered.contains(&id) {
+                return;
+            }
+            triggered.push(id);
+        }
+        if let Some(config) = self.state.config.get() {
+            config.hot_corner_triggered(id, self.id, output.global.connector.connector.id());
+        }
+    }
+
+    fn hot_corners_reset(&self) {
+        let hc = &self.hot_corners;
+        hc.pressure.set(0.0);
+        hc.triggered.borrow_mut().clear();
+        hc.dwell.borrow_mut().clear();
+    }
+
+    pub fn hot_corners_clear(&self) {
+        self.hot_corners.location.take();
+        self.hot_corners_reset();
+    }
+}
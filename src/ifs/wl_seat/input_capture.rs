@@ -0,0 +1,93 @@
+use {
+    crate::{
+        client::ClientId,
+        fixed::Fixed,
+        ifs::{jay_input_capture::JayInputCapture, wl_seat::WlSeatGlobal},
+    },
+    std::rc::Rc,
+};
+
+impl WlSeatGlobal {
+    pub fn input_capture(&self) -> Option<Rc<JayInputCapture>> {
+        self.input_capture.get()
+    }
+
+    pub fn input_captured(&self) -> bool {
+        self.input_capture.is_some()
+    }
+
+    pub fn is_input_captured_by(&self, ei_client: ClientId) -> bool {
+        match self.input_capture.get() {
+            Some(ic) => ic.ei_client_id.get() == Some(ei_client),
+            None => false,
+        }
+    }
+
+    pub(super) fn input_capture_motion(
+        self: &Rc<Self>,
+        (x1, y1): (Fixed, Fixed),
+        (x2, y2): (Fixed, Fixed),
+    ) -> bool {
+        if self.state.input_captures.is_empty()
+            || self.input_captured()
+            || self.state.lock.locked.get()
+        {
+            return false;
+        }
+        let (x1_int, y1_int) = (x1.round_down(), y1.round_down());
+        let (x2_int, y2_int) = (x2.round_down(), y2.round_down());
+        let captures: Vec<_> = self.state.input_captures.lock().values().cloned().collect();
+        for ic in captures {
+            if let Some(barrier) = ic.crossed_barrier(x1_int, y1_int, x2_int, y2_int) {
+                self.activate_input_capture(&ic, barrier, x1, y1);
+                return true;
+            }
+        }
+        false
+    }
+
+    fn activate_input_capture(
+        self: &Rc<Self>,
+        ic: &Rc<JayInputCapture>,
+        barrier: u32,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        let activation_id = ic.next_activation_id.fetch_add(1) + 1;
+        ic.activation_id.set(activation_id);
+        ic.seat.set(Some(self.clone()));
+        self.input_capture.set(Some(ic.clone()));
+        if let Some(ei_client) = ic.ei_client_id.get() {
+            self.for_each_ei_seat(|ei_seat| {
+                if ei_seat.client.id == ei_client
+                    && let Some(device) = ei_seat.device.get()
+                {
+                    device.send_start_emulating(ei_seat.client.serial(), activation_id);
+                }
+            });
+        }
+        ic.send_activated(activation_id, barrier, x, y);
+    }
+
+    pub fn deactivate_input_capture(self: &Rc<Self>, warp: Option<(Fixed, Fixed)>) {
+        let Some(ic) = self.input_capture.take() else {
+            return;
+        };
+        ic.seat.take();
+        if let Some(ei_client) = ic.ei_client_id.get() {
+            self.for_each_ei_seat(|ei_seat| {
+                if ei_seat.client.id == ei_client
+                    && let Some(device) = ei_seat.device.get()
+                {
+                    device.send_stop_emulating(ei_seat.client.serial());
+                }
+            });
+        }
+        if let Some((x, y)) = warp {
+            self.set_pointer_cursor_position(x, y);
+            self.cursor_moved(self.state.now_usec(), false);
+        }
+        let (x, y) = self.pointer_cursor.position();
+        ic.send_deactivated(ic.activation_id.get(), x, y);
+    }
+}
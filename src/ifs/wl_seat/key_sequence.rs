@@ -0,0 +1,80 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        ifs::wl_seat::WlSeatGlobal,
+        utils::{clonecell::CloneCell, errorfmt::ErrorFmt},
+    },
+    ahash::AHashSet,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+#[derive(Default)]
+pub struct KeySequenceSeatData {
+    prefix: CloneCell<Option<Rc<String>>>,
+    timeout: Cell<Option<SpawnedFuture<()>>>,
+    swallowed: RefCell<AHashSet<u32>>,
+}
+
+impl WlSeatGlobal {
+    pub fn begin_key_sequence(self: &Rc<Self>, prefix: &str, timeout_ms: u64) {
+        let ks = &self.key_sequence;
+        ks.timeout.take();
+        ks.prefix.set(Some(Rc::new(prefix.to_owned())));
+        if timeout_ms > 0 {
+            let seat = self.clone();
+            let future = self.state.eng.spawn("key sequence timeout", async move {
+                if let Err(e) = seat.state.wheel.timeout(timeout_ms).await {
+                    log::error!("Could not wait for key sequence timeout: {}", ErrorFmt(e));
+                    return;
+                }
+                seat.cancel_key_sequence();
+            });
+            ks.timeout.set(Some(future));
+        }
+        self.state.key_sequences_changed();
+    }
+
+    pub fn end_key_sequence(&self) -> bool {
+        let ks = &self.key_sequence;
+        ks.timeout.take();
+        if ks.prefix.take().is_none() {
+            return false;
+        }
+        self.state.key_sequences_changed();
+        true
+    }
+
+    pub fn cancel_key_sequence(&self) {
+        if self.end_key_sequence()
+            && let Some(config) = self.state.config.get()
+        {
+            config.key_sequence_cancelled(self.id);
+        }
+    }
+
+    pub fn clear_key_sequence(&self) {
+        self.end_key_sequence();
+        self.key_sequence.swallowed.borrow_mut().clear();
+    }
+
+    /// Records that the press of `key` cancelled the key sequence so that its release is
+    /// not forwarded to clients either.
+    pub fn swallow_key_sequence_key(&self, key: u32) {
+        self.key_sequence.swallowed.borrow_mut().insert(key);
+    }
+
+    pub fn take_swallowed_key_sequence_key(&self, key: u32) -> bool {
+        self.key_sequence.swallowed.borrow_mut().remove(&key)
+    }
+
+    pub fn key_sequence_pending(&self) -> bool {
+        self.key_sequence.prefix.is_some()
+    }
+
+    pub fn key_sequence_prefix(&self) -> Option<Rc<String>> {
+        self.key_sequence.prefix.get()
+    }
+}
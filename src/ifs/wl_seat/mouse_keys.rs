@@ -0,0 +1,178 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        backend::KeyState,
+        fixed::Fixed,
+        ifs::wl_seat::{BTN_LEFT, WlSeatGlobal, mouse_button_code},
+        tree::Direction,
+        utils::{errorfmt::ErrorFmt, smallmap::SmallMap},
+    },
+    jay_config::input::MouseButton,
+    std::{cell::Cell, rc::Rc},
+};
+
+const TICK_MS: u64 = 10;
+
+const LEFT: u8 = 1 << 0;
+const DOWN: u8 = 1 << 1;
+const UP: u8 = 1 << 2;
+const RIGHT: u8 = 1 << 3;
+
+pub struct MouseKeysSeatData {
+    directions: Cell<u8>,
+    motion_start_usec: Cell<u64>,
+    motion_last_usec: Cell<u64>,
+    motion: Cell<Option<SpawnedFuture<()>>>,
+    button: Cell<u32>,
+    pressed: SmallMap<u32, (), 3>,
+    initial_speed: Cell<f64>,
+    max_speed: Cell<f64>,
+    time_to_max_usec: Cell<u64>,
+}
+
+impl Default for MouseKeysSeatData {
+    fn default() -> Self {
+        Self {
+            directions: Default::default(),
+            motion_start_usec: Default::default(),
+            motion_last_usec: Default::default(),
+            motion: Default::default(),
+            button: Cell::new(BTN_LEFT),
+            pressed: Default::default(),
+            initial_speed: Cell::new(100.0),
+            max_speed: Cell::new(1200.0),
+            time_to_max_usec: Cell::new(1_000_000),
+        }
+    }
+}
+
+impl MouseKeysSeatData {
+    fn speed(&self, now: u64) -> f64 {
+        let initial = self.initial_speed.get();
+        let max = self.max_speed.get();
+        let time_to_max = self.time_to_max_usec.get();
+        let elapsed = now.saturating_sub(self.motion_start_usec.get());
+        if elapsed >= time_to_max {
+            return max;
+        }
+        initial + (max - initial) * elapsed as f64 / time_to_max as f64
+    }
+}
+
+impl WlSeatGlobal {
+    pub fn mouse_keys_set_motion(self: &Rc<Self>, direction: Direction, active: bool) {
+        let mk = &self.mouse_keys;
+        let bit = match direction {
+            Direction::Unspecified => return,
+            Direction::Left => LEFT,
+            Direction::Down => DOWN,
+            Direction::Up => UP,
+            Direction::Right => RIGHT,
+        };
+        let old = mk.directions.get();
+        let new = match active {
+            true => old | bit,
+            false => old & !bit,
+        };
+        mk.directions.set(new);
+        if new == 0 {
+            mk.motion.take();
+            return;
+        }
+        if old != 0 {
+            return;
+        }
+        let now = self.state.now_usec();
+        mk.motion_start_usec.set(now);
+        mk.motion_last_usec.set(now);
+        self.mouse_keys_step(now);
+        let seat = self.clone();
+        let future = self.state.eng.spawn("mouse keys", async move {
+            loop {
+                if let Err(e) = seat.state.wheel.timeout(TICK_MS).await {
+                    log::error!("Could not wait for mouse keys tick: {}", ErrorFmt(e));
+                    return;
+                }
+                seat.mouse_keys_step(seat.state.now_usec());
+            }
+        });
+        mk.motion.set(Some(future));
+    }
+
+    fn mouse_keys_step(self: &Rc<Self>, now: u64) {
+        let mk = &self.mouse_keys;
+        let directions = mk.directions.get();
+        let mut dx = 0.0;
+        let mut dy = 0.0;
+        if directions & LEFT != 0 {
+            dx -= 1.0;
+        }
+        if directions & RIGHT != 0 {
+            dx += 1.0;
+        }
+        if directions & UP != 0 {
+            dy -= 1.0;
+        }
+        if directions & DOWN != 0 {
+            dy += 1.0;
+        }
+        let dt = match now.saturating_sub(mk.motion_last_usec.replace(now)) {
+            0 => TICK_MS * 1000,
+            dt => dt,
+        };
+        let distance = mk.speed(now) * dt as f64 / 1_000_000.0;
+        let dx = Fixed::from_f64(dx * distance);
+        let dy = Fixed::from_f64(dy * distance);
+        if dx == Fixed(0) && dy == Fixed(0) {
+            return;
+        }
+        self.motion_event(now, dx, dy, dx, dy);
+    }
+
+    pub fn mouse_keys_select_button(&self, button: MouseButton) {
+        self.mouse_keys.button.set(mouse_button_code(button));
+    }
+
+    pub fn mouse_keys_click(self: &Rc<Self>, double: bool) {
+        let button = self.mouse_keys.button.get();
+        for _ in 0..1 + double as usize {
+            let now = self.state.now_usec();
+            self.button_event(now, button, KeyState::Pressed);
+            self.button_event(now, button, KeyState::Released);
+        }
+    }
+
+    pub fn mouse_keys_press(self: &Rc<Self>) {
+        let mk = &self.mouse_keys;
+        let button = mk.button.get();
+        if mk.pressed.insert(button, ()).is_some() {
+            return;
+        }
+        self.button_event(self.state.now_usec(), button, KeyState::Pressed);
+    }
+
+    pub fn mouse_keys_release(self: &Rc<Self>) {
+        let now = self.state.now_usec();
+        for (button, _) in self.mouse_keys.pressed.take() {
+            self.button_event(now, button, KeyState::Released);
+        }
+    }
+
+    pub fn mouse_keys_set_acceleration(
+        &self,
+        initial_speed: f64,
+        max_speed: f64,
+        time_to_max_usec: u64,
+    ) {
+        let mk = &self.mouse_keys;
+        mk.initial_speed.set(initial_speed);
+        mk.max_speed.set(max_speed);
+        mk.time_to_max_usec.set(time_to_max_usec);
+    }
+
+    pub fn mouse_keys_clear(&self) {
+        self.mouse_keys.directions.set(0);
+        self.mouse_keys.motion.take();
+        self.mouse_keys.pressed.take();
+    }
+}
@@ -16,9 +16,9 @@ use {
         rect::Rect,
         time::Time,
         tree::{
-            ContainerNode, ContainerSplit, ContainingNode, FindTreeUsecase, FoundNode, Node,
-            PlaceholderNode, TddType, ToplevelNode, WorkspaceDragDestination, WorkspaceNode,
-            WsMoveConfig, move_ws_to_output, toplevel_set_workspace,
+            ContainerNode, ContainingNode, FindTreeUsecase, FoundNode, Node, PlaceholderNode,
+            TddType, ToplevelNode, WorkspaceDragDestination, WorkspaceNode, WsMoveConfig,
+            move_ws_to_output, toplevel_set_workspace,
         },
         utils::{clonecell::CloneCell, smallmap::SmallMap},
     },
@@ -43,6 +43,17 @@ pub trait WorkspaceSelector: 'static {
     fn set(&self, ws: Rc<WorkspaceNode>);
 }
 
+pub trait RegionSelector: 'static {
+    fn set(&self, rect: Rect);
+}
+
+pub trait ColorPicker: 'static {
+    fn pick(&self, x: Fixed, y: Fixed);
+}
+
+/// The size of the color picker lens in logical pixels.
+const COLOR_PICKER_LENS_SIZE: i32 = 160;
+
 impl Default for PointerOwnerHolder {
     fn default() -> Self {
         let default = Rc::new(SimplePointerOwner {
@@ -89,12 +100,18 @@ impl PointerOwnerHolder {
                 pending.px[axis].set(Some(Fixed::from_f64(px)));
             }
         }
+        if seat.handle_zoom_scroll(&pending, px_per_scroll_wheel) {
+            return;
+        }
         seat.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_pending_scroll(time_usec, &pending);
         });
         seat.state.for_each_seat_tester(|t| {
             t.send_axis(seat.id, time_usec, &pending);
         });
+        if seat.input_captured() {
+            return;
+        }
         if let Some(node) = self.owner.get().axis_node(seat) {
             node.node_on_axis_event(seat, &pending);
         }
@@ -202,6 +219,23 @@ impl PointerOwnerHolder {
         self.select_element(seat, usecase)
     }
 
+    pub fn select_region(&self, seat: &Rc<WlSeatGlobal>, selector: impl RegionSelector) {
+        self.revert_to_default(seat);
+        seat.pointer_cursor.set_known(KnownCursor::Crosshair);
+        self.owner.set(Rc::new(SelectRegionPointerOwner {
+            start: Default::default(),
+            selector,
+        }));
+    }
+
+    pub fn pick_color(&self, seat: &Rc<WlSeatGlobal>, picker: impl ColorPicker) {
+        self.revert_to_default(seat);
+        seat.pointer_cursor.set_known(KnownCursor::Crosshair);
+        let owner = Rc::new(PickColorPointerOwner { picker });
+        owner.apply_changes(seat);
+        self.owner.set(owner);
+    }
+
     pub fn set_window_management_enabled(&self, seat: &Rc<WlSeatGlobal>, enabled: bool) {
         let owner = self.owner.get();
         if enabled {
@@ -317,6 +351,15 @@ struct SelectWorkspaceUsecase<S: ?Sized> {
 #[derive(Copy, Clone)]
 struct WindowManagementUsecase;
 
+struct SelectRegionPointerOwner<S> {
+    start: Cell<Option<(i32, i32)>>,
+    selector: S,
+}
+
+struct PickColorPointerOwner<P> {
+    picker: P,
+}
+
 impl<T: SimplePointerOwnerUsecase> PointerOwner for SimplePointerOwner<T> {
     fn button(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, button: u32, state: KeyState) {
         if state != KeyState::Pressed {
@@ -1261,6 +1304,98 @@ where
     }
 }
 
+impl<S: RegionSelector> SelectRegionPointerOwner<S> {
+    fn selection(&self, seat: &Rc<WlSeatGlobal>) -> Option<Rect> {
+        let (x1, y1) = self.start.get()?;
+        let (x2, y2) = seat.pointer_cursor.position_int();
+        Rect::new(x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2))
+    }
+}
+
+impl<S: RegionSelector> PointerOwner for SelectRegionPointerOwner<S> {
+    fn button(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, button: u32, state: KeyState) {
+        if button == BTN_RIGHT {
+            if state == KeyState::Pressed {
+                self.revert_to_default(seat);
+            }
+            return;
+        }
+        if button != BTN_LEFT {
+            return;
+        }
+        if state == KeyState::Pressed {
+            self.start.set(Some(seat.pointer_cursor.position_int()));
+            self.apply_changes(seat);
+            return;
+        }
+        let Some(rect) = self.selection(seat) else {
+            return;
+        };
+        if rect.is_empty() {
+            self.start.set(None);
+            handle_ui_drag_highlight(seat, None);
+            return;
+        }
+        self.selector.set(rect);
+        self.revert_to_default(seat);
+    }
+
+    fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) {
+        handle_ui_drag_highlight(seat, self.selection(seat));
+    }
+
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        handle_ui_drag_highlight(seat, None);
+        seat.pointer_cursor.set_known(KnownCursor::Default);
+        seat.pointer_owner.set_default_pointer_owner(seat);
+    }
+}
+
+impl<P: ColorPicker> PointerOwner for PickColorPointerOwner<P> {
+    fn button(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, button: u32, state: KeyState) {
+        if button == BTN_RIGHT {
+            if state == KeyState::Pressed {
+                self.revert_to_default(seat);
+            }
+            return;
+        }
+        if button != BTN_LEFT || state != KeyState::Released {
+            return;
+        }
+        let (x, y) = seat.pointer_cursor.position();
+        self.revert_to_default(seat);
+        self.picker.pick(x, y);
+    }
+
+    fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) {
+        let (x, y) = seat.pointer_cursor.position_int();
+        let offset = COLOR_PICKER_LENS_SIZE / 2;
+        let lens = Rect::new_sized_unchecked(
+            x - offset,
+            y - offset,
+            COLOR_PICKER_LENS_SIZE,
+            COLOR_PICKER_LENS_SIZE,
+        );
+        handle_color_picker_lens(seat, Some(lens));
+    }
+
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        handle_color_picker_lens(seat, None);
+        seat.pointer_cursor.set_known(KnownCursor::Default);
+        seat.pointer_owner.set_default_pointer_owner(seat);
+    }
+}
+
+fn handle_color_picker_lens(seat: &Rc<WlSeatGlobal>, new_lens: Option<Rect>) {
+    let prev_lens = seat.color_picker_lens.replace(new_lens);
+    if let Some(rect) = prev_lens {
+        seat.state.damage(rect);
+    }
+    if let Some(rect) = new_lens {
+        seat.state.damage(rect);
+    }
+}
+
 fn handle_ui_drag_highlight(seat: &Rc<WlSeatGlobal>, new_highlight: Option<Rect>) {
     let prev_highlight = seat.ui_drag_highlight.replace(new_highlight);
     if prev_highlight != new_highlight {
@@ -1300,12 +1435,8 @@ impl UiDragUsecase for TileDragUsecase {
         };
         let new_container = |workspace: &Rc<WorkspaceNode>| {
             src_parent.clone().cnode_remove_child2(&*src, true);
-            let cn = ContainerNode::new(
-                &seat.state,
-                &workspace,
-                src.clone(),
-                ContainerSplit::Horizontal,
-            );
+            let cn =
+                ContainerNode::new(&seat.state, &workspace, src.clone(), workspace.split.get());
             workspace.set_container(&cn);
         };
         match dest {
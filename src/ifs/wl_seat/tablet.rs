@@ -209,10 +209,12 @@ pub struct TabletPadGroup {
 
 pub struct TabletPadStrip {
     bindings: TabletBindings<ZwpTabletPadStripV2>,
+    position: Cell<Option<f64>>,
 }
 
 pub struct TabletPadRing {
     bindings: TabletBindings<ZwpTabletPadRingV2>,
+    angle: Cell<Option<f64>>,
 }
 
 pub struct TabletPadDial {
@@ -229,7 +231,57 @@ pub enum TabletStripEventSource {
     Finger,
 }
 
-#[derive(Debug, Default)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TabletOutputArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Default for TabletOutputArea {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PressureCurve {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
+impl PressureCurve {
+    pub fn apply(&self, pressure: f64) -> f64 {
+        fn bezier(p1: f64, p2: f64, t: f64) -> f64 {
+            let s = 1.0 - t;
+            3.0 * s * s * t * p1 + 3.0 * s * t * t * p2 + t * t * t
+        }
+        let pressure = pressure.clamp(0.0, 1.0);
+        // Since both control points are inside the unit square, x(t) is monotonic and we
+        // can find t by bisection.
+        let mut lo = 0.0;
+        let mut hi = 1.0;
+        for _ in 0..32 {
+            let mid = (lo + hi) / 2.0;
+            if bezier(self.x1, self.x2, mid) < pressure {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        bezier(self.y1, self.y2, (lo + hi) / 2.0)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
 pub struct TabletToolChanges {
     pub down: Option<bool>,
     pub pos: Option<TabletTool2dChange<TabletToolPositionChange>>,
@@ -14,9 +14,11 @@ use {
             },
             wl_surface::WlSurface,
         },
+        state::DeviceHandlerData,
         time::usec_to_msec,
         utils::{clonecell::CloneCell, hash_map_ext::HashMapExt},
     },
+    jay_config::input::{TabletPadElement, TabletPadEvent},
     std::{cell::Cell, rc::Rc},
 };
 
@@ -26,12 +28,14 @@ impl WlSeatGlobal {
         for _ in 0..init.strips {
             strips.push(Rc::new(TabletPadStrip {
                 bindings: Default::default(),
+                position: Default::default(),
             }));
         }
         let mut rings = Vec::new();
         for _ in 0..init.rings {
             rings.push(Rc::new(TabletPadRing {
                 bindings: Default::default(),
+                angle: Default::default(),
             }));
         }
         let mut dials = Vec::new();
@@ -111,8 +115,27 @@ impl WlSeatGlobal {
         }
     }
 
+    fn tablet_pad_binding(
+        &self,
+        dev: &DeviceHandlerData,
+        pad: &TabletPad,
+        element: TabletPadElement,
+        event: Option<TabletPadEvent>,
+    ) -> bool {
+        if !dev.tablet_pad_bindings.contains(&element) {
+            return false;
+        }
+        if let Some(event) = event
+            && let Some(config) = self.state.config.get()
+        {
+            config.tablet_pad_event(pad.dev, element, event);
+        }
+        true
+    }
+
     pub fn tablet_event_pad_button(
         self: &Rc<Self>,
+        dev: &DeviceHandlerData,
         pad: TabletPadId,
         time_usec: u64,
         button: u32,
@@ -122,6 +145,12 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_tablet_pad_button(self.id, pad.dev, time_usec, button, state)
             });
+            let event = TabletPadEvent::Button {
+                pressed: state == PadButtonState::Pressed,
+            };
+            if self.tablet_pad_binding(dev, &pad, TabletPadElement::Button(button), Some(event)) {
+                return;
+            }
             if pad.tablet.is_some() {
                 pad.pad_owner.button(&pad, time_usec, button, state);
             }
@@ -130,6 +159,7 @@ impl WlSeatGlobal {
 
     pub fn tablet_event_pad_ring(
         self: &Rc<Self>,
+        dev: &DeviceHandlerData,
         pad: TabletPadId,
         ring: u32,
         source: Option<TabletRingEventSource>,
@@ -140,17 +170,34 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_tablet_pad_ring(self.id, pad.dev, time_usec, ring, source, angle)
             });
-            if pad.tablet.is_some()
-                && let Some(ring) = pad.rings.get(ring as usize)
+            let Some(ring_obj) = pad.rings.get(ring as usize) else {
+                return;
+            };
+            let mut event = None;
+            if let Some(prev) = ring_obj.angle.replace(angle)
+                && let Some(angle) = angle
             {
+                let mut degrees = angle - prev;
+                if degrees > 180.0 {
+                    degrees -= 360.0;
+                } else if degrees <= -180.0 {
+                    degrees += 360.0;
+                }
+                event = Some(TabletPadEvent::Ring { degrees });
+            }
+            if self.tablet_pad_binding(dev, &pad, TabletPadElement::Ring(ring), event) {
+                return;
+            }
+            if pad.tablet.is_some() {
                 let node = self.keyboard_node.get();
-                node.node_on_tablet_pad_ring(&pad, ring, source, angle, time_usec);
+                node.node_on_tablet_pad_ring(&pad, ring_obj, source, angle, time_usec);
             }
         }
     }
 
     pub fn tablet_event_pad_strip(
         self: &Rc<Self>,
+        dev: &DeviceHandlerData,
         pad: TabletPadId,
         strip: u32,
         source: Option<TabletStripEventSource>,
@@ -161,17 +208,30 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_tablet_pad_strip(self.id, pad.dev, time_usec, strip, source, position)
             });
-            if pad.tablet.is_some()
-                && let Some(strip) = pad.strips.get(strip as usize)
+            let Some(strip_obj) = pad.strips.get(strip as usize) else {
+                return;
+            };
+            let mut event = None;
+            if let Some(prev) = strip_obj.position.replace(position)
+                && let Some(position) = position
             {
+                event = Some(TabletPadEvent::Strip {
+                    delta: position - prev,
+                });
+            }
+            if self.tablet_pad_binding(dev, &pad, TabletPadElement::Strip(strip), event) {
+                return;
+            }
+            if pad.tablet.is_some() {
                 let node = pad.node.get();
-                node.node_on_tablet_pad_strip(&pad, strip, source, position, time_usec);
+                node.node_on_tablet_pad_strip(&pad, strip_obj, source, position, time_usec);
             }
         }
     }
 
     pub fn tablet_event_pad_dial(
         self: &Rc<Self>,
+        dev: &DeviceHandlerData,
         pad: TabletPadId,
         dial: u32,
         value120: i32,
@@ -181,6 +241,10 @@ impl WlSeatGlobal {
             self.state.for_each_seat_tester(|t| {
                 t.send_tablet_pad_dial(self.id, pad.dev, time_usec, value120, dial)
             });
+            let event = TabletPadEvent::Dial { value120 };
+            if self.tablet_pad_binding(dev, &pad, TabletPadElement::Dial(dial), Some(event)) {
+                return;
+            }
             if pad.tablet.is_some()
                 && let Some(dial) = pad.dials.get(dial as usize)
             {
@@ -1,13 +1,14 @@
 use {
     crate::{
+        backend::KeyState,
         cursor::KnownCursor,
         fixed::Fixed,
         ifs::{
             wl_seat::{
                 WlSeatGlobal,
                 tablet::{
-                    TabletTool, TabletToolChanges, TabletToolId, TabletToolInit, TabletToolOpt,
-                    TabletToolType, ToolButtonState, normalizei, normalizeu,
+                    PressureCurve, TabletTool, TabletToolChanges, TabletToolId, TabletToolInit,
+                    TabletToolOpt, TabletToolType, ToolButtonState, normalizei, normalizeu,
                     zwp_tablet_tool_v2::ZwpTabletToolV2, zwp_tablet_v2::ZwpTabletV2,
                 },
             },
@@ -21,6 +22,12 @@ use {
 };
 
 impl WlSeatGlobal {
+    #[cfg(feature = "it")]
+    pub fn tablet_tool_state(&self, id: TabletToolId) -> Option<((Fixed, Fixed), f64)> {
+        let tool = self.tablet.tools.get(&id)?;
+        Some((tool.cursor.position(), tool.pressure.get()))
+    }
+
     pub fn tablet_handle_remove_tool(self: &Rc<Self>, time_usec: u64, id: TabletToolId) {
         let Some(tool) = self.tablet.tools.remove(&id) else {
             return;
@@ -75,6 +82,7 @@ impl WlSeatGlobal {
         id: TabletToolId,
         time_usec: u64,
         button: u32,
+        mouse_button: Option<u32>,
         state: ToolButtonState,
     ) {
         let Some(tool) = self.tablet.tools.get(&id) else {
@@ -83,6 +91,16 @@ impl WlSeatGlobal {
         self.state.for_each_seat_tester(|t| {
             t.send_tablet_tool_button(self.id, tool.tablet.dev, &tool, time_usec, button, state);
         });
+        if let Some(button) = mouse_button {
+            let (x, y) = tool.cursor.position();
+            self.motion_event_abs(time_usec, x, y, false);
+            let state = match state {
+                ToolButtonState::Released => KeyState::Released,
+                ToolButtonState::Pressed => KeyState::Pressed,
+            };
+            self.button_event(time_usec, button, state);
+            return;
+        }
         tool.cursor.activate();
         tool.tool_owner.button(&tool, time_usec, button, state);
     }
@@ -92,11 +110,23 @@ impl WlSeatGlobal {
         id: TabletToolId,
         time_usec: u64,
         rect: Rect,
+        pressure_curve: Option<PressureCurve>,
         changes: &TabletToolChanges,
     ) {
         let Some(tool) = self.tablet.tools.get(&id) else {
             return;
         };
+        let curved;
+        let changes = match (pressure_curve, changes.pressure) {
+            (Some(curve), Some(pressure)) => {
+                curved = TabletToolChanges {
+                    pressure: Some(curve.apply(pressure)),
+                    ..changes.clone()
+                };
+                &curved
+            }
+            _ => changes,
+        };
         self.state.for_each_seat_tester(|t| {
             t.send_tablet_tool_changes(self.id, tool.tablet.dev, &tool, time_usec, changes);
         });
@@ -0,0 +1,465 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        gfx_api::{AcquireSync, GfxTexture},
+        ifs::wl_seat::WlSeatGlobal,
+        rect::Rect,
+        scale::Scale,
+        state::State,
+        text::TextTexture,
+        tree::{NodeId, OffscreenBuffer, OutputNode, ToplevelNode},
+        utils::{clonecell::CloneCell, errorfmt::ErrorFmt, on_drop_event::OnDropEvent},
+    },
+    ahash::AHashSet,
+    jay_config::keyboard::mods::{CAPS, NUM},
+    kbvm::{Keycode, evdev},
+    std::{
+        cell::{Cell, RefCell},
+        rc::{Rc, Weak},
+    },
+};
+
+/// The space between the switcher and the edges of the output.
+const GAP: i32 = 20;
+const PADDING: i32 = 10;
+const MAX_WIDTH: i32 = 600;
+const THUMBNAIL_WIDTH: i32 = 160;
+const THUMBNAIL_HEIGHT: i32 = 90;
+
+#[derive(Default)]
+pub struct WindowSwitcherSeatData {
+    thumbnails: Cell<bool>,
+    active: CloneCell<Option<Rc<WindowSwitcher>>>,
+}
+
+/// An on-screen list of the windows in the focus history of a seat.
+pub struct WindowSwitcher {
+    state: Rc<State>,
+    pub output: Rc<OutputNode>,
+    mods: u32,
+    pub entries: Vec<WindowSwitcherEntry>,
+    pub selected: Cell<Option<usize>>,
+    pub thumbnails: bool,
+    titles: Cell<Option<SpawnedFuture<()>>>,
+}
+
+pub struct WindowSwitcherEntry {
+    pub window: Weak<dyn ToplevelNode>,
+    pub title: Option<TextTexture>,
+    thumbnail: RefCell<Option<Thumbnail>>,
+}
+
+struct Thumbnail {
+    buffer: OffscreenBuffer,
+    sync: AcquireSync,
+    damaged: bool,
+}
+
+pub struct WindowSwitcherLayout {
+    pub panel: Rect,
+    pub rows: Vec<WindowSwitcherRow>,
+}
+
+pub struct WindowSwitcherRow {
+    pub entry: usize,
+    pub rect: Rect,
+    pub thumbnail: Option<Rect>,
+    pub title: Rect,
+}
+
+impl WindowSwitcher {
+    /// Computes the position of the switcher in global coordinates.
+    pub fn layout(&self) -> WindowSwitcherLayout {
+        let opos = self.output.global.pos.get();
+        let th = self.state.theme.sizes.title_height.get();
+        let content_height = match self.thumbnails {
+            true => THUMBNAIL_HEIGHT.max(th),
+            false => th,
+        };
+        let row_height = content_height + 2 * PADDING;
+        let width = MAX_WIDTH.min(opos.width() - 2 * GAP).max(0);
+        let max_rows = ((opos.height() - 2 * GAP - 2 * PADDING) / row_height).max(1) as usize;
+        let num_rows = self.entries.len().min(max_rows);
+        let first = match self.selected.get() {
+            Some(s) if s >= num_rows => s + 1 - num_rows,
+            _ => 0,
+        };
+        let height = num_rows as i32 * row_height + 2 * PADDING;
+        let x1 = opos.x1() + (opos.width() - width) / 2;
+        let y1 = opos.y1() + (opos.height() - height) / 2;
+        let panel = Rect::new_sized_unchecked(x1, y1, width, height);
+        let mut rows = vec![];
+        for (idx, entry) in (first..first + num_rows).enumerate() {
+            let rect = Rect::new_sized_unchecked(
+                x1 + PADDING,
+                y1 + PADDING + idx as i32 * row_height,
+                (width - 2 * PADDING).max(0),
+                row_height,
+            );
+            let mut title_x = rect.x1() + PADDING;
+            let mut thumbnail = None;
+            if self.thumbnails {
+                if let Some(window) = self.entries[entry].window.upgrade() {
+                    let pos = window.node_absolute_position();
+                    let scale = (THUMBNAIL_WIDTH as f64 / pos.width().max(1) as f64)
+                        .min(THUMBNAIL_HEIGHT as f64 / pos.height().max(1) as f64);
+                    let tw = (pos.width() as f64 * scale).round() as i32;
+                    let tht = (pos.height() as f64 * scale).round() as i32;
+                    thumbnail = Some(Rect::new_sized_unchecked(
+                        title_x + (THUMBNAIL_WIDTH - tw) / 2,
+                        rect.y1() + PADDING + (content_height - tht) / 2,
+                        tw,
+                        tht,
+                    ));
+                }
+                title_x += THUMBNAIL_WIDTH + PADDING;
+            }
+            let title = Rect::new_sized_unchecked(
+                title_x,
+                rect.y1() + PADDING + (content_height - th) / 2,
+                (rect.x2() - PADDING - title_x).max(0),
+                th,
+            );
+            rows.push(WindowSwitcherRow {
+                entry,
+                rect,
+                thumbnail,
+                title,
+            });
+        }
+        WindowSwitcherLayout { panel, rows }
+    }
+
+    /// Returns the last rendered thumbnail of an entry.
+    pub fn thumbnail(
+        &self,
+        entry: &WindowSwitcherEntry,
+    ) -> Option<(Rc<dyn GfxTexture>, AcquireSync)> {
+        let thumbnail = &*entry.thumbnail.borrow();
+        let thumbnail = thumbnail.as_ref()?;
+        Some((thumbnail.buffer.tex.clone(), thumbnail.sync.clone()))
+    }
+
+    /// Marks the thumbnails of the windows intersecting the rectangle as out of date.
+    pub fn damage_thumbnails(&self, rect: &Rect) {
+        for entry in &self.entries {
+            if let Some(thumbnail) = &mut *entry.thumbnail.borrow_mut()
+                && let Some(window) = entry.window.upgrade()
+                && window.node_absolute_position().intersects(rect)
+            {
+                thumbnail.damaged = true;
+            }
+        }
+    }
+
+    #[cfg(feature = "it")]
+    pub fn thumbnails_damaged(&self) -> Vec<bool> {
+        self.entries
+            .iter()
+            .filter_map(|e| e.thumbnail.borrow().as_ref().map(|t| t.damaged))
+            .collect()
+    }
+
+    /// Re-renders the thumbnails of damaged windows.
+    ///
+    /// This is only called before the output is presented. Captures of the output reuse
+    /// the existing thumbnails.
+    pub fn update_thumbnails(&self) {
+        if !self.thumbnails {
+            return;
+        }
+        let scale = self.output.global.persistent.scale.get();
+        for row in self.layout().rows {
+            let Some(rect) = row.thumbnail else {
+                continue;
+            };
+            let [width, height] = scale.pixel_size([rect.width(), rect.height()]);
+            if width <= 0 || height <= 0 {
+                continue;
+            }
+            self.update_thumbnail(&self.entries[row.entry], width, height, rect, scale);
+        }
+    }
+
+    /// Renders the window of an entry into a buffer of the given size unless the existing
+    /// thumbnail is still up to date.
+    fn update_thumbnail(
+        &self,
+        entry: &WindowSwitcherEntry,
+        width: i32,
+        height: i32,
+        rect: Rect,
+        scale: Scale,
+    ) {
+        let Some(window) = entry.window.upgrade() else {
+            entry.thumbnail.take();
+            return;
+        };
+        let thumbnail = &mut *entry.thumbnail.borrow_mut();
+        match thumbnail {
+            Some(t) if t.buffer.fb.physical_size() == (width, height) => {
+                if !t.damaged {
+                    return;
+                }
+            }
+            _ => {
+                *thumbnail = match OffscreenBuffer::new(&self.state, width, height) {
+                    Ok(buffer) => Some(Thumbnail {
+                        buffer,
+                        sync: AcquireSync::Unnecessary,
+                        damaged: true,
+                    }),
+                    Err(e) => {
+                        log::error!("Could not allocate window thumbnail: {}", ErrorFmt(e));
+                        return;
+                    }
+                };
+            }
+        }
+        let Some(t) = thumbnail else {
+            return;
+        };
+        let pos = window.node_absolute_position();
+        let tscale =
+            Scale::from_f64(scale.to_f64() * rect.width() as f64 / pos.width().max(1) as f64);
+        let res = t.buffer.render(&self.state, pos, tscale, |renderer| {
+            window.node_render(renderer, 0, 0, None);
+        });
+        match res {
+            Ok(sync) => {
+                t.sync = sync;
+                t.damaged = false;
+            }
+            Err(e) => {
+                log::error!("Could not render window thumbnail: {}", ErrorFmt(e));
+                *thumbnail = None;
+            }
+        }
+    }
+
+    fn schedule_render_titles(self: &Rc<Self>) {
+        let layout = self.layout();
+        let Some(row) = layout.rows.first() else {
+            return;
+        };
+        let on_completed = Rc::new(OnDropEvent::default());
+        let font = self.state.theme.font.get();
+        let tc = self.state.theme.colors.focused_title_text.get();
+        let scale = self.output.global.persistent.scale.get();
+        let mut width = row.title.width();
+        let mut height = row.title.height();
+        let mut scalef = None;
+        if scale != 1 {
+            let scale = scale.to_f64();
+            width = (width as f64 * scale).round() as _;
+            height = (height as f64 * scale).round() as _;
+            scalef = Some(scale);
+        }
+        if width == 0 || height == 0 {
+            return;
+        }
+        for entry in &self.entries {
+            let Some(window) = entry.window.upgrade() else {
+                continue;
+            };
+            let Some(title) = &entry.title else {
+                continue;
+            };
+            title.schedule_render(
+                on_completed.clone(),
+                1,
+                None,
+                width,
+                height,
+                1,
+                &font,
+                &window.tl_data().title.borrow(),
+                tc,
+                true,
+                false,
+                scalef,
+            );
+        }
+        let event = on_completed.event();
+        drop(on_completed);
+        let slf = Rc::downgrade(self);
+        let future = self.state.eng.spawn("window switcher titles", async move {
+            event.triggered().await;
+            if let Some(slf) = slf.upgrade() {
+                for title in slf.entries.iter().flat_map(|e| &e.title) {
+                    if let Err(e) = title.flip() {
+                        log::error!("Could not render window switcher title: {}", ErrorFmt(e));
+                    }
+                }
+                slf.damage();
+            }
+        });
+        self.titles.set(Some(future));
+    }
+
+    /// Damages the switcher without invalidating its thumbnails.
+    fn damage(&self) {
+        let global = &self.output.global;
+        global.add_damage_area(&self.layout().panel);
+        global.connector.damage();
+    }
+
+    fn step(&self, older: bool) {
+        let n = self.entries.len();
+        let selected = match (self.selected.get(), older) {
+            (None, true) => 0,
+            (None, false) => n - 1,
+            (Some(s), true) => (s + 1) % n,
+            (Some(s), false) => (s + n - 1) % n,
+        };
+        self.damage();
+        self.selected.set(Some(selected));
+        self.damage();
+    }
+}
+
+impl WlSeatGlobal {
+    /// Opens the window switcher or selects the next older window.
+    pub fn window_switcher_prev(self: &Rc<Self>) {
+        self.window_switcher_step(true);
+    }
+
+    /// Opens the window switcher or selects the next newer window.
+    pub fn window_switcher_next(self: &Rc<Self>) {
+        self.window_switcher_step(false);
+    }
+
+    pub fn window_switcher_set_thumbnails(&self, thumbnails: bool) {
+        self.window_switcher.thumbnails.set(thumbnails);
+    }
+
+    pub fn window_switcher_active(&self) -> bool {
+        self.window_switcher.active.is_some()
+    }
+
+    fn window_switcher_step(self: &Rc<Self>, older: bool) {
+        if self.state.lock.locked.get() {
+            return;
+        }
+        let switcher = match self.window_switcher.active.get() {
+            Some(s) => s,
+            _ => match self.open_window_switcher() {
+                Some(s) => s,
+                _ => return,
+            },
+        };
+        switcher.step(older);
+        if switcher.mods == 0 {
+            self.close_window_switcher(true);
+        }
+    }
+
+    fn open_window_switcher(self: &Rc<Self>) -> Option<Rc<WindowSwitcher>> {
+        let original = self.keyboard_node.get();
+        let filter = self.focus_history_filter(&original);
+        let mut seen = AHashSet::<NodeId>::new();
+        let mut windows = vec![];
+        for entry in self.focus_history.rev_iter() {
+            if let Some((node, _)) = filter(&entry)
+                && let Some(tl) = node.node_toplevel()
+                && seen.insert(tl.node_id())
+            {
+                windows.push(tl);
+            }
+        }
+        if windows.is_empty() {
+            return None;
+        }
+        let current = original.clone().node_toplevel().map(|tl| tl.node_id());
+        let selected = match Some(windows[0].node_id()) == current {
+            true => Some(0),
+            false => None,
+        };
+        let output = original
+            .node_output()
+            .unwrap_or_else(|| self.pointer_cursor.output());
+        let ctx = self.state.render_ctx.get();
+        let mods = self.latest_kb_state.get().borrow().mods.mods.0 & !(CAPS.0 | NUM.0);
+        let entries = windows
+            .iter()
+            .map(|w| WindowSwitcherEntry {
+                window: Rc::downgrade(w),
+                title: ctx
+                    .as_ref()
+                    .map(|ctx| TextTexture::new(&self.state.cpu_worker, ctx)),
+                thumbnail: Default::default(),
+            })
+            .collect();
+        let switcher = Rc::new(WindowSwitcher {
+            state: self.state.clone(),
+            output: output.clone(),
+            mods,
+            entries,
+            selected: Cell::new(selected),
+            thumbnails: self.window_switcher.thumbnails.get(),
+            titles: Default::default(),
+        });
+        if mods != 0 {
+            switcher.schedule_render_titles();
+            if let Some(prev) = output.window_switcher.set(Some(switcher.clone())) {
+                prev.damage();
+            }
+            switcher.damage();
+        }
+        self.window_switcher.active.set(Some(switcher.clone()));
+        Some(switcher)
+    }
+
+    /// Closes the window switcher and optionally focuses the selected window.
+    pub fn close_window_switcher(self: &Rc<Self>, focus: bool) {
+        let Some(switcher) = self.window_switcher.active.take() else {
+            return;
+        };
+        switcher.titles.take();
+        if let Some(s) = switcher.output.window_switcher.get()
+            && Rc::ptr_eq(&s, &switcher)
+        {
+            switcher.output.window_switcher.take();
+            switcher.damage();
+        }
+        if !focus {
+            return;
+        }
+        let Some(selected) = switcher.selected.get() else {
+            return;
+        };
+        let Some(tl) = switcher.entries[selected].window.upgrade() else {
+            return;
+        };
+        if !tl.node_visible() {
+            tl.clone().node_make_visible();
+            if !tl.node_visible() {
+                return;
+            }
+        }
+        self.focus_toplevel(tl);
+    }
+
+    /// Handles a key event while the window switcher is open.
+    ///
+    /// Returns `true` if the event was consumed.
+    pub(super) fn window_switcher_key(
+        self: &Rc<Self>,
+        kc: Keycode,
+        pressed: bool,
+        mods: u32,
+    ) -> bool {
+        let Some(switcher) = self.window_switcher.active.get() else {
+            return false;
+        };
+        if pressed && kc == evdev::ESC {
+            self.close_window_switcher(false);
+            return true;
+        }
+        let mods = mods & !(CAPS.0 | NUM.0);
+        if mods & switcher.mods != switcher.mods {
+            self.close_window_switcher(true);
+        }
+        false
+    }
+}
@@ -302,7 +302,7 @@ impl Xwindow {
                 self.data.title_changed();
             }
             Change::Map => {
-                self.data.state.map_tiled(self.clone());
+                self.data.state.map_new_tiled(self.clone());
                 if self.data.info.fullscreen.get() {
                     self.clone().tl_set_fullscreen(true, None);
                 }
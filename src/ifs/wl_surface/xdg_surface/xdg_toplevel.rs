@@ -422,7 +422,7 @@ impl XdgToplevel {
     }
 
     fn map_tiled(self: &Rc<Self>) {
-        self.state.map_tiled(self.clone());
+        self.state.map_new_tiled(self.clone());
         let fullscreen = self.states.borrow().contains(&STATE_FULLSCREEN);
         if fullscreen && let Some(ws) = self.xdg.workspace.get() {
             self.toplevel_data
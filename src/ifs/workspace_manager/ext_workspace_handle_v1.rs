@@ -143,6 +143,13 @@ impl ExtWorkspaceHandleV1 {
         }
     }
 
+    pub fn handle_name_changed(&self, name: &str) {
+        if let Some(manager) = self.manager.get() {
+            self.send_name(name);
+            manager.schedule_done();
+        }
+    }
+
     pub fn handle_urgent_changed(&self) {
         self.handle_visibility_changed();
     }
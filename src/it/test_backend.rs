@@ -20,7 +20,13 @@ use {
         format::XRGB8888,
         gfx_api::GfxError,
         gfx_apis::create_vulkan_allocator,
-        ifs::wl_output::OutputId,
+        ifs::{
+            wl_output::OutputId,
+            wl_seat::tablet::{
+                TabletInit, TabletTool2dChange, TabletToolChanges, TabletToolId, TabletToolInit,
+                TabletToolPositionChange, TabletToolType,
+            },
+        },
         it::{
             test_error::TestResult, test_gfx_api::TestGfxCtx, test_utils::test_expected_event::TEEH,
         },
@@ -196,6 +202,38 @@ impl TestBackend {
         Ok(())
     }
 
+    pub fn add_tablet(&self) -> Rc<TestBackendTablet> {
+        let state = &self.state;
+        let tablet = Rc::new(TestBackendTablet {
+            common: TestInputDeviceCommon {
+                id: state.input_device_ids.next(),
+                removed: Cell::new(false),
+                events: Default::default(),
+                on_change: Default::default(),
+                capabilities: {
+                    let chm = CopyHashMap::new();
+                    chm.set(InputDeviceCapability::TabletTool, ());
+                    chm
+                },
+                name: Rc::new("default-tablet".to_string()),
+                state: state.clone(),
+            },
+            init: TabletInit {
+                id: state.tablet_ids.next(),
+                group: state.input_device_group_ids.next(),
+                name: "default-tablet".to_string(),
+                pid: 0,
+                vid: 0,
+                bustype: None,
+                path: "default-tablet".to_string(),
+            },
+        });
+        state
+            .backend_events
+            .push(BackendEvent::NewInputDevice(tablet.clone()));
+        tablet
+    }
+
     fn create_render_context(&self, need_drm: bool) -> Result<(), TestBackendError> {
         macro_rules! constructor {
             ($c:expr) => {
@@ -482,6 +520,24 @@ impl TestBackendMouse {
         });
     }
 
+    pub fn swipe(&self, finger_count: u32, dy: f64) {
+        self.common.event(InputEvent::SwipeBegin {
+            time_usec: self.common.state.now_usec(),
+            finger_count,
+        });
+        self.common.event(InputEvent::SwipeUpdate {
+            time_usec: self.common.state.now_usec(),
+            dx: Fixed(0),
+            dy: Fixed::from_f64(dy),
+            dx_unaccelerated: Fixed(0),
+            dy_unaccelerated: Fixed::from_f64(dy),
+        });
+        self.common.event(InputEvent::SwipeEnd {
+            time_usec: self.common.state.now_usec(),
+            cancelled: false,
+        });
+    }
+
     pub fn scroll_px(&self, dy: i32) {
         self.scroll_px2(dy, false);
     }
@@ -501,6 +557,44 @@ impl TestBackendMouse {
     }
 }
 
+pub struct TestBackendTablet {
+    pub common: TestInputDeviceCommon,
+    pub init: TabletInit,
+}
+
+impl TestBackendTablet {
+    pub fn add_pen(&self) -> TabletToolId {
+        let id = self.common.state.tablet_tool_ids.next();
+        self.common.event(InputEvent::TabletToolAdded {
+            time_usec: self.common.state.now_usec(),
+            init: Box::new(TabletToolInit {
+                tablet_id: self.init.id,
+                id,
+                type_: TabletToolType::Pen,
+                hardware_serial: 0,
+                hardware_id_wacom: 0,
+                capabilities: vec![],
+            }),
+        });
+        id
+    }
+
+    pub fn tool(&self, id: TabletToolId, x: f64, y: f64, pressure: f64) {
+        self.common.event(InputEvent::TabletToolChanged {
+            time_usec: self.common.state.now_usec(),
+            id,
+            changes: Box::new(TabletToolChanges {
+                pos: Some(TabletTool2dChange {
+                    x: TabletToolPositionChange { x, dx: 0.0 },
+                    y: TabletToolPositionChange { x: y, dx: 0.0 },
+                }),
+                pressure: Some(pressure),
+                ..Default::default()
+            }),
+        });
+    }
+}
+
 pub struct TestBackendKb {
     pub common: TestInputDeviceCommon,
 }
@@ -540,6 +634,16 @@ impl TestInputDevice for TestBackendKb {
     }
 }
 
+impl TestInputDevice for TestBackendTablet {
+    fn common(&self) -> &TestInputDeviceCommon {
+        &self.common
+    }
+
+    fn tablet_info(&self) -> Option<Box<TabletInit>> {
+        Some(Box::new(self.init.clone()))
+    }
+}
+
 impl TestInputDevice for TestBackendMouse {
     fn common(&self) -> &TestInputDeviceCommon {
         &self.common
@@ -623,6 +727,10 @@ trait TestInputDevice: InputDevice {
     fn set_middle_button_emulation_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
+
+    fn tablet_info(&self) -> Option<Box<TabletInit>> {
+        None
+    }
 }
 
 impl<T: TestInputDevice> InputDevice for T {
@@ -697,4 +805,8 @@ impl<T: TestInputDevice> InputDevice for T {
     fn set_middle_button_emulation_enabled(&self, enabled: bool) {
         <Self as TestInputDevice>::set_middle_button_emulation_enabled(self, enabled)
     }
+
+    fn tablet_info(&self) -> Option<Box<TabletInit>> {
+        <Self as TestInputDevice>::tablet_info(self)
+    }
 }
@@ -10,13 +10,17 @@ use {
     isnt::std_1::primitive::IsntConstPtrExt,
     jay_config::{
         _private::{
-            ConfigEntry, VERSION, bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage},
+            ConfigEntry, VERSION, WindowCriterionIpc, bincode_ops,
+            ipc::{ClientMessage, Response, SeatZoom, ServerMessage},
         },
-        Axis, Direction,
-        input::{InputDevice, Seat},
+        Axis, Direction, Workspace,
+        input::{HotCorner, InputDevice, ScreenEdge, Seat, Timeline},
         keyboard::{Keymap, ModifiedKeySym},
+        portal::{ColorScheme, Contrast},
+        theme::Color,
         video::{Connector, Transform},
+        window::{FloatPlacement, FloatSnap, WindowMatcher},
+        workspace::WorkspaceLayout,
     },
     std::{cell::Cell, ops::Deref, ptr, rc::Rc, time::Duration},
 };
@@ -41,6 +45,8 @@ where
         responses: Default::default(),
         invoked_shortcuts: Default::default(),
         graphics_initialized: Cell::new(false),
+        key_sequences_cancelled: Default::default(),
+        hot_corners_triggered: Default::default(),
     });
     let old = CONFIG.get();
     CONFIG.set(tc.deref());
@@ -128,6 +134,15 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::ClientMatcherUnmatched { .. } => {}
         ServerMessage::WindowMatcherMatched { .. } => {}
         ServerMessage::WindowMatcherUnmatched { .. } => {}
+        ServerMessage::TabletPadEvent { .. } => {}
+        ServerMessage::HotCornerTriggered { corner, seat, .. } => {
+            tc.hot_corners_triggered
+                .set((corner, SeatId::from_raw(seat.0 as _)), ());
+        }
+        ServerMessage::KeySequenceCancelled { seat } => {
+            tc.key_sequences_cancelled
+                .set(SeatId::from_raw(seat.0 as _), ());
+        }
     }
 }
 
@@ -143,6 +158,8 @@ pub struct TestConfig {
     responses: Stack<Response>,
     pub invoked_shortcuts: CopyHashMap<(SeatId, ModifiedKeySym), ()>,
     pub graphics_initialized: Cell<bool>,
+    pub key_sequences_cancelled: CopyHashMap<SeatId, ()>,
+    pub hot_corners_triggered: CopyHashMap<(HotCorner, SeatId), ()>,
 }
 
 macro_rules! get_response {
@@ -192,6 +209,12 @@ impl TestConfig {
         Ok(SeatId::from_raw(seat.0 as _))
     }
 
+    pub fn get_workspace(&self, name: &str) -> Result<Workspace, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        Ok(workspace)
+    }
+
     pub fn show_workspace(&self, seat: SeatId, name: &str) -> Result<(), TestError> {
         let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
         get_response!(reply, GetWorkspace { workspace });
@@ -201,6 +224,81 @@ impl TestConfig {
         })
     }
 
+    pub fn set_workspace_layout(&self, name: &str, layout: WorkspaceLayout) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWorkspaceLayout { workspace, layout })
+    }
+
+    pub fn rename_workspace(&self, name: &str, new_name: &str) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::RenameWorkspace {
+            workspace,
+            name: new_name,
+        })
+    }
+
+    pub fn set_workspace_persistent(&self, name: &str, persistent: bool) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWorkspacePersistent {
+            workspace,
+            persistent,
+        })
+    }
+
+    pub fn set_workspace_preferred_outputs(&self, name: &str, outputs: &[&str]) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWorkspacePreferredOutputs {
+            workspace,
+            outputs: outputs.iter().map(|o| o.to_string()).collect(),
+        })
+    }
+
+    pub fn set_workspace_gap(&self, name: &str, gap: i32) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWorkspaceGap { workspace, gap })
+    }
+
+    pub fn set_workspace_master_count(&self, name: &str, count: u32) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWorkspaceMasterCount { workspace, count })
+    }
+
+    pub fn set_workspace_master_ratio(&self, name: &str, ratio: f64) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWorkspaceMasterRatio { workspace, ratio })
+    }
+
+    pub fn create_window_matcher(
+        &self,
+        criterion: WindowCriterionIpc,
+    ) -> Result<WindowMatcher, TestError> {
+        let reply = self.send_with_reply(ClientMessage::CreateWindowMatcher { criterion })?;
+        get_response!(reply, CreateWindowMatcher { matcher });
+        Ok(matcher)
+    }
+
+    pub fn set_window_matcher_swallow(&self, matcher: WindowMatcher, swallow: bool) -> TestResult {
+        self.send(ClientMessage::SetWindowMatcherSwallow { matcher, swallow })
+    }
+
+    pub fn set_window_matcher_swallowable(
+        &self,
+        matcher: WindowMatcher,
+        swallowable: bool,
+    ) -> TestResult {
+        self.send(ClientMessage::SetWindowMatcherSwallowable {
+            matcher,
+            swallowable,
+        })
+    }
+
     pub fn parse_keymap(&self, keymap: &str) -> Result<Keymap, TestError> {
         let reply = self.send_with_reply(ClientMessage::ParseKeymap { keymap })?;
         get_response!(reply, ParseKeymap { keymap });
@@ -251,6 +349,62 @@ impl TestConfig {
         })
     }
 
+    pub fn set_tablet_output_area(
+        &self,
+        id: InputDeviceId,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> TestResult {
+        self.send(ClientMessage::SetTabletOutputArea {
+            device: InputDevice(id.raw() as _),
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    pub fn set_tablet_pressure_curve(&self, id: InputDeviceId, curve: [f64; 4]) -> TestResult {
+        self.send(ClientMessage::SetTabletPressureCurve {
+            device: InputDevice(id.raw() as _),
+            curve,
+        })
+    }
+
+    pub fn set_overview_swipe_enabled(&self, seat: SeatId, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SeatSetOverviewSwipeEnabled {
+            seat: Seat(seat.raw() as _),
+            enabled,
+        })
+    }
+
+    pub fn set_overview_swipe_fingers(&self, seat: SeatId, fingers: u32) -> TestResult {
+        self.send(ClientMessage::SeatSetOverviewSwipeFingers {
+            seat: Seat(seat.raw() as _),
+            fingers,
+        })
+    }
+
+    pub fn cycle_column_width(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatCycleColumnWidth {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn consume_into_column(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatConsumeIntoColumn {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn expel_from_column(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatExpelFromColumn {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
     pub fn focus(&self, seat: SeatId, direction: Direction) -> TestResult {
         self.send(ClientMessage::SeatFocus {
             seat: Seat(seat.raw() as _),
@@ -258,6 +412,53 @@ impl TestConfig {
         })
     }
 
+    pub fn swap(&self, seat: SeatId, direction: Direction) -> TestResult {
+        self.send(ClientMessage::SeatSwap {
+            seat: Seat(seat.raw() as _),
+            direction,
+        })
+    }
+
+    pub fn window_switcher(&self, seat: SeatId, timeline: Timeline) -> TestResult {
+        self.send(ClientMessage::SeatWindowSwitcher {
+            seat: Seat(seat.raw() as _),
+            timeline,
+        })
+    }
+
+    pub fn toggle_overview(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatToggleOverview {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn release_input_capture(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatReleaseInputCapture {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn create_mark(&self, seat: SeatId, kc: u32) -> TestResult {
+        self.send(ClientMessage::SeatCreateMark {
+            seat: Seat(seat.raw() as _),
+            kc: Some(kc),
+        })
+    }
+
+    pub fn swap_with_mark(&self, seat: SeatId, kc: u32) -> TestResult {
+        self.send(ClientMessage::SeatSwapWithMark {
+            seat: Seat(seat.raw() as _),
+            kc: Some(kc),
+        })
+    }
+
+    pub fn move_to_mark(&self, seat: SeatId, kc: u32) -> TestResult {
+        self.send(ClientMessage::SeatMoveToMark {
+            seat: Seat(seat.raw() as _),
+            kc: Some(kc),
+        })
+    }
+
     pub fn set_fullscreen(&self, seat: SeatId, fs: bool) -> TestResult {
         self.send(ClientMessage::SetSeatFullscreen {
             seat: Seat(seat.raw() as _),
@@ -280,6 +481,63 @@ impl TestConfig {
         })
     }
 
+    pub fn set_sticky(&self, seat: SeatId, sticky: bool) -> TestResult {
+        self.send(ClientMessage::SetSeatFloatSticky {
+            seat: Seat(seat.raw() as _),
+            sticky,
+        })
+    }
+
+    pub fn set_color_scheme(&self, scheme: ColorScheme) -> TestResult {
+        self.send(ClientMessage::SetColorScheme { scheme })
+    }
+
+    pub fn set_accent_color(&self, color: Option<Color>) -> TestResult {
+        self.send(ClientMessage::SetAccentColor { color })
+    }
+
+    pub fn set_contrast(&self, contrast: Contrast) -> TestResult {
+        self.send(ClientMessage::SetContrast { contrast })
+    }
+
+    pub fn set_reduced_motion(&self, reduced: bool) -> TestResult {
+        self.send(ClientMessage::SetReducedMotion { reduced })
+    }
+
+    pub fn set_float_placement(&self, placement: FloatPlacement) -> TestResult {
+        self.send(ClientMessage::SetFloatPlacement { placement })
+    }
+
+    pub fn snap_float(&self, seat: SeatId, snap: FloatSnap) -> TestResult {
+        self.send(ClientMessage::SeatSnapFloat {
+            seat: Seat(seat.raw() as _),
+            snap,
+        })
+    }
+
+    pub fn begin_key_sequence(&self, seat: SeatId, prefix: &str, timeout_ms: u64) -> TestResult {
+        self.send(ClientMessage::SeatBeginKeySequence {
+            seat: Seat(seat.raw() as _),
+            prefix,
+            timeout_ms,
+        })
+    }
+
+    pub fn create_hot_corner(
+        &self,
+        edge: ScreenEdge,
+        dwell: Duration,
+        pressure: f64,
+    ) -> Result<HotCorner, TestError> {
+        let reply = self.send_with_reply(ClientMessage::CreateHotCorner {
+            edge,
+            dwell,
+            pressure,
+        })?;
+        get_response!(reply, CreateHotCorner { corner });
+        Ok(corner)
+    }
+
     fn clear(&self) {
         unsafe {
             if let Some(srv) = self.srv.take() {
@@ -301,6 +559,33 @@ impl TestConfig {
             transform,
         })
     }
+
+    pub fn mouse_keys_motion(
+        &self,
+        seat: SeatId,
+        direction: Direction,
+        active: bool,
+    ) -> TestResult {
+        self.send(ClientMessage::SeatMouseKeysMotion {
+            seat: Seat(seat.raw() as _),
+            direction,
+            active,
+        })
+    }
+
+    pub fn mouse_keys_click(&self, seat: SeatId, double: bool) -> TestResult {
+        self.send(ClientMessage::SeatMouseKeysClick {
+            seat: Seat(seat.raw() as _),
+            double,
+        })
+    }
+
+    pub fn zoom(&self, seat: SeatId, zoom: SeatZoom) -> TestResult {
+        self.send(ClientMessage::SeatZoom {
+            seat: Seat(seat.raw() as _),
+            zoom,
+        })
+    }
 }
 
 impl Drop for TestConfig {
@@ -21,18 +21,25 @@ pub mod test_dmabuf_feedback;
 pub mod test_ext_foreign_toplevel_handle;
 pub mod test_ext_foreign_toplevel_list;
 pub mod test_fifo_manager;
+pub mod test_global_shortcuts;
+pub mod test_input_capture;
 pub mod test_input_method;
 pub mod test_input_method_keyboard_grab;
 pub mod test_input_method_manager;
 pub mod test_input_popup_surface;
 pub mod test_jay_compositor;
+pub mod test_jay_output;
 pub mod test_keyboard;
+pub mod test_output;
+pub mod test_pick_color;
 pub mod test_pointer;
 pub mod test_pointer_warp;
 pub mod test_region;
 pub mod test_registry;
+pub mod test_screencast;
 pub mod test_screenshot;
 pub mod test_seat;
+pub mod test_select_region;
 pub mod test_shm;
 pub mod test_shm_buffer;
 pub mod test_shm_pool;
@@ -0,0 +1,83 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_object::TestObject, test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{JayGlobalShortcutsId, jay_global_shortcuts::*},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestJayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub activated: TEEH<Activated>,
+    pub deactivated: TEEH<Deactivated>,
+    pub trigger_captured: TEEH<TriggerCaptured>,
+}
+
+impl TestJayGlobalShortcuts {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn bind(&self, id: u32, mods: u32, keysym: u32) -> TestResult {
+        self.tran.send(Bind {
+            self_id: self.id,
+            id,
+            mods,
+            keysym,
+        })
+    }
+
+    pub fn unbind(&self, id: u32) -> TestResult {
+        self.tran.send(Unbind {
+            self_id: self.id,
+            id,
+        })
+    }
+
+    pub fn capture_trigger(&self) -> TestResult {
+        self.tran.send(CaptureTrigger { self_id: self.id })
+    }
+
+    fn handle_activated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Activated::parse_full(parser)?;
+        self.activated.push(ev);
+        Ok(())
+    }
+
+    fn handle_deactivated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Deactivated::parse_full(parser)?;
+        self.deactivated.push(ev);
+        Ok(())
+    }
+
+    fn handle_trigger_captured(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = TriggerCaptured::parse_full(parser)?;
+        self.trigger_captured.push(ev);
+        Ok(())
+    }
+}
+
+impl Drop for TestJayGlobalShortcuts {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestJayGlobalShortcuts, JayGlobalShortcuts;
+
+    ACTIVATED => handle_activated,
+    DEACTIVATED => handle_deactivated,
+    TRIGGER_CAPTURED => handle_trigger_captured,
+}
+
+impl TestObject for TestJayGlobalShortcuts {}
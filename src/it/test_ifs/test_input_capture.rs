@@ -0,0 +1,126 @@
+use {
+    crate::{
+        fixed::Fixed,
+        it::{
+            test_error::TestResult, test_object::TestObject, test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH, testrun::ParseFull,
+        },
+        rect::Rect,
+        utils::buffd::MsgParser,
+        wire::{JayInputCaptureId, jay_input_capture::*},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestJayInputCapture {
+    pub id: JayInputCaptureId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub zones: RefCell<Vec<Rect>>,
+    pub activated: TEEH<Activated>,
+    pub deactivated: TEEH<Deactivated>,
+    pub disabled: TEEH<Disabled>,
+}
+
+impl TestJayInputCapture {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn add_barrier(&self, id: u32, x1: i32, y1: i32, x2: i32, y2: i32) -> TestResult {
+        self.tran.send(AddBarrier {
+            self_id: self.id,
+            id,
+            x1,
+            y1,
+            x2,
+            y2,
+        })
+    }
+
+    pub fn enable(&self) -> TestResult {
+        self.tran.send(Enable { self_id: self.id })
+    }
+
+    pub fn disable(&self) -> TestResult {
+        self.tran.send(Disable { self_id: self.id })
+    }
+
+    pub fn release(&self, activation_id: u32, warp: Option<(Fixed, Fixed)>) -> TestResult {
+        let (x, y) = warp.unwrap_or_default();
+        self.tran.send(Release {
+            self_id: self.id,
+            activation_id,
+            warp: warp.is_some() as u32,
+            x,
+            y,
+        })
+    }
+
+    fn handle_zone(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Zone::parse_full(parser)?;
+        self.zones
+            .borrow_mut()
+            .push(Rect::new_sized_unchecked(ev.x, ev.y, ev.width, ev.height));
+        Ok(())
+    }
+
+    fn handle_zones_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = ZonesDone::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_ei_created(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = EiCreated::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_ei_failed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = EiFailed::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_activated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Activated::parse_full(parser)?;
+        self.activated.push(ev);
+        Ok(())
+    }
+
+    fn handle_deactivated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Deactivated::parse_full(parser)?;
+        self.deactivated.push(ev);
+        Ok(())
+    }
+
+    fn handle_disabled(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Disabled::parse_full(parser)?;
+        self.disabled.push(ev);
+        Ok(())
+    }
+}
+
+impl Drop for TestJayInputCapture {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestJayInputCapture, JayInputCapture;
+
+    ZONE => handle_zone,
+    ZONES_DONE => handle_zones_done,
+    EI_CREATED => handle_ei_created,
+    EI_FAILED => handle_ei_failed,
+    ACTIVATED => handle_activated,
+    DEACTIVATED => handle_deactivated,
+    DISABLED => handle_disabled,
+}
+
+impl TestObject for TestJayInputCapture {}
@@ -1,17 +1,24 @@
 use {
     crate::{
-        client::ClientId,
+        client::{ClientId, EventFormatter},
         it::{
             test_error::{TestError, TestResult},
-            test_ifs::test_screenshot::TestJayScreenshot,
+            test_ifs::{
+                test_global_shortcuts::TestJayGlobalShortcuts,
+                test_input_capture::TestJayInputCapture, test_jay_output::TestJayOutput,
+                test_output::TestOutput, test_pick_color::TestJayPickColor,
+                test_screencast::TestJayScreencast, test_screenshot::TestJayScreenshot,
+                test_seat::TestSeat, test_select_region::TestJaySelectRegion,
+            },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
         },
+        rect::Rect,
         utils::{buffd::MsgParser, cell_ext::CellExt},
         video::dmabuf::DmaBuf,
         wire::{
-            JayCompositorId,
+            JayCompositorId, JayScreenshotId,
             jay_compositor::{self, *},
         },
     },
@@ -53,6 +60,35 @@ impl TestJayCompositor {
     pub async fn take_screenshot(
         &self,
         include_cursor: bool,
+    ) -> Result<(DmaBuf, Option<Rc<OwnedFd>>), TestError> {
+        self.screenshot(|id| TakeScreenshot2 {
+            self_id: self.id,
+            id,
+            include_cursor: include_cursor as _,
+        })
+        .await
+    }
+
+    pub async fn take_region_screenshot(
+        &self,
+        rect: Rect,
+        include_cursor: bool,
+    ) -> Result<(DmaBuf, Option<Rc<OwnedFd>>), TestError> {
+        self.screenshot(|id| TakeRegionScreenshot {
+            self_id: self.id,
+            id,
+            x: rect.x1(),
+            y: rect.y1(),
+            width: rect.width(),
+            height: rect.height(),
+            include_cursor: include_cursor as _,
+        })
+        .await
+    }
+
+    async fn screenshot<M: EventFormatter>(
+        &self,
+        req: impl FnOnce(JayScreenshotId) -> M,
     ) -> Result<(DmaBuf, Option<Rc<OwnedFd>>), TestError> {
         self.tran.sync().await;
         self.tran.run.state.idle().await;
@@ -63,11 +99,7 @@ impl TestJayCompositor {
             planes: Default::default(),
             result: Default::default(),
         });
-        self.tran.send(TakeScreenshot2 {
-            self_id: self.id,
-            id: js.id,
-            include_cursor: include_cursor as _,
-        })?;
+        self.tran.send(req(js.id))?;
         self.tran.add_obj(js.clone())?;
         self.tran.sync().await;
         match js.result.take() {
@@ -77,6 +109,138 @@ impl TestJayCompositor {
         }
     }
 
+    pub async fn select_region(
+        &self,
+        seat: &TestSeat,
+    ) -> Result<Rc<TestJaySelectRegion>, TestError> {
+        let sr = Rc::new(TestJaySelectRegion {
+            id: self.tran.id(),
+            cancelled: Default::default(),
+            selected: Default::default(),
+        });
+        self.tran.send(SelectRegion {
+            self_id: self.id,
+            id: sr.id,
+            seat: seat.id,
+        })?;
+        self.tran.add_obj(sr.clone())?;
+        self.tran.sync().await;
+        Ok(sr)
+    }
+
+    fn pick_color_obj(&self) -> Rc<TestJayPickColor> {
+        Rc::new(TestJayPickColor {
+            id: self.tran.id(),
+            cancelled: Default::default(),
+            failed: Default::default(),
+            color: Default::default(),
+        })
+    }
+
+    pub fn pick_color(&self, seat: &TestSeat) -> Result<Rc<TestJayPickColor>, TestError> {
+        let pc = self.pick_color_obj();
+        self.tran.send(PickColor {
+            self_id: self.id,
+            id: pc.id,
+            seat: seat.id,
+        })?;
+        self.tran.add_obj(pc.clone())?;
+        Ok(pc)
+    }
+
+    pub fn sample_color(&self, x: i32, y: i32) -> Result<Rc<TestJayPickColor>, TestError> {
+        let pc = self.pick_color_obj();
+        self.tran.send(SampleColor {
+            self_id: self.id,
+            id: pc.id,
+            x,
+            y,
+        })?;
+        self.tran.add_obj(pc.clone())?;
+        Ok(pc)
+    }
+
+    pub async fn get_global_shortcuts(&self) -> Result<Rc<TestJayGlobalShortcuts>, TestError> {
+        let gs = Rc::new(TestJayGlobalShortcuts {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            activated: Default::default(),
+            deactivated: Default::default(),
+            trigger_captured: Default::default(),
+        });
+        self.tran.send(GetGlobalShortcuts {
+            self_id: self.id,
+            id: gs.id,
+        })?;
+        self.tran.add_obj(gs.clone())?;
+        self.tran.sync().await;
+        Ok(gs)
+    }
+
+    pub async fn create_input_capture(&self) -> Result<Rc<TestJayInputCapture>, TestError> {
+        let ic = Rc::new(TestJayInputCapture {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            zones: Default::default(),
+            activated: Default::default(),
+            deactivated: Default::default(),
+            disabled: Default::default(),
+        });
+        self.tran.send(CreateInputCapture {
+            self_id: self.id,
+            id: ic.id,
+        })?;
+        self.tran.add_obj(ic.clone())?;
+        self.tran.sync().await;
+        Ok(ic)
+    }
+
+    pub async fn get_output(&self, output: &TestOutput) -> Result<Rc<TestJayOutput>, TestError> {
+        let jo = Rc::new(TestJayOutput {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            linear_id: Default::default(),
+        });
+        self.tran.send(GetOutput {
+            self_id: self.id,
+            id: jo.id,
+            output: output.id,
+        })?;
+        self.tran.add_obj(jo.clone())?;
+        self.tran.sync().await;
+        Ok(jo)
+    }
+
+    pub async fn create_screencast(&self) -> Result<Rc<TestJayScreencast>, TestError> {
+        let sc = Rc::new(TestJayScreencast {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            server: Default::default(),
+            destroyed: Cell::new(false),
+            planes: Default::default(),
+            pending_buffers: Default::default(),
+            buffers: Default::default(),
+            buffers_serial: Default::default(),
+            config_serial: Default::default(),
+            config_size: Default::default(),
+            ready: Default::default(),
+            missed_frame: Default::default(),
+            cursor_position: Default::default(),
+            cursor_image: Default::default(),
+        });
+        self.tran.send(CreateScreencast {
+            self_id: self.id,
+            id: sc.id,
+        })?;
+        self.tran.add_obj(sc.clone())?;
+        self.tran.sync().await;
+        sc.server.set(Some(self.tran.get_server_obj(sc.id)?));
+        Ok(sc)
+    }
+
     fn handle_client_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = jay_compositor::ClientId::parse_full(parser)?;
         self.client_id.set(Some(ClientId::from_raw(ev.client_id)));
@@ -0,0 +1,59 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{JayOutputId, jay_output::*},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestJayOutput {
+    pub id: JayOutputId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub linear_id: Cell<Option<u32>>,
+}
+
+impl TestJayOutput {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    fn handle_linear_id(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = LinearId::parse_full(parser)?;
+        self.linear_id.set(Some(ev.linear_id));
+        Ok(())
+    }
+
+    fn handle_unused(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Unused::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_destroyed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Destroyed::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+impl Drop for TestJayOutput {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestJayOutput, JayOutput;
+
+    LINEAR_ID => handle_linear_id,
+    UNUSED => handle_unused,
+    DESTROYED => handle_destroyed,
+}
+
+impl TestObject for TestJayOutput {}
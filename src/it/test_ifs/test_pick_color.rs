@@ -0,0 +1,59 @@
+use crate::{
+    it::{
+        test_error::TestResult, test_object::TestObject, test_utils::test_expected_event::TEEH,
+        testrun::ParseFull,
+    },
+    utils::buffd::MsgParser,
+    wire::{JayPickColorId, jay_pick_color::*},
+};
+
+pub struct TestPickedColor {
+    pub x: i32,
+    pub y: i32,
+    pub output: String,
+    pub srgb: [u32; 3],
+    pub native: [u32; 3],
+}
+
+pub struct TestJayPickColor {
+    pub id: JayPickColorId,
+    pub cancelled: TEEH<()>,
+    pub failed: TEEH<String>,
+    pub color: TEEH<TestPickedColor>,
+}
+
+impl TestJayPickColor {
+    fn handle_cancelled(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Cancelled::parse_full(parser)?;
+        self.cancelled.push(());
+        Ok(())
+    }
+
+    fn handle_failed(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Failed::parse_full(parser)?;
+        self.failed.push(ev.msg.to_string());
+        Ok(())
+    }
+
+    fn handle_color(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Color::parse_full(parser)?;
+        self.color.push(TestPickedColor {
+            x: ev.x,
+            y: ev.y,
+            output: ev.output.to_string(),
+            srgb: [ev.srgb_r, ev.srgb_g, ev.srgb_b],
+            native: [ev.r, ev.g, ev.b],
+        });
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayPickColor, JayPickColor;
+
+    CANCELLED => handle_cancelled,
+    FAILED => handle_failed,
+    COLOR => handle_color,
+}
+
+impl TestObject for TestJayPickColor {}
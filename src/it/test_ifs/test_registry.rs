@@ -13,8 +13,9 @@ use {
                 test_ext_foreign_toplevel_list::TestExtForeignToplevelList,
                 test_fifo_manager::TestFifoManager,
                 test_input_method_manager::TestInputMethodManager,
-                test_jay_compositor::TestJayCompositor, test_pointer_warp::TestPointerWarp,
-                test_shm::TestShm, test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
+                test_jay_compositor::TestJayCompositor, test_output::TestOutput,
+                test_pointer_warp::TestPointerWarp, test_shm::TestShm,
+                test_single_pixel_buffer_manager::TestSinglePixelBufferManager,
                 test_subcompositor::TestSubcompositor, test_syncobj_manager::TestSyncobjManager,
                 test_text_input_manager::TestTextInputManager,
                 test_toplevel_drag_manager::TestToplevelDragManager,
@@ -176,7 +177,7 @@ impl TestRegistry {
         get_jay_compositor,
         jay_compositor,
         jay_compositor,
-        6,
+        31,
         TestJayCompositor
     );
     create_singleton!(get_compositor, compositor, wl_compositor, 6, TestCompositor);
@@ -315,6 +316,12 @@ impl TestRegistry {
         Ok(())
     }
 
+    pub fn bind_output(&self, name: GlobalName) -> Result<Rc<TestOutput>, TestError> {
+        let output = Rc::new(TestOutput::new(&self.tran));
+        self.bind(&output, name.raw(), 3)?;
+        Ok(output)
+    }
+
     fn handle_global(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = Global::parse_full(parser)?;
         let global = Rc::new(TestGlobal {
@@ -0,0 +1,248 @@
+use {
+    crate::{
+        format::formats,
+        ifs::jay_screencast::JayScreencast,
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::test_jay_output::TestJayOutput,
+            test_object::TestObject,
+            test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH,
+            testrun::ParseFull,
+        },
+        rect::Rect,
+        utils::{buffd::MsgParser, clonecell::CloneCell},
+        video::dmabuf::{DmaBuf, DmaBufPlane, PlaneVec},
+        wire::{JayScreencastId, jay_screencast::*},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestJayScreencast {
+    pub id: JayScreencastId,
+    pub tran: Rc<TestTransport>,
+    pub server: CloneCell<Option<Rc<JayScreencast>>>,
+    pub destroyed: Cell<bool>,
+    pub planes: RefCell<PlaneVec<DmaBufPlane>>,
+    pub pending_buffers: RefCell<Vec<DmaBuf>>,
+    pub buffers: RefCell<Vec<DmaBuf>>,
+    pub buffers_serial: Cell<Option<u32>>,
+    pub config_serial: Cell<Option<u32>>,
+    pub config_size: Cell<(i32, i32)>,
+    pub ready: TEEH<u32>,
+    pub missed_frame: TEEH<()>,
+    pub cursor_position: TEEH<CursorPosition>,
+    pub cursor_image: TEEH<CursorImage>,
+}
+
+impl TestJayScreencast {
+    pub fn destroy(&self) -> TestResult {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn set_output(&self, output: &TestJayOutput) -> TestResult {
+        self.tran.send(SetOutput {
+            self_id: self.id,
+            output: output.id,
+        })
+    }
+
+    pub fn set_region(&self, output: &TestJayOutput, region: Rect) -> TestResult {
+        self.tran.send(SetRegion {
+            self_id: self.id,
+            output: output.id,
+            x: region.x1(),
+            y: region.y1(),
+            width: region.width(),
+            height: region.height(),
+        })
+    }
+
+    pub fn set_allow_all_workspaces(&self, allow_all: bool) -> TestResult {
+        self.tran.send(SetAllowAllWorkspaces {
+            self_id: self.id,
+            allow_all: allow_all as _,
+        })
+    }
+
+    pub fn set_running(&self, running: bool) -> TestResult {
+        self.tran.send(SetRunning {
+            self_id: self.id,
+            running: running as _,
+        })
+    }
+
+    pub fn set_cursor_mode(&self, mode: u32) -> TestResult {
+        self.tran.send(SetCursorMode {
+            self_id: self.id,
+            mode,
+        })
+    }
+
+    pub fn configure(&self) -> TestResult {
+        self.tran.send(Configure { self_id: self.id })
+    }
+
+    pub fn ack_config(&self) -> TestResult {
+        let Some(serial) = self.config_serial.take() else {
+            bail!("Compositor did not send a config");
+        };
+        self.tran.send(AckConfig {
+            self_id: self.id,
+            serial,
+        })
+    }
+
+    pub fn ack_buffers(&self) -> TestResult {
+        let Some(serial) = self.buffers_serial.take() else {
+            bail!("Compositor did not send buffers");
+        };
+        self.tran.send(AckBuffers {
+            self_id: self.id,
+            serial,
+        })
+    }
+
+    pub fn release_buffer(&self, idx: u32) -> TestResult {
+        self.tran.send(ReleaseBuffer {
+            self_id: self.id,
+            idx,
+        })
+    }
+
+    fn handle_plane(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Plane::parse_full(parser)?;
+        self.planes.borrow_mut().push(DmaBufPlane {
+            offset: ev.offset,
+            stride: ev.stride,
+            fd: ev.fd,
+        });
+        Ok(())
+    }
+
+    fn handle_buffer(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Buffer::parse_full(parser)?;
+        let Some(&format) = formats().get(&ev.format) else {
+            bail!("Compositor sent unknown format {}", ev.format);
+        };
+        self.pending_buffers.borrow_mut().push(DmaBuf {
+            id: self.tran.run.state.dma_buf_ids.next(),
+            width: ev.width,
+            height: ev.height,
+            format,
+            modifier: ev.modifier,
+            planes: self.planes.take(),
+        });
+        Ok(())
+    }
+
+    fn handle_buffers_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = BuffersDone::parse_full(parser)?;
+        *self.buffers.borrow_mut() = self.pending_buffers.take();
+        self.buffers_serial.set(Some(ev.serial));
+        Ok(())
+    }
+
+    fn handle_ready(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Ready::parse_full(parser)?;
+        self.ready.push(ev.idx);
+        Ok(())
+    }
+
+    fn handle_destroyed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Destroyed::parse_full(parser)?;
+        bail!("Compositor destroyed the screencast");
+    }
+
+    fn handle_missed_frame(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = MissedFrame::parse_full(parser)?;
+        self.missed_frame.push(());
+        Ok(())
+    }
+
+    fn handle_config_output(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigOutput::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_allow_all_workspaces(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), TestError> {
+        let _ev = ConfigAllowAllWorkspaces::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_allow_workspace(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigAllowWorkspace::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_use_linear_buffers(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigUseLinearBuffers::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_running(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigRunning::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = ConfigDone::parse_full(parser)?;
+        self.config_serial.set(Some(ev.serial));
+        Ok(())
+    }
+
+    fn handle_config_size(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = ConfigSize::parse_full(parser)?;
+        self.config_size.set((ev.width, ev.height));
+        Ok(())
+    }
+
+    fn handle_cursor_position(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = CursorPosition::parse_full(parser)?;
+        self.cursor_position.push(ev);
+        Ok(())
+    }
+
+    fn handle_cursor_image(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = CursorImage::parse_full(parser)?;
+        self.cursor_image.push(ev);
+        Ok(())
+    }
+}
+
+impl Drop for TestJayScreencast {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestJayScreencast, JayScreencast;
+
+    PLANE => handle_plane,
+    BUFFER => handle_buffer,
+    BUFFERS_DONE => handle_buffers_done,
+    READY => handle_ready,
+    DESTROYED => handle_destroyed,
+    MISSED_FRAME => handle_missed_frame,
+    CONFIG_OUTPUT => handle_config_output,
+    CONFIG_ALLOW_ALL_WORKSPACES => handle_config_allow_all_workspaces,
+    CONFIG_ALLOW_WORKSPACE => handle_config_allow_workspace,
+    CONFIG_USE_LINEAR_BUFFERS => handle_config_use_linear_buffers,
+    CONFIG_RUNNING => handle_config_running,
+    CONFIG_DONE => handle_config_done,
+    CONFIG_SIZE => handle_config_size,
+    CURSOR_POSITION => handle_cursor_position,
+    CURSOR_IMAGE => handle_cursor_image,
+}
+
+impl TestObject for TestJayScreencast {}
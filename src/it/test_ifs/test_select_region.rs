@@ -0,0 +1,39 @@
+use crate::{
+    it::{
+        test_error::TestResult, test_object::TestObject, test_utils::test_expected_event::TEEH,
+        testrun::ParseFull,
+    },
+    rect::Rect,
+    utils::buffd::MsgParser,
+    wire::{JaySelectRegionId, jay_select_region::*},
+};
+
+pub struct TestJaySelectRegion {
+    pub id: JaySelectRegionId,
+    pub cancelled: TEEH<()>,
+    pub selected: TEEH<Rect>,
+}
+
+impl TestJaySelectRegion {
+    fn handle_cancelled(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Cancelled::parse_full(parser)?;
+        self.cancelled.push(());
+        Ok(())
+    }
+
+    fn handle_selected(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Selected::parse_full(parser)?;
+        self.selected
+            .push(Rect::new_sized_unchecked(ev.x, ev.y, ev.width, ev.height));
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJaySelectRegion, JaySelectRegion;
+
+    CANCELLED => handle_cancelled,
+    SELECTED => handle_selected,
+}
+
+impl TestObject for TestJaySelectRegion {}
@@ -1,5 +1,6 @@
 use {
     crate::{
+        gfx_api::{AcquireSync, ReleaseSync},
         it::{
             test_error::TestResult,
             test_utils::{
@@ -7,7 +8,7 @@ use {
                 test_workspace_node_ext::TestWorkspaceNodeExt,
             },
         },
-        tree::{OutputNode, ToplevelNode, WorkspaceNode},
+        tree::{OffscreenBuffer, OutputNode, ToplevelNode, WorkspaceNode},
     },
     std::rc::Rc,
 };
@@ -15,6 +16,7 @@ use {
 pub trait TestOutputNodeExt {
     fn workspace(&self) -> TestResult<Rc<WorkspaceNode>>;
     fn first_toplevel(&self) -> TestResult<Rc<dyn ToplevelNode>>;
+    fn perform_test_screencopies(&self) -> TestResult;
 }
 
 impl TestOutputNodeExt for OutputNode {
@@ -28,4 +30,40 @@ impl TestOutputNodeExt for OutputNode {
     fn first_toplevel(&self) -> TestResult<Rc<dyn ToplevelNode>> {
         self.workspace()?.container()?.first_toplevel()
     }
+
+    /// Renders the output without the hardware cursor and hands the result to all
+    /// screencopies, as a backend would after presenting a frame.
+    fn perform_test_screencopies(&self) -> TestResult {
+        let (width, height) = self.global.pixel_size();
+        let buffer = OffscreenBuffer::new(&self.state, width, height)?;
+        let cd = self.state.color_manager.srgb_gamma22();
+        let sf = buffer.fb.render_node(
+            AcquireSync::Unnecessary,
+            ReleaseSync::Explicit,
+            cd,
+            self,
+            &self.state,
+            Some(self.global.pos.get()),
+            self.global.persistent.scale.get(),
+            true,
+            false,
+            false,
+            false,
+            self.global.persistent.transform.get(),
+            None,
+            cd,
+        )?;
+        self.perform_screencopies(
+            &buffer.tex,
+            cd,
+            None,
+            &AcquireSync::from_sync_file(sf),
+            ReleaseSync::None,
+            true,
+            0,
+            0,
+            None,
+        );
+        Ok(())
+    }
 }
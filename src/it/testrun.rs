@@ -14,13 +14,19 @@ use {
         object::WL_DISPLAY_ID,
         state::State,
         tree::OutputNode,
-        utils::{bitfield::Bitfield, buffd::MsgParser, oserror::OsErrorExt, stack::Stack},
+        utils::{
+            bitfield::Bitfield,
+            buffd::MsgParser,
+            clone3::{Forked, fork_with_pidfd},
+            oserror::OsErrorExt,
+            stack::Stack,
+        },
     },
     std::{
         cell::{Cell, RefCell},
         rc::Rc,
     },
-    uapi::c,
+    uapi::{OwnedFd, c},
 };
 
 pub struct TestRun {
@@ -51,6 +57,69 @@ impl TestRun {
             .connect(&socket, &self.server_addr)
             .await
             .with_context(|| "Could not connect to the compositor")?;
+        self.init_client(socket).await
+    }
+
+    /// Creates a client whose connection was established by a child process.
+    ///
+    /// The compositor sees the pid of the child as the pid of the client. The child
+    /// exits when the returned [`TestChild`] is dropped.
+    pub async fn create_child_client(
+        self: &Rc<Self>,
+    ) -> Result<(Rc<TestClient>, TestChild), TestError> {
+        self.create_child_client2()
+            .await
+            .with_context(|| "Could not create a child client")
+    }
+
+    async fn create_child_client2(
+        self: &Rc<Self>,
+    ) -> Result<(Rc<TestClient>, TestChild), TestError> {
+        let socket = uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0)
+            .to_os_error()
+            .with_context(|| "Could not create a unix socket")?;
+        let (ready_r, ready_w) = uapi::pipe2(c::O_CLOEXEC)
+            .to_os_error()
+            .with_context(|| "Could not create a pipe")?;
+        let (exit_r, exit_w) = uapi::pipe2(c::O_CLOEXEC)
+            .to_os_error()
+            .with_context(|| "Could not create a pipe")?;
+        let pid = match fork_with_pidfd(false)? {
+            Forked::Parent { pid, .. } => pid,
+            Forked::Child { .. } => unsafe {
+                // Only async-signal-safe functions may be used here.
+                c::close(exit_w.raw());
+                let res = c::connect(
+                    socket.raw(),
+                    &self.server_addr as *const c::sockaddr_un as *const c::sockaddr,
+                    size_of::<c::sockaddr_un>() as _,
+                );
+                if res == 0 {
+                    c::write(ready_w.raw(), [0u8].as_ptr().cast(), 1);
+                    c::read(exit_r.raw(), [0u8].as_mut_ptr().cast(), 1);
+                }
+                c::_exit(0);
+            },
+        };
+        let child = TestChild {
+            pid,
+            exit: Cell::new(Some(exit_w)),
+        };
+        drop(ready_w);
+        drop(exit_r);
+        let mut buf = [0u8];
+        let n = unsafe { c::read(ready_r.raw(), buf.as_mut_ptr().cast(), 1) };
+        if n != 1 {
+            bail!("The child could not connect to the compositor");
+        }
+        let client = self.init_client(Rc::new(socket)).await?;
+        Ok((client, child))
+    }
+
+    async fn init_client(
+        self: &Rc<Self>,
+        socket: Rc<OwnedFd>,
+    ) -> Result<Rc<TestClient>, TestError> {
         let mut obj_ids = Bitfield::default();
         obj_ids.take(0);
         obj_ids.take(1);
@@ -140,6 +209,18 @@ impl TestRun {
     }
 }
 
+pub struct TestChild {
+    pub pid: c::pid_t,
+    exit: Cell<Option<OwnedFd>>,
+}
+
+impl Drop for TestChild {
+    fn drop(&mut self) {
+        self.exit.take();
+        let _ = uapi::waitpid(self.pid, 0);
+    }
+}
+
 pub trait ParseFull<'a>: Sized {
     fn parse_full(parser: MsgParser<'_, 'a>) -> Result<Self, TestError>;
 }
@@ -82,6 +82,29 @@ mod t0048_frame_callback;
 mod t0049_surface_damage_backend;
 mod t0050_fifo;
 mod t0051_pointer_warp;
+mod t0052_mouse_keys;
+mod t0053_magnifier;
+mod t0054_key_sequence;
+mod t0055_hot_corners;
+mod t0056_workspace_layout;
+mod t0057_scrolling_layout;
+mod t0058_rename_workspace;
+mod t0059_persistent_workspaces;
+mod t0060_swap_windows;
+mod t0061_overview;
+mod t0062_window_switcher;
+mod t0063_sticky_float;
+mod t0064_float_placement;
+mod t0065_node_screenshot;
+mod t0066_portal_settings;
+mod t0067_tablet;
+mod t0068_swallow;
+mod t0069_global_shortcuts;
+mod t0070_input_capture;
+mod t0071_screencast_cursor;
+mod t0072_screencast_region;
+mod t0073_region_screenshot;
+mod t0074_pick_color;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -152,5 +175,28 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0049_surface_damage_backend,
         t0050_fifo,
         t0051_pointer_warp,
+        t0052_mouse_keys,
+        t0053_magnifier,
+        t0054_key_sequence,
+        t0055_hot_corners,
+        t0056_workspace_layout,
+        t0057_scrolling_layout,
+        t0058_rename_workspace,
+        t0059_persistent_workspaces,
+        t0060_swap_windows,
+        t0061_overview,
+        t0062_window_switcher,
+        t0063_sticky_float,
+        t0064_float_placement,
+        t0065_node_screenshot,
+        t0066_portal_settings,
+        t0067_tablet,
+        t0068_swallow,
+        t0069_global_shortcuts,
+        t0070_input_capture,
+        t0071_screencast_cursor,
+        t0072_screencast_region,
+        t0073_region_screenshot,
+        t0074_pick_color,
     }
 }
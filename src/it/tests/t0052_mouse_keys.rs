@@ -0,0 +1,52 @@
+use {
+    crate::{
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    let seat = client.get_default_seat().await?;
+    let button = seat.pointer.button.expect()?;
+
+    let (x, y) = win1.tl.server.node_absolute_position().center();
+    ds.move_to(x, y);
+
+    run.cfg.mouse_keys_click(ds.seat.id(), false)?;
+    client.sync().await;
+    let press = button.next()?;
+    let release = button.next()?;
+    tassert_eq!(press.button, BTN_LEFT);
+    tassert_eq!(press.state, 1);
+    tassert_eq!(release.button, BTN_LEFT);
+    tassert_eq!(release.state, 0);
+    tassert!(button.next().is_err());
+
+    let (x1, y1) = ds.seat.pointer_cursor().position();
+    run.cfg
+        .mouse_keys_motion(ds.seat.id(), Direction::Right, true)?;
+    run.state.wheel.timeout(50).await?;
+    run.cfg
+        .mouse_keys_motion(ds.seat.id(), Direction::Right, false)?;
+    let (x2, y2) = ds.seat.pointer_cursor().position();
+    tassert!(x2 > x1);
+    tassert_eq!(y2, y1);
+
+    run.state.wheel.timeout(50).await?;
+    let (x3, _) = ds.seat.pointer_cursor().position();
+    tassert_eq!(x3, x2);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::_private::ipc::SeatZoom,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    tassert!(!ds.output.is_magnified());
+
+    run.cfg.zoom(ds.seat.id(), SeatZoom::In)?;
+    tassert_eq!(ds.output.zoom.get(), 1.25);
+    tassert!(ds.output.is_magnified());
+
+    run.cfg.zoom(ds.seat.id(), SeatZoom::In)?;
+    run.cfg.zoom(ds.seat.id(), SeatZoom::Out)?;
+    tassert_eq!(ds.output.zoom.get(), 1.25);
+
+    run.cfg.zoom(ds.seat.id(), SeatZoom::Out)?;
+    run.cfg.zoom(ds.seat.id(), SeatZoom::Out)?;
+    tassert_eq!(ds.output.zoom.get(), 1.0);
+    tassert!(!ds.output.is_magnified());
+
+    run.cfg.zoom(ds.seat.id(), SeatZoom::In)?;
+    run.cfg.zoom(ds.seat.id(), SeatZoom::Reset)?;
+    tassert!(!ds.output.is_magnified());
+
+    Ok(())
+}
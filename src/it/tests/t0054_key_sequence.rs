@@ -0,0 +1,61 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    client.sync().await;
+
+    let key = seat.kb.key.expect()?;
+
+    run.cfg.begin_key_sequence(ds.seat.id(), "logo-w", 0)?;
+    tassert_eq!(
+        run.state
+            .pending_key_sequence()
+            .as_deref()
+            .map(|s| s.as_str()),
+        Some("logo-w")
+    );
+
+    let pressed = ds.kb.press(1);
+    run.sync().await;
+    client.sync().await;
+    tassert!(key.next().is_err());
+    tassert!(
+        run.cfg
+            .key_sequences_cancelled
+            .remove(&ds.seat.id())
+            .is_some()
+    );
+    tassert!(run.state.pending_key_sequence().is_none());
+    drop(pressed);
+    run.sync().await;
+    client.sync().await;
+    tassert!(key.next().is_err());
+
+    let pressed = ds.kb.press(1);
+    run.sync().await;
+    client.sync().await;
+    tassert_eq!(key.next()?.1.state, 1);
+    drop(pressed);
+
+    run.cfg.begin_key_sequence(ds.seat.id(), "logo-w", 10)?;
+    run.state.wheel.timeout(50).await?;
+    tassert!(
+        run.cfg
+            .key_sequences_cancelled
+            .remove(&ds.seat.id())
+            .is_some()
+    );
+    tassert!(run.state.pending_key_sequence().is_none());
+
+    Ok(())
+}
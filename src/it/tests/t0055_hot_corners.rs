@@ -0,0 +1,56 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::input::ScreenEdge,
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let corner = run
+        .cfg
+        .create_hot_corner(ScreenEdge::TopLeft, Duration::ZERO, 0.0)?;
+    let edge = run
+        .cfg
+        .create_hot_corner(ScreenEdge::Left, Duration::ZERO, 100.0)?;
+    let triggered = |c| {
+        run.cfg
+            .hot_corners_triggered
+            .remove(&(c, ds.seat.id()))
+            .is_some()
+    };
+
+    ds.move_to(100, 100);
+    run.sync().await;
+    ds.move_to(0, 0);
+    run.sync().await;
+    tassert!(triggered(corner));
+    tassert!(!triggered(edge));
+
+    ds.mouse.rel(-10.0, -10.0);
+    run.sync().await;
+    tassert!(!triggered(corner));
+
+    ds.move_to(0, 100);
+    run.sync().await;
+    tassert!(!triggered(edge));
+    ds.mouse.rel(-60.0, 0.0);
+    run.sync().await;
+    tassert!(!triggered(edge));
+    ds.mouse.rel(-60.0, 0.0);
+    run.sync().await;
+    tassert!(triggered(edge));
+    ds.mouse.rel(-60.0, 0.0);
+    run.sync().await;
+    tassert!(!triggered(edge));
+
+    ds.move_to(100, 100);
+    run.sync().await;
+    ds.move_to(0, 0);
+    run.sync().await;
+    tassert!(triggered(corner));
+
+    Ok(())
+}
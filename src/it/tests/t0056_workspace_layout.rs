@@ -0,0 +1,103 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::workspace::WorkspaceLayout,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let ws = ds.output.workspace.get().unwrap();
+
+    run.cfg
+        .set_workspace_layout(&ws.name.borrow(), WorkspaceLayout::MasterStack)?;
+
+    let client = run.create_client().await?;
+    let mut windows = vec![];
+    for _ in 0..4 {
+        let window = client.create_window().await?;
+        window.map2().await?;
+        windows.push(window);
+    }
+    run.sync().await;
+    client.sync().await;
+
+    let pos: Vec<_> = windows
+        .iter()
+        .map(|w| w.tl.server.node_absolute_position())
+        .collect();
+    tassert_eq!(pos[0].x1(), 0);
+    tassert!(pos[0].height() > pos[1].height());
+    for p in &pos[1..] {
+        tassert!(p.x1() > pos[0].x2());
+        tassert_eq!(p.x1(), pos[1].x1());
+    }
+    tassert!(pos[1].y2() < pos[2].y1());
+    tassert!(pos[2].y2() < pos[3].y1());
+
+    run.cfg.set_workspace_master_count(&ws.name.borrow(), 2)?;
+    run.sync().await;
+    let pos: Vec<_> = windows
+        .iter()
+        .map(|w| w.tl.server.node_absolute_position())
+        .collect();
+    tassert_eq!(pos[0].x1(), pos[1].x1());
+    tassert_eq!(pos[2].x1(), pos[3].x1());
+    tassert!(pos[1].x2() < pos[2].x1());
+
+    run.cfg
+        .set_workspace_layout(&ws.name.borrow(), WorkspaceLayout::Grid)?;
+    run.sync().await;
+    let pos: Vec<_> = windows
+        .iter()
+        .map(|w| w.tl.server.node_absolute_position())
+        .collect();
+    tassert_eq!(pos[0].y1(), pos[1].y1());
+    tassert_eq!(pos[2].y1(), pos[3].y1());
+    tassert_eq!(pos[0].x1(), pos[2].x1());
+    tassert_eq!(pos[1].x1(), pos[3].x1());
+    tassert!(pos[0].y2() < pos[2].y1());
+
+    run.cfg
+        .set_workspace_layout(&ws.name.borrow(), WorkspaceLayout::Dwindle)?;
+    run.sync().await;
+    let pos: Vec<_> = windows
+        .iter()
+        .map(|w| w.tl.server.node_absolute_position())
+        .collect();
+    tassert!(pos[0].x2() < pos[1].x1());
+    tassert!(pos[1].y2() < pos[2].y1());
+    tassert!(pos[2].x2() < pos[3].x1());
+    tassert_eq!(pos[2].y1(), pos[3].y1());
+
+    windows.remove(1).tl.core.destroy()?;
+    client.sync().await;
+    run.sync().await;
+    let pos: Vec<_> = windows
+        .iter()
+        .map(|w| w.tl.server.node_absolute_position())
+        .collect();
+    tassert!(pos[0].x2() < pos[1].x1());
+    tassert!(pos[1].y2() < pos[2].y1());
+    tassert_eq!(pos[1].x1(), pos[2].x1());
+
+    run.cfg.set_workspace_master_count("other", 3)?;
+    run.cfg.set_workspace_master_ratio("other", 0.7)?;
+    for _ in 0..2 {
+        run.cfg.show_workspace(ds.seat.id(), "other")?;
+        run.sync().await;
+        let other = ds.output.workspace.get().unwrap();
+        tassert_eq!(&*other.name.borrow(), "other");
+        tassert_eq!(other.master_count.get(), 3);
+        tassert_eq!(other.master_ratio.get(), 0.7);
+        run.cfg.show_workspace(ds.seat.id(), &ws.name.borrow())?;
+        run.sync().await;
+        tassert!(run.state.workspaces.get("other").is_none());
+    }
+
+    Ok(())
+}
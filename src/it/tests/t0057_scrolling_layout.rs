@@ -0,0 +1,73 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        tree::Node,
+    },
+    jay_config::{Direction, workspace::WorkspaceLayout},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let ws = ds.output.workspace.get().unwrap();
+
+    run.cfg
+        .set_workspace_layout(&ws.name.borrow(), WorkspaceLayout::Scrolling)?;
+
+    let client = run.create_client().await?;
+    let mut windows = vec![];
+    for _ in 0..3 {
+        let window = client.create_window().await?;
+        window.map2().await?;
+        windows.push(window);
+    }
+
+    let settle = || async {
+        run.sync().await;
+        run.state.wheel.timeout(300).await?;
+        run.sync().await;
+        TestResult::Ok(())
+    };
+    let positions = || -> Vec<Rect> {
+        windows
+            .iter()
+            .map(|w| w.tl.server.node_absolute_position())
+            .collect()
+    };
+
+    settle().await?;
+    let ws_pos = ws.position.get();
+    let pos = positions();
+    tassert_eq!(pos[0].y1(), pos[1].y1());
+    tassert_eq!(pos[1].y1(), pos[2].y1());
+    tassert!(pos[0].x2() < pos[1].x1());
+    tassert!(pos[1].x2() < pos[2].x1());
+    tassert!(pos[2].width() < ws_pos.width());
+    tassert!(pos[2].x1() >= ws_pos.x1());
+    tassert!(pos[2].x2() <= ws_pos.x2());
+
+    let width = pos[2].width();
+    run.cfg.cycle_column_width(ds.seat.id())?;
+    settle().await?;
+    let pos = positions();
+    tassert!(pos[2].width() > width);
+    tassert!(pos[2].x2() <= ws_pos.x2());
+
+    run.cfg.focus(ds.seat.id(), Direction::Left)?;
+    run.cfg.consume_into_column(ds.seat.id())?;
+    settle().await?;
+    let pos = positions();
+    tassert_eq!(pos[1].x1(), pos[2].x1());
+    tassert!(pos[1].y2() < pos[2].y1());
+
+    run.cfg.expel_from_column(ds.seat.id())?;
+    settle().await?;
+    let pos = positions();
+    tassert_eq!(pos[1].y1(), pos[2].y1());
+    tassert!(pos[1].x1() != pos[2].x1());
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use {
+    crate::{
+        criteria::{CritLiteralOrRegex, CritMgrExt},
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    jay_config::workspace::WorkspaceLayout,
+    std::{cell::Cell, rc::Rc},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let ws = ds.output.workspace.get().unwrap();
+    let old_name = ws.name.borrow().clone();
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map2().await?;
+
+    run.cfg.rename_workspace(&old_name, "renamed")?;
+    run.sync().await;
+
+    tassert_eq!(&*ws.name.borrow(), "renamed");
+    tassert!(run.state.workspaces.get(&old_name).is_none());
+    tassert!(run.state.workspaces.get("renamed").is_some());
+    tassert_eq!(ws.output.get().id, ds.output.id);
+    let tl_ws = window.tl.server.tl_data().workspace.get().map(|w| w.id);
+    tassert_eq!(tl_ws, Some(ws.id));
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    run.sync().await;
+    run.cfg.rename_workspace("2", "renamed")?;
+    run.sync().await;
+    tassert!(run.state.workspaces.get("2").is_some());
+    tassert_eq!(&*ws.name.borrow(), "renamed");
+
+    run.cfg.show_workspace(ds.seat.id(), "renamed")?;
+    run.sync().await;
+    run.cfg
+        .set_workspace_layout("renamed", WorkspaceLayout::Grid)?;
+    let handle = run.cfg.get_workspace("renamed")?;
+
+    let matched = Rc::new(Cell::new(false));
+    let mgr = &run.state.tl_matcher_manager;
+    let node = mgr.workspace(CritLiteralOrRegex::Literal("other".to_string()));
+    let _leaf = mgr.leaf(&node, {
+        let matched = matched.clone();
+        move |_| {
+            matched.set(true);
+            let matched = matched.clone();
+            Box::new(move || matched.set(false))
+        }
+    });
+    mgr.rematch_all(&run.state);
+    run.sync().await;
+    tassert!(!matched.get());
+
+    ws.rename("other")?;
+    run.sync().await;
+    run.sync().await;
+    tassert!(matched.get());
+    tassert_eq!(run.cfg.get_workspace("other")?, handle);
+    tassert_eq!(
+        run.state.workspace_layouts.get("other"),
+        Some(WorkspaceLayout::Grid)
+    );
+    tassert!(run.state.workspace_layouts.get("renamed").is_none());
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    run.sync().await;
+    run.cfg.rename_workspace("2", "renamed")?;
+    run.sync().await;
+    tassert!(run.state.workspaces.get("renamed").is_some());
+    tassert_eq!(&*ws.name.borrow(), "other");
+    tassert_eq!(run.cfg.get_workspace("other")?, handle);
+
+    // Definitions follow the workspace and are not recreated under the old name.
+    run.cfg.set_workspace_persistent("other", true)?;
+    run.cfg.set_workspace_gap("other", 7)?;
+    ws.rename("final")?;
+    run.sync().await;
+    tassert!(run.state.workspace_definitions.get("other").is_none());
+    tassert!(run.state.is_persistent_workspace("final"));
+    tassert_eq!(run.state.workspace_definition("final").gap.get(), 7);
+    run.state.create_persistent_workspaces();
+    tassert!(run.state.workspaces.get("other").is_none());
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        backend::{
+            BackendConnectorState, BackendEvent, ConnectorEvent, ConnectorKernelId, MonitorInfo,
+        },
+        ifs::wl_output::OutputId,
+        it::{test_backend::TestConnector, test_error::TestResult, testrun::TestRun},
+        utils::numcell::NumCell,
+        video::drm::ConnectorType,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg
+        .set_workspace_preferred_outputs("5", &["VGA-2", "VGA-1"])?;
+    run.cfg.set_workspace_gap("5", 10)?;
+    run.cfg.set_workspace_persistent("5", true)?;
+    run.sync().await;
+
+    let Some(ws) = run.state.workspaces.get("5") else {
+        bail!("persistent workspace was not created");
+    };
+    tassert_eq!(ws.output.get().id, ds.output.id);
+    let pos = ws.position.get();
+    let tiling = ws.tiling_rect();
+    tassert_eq!(tiling.x1(), pos.x1() + 10);
+    tassert_eq!(tiling.width(), pos.width() - 20);
+
+    run.cfg.show_workspace(ds.seat.id(), "5")?;
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    run.sync().await;
+    tassert!(run.state.workspaces.get("5").is_some());
+
+    let new_connector = Rc::new(TestConnector {
+        id: run.state.connector_ids.next(),
+        kernel_id: ConnectorKernelId {
+            ty: ConnectorType::VGA,
+            idx: 2,
+        },
+        events: Default::default(),
+        feedback: Default::default(),
+        idle: Default::default(),
+        damage_calls: NumCell::new(0),
+    });
+    let new_monitor_info = MonitorInfo {
+        output_id: Rc::new(OutputId {
+            connector: None,
+            manufacturer: "jay".to_string(),
+            model: "jay second connector".to_string(),
+            serial_number: "".to_string(),
+        }),
+        state: BackendConnectorState {
+            serial: run.state.backend_connector_state_serials.next(),
+            ..run.backend.default_monitor_info.state
+        },
+        ..run.backend.default_monitor_info.clone()
+    };
+    run.backend
+        .state
+        .backend_events
+        .push(BackendEvent::NewConnector(new_connector.clone()));
+    new_connector
+        .events
+        .send_event(ConnectorEvent::Connected(new_monitor_info));
+    run.state.eng.yield_now().await;
+    tassert_eq!(ws.output.get().global.connector.name.as_str(), "VGA-2");
+
+    new_connector
+        .events
+        .send_event(ConnectorEvent::Disconnected);
+    run.state.eng.yield_now().await;
+    tassert_eq!(ws.output.get().id, ds.output.id);
+    tassert!(run.state.workspaces.get("5").is_some());
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let mut windows = vec![];
+    for _ in 0..3 {
+        let window = client.create_window().await?;
+        window.map2().await?;
+        windows.push(window);
+    }
+    let order = || -> Vec<usize> {
+        let mut order: Vec<_> = (0..windows.len()).collect();
+        order.sort_by_key(|&i| windows[i].tl.server.node_absolute_position().x1());
+        order
+    };
+
+    run.cfg.swap(ds.seat.id(), Direction::Left)?;
+    run.sync().await;
+    tassert_eq!(order(), vec![0, 2, 1]);
+
+    run.cfg.create_mark(ds.seat.id(), 1000)?;
+    run.cfg.focus(ds.seat.id(), Direction::Left)?;
+    run.cfg.move_to_mark(ds.seat.id(), 1000)?;
+    run.sync().await;
+    tassert_eq!(order(), vec![2, 0, 1]);
+
+    run.cfg.swap_with_mark(ds.seat.id(), 1000)?;
+    run.sync().await;
+    tassert_eq!(order(), vec![0, 2, 1]);
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use {
+    crate::{
+        format::XRGB8888,
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        screenshoter::take_screenshot,
+        tree::{Node, ToplevelNodeBase},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let w1 = client.create_window().await?;
+    w1.map2().await?;
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    let w2 = client.create_window().await?;
+    w2.map2().await?;
+
+    let thumbnail = |window: &Rc<dyn Node>| {
+        ds.output
+            .overview_layout()
+            .into_iter()
+            .flat_map(|ws| ws.windows)
+            .find(|w| w.window.node_id() == window.node_id())
+            .map(|w| w.rect.center())
+    };
+
+    run.cfg.toggle_overview(ds.seat.id())?;
+    run.sync().await;
+    tassert!(ds.output.overview.is_some());
+    tassert_eq!(ds.output.overview_layout().len(), 2);
+
+    let Some(overview) = ds.output.overview.get() else {
+        bail!("overview was closed");
+    };
+    let _ = take_screenshot(&run.state, false, XRGB8888);
+    tassert!(overview.thumbnails_damaged().is_empty());
+    ds.output.update_overview_thumbnails();
+    tassert_eq!(overview.thumbnails_damaged(), [false, false]);
+    ds.kb.press(15);
+    run.sync().await;
+    tassert_eq!(overview.thumbnails_damaged(), [false, false]);
+    run.state.damage(ds.output.global.pos.get());
+    tassert_eq!(overview.thumbnails_damaged(), [true, true]);
+    ds.output.update_overview_thumbnails();
+    tassert_eq!(overview.thumbnails_damaged(), [false, false]);
+
+    let w1_node: Rc<dyn Node> = w1.tl.server.clone();
+    let Some((x, y)) = thumbnail(&w1_node) else {
+        bail!("window 1 has no thumbnail");
+    };
+    ds.move_to(x, y);
+    ds.mouse.click(BTN_LEFT);
+    run.sync().await;
+    tassert!(ds.output.overview.is_none());
+    let name = ds.output.workspace.get().map(|ws| ws.name.borrow().clone());
+    tassert_eq!(name.as_deref(), Some("1"));
+    let focus = ds.seat.get_keyboard_node().node_toplevel();
+    tassert_eq!(focus.map(|tl| tl.node_id()), Some(w1_node.node_id()));
+
+    run.cfg.toggle_overview(ds.seat.id())?;
+    run.sync().await;
+    let Some((x, y)) = thumbnail(&w1_node) else {
+        bail!("window 1 has no thumbnail");
+    };
+    let Some(target) = ds
+        .output
+        .overview_layout()
+        .into_iter()
+        .find(|ws| *ws.workspace.name.borrow() == "2")
+    else {
+        bail!("workspace 2 has no thumbnail");
+    };
+    ds.move_to(x, y);
+    let click = ds.mouse.click(BTN_LEFT);
+    run.sync().await;
+    let (tx, ty) = target.rect.center();
+    ds.move_to(tx, ty);
+    run.sync().await;
+    drop(click);
+    run.sync().await;
+    tassert!(ds.output.overview.is_some());
+    let name = w1
+        .tl
+        .server
+        .tl_data()
+        .workspace
+        .get()
+        .map(|ws| ws.name.borrow().clone());
+    tassert_eq!(name.as_deref(), Some("2"));
+
+    run.cfg.toggle_overview(ds.seat.id())?;
+    run.sync().await;
+    tassert!(ds.output.overview.is_none());
+
+    ds.mouse.swipe(4, -200.0);
+    run.sync().await;
+    tassert!(ds.output.overview.is_some());
+    ds.mouse.swipe(4, 200.0);
+    run.sync().await;
+    tassert!(ds.output.overview.is_none());
+
+    run.cfg.set_overview_swipe_enabled(ds.seat.id(), false)?;
+    ds.mouse.swipe(4, -200.0);
+    run.sync().await;
+    tassert!(ds.output.overview.is_none());
+
+    run.cfg.set_overview_swipe_enabled(ds.seat.id(), true)?;
+    run.cfg.set_overview_swipe_fingers(ds.seat.id(), 3)?;
+    ds.mouse.swipe(4, -200.0);
+    run.sync().await;
+    tassert!(ds.output.overview.is_none());
+    ds.mouse.swipe(3, -200.0);
+    run.sync().await;
+    tassert!(ds.output.overview.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,113 @@
+use {
+    crate::{
+        format::XRGB8888,
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        screenshoter::take_screenshot,
+        tree::Node,
+    },
+    jay_config::input::Timeline,
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_ESC: u32 = 1;
+const KEY_LEFTALT: u32 = 56;
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let w1 = client.create_window().await?;
+    w1.map2().await?;
+    let w2 = client.create_window().await?;
+    w2.map2().await?;
+    let w3 = client.create_window().await?;
+    w3.map2().await?;
+
+    let focus = || {
+        ds.seat
+            .get_keyboard_node()
+            .node_toplevel()
+            .map(|tl| tl.node_id())
+    };
+    let selected = || {
+        ds.output
+            .window_switcher
+            .get()
+            .and_then(|s| s.selected.get())
+    };
+    tassert_eq!(focus(), Some(w3.tl.server.node_id()));
+
+    let alt = ds.kb.press(KEY_LEFTALT);
+    run.sync().await;
+    run.cfg.window_switcher(ds.seat.id(), Timeline::Older)?;
+    run.sync().await;
+    let Some(switcher) = ds.output.window_switcher.get() else {
+        bail!("window switcher is not shown");
+    };
+    tassert_eq!(switcher.entries.len(), 3);
+    tassert_eq!(selected(), Some(1));
+    run.cfg.window_switcher(ds.seat.id(), Timeline::Older)?;
+    run.sync().await;
+    tassert_eq!(selected(), Some(2));
+    tassert_eq!(focus(), Some(w3.tl.server.node_id()));
+    drop(alt);
+    run.sync().await;
+    tassert!(ds.output.window_switcher.is_none());
+    tassert_eq!(focus(), Some(w1.tl.server.node_id()));
+
+    let alt = ds.kb.press(KEY_LEFTALT);
+    run.sync().await;
+    run.cfg.window_switcher(ds.seat.id(), Timeline::Newer)?;
+    run.sync().await;
+    tassert_eq!(selected(), Some(2));
+    let esc = ds.kb.press(KEY_ESC);
+    run.sync().await;
+    tassert!(ds.output.window_switcher.is_none());
+    drop(esc);
+    drop(alt);
+    run.sync().await;
+    tassert_eq!(focus(), Some(w1.tl.server.node_id()));
+
+    run.cfg.window_switcher(ds.seat.id(), Timeline::Older)?;
+    run.sync().await;
+    tassert!(ds.output.window_switcher.is_none());
+    tassert_eq!(focus(), Some(w3.tl.server.node_id()));
+
+    // Thumbnails are only rendered before presentation and only when their window is
+    // damaged.
+    ds.seat.window_switcher_set_thumbnails(true);
+    let alt = ds.kb.press(KEY_LEFTALT);
+    run.sync().await;
+    run.cfg.window_switcher(ds.seat.id(), Timeline::Older)?;
+    run.sync().await;
+    let Some(switcher) = ds.output.window_switcher.get() else {
+        bail!("window switcher is not shown");
+    };
+    let _ = take_screenshot(&run.state, false, XRGB8888);
+    tassert!(switcher.thumbnails_damaged().is_empty());
+    ds.output.update_window_switcher_thumbnails();
+    tassert_eq!(switcher.thumbnails_damaged(), [false, false, false]);
+    run.cfg.window_switcher(ds.seat.id(), Timeline::Older)?;
+    run.sync().await;
+    tassert_eq!(switcher.thumbnails_damaged(), [false, false, false]);
+    let pos = w1.tl.server.node_absolute_position();
+    let (x, y) = pos.center();
+    run.state.damage(Rect::new_sized_unchecked(x, y, 1, 1));
+    let w1_id = w1.tl.server.node_id();
+    let expected: Vec<_> = switcher
+        .entries
+        .iter()
+        .map(|e| e.window.upgrade().map(|w| w.node_id()) == Some(w1_id))
+        .collect();
+    tassert!(expected.contains(&true));
+    tassert_eq!(switcher.thumbnails_damaged(), expected);
+    ds.output.update_window_switcher_thumbnails();
+    tassert_eq!(switcher.thumbnails_damaged(), [false, false, false]);
+    drop(alt);
+    run.sync().await;
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use {
+    crate::{
+        backend::{
+            BackendConnectorState, BackendEvent, ConnectorEvent, ConnectorKernelId, MonitorInfo,
+        },
+        ifs::wl_output::OutputId,
+        it::{test_backend::TestConnector, test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNode,
+        utils::numcell::NumCell,
+        video::drm::ConnectorType,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+    run.cfg.set_sticky(ds.seat.id(), true)?;
+    run.sync().await;
+    tassert!(win.tl.server.tl_sticky());
+    tassert!(win.tl.server.tl_pinned());
+
+    let new_connector = Rc::new(TestConnector {
+        id: run.state.connector_ids.next(),
+        kernel_id: ConnectorKernelId {
+            ty: ConnectorType::VGA,
+            idx: 2,
+        },
+        events: Default::default(),
+        feedback: Default::default(),
+        idle: Default::default(),
+        damage_calls: NumCell::new(0),
+    });
+    let new_monitor_info = MonitorInfo {
+        output_id: Rc::new(OutputId {
+            connector: None,
+            manufacturer: "jay".to_string(),
+            model: "jay second connector".to_string(),
+            serial_number: "".to_string(),
+        }),
+        state: BackendConnectorState {
+            serial: run.state.backend_connector_state_serials.next(),
+            ..run.backend.default_monitor_info.state
+        },
+        ..run.backend.default_monitor_info.clone()
+    };
+    run.backend
+        .state
+        .backend_events
+        .push(BackendEvent::NewConnector(new_connector.clone()));
+    new_connector
+        .events
+        .send_event(ConnectorEvent::Connected(new_monitor_info));
+    run.state.eng.yield_now().await;
+    run.sync().await;
+
+    let Some(output) = run.state.root.outputs.get(&new_connector.id) else {
+        bail!("second output was not created");
+    };
+    let float = win.tl.float_parent()?;
+    let offset = {
+        let pos = float.position.get();
+        let opos = ds.output.global.pos.get();
+        (pos.x1() - opos.x1(), pos.y1() - opos.y1())
+    };
+
+    let opos = output.global.pos.get();
+    ds.move_to(opos.x1() + opos.width() / 2, opos.y1() + opos.height() / 2);
+    run.sync().await;
+    tassert_eq!(float.workspace.get().output.get().id, output.id);
+    let pos = float.position.get();
+    tassert_eq!((pos.x1() - opos.x1(), pos.y1() - opos.y1()), offset);
+
+    run.cfg.set_sticky(ds.seat.id(), false)?;
+    run.sync().await;
+    tassert!(!win.tl.server.tl_sticky());
+    tassert!(win.tl.server.tl_pinned());
+    let opos = ds.output.global.pos.get();
+    ds.move_to(opos.x1() + opos.width() / 2, opos.y1() + opos.height() / 2);
+    run.sync().await;
+    tassert_eq!(float.workspace.get().output.get().id, output.id);
+
+    Ok(())
+}
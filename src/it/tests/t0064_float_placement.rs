@@ -0,0 +1,63 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+    },
+    jay_config::window::{FloatPlacement, FloatSnap},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    run.cfg.set_float_placement(FloatPlacement::Cascade)?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+    run.sync().await;
+    let float1 = win1.tl.float_parent()?;
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+    run.sync().await;
+    let float2 = win2.tl.float_parent()?;
+
+    let theme = &run.state.theme;
+    let offset = theme.sizes.border_width.get() + theme.sizes.title_height.get() + 1;
+    let pos1 = float1.position.get();
+    let pos2 = float2.position.get();
+    tassert_eq!(pos2.position(), (pos1.x1() + offset, pos1.y1() + offset));
+
+    let area = ds.output.workspace_rect.get();
+    run.cfg.snap_float(ds.seat.id(), FloatSnap::Right)?;
+    run.sync().await;
+    tassert_eq!(
+        float2.position.get(),
+        Rect::new(
+            area.x1() + area.width() / 2,
+            area.y1(),
+            area.x2(),
+            area.y2()
+        )
+        .unwrap()
+    );
+
+    run.cfg.snap_float(ds.seat.id(), FloatSnap::TopLeft)?;
+    run.sync().await;
+    tassert_eq!(
+        float2.position.get(),
+        Rect::new(
+            area.x1(),
+            area.y1(),
+            area.x1() + area.width() / 2,
+            area.y1() + area.height() / 2,
+        )
+        .unwrap()
+    );
+
+    Ok(())
+}
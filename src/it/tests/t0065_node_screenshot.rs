@@ -0,0 +1,38 @@
+use {
+    crate::{
+        cli::{ScreenshotFormat, screenshot::buf_region_to_bytes},
+        format::XRGB8888,
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        screenshoter::{take_output_screenshot, take_toplevel_screenshot},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    run.sync().await;
+
+    let output = take_output_screenshot(&run.state, &ds.output, false, XRGB8888)?;
+    let pos = ds.output.global.pos.get();
+    let dmabuf = output.bo.dmabuf();
+    tassert_eq!((dmabuf.width, dmabuf.height), (pos.width(), pos.height()));
+
+    let tl = take_toplevel_screenshot(&run.state, &*win.tl.server, false, XRGB8888)?;
+    let pos = win.tl.server.node_absolute_position();
+    let dmabuf = tl.bo.dmabuf();
+    tassert_eq!((dmabuf.width, dmabuf.height), (pos.width(), pos.height()));
+
+    let region = Rect::new_sized(10, 20, 30, 40);
+    let qoi = buf_region_to_bytes(tl.drm.as_ref(), dmabuf, region, ScreenshotFormat::Qoi)?;
+    tassert_eq!(&qoi[4..12], &[0, 0, 0, 30, 0, 0, 0, 40]);
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::{
+        portal::{ColorScheme, Contrast},
+        theme::Color,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let settings = &run.state.portal_settings;
+    tassert_eq!(settings.color_scheme.get(), ColorScheme::DEFAULT);
+    tassert_eq!(settings.accent_color.get(), None);
+
+    run.cfg.set_color_scheme(ColorScheme::PREFER_DARK)?;
+    run.cfg.set_accent_color(Some(Color::new(255, 0, 255)))?;
+    run.cfg.set_contrast(Contrast::HIGH)?;
+    run.cfg.set_reduced_motion(true)?;
+    run.sync().await;
+    tassert_eq!(settings.color_scheme.get(), ColorScheme::PREFER_DARK);
+    tassert_eq!(settings.accent_color.get(), Some([1.0, 0.0, 1.0]));
+    tassert_eq!(settings.contrast.get(), Contrast::HIGH);
+    tassert!(settings.reduced_motion.get());
+
+    run.cfg.set_color_scheme(ColorScheme(3))?;
+    run.cfg.set_accent_color(None)?;
+    run.sync().await;
+    tassert_eq!(settings.color_scheme.get(), ColorScheme::PREFER_DARK);
+    tassert_eq!(settings.accent_color.get(), None);
+
+    Ok(())
+}
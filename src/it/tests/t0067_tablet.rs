@@ -0,0 +1,51 @@
+use {
+    crate::{
+        fixed::Fixed,
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let tablet = run.backend.add_tablet();
+    run.sync().await;
+    run.cfg
+        .set_input_device_seat(tablet.common.id, ds.seat.id())?;
+    let pen = tablet.add_pen();
+    run.sync().await;
+
+    tablet.tool(pen, 0.5, 0.5, 0.5);
+    run.sync().await;
+    let (pos, pressure) = ds.seat.tablet_tool_state(pen).unwrap();
+    tassert_eq!(pos, (Fixed::from_int(400), Fixed::from_int(300)));
+    tassert_eq!(pressure, 0.5);
+
+    run.cfg
+        .set_tablet_output_area(tablet.common.id, 0.5, 0.0, 0.5, 1.0)?;
+    tablet.tool(pen, 0.5, 0.5, 0.5);
+    run.sync().await;
+    let (pos, _) = ds.seat.tablet_tool_state(pen).unwrap();
+    tassert_eq!(pos, (Fixed::from_int(600), Fixed::from_int(300)));
+
+    run.cfg
+        .set_tablet_pressure_curve(tablet.common.id, [0.0, 1.0, 0.0, 1.0])?;
+    tablet.tool(pen, 0.5, 0.5, 0.5);
+    run.sync().await;
+    let (_, pressure) = ds.seat.tablet_tool_state(pen).unwrap();
+    // With these control points x(t) = t^3 and y(t) = 1 - (1 - t)^3.
+    let expected = 1.0 - (1.0 - 0.5f64.cbrt()).powi(3);
+    tassert_eq!((pressure * 1e6).round(), (expected * 1e6).round());
+
+    run.cfg
+        .set_tablet_pressure_curve(tablet.common.id, [0.0, 0.0, 1.0, 1.0])?;
+    tablet.tool(pen, 0.5, 0.5, 0.5);
+    run.sync().await;
+    let (_, pressure) = ds.seat.tablet_tool_state(pen).unwrap();
+    tassert_eq!(pressure, 0.5);
+
+    Ok(())
+}
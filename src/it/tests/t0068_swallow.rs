@@ -0,0 +1,82 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    jay_config::_private::{WindowCriterionIpc, WindowCriterionStringField},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let title = |title: &str| WindowCriterionIpc::String {
+        string: title.to_string(),
+        field: WindowCriterionStringField::Title,
+        regex: false,
+    };
+    let swallow = run.cfg.create_window_matcher(title("parent"))?;
+    run.cfg.set_window_matcher_swallow(swallow, true)?;
+    let excluded = run.cfg.create_window_matcher(title("excluded"))?;
+    run.cfg.set_window_matcher_swallowable(excluded, false)?;
+
+    let client = run.create_client().await?;
+    let parent = client.create_window().await?;
+    parent.tl.core.set_title("parent")?;
+    parent.map2().await?;
+    let sibling = client.create_window().await?;
+    sibling.map2().await?;
+    run.sync().await;
+    client.sync().await;
+
+    let parent_pos = parent.tl.server.node_absolute_position();
+    let sibling_pos = sibling.tl.server.node_absolute_position();
+    tassert!(parent_pos.x2() <= sibling_pos.x1());
+
+    let (child_client, child_proc) = run.create_child_client().await?;
+    tassert_eq!(
+        child_client.server.pid_info.pid as u32,
+        child_proc.pid as u32
+    );
+
+    let excluded = child_client.create_window().await?;
+    excluded.tl.core.set_title("excluded")?;
+    excluded.map2().await?;
+    run.sync().await;
+    tassert!(parent.tl.server.tl_data().parent.is_some());
+    tassert!(parent.tl.server.tl_data().swallowed_by.is_none());
+    excluded.tl.core.destroy()?;
+    child_client.sync().await;
+    run.sync().await;
+    tassert_eq!(parent.tl.server.node_absolute_position(), parent_pos);
+
+    let child = child_client.create_window().await?;
+    child.map2().await?;
+    run.sync().await;
+    client.sync().await;
+    let parent_data = parent.tl.server.tl_data();
+    tassert!(parent_data.parent.is_none());
+    tassert!(!parent_data.visible.get());
+    tassert!(parent_data.swallowed_by.is_some());
+    tassert_eq!(child.tl.server.node_absolute_position(), parent_pos);
+    tassert_eq!(sibling.tl.server.node_absolute_position(), sibling_pos);
+
+    child.tl.core.destroy()?;
+    child_client.sync().await;
+    run.sync().await;
+    client.sync().await;
+    tassert!(parent_data.parent.is_some());
+    tassert!(parent_data.visible.get());
+    tassert!(parent_data.swallowed_by.is_none());
+    tassert_eq!(parent.tl.server.node_absolute_position(), parent_pos);
+    tassert_eq!(sibling.tl.server.node_absolute_position(), sibling_pos);
+    let focus = ds.seat.get_keyboard_node().node_toplevel();
+    tassert_eq!(
+        focus.map(|tl| tl.node_id()),
+        Some(parent.tl.server.node_id())
+    );
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::syms::SYM_a,
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_A: u32 = 30;
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    client.sync().await;
+
+    let key = seat.kb.key.expect()?;
+    let gs = client.jc.get_global_shortcuts().await?;
+    let activated = gs.activated.expect()?;
+    let deactivated = gs.deactivated.expect()?;
+    let captured = gs.trigger_captured.expect()?;
+
+    gs.bind(1, 0, SYM_a.0)?;
+    client.sync().await;
+    let pressed = ds.kb.press(KEY_A);
+    run.sync().await;
+    client.sync().await;
+    tassert_eq!(activated.next()?.id, 1);
+    tassert!(deactivated.next().is_err());
+    tassert!(key.next().is_err());
+    drop(pressed);
+    run.sync().await;
+    client.sync().await;
+    tassert_eq!(deactivated.next()?.id, 1);
+    tassert!(key.next().is_err());
+
+    gs.unbind(1)?;
+    client.sync().await;
+    let pressed = ds.kb.press(KEY_A);
+    run.sync().await;
+    client.sync().await;
+    tassert!(activated.next().is_err());
+    tassert_eq!(key.next()?.1.state, 1);
+    drop(pressed);
+    run.sync().await;
+    client.sync().await;
+    tassert_eq!(key.next()?.1.state, 0);
+
+    gs.capture_trigger()?;
+    client.sync().await;
+    let pressed = ds.kb.press(KEY_A);
+    run.sync().await;
+    client.sync().await;
+    let ev = captured.next()?;
+    tassert_eq!((ev.mods, ev.keysym), (0, SYM_a.0));
+    drop(pressed);
+    run.sync().await;
+    client.sync().await;
+    tassert!(key.next().is_err());
+    tassert!(deactivated.next().is_err());
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use {
+    crate::{
+        fixed::Fixed,
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+const KEY_A: u32 = 30;
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    client.sync().await;
+
+    let key = seat.kb.key.expect()?;
+    let ic = client.jc.create_input_capture().await?;
+    let activated = ic.activated.expect()?;
+    let deactivated = ic.deactivated.expect()?;
+    let disabled = ic.disabled.expect()?;
+
+    let pos = ds.output.global.pos.get();
+    tassert_eq!(&*ic.zones.borrow(), &[pos]);
+    let (cx, cy) = pos.center();
+    ic.add_barrier(1, pos.x2(), pos.y1(), pos.x2(), pos.y2())?;
+    client.sync().await;
+
+    ds.move_to(cx, cy);
+    ds.mouse.rel(pos.width() as f64, 0.0);
+    run.sync().await;
+    client.sync().await;
+    tassert!(activated.next().is_err());
+    tassert!(!ds.seat.input_captured());
+
+    ic.enable()?;
+    client.sync().await;
+    ds.move_to(cx, cy);
+    ds.mouse.rel(pos.width() as f64, 0.0);
+    run.sync().await;
+    client.sync().await;
+    let ev = activated.next()?;
+    tassert_eq!(ev.barrier_id, 1);
+    tassert_eq!(ev.x, Fixed::from_int(cx));
+    tassert!(ds.seat.input_captured());
+
+    ds.mouse.rel(-100.0, 0.0);
+    run.sync().await;
+    tassert_eq!(ds.seat.pointer_cursor().position().0, Fixed::from_int(cx));
+    let pressed = ds.kb.press(KEY_A);
+    run.sync().await;
+    client.sync().await;
+    tassert!(key.next().is_err());
+    drop(pressed);
+
+    run.cfg.release_input_capture(ds.seat.id())?;
+    run.sync().await;
+    client.sync().await;
+    tassert_eq!(deactivated.next()?.activation_id, ev.activation_id);
+    tassert!(!ds.seat.input_captured());
+    let pressed = ds.kb.press(KEY_A);
+    run.sync().await;
+    client.sync().await;
+    tassert_eq!(key.next()?.1.state, 1);
+    drop(pressed);
+    run.sync().await;
+    client.sync().await;
+    while key.next().is_ok() {}
+
+    ds.move_to(cx, cy);
+    ds.mouse
+        .abs(&ds.connector, pos.width() as f64, (cy - pos.y1()) as f64);
+    run.sync().await;
+    client.sync().await;
+    let ev = activated.next()?;
+    tassert_eq!(ev.barrier_id, 1);
+    tassert!(ds.seat.input_captured());
+
+    let warp = (Fixed::from_int(cx), Fixed::from_int(cy + 10));
+    ic.release(ev.activation_id, Some(warp))?;
+    client.sync().await;
+    run.sync().await;
+    tassert_eq!(deactivated.next()?.activation_id, ev.activation_id);
+    tassert!(!ds.seat.input_captured());
+    tassert_eq!(ds.seat.pointer_cursor().position(), warp);
+
+    ic.disable()?;
+    client.sync().await;
+    tassert!(disabled.next().is_ok());
+    ds.mouse.rel(pos.width() as f64, 0.0);
+    run.sync().await;
+    client.sync().await;
+    tassert!(activated.next().is_err());
+
+    Ok(())
+}
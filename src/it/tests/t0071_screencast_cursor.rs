@@ -0,0 +1,143 @@
+use {
+    crate::{
+        ifs::jay_screencast::CURSOR_MODE_METADATA,
+        it::{
+            test_client::TestClient,
+            test_error::TestResult,
+            test_utils::{
+                test_container_node_ext::TestContainerExt, test_expected_event::TestExpectedEvent,
+                test_ouput_node_ext::TestOutputNodeExt,
+                test_toplevel_node_ext::TestToplevelNodeExt,
+                test_workspace_node_ext::TestWorkspaceNodeExt,
+            },
+            testrun::TestRun,
+        },
+        theme::Color,
+        wire::jay_screencast::CursorImage,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    ds.seat.cursor_group().set_hardware_cursor(true);
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let enter = seat.pointer.enter.expect()?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let red = client.spbm.create_buffer(Color::from_srgb(255, 0, 0))?;
+    let green = client.spbm.create_buffer(Color::from_srgb(0, 255, 0))?;
+    let surface = client.comp.create_surface().await?;
+    let vp = client.viewporter.get_viewport(&surface)?;
+    vp.set_destination(100, 100)?;
+    surface.attach(red.id)?;
+    surface.commit()?;
+
+    let (x, y) = ds
+        .output
+        .workspace()?
+        .container()?
+        .first_toplevel()?
+        .center();
+    ds.move_to(x, y);
+    client.sync().await;
+    let enter = enter.next()?;
+    seat.pointer
+        .set_cursor(enter.serial, Some(&surface), 0, 0)?;
+    client.sync().await;
+
+    let output = client.registry.bind_output(ds.output.global.name)?;
+    let jo = client.jc.get_output(&output).await?;
+    let sc = client.jc.create_screencast().await?;
+    sc.set_output(&jo)?;
+    sc.set_allow_all_workspaces(true)?;
+    sc.set_cursor_mode(CURSOR_MODE_METADATA)?;
+    sc.set_running(true)?;
+    sc.configure()?;
+    client.sync().await;
+    sc.ack_config()?;
+    let Some(ctx) = run.state.render_ctx.get() else {
+        bail!("There is no render context");
+    };
+    sc.server.get().unwrap().allocate_buffers(&ctx)?;
+    client.sync().await;
+    sc.ack_buffers()?;
+    client.sync().await;
+    tassert_eq!(sc.buffers.borrow().len(), 3);
+
+    let pos = ds.output.global.pos.get();
+    let (cx, cy) = (x - pos.x1(), y - pos.y1());
+
+    let ready = sc.ready.expect()?;
+    let position = sc.cursor_position.expect()?;
+    let image = sc.cursor_image.expect()?;
+
+    // The first frame sends the position and the image of the cursor.
+    ds.output.perform_test_screencopies()?;
+    let image1 = wait_for_image(&run, &client, &image).await?;
+    let idx = ready.next()?;
+    let ev = position.next()?;
+    tassert_eq!((ev.visible, ev.x, ev.y), (1, cx, cy));
+    tassert_eq!((image1.width, image1.height), (100, 100));
+    tassert_eq!((image1.hotspot_x, image1.hotspot_y), (0, 0));
+    tassert_eq!(first_pixel(&image1)?, [0, 0, 255, 255]);
+    sc.release_buffer(idx)?;
+
+    // Nothing changed, so no cursor events are sent.
+    ds.output.perform_test_screencopies()?;
+    client.sync().await;
+    let idx = ready.next()?;
+    position.none()?;
+    image.none()?;
+    sc.release_buffer(idx)?;
+
+    // Attaching a new buffer to the same cursor surface re-sends the image.
+    surface.attach(green.id)?;
+    surface.commit()?;
+    client.sync().await;
+    ds.output.perform_test_screencopies()?;
+    let image2 = wait_for_image(&run, &client, &image).await?;
+    let idx = ready.next()?;
+    position.none()?;
+    tassert_eq!(first_pixel(&image2)?, [0, 255, 0, 255]);
+    sc.release_buffer(idx)?;
+
+    // Moving the cursor only sends the position.
+    ds.move_to(x + 10, y + 5);
+    client.sync().await;
+    ds.output.perform_test_screencopies()?;
+    client.sync().await;
+    ready.next()?;
+    let ev = position.next()?;
+    tassert_eq!((ev.visible, ev.x, ev.y), (1, cx + 10, cy + 5));
+    image.none()?;
+
+    Ok(())
+}
+
+/// Waits for the asynchronous download of the cursor image to complete.
+async fn wait_for_image(
+    run: &TestRun,
+    client: &TestClient,
+    image: &TestExpectedEvent<CursorImage>,
+) -> TestResult<CursorImage> {
+    for _ in 0..100 {
+        client.sync().await;
+        if let Ok(image) = image.next() {
+            return Ok(image);
+        }
+        run.state.wheel.timeout(1).await?;
+    }
+    bail!("Compositor did not send a cursor image");
+}
+
+fn first_pixel(image: &CursorImage) -> TestResult<[u8; 4]> {
+    let mut pixel = [0; 4];
+    uapi::pread(image.fd.raw(), &mut pixel[..], 0)?;
+    Ok(pixel)
+}
@@ -0,0 +1,93 @@
+use {
+    crate::{
+        cli::screenshot::buf_pixel,
+        format::XRGB8888,
+        ifs::jay_screencast::CURSOR_MODE_HIDDEN,
+        it::{
+            test_error::TestResult, test_utils::test_ouput_node_ext::TestOutputNodeExt,
+            testrun::TestRun,
+        },
+        rect::Rect,
+        screenshoter::take_output_screenshot,
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.set_color(255, 0, 0, 255);
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.set_color(0, 0, 255, 255);
+    win2.map2().await?;
+    client.sync().await;
+
+    let pos = ds.output.global.pos.get();
+    ds.move_to(pos.x2() - 1, pos.y2() - 1);
+    client.sync().await;
+
+    let left = win1.tl.server.node_absolute_position();
+    let right = win2.tl.server.node_absolute_position();
+    tassert!(left.x2() <= right.x1());
+    let region = Rect::new_sized_unchecked(
+        left.x2() - 50 - pos.x1(),
+        left.y1() + 20 - pos.y1(),
+        right.x1() - left.x2() + 100,
+        60,
+    );
+
+    let output = client.registry.bind_output(ds.output.global.name)?;
+    let jo = client.jc.get_output(&output).await?;
+    let sc = client.jc.create_screencast().await?;
+    sc.set_region(&jo, region)?;
+    sc.set_allow_all_workspaces(true)?;
+    sc.set_cursor_mode(CURSOR_MODE_HIDDEN)?;
+    sc.set_running(true)?;
+    sc.configure()?;
+    client.sync().await;
+    tassert_eq!(sc.config_size.get(), (region.width(), region.height()));
+    sc.ack_config()?;
+    let Some(ctx) = run.state.render_ctx.get() else {
+        bail!("There is no render context");
+    };
+    sc.server.get().unwrap().allocate_buffers(&ctx)?;
+    client.sync().await;
+    sc.ack_buffers()?;
+    client.sync().await;
+
+    let ready = sc.ready.expect()?;
+    ds.output.perform_test_screencopies()?;
+    client.sync().await;
+    let idx = ready.next()?;
+    let buffer = &sc.buffers.borrow()[idx as usize];
+    tassert_eq!(
+        (buffer.width, buffer.height),
+        (region.width(), region.height())
+    );
+
+    let screenshot = take_output_screenshot(&run.state, &ds.output, false, XRGB8888)?;
+    let drm = screenshot.drm.as_ref();
+    for (x, y, color) in [
+        (10, 10, [255, 0, 0]),
+        (region.width() - 10, 10, [0, 0, 255]),
+        (10, region.height() - 10, [255, 0, 0]),
+    ] {
+        let pixel = buf_pixel(drm, buffer, x, y)?;
+        tassert_eq!(pixel, color);
+        let expected = buf_pixel(
+            drm,
+            screenshot.bo.dmabuf(),
+            region.x1() + x,
+            region.y1() + y,
+        )?;
+        tassert_eq!(pixel, expected);
+    }
+
+    Ok(())
+}
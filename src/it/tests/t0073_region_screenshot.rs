@@ -0,0 +1,87 @@
+use {
+    crate::{
+        cli::screenshot::buf_pixel,
+        ifs::wl_seat::{BTN_LEFT, BTN_RIGHT},
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win1 = client.create_window().await?;
+    win1.set_color(255, 0, 0, 255);
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.set_color(0, 0, 255, 255);
+    win2.map2().await?;
+    client.sync().await;
+
+    let left = win1.tl.server.node_absolute_position();
+    let right = win2.tl.server.node_absolute_position();
+    tassert!(left.x2() <= right.x1());
+
+    // The screenshot only contains the region.
+    let rect = Rect::new_sized_unchecked(
+        left.x2() - 20,
+        left.y1() + 10,
+        right.x1() - left.x2() + 40,
+        30,
+    );
+    let (buf, drm) = client.jc.take_region_screenshot(rect, false).await?;
+    tassert_eq!((buf.width, buf.height), (rect.width(), rect.height()));
+    tassert_eq!(buf_pixel(drm.as_ref(), &buf, 5, 5)?, [255, 0, 0]);
+    tassert_eq!(
+        buf_pixel(drm.as_ref(), &buf, rect.width() - 5, rect.height() - 5)?,
+        [0, 0, 255]
+    );
+
+    // Regions are clipped to the compositor extents.
+    let extents = run.state.root.extents.get();
+    let rect = Rect::new_sized_unchecked(extents.x2() - 10, extents.y2() - 20, 50, 50);
+    let (buf, _) = client.jc.take_region_screenshot(rect, false).await?;
+    tassert_eq!((buf.width, buf.height), (10, 20));
+
+    // Regions outside of all outputs fail.
+    let rect = Rect::new_sized_unchecked(extents.x2() + 10, extents.y1(), 10, 10);
+    tassert!(client.jc.take_region_screenshot(rect, false).await.is_err());
+
+    // A region can be selected by dragging.
+    let sr = client.jc.select_region(&seat.seat).await?;
+    let selected = sr.selected.expect()?;
+    let cancelled = sr.cancelled.expect()?;
+    let (x, y) = ds.output.global.pos.get().position();
+    ds.move_to(x + 100, y + 80);
+    let click = ds.mouse.click(BTN_LEFT);
+    ds.move_to(x + 40, y + 120);
+    drop(click);
+    client.sync().await;
+    tassert_eq!(
+        selected.next()?,
+        Rect::new_unchecked(x + 40, y + 80, x + 100, y + 120)
+    );
+    cancelled.none()?;
+
+    // Clicking without dragging does not select an empty region and right-clicking
+    // cancels the selection.
+    let sr = client.jc.select_region(&seat.seat).await?;
+    let selected = sr.selected.expect()?;
+    let cancelled = sr.cancelled.expect()?;
+    ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    selected.none()?;
+    cancelled.none()?;
+    ds.mouse.click(BTN_RIGHT);
+    client.sync().await;
+    cancelled.next()?;
+    selected.none()?;
+
+    Ok(())
+}
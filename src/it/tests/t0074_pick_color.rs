@@ -0,0 +1,138 @@
+use {
+    crate::{
+        ifs::wl_seat::{BTN_LEFT, BTN_RIGHT},
+        it::{
+            test_client::TestClient,
+            test_error::TestResult,
+            test_ifs::test_pick_color::{TestJayPickColor, TestPickedColor},
+            test_utils::test_expected_event::TestExpectedEvent,
+            testrun::TestRun,
+        },
+        rect::Rect,
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win1 = client.create_window().await?;
+    win1.set_color(255, 0, 0, 255);
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.set_color(0, 0, 255, 255);
+    win2.map2().await?;
+    client.sync().await;
+
+    let left = win1.tl.server.node_absolute_position();
+    let right = win2.tl.server.node_absolute_position();
+    let output = ds.output.global.connector.name.to_string();
+
+    // Sampling a position reports the color of the pixel at that position.
+    let (x, y) = (left.x1() + left.width() / 2, left.y1() + left.height() / 2);
+    let pc = client.jc.sample_color(x, y)?;
+    let color = wait_for_color(&run, &client, &pc).await?;
+    tassert_eq!((color.x, color.y), (x, y));
+    tassert_eq!(color.output, output);
+    tassert_eq!(color.srgb, [255, 0, 0]);
+    tassert_eq!(color.native, [255, 0, 0]);
+
+    // Positions outside of all outputs fail.
+    let extents = run.state.root.extents.get();
+    let pc = client.jc.sample_color(extents.x2() + 10, extents.y1())?;
+    let failed = pc.failed.expect()?;
+    client.sync().await;
+    failed.next()?;
+
+    // Picking shows a lens around the pointer and damages its old and new position
+    // when the pointer moves.
+    let (x, y) = (
+        right.x1() + right.width() / 2,
+        right.y1() + right.height() / 2,
+    );
+    ds.move_to(x - 10, y - 10);
+    let pc = client.jc.pick_color(&seat.seat)?;
+    let cancelled = pc.cancelled.expect()?;
+    let color = pc.color.expect()?;
+    let failed = pc.failed.expect()?;
+    client.sync().await;
+    let old_lens = lens(x - 10, y - 10);
+    tassert_eq!(ds.seat.color_picker_lens(), Some(old_lens));
+    tassert!(ds.output.has_color_picker_lens());
+    let damage = &ds.output.global.connector.damage;
+    damage.borrow_mut().clear();
+    ds.move_to(x, y);
+    client.sync().await;
+    let new_lens = lens(x, y);
+    tassert_eq!(ds.seat.color_picker_lens(), Some(new_lens));
+    let pos = ds.output.global.pos.get();
+    let damaged = |lens: Rect| {
+        let lens = lens.move_(-pos.x1(), -pos.y1());
+        damage.borrow().iter().any(|d| d.contains_rect(&lens))
+    };
+    tassert!(damaged(old_lens));
+    tassert!(damaged(new_lens));
+
+    // Clicking picks the color under the pointer and removes the lens.
+    damage.borrow_mut().clear();
+    ds.mouse.click(BTN_LEFT);
+    let color = wait_for(&run, &client, &color, &failed).await?;
+    tassert_eq!((color.x, color.y), (x, y));
+    tassert_eq!(color.srgb, [0, 0, 255]);
+    cancelled.none()?;
+    tassert_eq!(ds.seat.color_picker_lens(), None);
+    tassert!(!ds.output.has_color_picker_lens());
+    tassert!(damaged(new_lens));
+
+    // Right-clicking cancels the picker.
+    let pc = client.jc.pick_color(&seat.seat)?;
+    let cancelled = pc.cancelled.expect()?;
+    let color = pc.color.expect()?;
+    client.sync().await;
+    tassert!(ds.seat.color_picker_lens().is_some());
+    ds.mouse.click(BTN_RIGHT);
+    client.sync().await;
+    cancelled.next()?;
+    color.none()?;
+    tassert_eq!(ds.seat.color_picker_lens(), None);
+
+    Ok(())
+}
+
+fn lens(x: i32, y: i32) -> Rect {
+    Rect::new_sized_unchecked(x - 80, y - 80, 160, 160)
+}
+
+async fn wait_for_color(
+    run: &TestRun,
+    client: &TestClient,
+    pc: &TestJayPickColor,
+) -> TestResult<TestPickedColor> {
+    let color = pc.color.expect()?;
+    let failed = pc.failed.expect()?;
+    wait_for(run, client, &color, &failed).await
+}
+
+async fn wait_for(
+    run: &TestRun,
+    client: &TestClient,
+    color: &TestExpectedEvent<TestPickedColor>,
+    failed: &TestExpectedEvent<String>,
+) -> TestResult<TestPickedColor> {
+    for _ in 0..100 {
+        client.sync().await;
+        if let Ok(color) = color.next() {
+            return Ok(color);
+        }
+        if let Ok(msg) = failed.next() {
+            bail!("Could not pick the color: {}", msg);
+        }
+        run.state.wheel.timeout(1).await?;
+    }
+    bail!("Compositor did not send a color");
+}
@@ -102,8 +102,14 @@ bitflags! {
     SUPPORTED_META_HEADER = 1 << 0,
     SUPPORTED_META_BUSY = 1 << 1,
     SUPPORTED_META_VIDEO_CROP = 1 << 2,
+    SUPPORTED_META_CURSOR = 1 << 3,
 }
 
+pub const CURSOR_META_MAX_SIZE: i32 = 256;
+const CURSOR_META_SIZE: usize = size_of::<spa_meta_cursor>()
+    + size_of::<spa_meta_bitmap>()
+    + (CURSOR_META_MAX_SIZE * CURSOR_META_MAX_SIZE * 4) as usize;
+
 pub struct PwClientNodePort {
     pub node: Rc<PwClientNode>,
 
@@ -137,10 +143,17 @@ pub struct PwClientNodeBuffer {
     pub _meta_header: Option<Rc<PwMemTyped<spa_meta_header>>>,
     pub _meta_busy: Option<Rc<PwMemTyped<spa_meta_busy>>>,
     pub meta_video_crop: Option<Rc<PwMemTyped<spa_meta_region>>>,
+    pub meta_cursor: Option<PwClientNodeCursorMeta>,
     pub chunks: Vec<Rc<PwMemTyped<spa_chunk>>>,
     pub _slices: Vec<Rc<PwMemSlice>>,
 }
 
+pub struct PwClientNodeCursorMeta {
+    pub cursor: Rc<PwMemTyped<spa_meta_cursor>>,
+    pub bitmap: Option<Rc<PwMemTyped<spa_meta_bitmap>>>,
+    pub pixels: Option<Rc<PwMemSlice>>,
+}
+
 #[derive(Clone, Debug)]
 pub struct PwClientNodePortSupportedFormat {
     pub format: &'static Format,
@@ -333,6 +346,9 @@ impl PwClientNode {
                 if sm.contains(SUPPORTED_META_VIDEO_CROP) {
                     metas.push((SPA_META_VideoCrop, size_of::<spa_meta_region>()));
                 }
+                if sm.contains(SUPPORTED_META_CURSOR) {
+                    metas.push((SPA_META_Cursor, CURSOR_META_SIZE));
+                }
                 let sf = &*port.supported_formats.borrow();
                 let num_formats = sf.formats.len() as u32;
                 let bc = &*port.buffer_config.borrow();
@@ -607,6 +623,7 @@ impl PwClientNode {
             let mut meta_header = Default::default();
             let mut meta_video_crop = Default::default();
             let mut meta_busy = Default::default();
+            let mut meta_cursor = Default::default();
             let mut chunks = vec![];
             let mut slices = vec![];
 
@@ -637,7 +654,20 @@ impl PwClientNode {
                         let _bitmap = mem.typed_at::<spa_meta_bitmap>(offset);
                     }
                     SPA_META_Cursor => {
-                        let _cursor = mem.typed_at::<spa_meta_cursor>(offset);
+                        let cursor = mem.typed_at::<spa_meta_cursor>(offset);
+                        let mut bitmap = None;
+                        let mut pixels = None;
+                        let bitmap_offset = offset + size_of::<spa_meta_cursor>();
+                        let pixels_offset = bitmap_offset + size_of::<spa_meta_bitmap>();
+                        if size > pixels_offset - offset {
+                            bitmap = Some(mem.typed_at::<spa_meta_bitmap>(bitmap_offset));
+                            pixels = Some(mem.slice(pixels_offset..offset + size));
+                        }
+                        meta_cursor = Some(PwClientNodeCursorMeta {
+                            cursor,
+                            bitmap,
+                            pixels,
+                        });
                     }
                     SPA_META_Control => {}
                     SPA_META_Busy => {
@@ -679,6 +709,7 @@ impl PwClientNode {
                 _meta_header: meta_header,
                 _meta_busy: meta_busy,
                 meta_video_crop,
+                meta_cursor,
                 chunks,
                 _slices: slices,
             }));
@@ -41,7 +41,6 @@ pub struct PwMemTyped<T> {
     _phantom: PhantomData<T>,
 }
 
-#[expect(dead_code)]
 pub struct PwMemSlice {
     mem: Rc<PwMemMap>,
     range: Range<usize>,
@@ -136,6 +135,17 @@ impl PwMemMap {
     }
 }
 
+impl PwMemSlice {
+    pub unsafe fn bytes_mut(&self) -> &mut [u8] {
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.mem.map.ptr.cast::<u8>().add(self.range.start) as _,
+                self.range.len(),
+            )
+        }
+    }
+}
+
 impl<T: Pod> PwMemTyped<T> {
     pub unsafe fn read(&self) -> &T {
         unsafe { (self.mem.map.ptr.cast::<u8>().add(self.offset) as *const T).deref() }
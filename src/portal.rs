@@ -1,8 +1,12 @@
 mod ptl_display;
+mod ptl_global_shortcuts;
+mod ptl_input_capture;
 mod ptl_remote_desktop;
 mod ptl_render_ctx;
 mod ptl_screencast;
+mod ptl_screenshot;
 mod ptl_session;
+mod ptl_settings;
 mod ptl_text;
 mod ptr_gui;
 
@@ -21,10 +25,14 @@ use {
         pipewire::pw_con::{PwCon, PwConHolder, PwConOwner},
         portal::{
             ptl_display::{PortalDisplay, PortalDisplayId, watch_displays},
+            ptl_global_shortcuts::add_global_shortcuts_dbus_members,
+            ptl_input_capture::add_input_capture_dbus_members,
             ptl_remote_desktop::add_remote_desktop_dbus_members,
             ptl_render_ctx::PortalRenderCtx,
             ptl_screencast::add_screencast_dbus_members,
+            ptl_screenshot::{ScreenshotRequest, ScreenshotRequestId, add_screenshot_dbus_members},
             ptl_session::PortalSession,
+            ptl_settings::add_settings_dbus_members,
         },
         utils::{
             clone3::{Forked, fork_with_pidfd},
@@ -41,9 +49,11 @@ use {
         video::dmabuf::DmaBufIds,
         wheel::Wheel,
         wire_dbus::org,
+        wl_usr::usr_ifs::usr_jay_portal_settings::PortalSettingsValues,
     },
     log::Level,
     std::{
+        cell::Cell,
         ffi::OsStr,
         io::{BufReader, BufWriter},
         os::unix::{ffi::OsStrExt, process::CommandExt},
@@ -56,7 +66,6 @@ use {
 };
 
 const PORTAL_SUCCESS: u32 = 0;
-#[expect(dead_code)]
 const PORTAL_CANCELLED: u32 = 1;
 #[expect(dead_code)]
 const PORTAL_ENDED: u32 = 2;
@@ -249,6 +258,8 @@ async fn run_async(
         displays: Default::default(),
         dbus,
         sessions: Default::default(),
+        screenshots: Default::default(),
+        settings: Default::default(),
         next_id: NumCell::new(1),
         render_ctxs: Default::default(),
         dma_buf_ids: Default::default(),
@@ -267,6 +278,10 @@ async fn run_async(
             add_screencast_dbus_members(&state, &pw_con.con, &obj);
         }
         add_remote_desktop_dbus_members(&state, &obj);
+        add_screenshot_dbus_members(&state, &obj);
+        add_settings_dbus_members(&state, &obj);
+        add_global_shortcuts_dbus_members(&state, &obj);
+        add_input_capture_dbus_members(&state, &obj);
         obj
     };
     watch_displays(state.clone()).await;
@@ -328,6 +343,8 @@ struct PortalState {
     displays: CopyHashMap<PortalDisplayId, Rc<PortalDisplay>>,
     dbus: Rc<DbusSocket>,
     sessions: CopyHashMap<String, Rc<PortalSession>>,
+    screenshots: CopyHashMap<ScreenshotRequestId, Rc<ScreenshotRequest>>,
+    settings: Cell<PortalSettingsValues>,
     next_id: NumCell<u32>,
     render_ctxs: CopyHashMap<c::dev_t, Weak<PortalRenderCtx>>,
     dma_buf_ids: Rc<DmaBufIds>,
@@ -2,7 +2,7 @@ use {
     crate::{
         gfx_api::{GfxFormat, cross_intersect_formats},
         gfx_apis::create_gfx_context,
-        ifs::wl_seat::POINTER,
+        ifs::{jay_compositor::GET_PORTAL_SETTINGS_SINCE, wl_seat::POINTER},
         object::Version,
         portal::{
             PortalState,
@@ -30,6 +30,7 @@ use {
                 usr_jay_compositor::UsrJayCompositor,
                 usr_jay_output::{UsrJayOutput, UsrJayOutputOwner},
                 usr_jay_pointer::UsrJayPointer,
+                usr_jay_portal_settings::UsrJayPortalSettings,
                 usr_jay_render_ctx::UsrJayRenderCtxOwner,
                 usr_jay_workspace::{UsrJayWorkspace, UsrJayWorkspaceOwner},
                 usr_jay_workspace_watcher::{UsrJayWorkspaceWatcher, UsrJayWorkspaceWatcherOwner},
@@ -72,6 +73,7 @@ pub struct PortalDisplay {
     pub(super) state: Rc<PortalState>,
     registry: Rc<UsrWlRegistry>,
     _workspace_watcher: Rc<UsrJayWorkspaceWatcher>,
+    portal_settings: Option<Rc<UsrJayPortalSettings>>,
     pub dmabuf: CloneCell<Option<Rc<UsrLinuxDmabuf>>>,
 
     pub jc: Rc<UsrJayCompositor>,
@@ -231,6 +233,16 @@ impl UsrConOwner for PortalDisplay {
         for sc in self.sessions.lock().drain_values() {
             sc.kill();
         }
+        for ss in self
+            .state
+            .screenshots
+            .lock()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>()
+        {
+            ss.display_removed(self);
+        }
         self.windows.clear();
         self.state.displays.remove(&self.id);
     }
@@ -350,7 +362,7 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
                     con: dpy.con.clone(),
                     owner: Default::default(),
                     caps: Default::default(),
-                    version: Version(version.min(12)),
+                    version: Version(version.min(28)),
                 });
                 dpy.con.add_object(jc.clone());
                 dpy.registry.request_bind(name, jc.version.0, jc.deref());
@@ -426,6 +438,7 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
     let fsm = get!(fsm_opt, WpFractionalScaleManagerV1);
     let vp = get!(vp_opt, WpViewporter);
     let ww = jc.watch_workspaces();
+    let ps = (jc.version >= GET_PORTAL_SETTINGS_SINCE).then(|| jc.get_portal_settings());
 
     let dpy = Rc::new(PortalDisplay {
         id: dpy.state.id(),
@@ -434,6 +447,7 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
         state: dpy.state.clone(),
         registry: dpy.registry.clone(),
         _workspace_watcher: ww.clone(),
+        portal_settings: ps,
         dmabuf: CloneCell::new(dmabuf_opt),
         jc,
         outputs: Default::default(),
@@ -452,6 +466,9 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
     dpy.con.owner.set(Some(dpy.clone()));
     dpy.registry.owner.set(Some(dpy.clone()));
     ww.owner.set(Some(dpy.clone()));
+    if let Some(ps) = &dpy.portal_settings {
+        ps.owner.set(Some(dpy.clone()));
+    }
 
     let jrc = dpy.jc.get_render_context();
     jrc.owner.set(Some(dpy.clone()));
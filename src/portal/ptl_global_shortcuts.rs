@@ -0,0 +1,531 @@
+mod shortcuts_gui;
+
+use {
+    crate::{
+        dbus::{
+            DbusObject, DictEntry, DynamicType, PendingReply,
+            prelude::{ObjectPath, Variant},
+        },
+        ifs::jay_compositor::GET_GLOBAL_SHORTCUTS_SINCE,
+        portal::{
+            PORTAL_CANCELLED, PORTAL_SUCCESS, PortalState,
+            ptl_display::PortalDisplayId,
+            ptl_global_shortcuts::shortcuts_gui::ShortcutsGui,
+            ptl_input_capture::InputCapturePhase,
+            ptl_remote_desktop::RemoteDesktopPhase,
+            ptl_screencast::{EMBEDDED, ScreencastPhase},
+            ptl_session::PortalSession,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            hash_map_ext::HashMapExt,
+        },
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::{
+                global_shortcuts::{
+                    Activated, BindShortcuts, BindShortcutsReply, CreateSession,
+                    CreateSessionReply, Deactivated, ListShortcuts, ListShortcutsReply,
+                },
+                session::CloseReply as SessionCloseReply,
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_global_shortcuts::{
+            UsrJayGlobalShortcuts, UsrJayGlobalShortcutsOwner,
+        },
+    },
+    jay_config::keyboard::mods::{ALT, CTRL, LOGO, SHIFT},
+    kbvm::Keysym,
+    std::{
+        borrow::Cow,
+        cell::Cell,
+        collections::BTreeMap,
+        fmt::{self, Display, Formatter},
+        fs,
+        ops::Deref,
+        path::PathBuf,
+        rc::Rc,
+    },
+};
+
+const MODS: [(u32, &str); 4] = [
+    (CTRL.0, "CTRL"),
+    (ALT.0, "ALT"),
+    (SHIFT.0, "SHIFT"),
+    (LOGO.0, "LOGO"),
+];
+
+#[derive(Clone)]
+pub enum GlobalShortcutsPhase {
+    Init,
+    Binding(Rc<BindingShortcuts>),
+    Bound(Rc<BoundShortcuts>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for GlobalShortcutsPhase {}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Trigger {
+    pub mods: u32,
+    pub keysym: u32,
+}
+
+pub struct GlobalShortcut {
+    pub id: String,
+    pub description: String,
+    pub trigger: Cell<Option<Trigger>>,
+}
+
+pub struct BindingShortcuts {
+    pub session: Rc<PortalSession>,
+    pub request_obj: DbusObject,
+    pub reply: Cell<Option<PendingReply<BindShortcutsReply<'static>>>>,
+    pub shortcuts: Rc<Vec<GlobalShortcut>>,
+    pub objs: CopyHashMap<PortalDisplayId, Rc<UsrJayGlobalShortcuts>>,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ShortcutsGui>>,
+}
+
+pub struct BoundShortcuts {
+    pub session: Rc<PortalSession>,
+    pub shortcuts: Rc<Vec<GlobalShortcut>>,
+    pub objs: CopyHashMap<PortalDisplayId, Rc<UsrJayGlobalShortcuts>>,
+}
+
+impl Trigger {
+    fn parse(s: &str) -> Option<Self> {
+        let mut mods = 0;
+        let mut keysym = None;
+        for part in s.split('+') {
+            if let Some((m, _)) = MODS.iter().find(|(_, n)| n.eq_ignore_ascii_case(part)) {
+                mods |= *m;
+                continue;
+            }
+            if keysym.is_some() {
+                return None;
+            }
+            let sym = Keysym::from_str(part).or_else(|| Keysym::from_str_insensitive(part))?;
+            keysym = Some(sym.to_lowercase().0);
+        }
+        Some(Self {
+            mods,
+            keysym: keysym?,
+        })
+    }
+}
+
+impl Display for Trigger {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (m, name) in MODS {
+            if self.mods & m != 0 {
+                write!(f, "{}+", name)?;
+            }
+        }
+        match Keysym(self.keysym).name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:#x}", self.keysym),
+        }
+    }
+}
+
+impl GlobalShortcut {
+    fn trigger_description(&self) -> String {
+        match self.trigger.get() {
+            Some(t) => t.to_string(),
+            None => "Unassigned".to_string(),
+        }
+    }
+}
+
+type StoredTriggers = BTreeMap<String, BTreeMap<String, String>>;
+
+fn store_path() -> Option<PathBuf> {
+    let mut path = dirs::data_local_dir()?;
+    path.push("jay");
+    path.push("global-shortcuts.json");
+    Some(path)
+}
+
+fn load_triggers() -> StoredTriggers {
+    let Some(path) = store_path() else {
+        return Default::default();
+    };
+    let Ok(data) = fs::read_to_string(&path) else {
+        return Default::default();
+    };
+    match serde_json::from_str(&data) {
+        Ok(t) => t,
+        Err(e) => {
+            log::error!("Could not parse {}: {}", path.display(), ErrorFmt(e));
+            Default::default()
+        }
+    }
+}
+
+fn store_triggers(app: &str, shortcuts: &[GlobalShortcut]) {
+    let Some(path) = store_path() else {
+        log::error!("Could not determine the data directory");
+        return;
+    };
+    let mut triggers = load_triggers();
+    let app_triggers = triggers.entry(app.to_string()).or_default();
+    for shortcut in shortcuts {
+        let trigger = match shortcut.trigger.get() {
+            Some(t) => t.to_string(),
+            None => String::new(),
+        };
+        app_triggers.insert(shortcut.id.clone(), trigger);
+    }
+    let res = (|| {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let data = serde_json::to_string_pretty(&triggers)?;
+        fs::write(&path, data)
+    })();
+    if let Err(e) = res {
+        log::error!("Could not write {}: {}", path.display(), ErrorFmt(e));
+    }
+}
+
+fn shortcuts_variant(shortcuts: &[GlobalShortcut]) -> Variant<'static> {
+    let dict_type = DynamicType::DictEntry(
+        Box::new(DynamicType::String),
+        Box::new(DynamicType::Variant),
+    );
+    let struct_type = DynamicType::Struct(vec![
+        DynamicType::String,
+        DynamicType::Array(Box::new(dict_type.clone())),
+    ]);
+    let shortcuts = shortcuts
+        .iter()
+        .map(|s| {
+            let props = vec![
+                Variant::DictEntry(
+                    Box::new(Variant::String(Cow::Borrowed("description"))),
+                    Box::new(Variant::Variant(Box::new(Variant::String(
+                        s.description.clone().into(),
+                    )))),
+                ),
+                Variant::DictEntry(
+                    Box::new(Variant::String(Cow::Borrowed("trigger_description"))),
+                    Box::new(Variant::Variant(Box::new(Variant::String(
+                        s.trigger_description().into(),
+                    )))),
+                ),
+            ];
+            Variant::Struct(vec![
+                Variant::String(s.id.clone().into()),
+                Variant::Array(dict_type.clone(), props),
+            ])
+        })
+        .collect();
+    Variant::Array(struct_type, shortcuts)
+}
+
+impl BindingShortcuts {
+    pub fn kill(&self) {
+        for gui in self.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        for obj in self.objs.lock().drain_values() {
+            obj.con.remove_obj(obj.deref());
+        }
+        if let Some(reply) = self.reply.take() {
+            reply.ok(&BindShortcutsReply {
+                response: PORTAL_CANCELLED,
+                results: Default::default(),
+            });
+        }
+    }
+
+    pub fn accept(&self) {
+        for gui in self.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        store_triggers(&self.session.app, &self.shortcuts);
+        let bound = Rc::new(BoundShortcuts {
+            session: self.session.clone(),
+            shortcuts: self.shortcuts.clone(),
+            objs: Default::default(),
+        });
+        for (dpy, obj) in self.objs.lock().drain() {
+            bound.bind(&obj);
+            obj.owner.set(Some(bound.clone()));
+            bound.objs.set(dpy, obj);
+        }
+        self.session
+            .gs_phase
+            .set(GlobalShortcutsPhase::Bound(bound.clone()));
+        if let Some(reply) = self.reply.take() {
+            bound.reply_ok(reply);
+        }
+    }
+}
+
+impl BoundShortcuts {
+    fn bind(&self, obj: &UsrJayGlobalShortcuts) {
+        for (idx, shortcut) in self.shortcuts.iter().enumerate() {
+            if let Some(t) = shortcut.trigger.get() {
+                obj.bind(idx as u32, t.mods, t.keysym);
+            }
+        }
+    }
+
+    fn reply_ok(&self, reply: PendingReply<BindShortcutsReply<'static>>) {
+        reply.ok(&BindShortcutsReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Owned(vec![DictEntry {
+                key: "shortcuts".into(),
+                value: shortcuts_variant(&self.shortcuts),
+            }]),
+        });
+    }
+
+    pub fn kill(&self) {
+        for obj in self.objs.lock().drain_values() {
+            obj.con.remove_obj(obj.deref());
+        }
+    }
+
+    fn shortcut_id(&self, id: u32) -> Option<&str> {
+        self.shortcuts.get(id as usize).map(|s| &*s.id)
+    }
+}
+
+impl UsrJayGlobalShortcutsOwner for BoundShortcuts {
+    fn activated(&self, id: u32, time_usec: u64) {
+        let Some(shortcut_id) = self.shortcut_id(id) else {
+            return;
+        };
+        self.session.state.dbus.emit_signal(
+            "/org/freedesktop/portal/desktop",
+            &Activated {
+                session_handle: ObjectPath(self.session.session_obj.path().into()),
+                shortcut_id: shortcut_id.into(),
+                timestamp: time_usec / 1000,
+                options: Default::default(),
+            },
+        );
+    }
+
+    fn deactivated(&self, id: u32, time_usec: u64) {
+        let Some(shortcut_id) = self.shortcut_id(id) else {
+            return;
+        };
+        self.session.state.dbus.emit_signal(
+            "/org/freedesktop/portal/desktop",
+            &Deactivated {
+                session_handle: ObjectPath(self.session.session_obj.path().into()),
+                shortcut_id: shortcut_id.into(),
+                timestamp: time_usec / 1000,
+                options: Default::default(),
+            },
+        );
+    }
+}
+
+impl PortalSession {
+    fn dbus_bind_shortcuts(
+        self: &Rc<Self>,
+        req: BindShortcuts<'_>,
+        reply: PendingReply<BindShortcutsReply<'static>>,
+    ) {
+        match self.gs_phase.get() {
+            GlobalShortcutsPhase::Init => {}
+            _ => {
+                self.kill();
+                reply.err("Shortcuts have already been bound");
+                return;
+            }
+        }
+        let request_obj = match self.state.dbus.add_object(req.handle.to_string()) {
+            Ok(r) => r,
+            Err(_) => {
+                self.kill();
+                reply.err("Request handle is not unique");
+                return;
+            }
+        };
+        let stored = load_triggers();
+        let stored = stored.get(&self.app);
+        let mut all_stored = !self.app.is_empty();
+        let mut shortcuts = vec![];
+        for (id, props) in req.shortcuts.iter() {
+            let mut description = String::new();
+            let mut preferred_trigger = None;
+            for prop in props.iter() {
+                let Variant::String(value) = &prop.value else {
+                    continue;
+                };
+                match &*prop.key {
+                    "description" => description = value.to_string(),
+                    "preferred_trigger" => preferred_trigger = Trigger::parse(value),
+                    _ => {}
+                }
+            }
+            let trigger = match stored.and_then(|s| s.get(&**id)) {
+                Some(t) => Trigger::parse(t),
+                None => {
+                    all_stored = false;
+                    preferred_trigger
+                }
+            };
+            shortcuts.push(GlobalShortcut {
+                id: id.to_string(),
+                description,
+                trigger: Cell::new(trigger),
+            });
+        }
+        let binding = Rc::new(BindingShortcuts {
+            session: self.clone(),
+            request_obj,
+            reply: Cell::new(Some(reply)),
+            shortcuts: Rc::new(shortcuts),
+            objs: Default::default(),
+            guis: Default::default(),
+        });
+        {
+            use org::freedesktop::impl_::portal::request::*;
+            binding.request_obj.add_method::<Close, _>({
+                let session = self.clone();
+                move |_, pr| {
+                    session.kill();
+                    pr.ok(&CloseReply);
+                }
+            });
+        }
+        for dpy in self.state.displays.lock().values() {
+            if dpy.jc.version >= GET_GLOBAL_SHORTCUTS_SINCE {
+                binding.objs.set(dpy.id, dpy.jc.get_global_shortcuts());
+            }
+        }
+        if binding.objs.is_empty() {
+            if let Some(reply) = binding.reply.take() {
+                reply.err("There are no running displays");
+            }
+            self.kill();
+            return;
+        }
+        self.gs_phase
+            .set(GlobalShortcutsPhase::Binding(binding.clone()));
+        if all_stored {
+            binding.accept();
+            return;
+        }
+        for dpy in self.state.displays.lock().values() {
+            if dpy.outputs.len() > 0
+                && let Some(obj) = binding.objs.get(&dpy.id)
+            {
+                binding
+                    .guis
+                    .set(dpy.id, ShortcutsGui::new(&binding, dpy, &obj));
+            }
+        }
+        if binding.guis.is_empty() {
+            self.kill();
+        }
+    }
+
+    fn dbus_list_shortcuts(
+        self: &Rc<Self>,
+        _req: ListShortcuts<'_>,
+        reply: PendingReply<ListShortcutsReply<'static>>,
+    ) {
+        let shortcuts = match self.gs_phase.get() {
+            GlobalShortcutsPhase::Bound(b) => shortcuts_variant(&b.shortcuts),
+            _ => shortcuts_variant(&[]),
+        };
+        reply.ok(&ListShortcutsReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Owned(vec![DictEntry {
+                key: "shortcuts".into(),
+                value: shortcuts,
+            }]),
+        });
+    }
+}
+
+pub(super) fn add_global_shortcuts_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::global_shortcuts::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<BindShortcuts, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_bind_shortcuts(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<ListShortcuts, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_list_shortcuts(req, pr);
+        }
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create global shortcuts session {:#?}", req);
+    if state.sessions.contains(req.session_handle.0.deref()) {
+        reply.err("Session already exists");
+        return;
+    }
+    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Session path is not unique");
+            return;
+        }
+    };
+    let session = Rc::new(PortalSession {
+        _id: state.id(),
+        state: state.clone(),
+        pw_con: state.pw_con.clone(),
+        app: req.app_id.to_string(),
+        session_obj: obj,
+        sc_phase: CloneCell::new(ScreencastPhase::Init),
+        rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        gs_phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        sc_cursor_mode: Cell::new(EMBEDDED),
+        start_reply: Default::default(),
+    });
+    {
+        use org::freedesktop::impl_::portal::session::*;
+        let ses = session.clone();
+        session.session_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&SessionCloseReply);
+        });
+        session.session_obj.set_property::<version>(Variant::U32(1));
+    }
+    state
+        .sessions
+        .set(req.session_handle.0.to_string(), session);
+    reply.ok(&CreateSessionReply {
+        response: PORTAL_SUCCESS,
+        results: Default::default(),
+    });
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<PortalSession>> {
+    let res = state.sessions.get(handle);
+    if res.is_none() {
+        let msg = format!("Global shortcuts session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
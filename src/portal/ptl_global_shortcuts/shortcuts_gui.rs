@@ -0,0 +1,228 @@
+use {
+    crate::{
+        ifs::wl_seat::{BTN_LEFT, wl_pointer::PRESSED},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_global_shortcuts::{BindingShortcuts, GlobalShortcutsPhase, Trigger},
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, Label, Orientation, OverlayWindow,
+                OverlayWindowOwner,
+            },
+        },
+        theme::Color,
+        utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+        wl_usr::usr_ifs::usr_jay_global_shortcuts::{
+            UsrJayGlobalShortcuts, UsrJayGlobalShortcutsOwner,
+        },
+    },
+    kbvm::syms,
+    std::{cell::Cell, rc::Rc},
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+
+pub struct ShortcutsGui {
+    binding: Rc<BindingShortcuts>,
+    dpy: Rc<PortalDisplay>,
+    obj: Rc<UsrJayGlobalShortcuts>,
+    capturing: Cell<Option<usize>>,
+    surfaces: CopyHashMap<u32, Rc<ShortcutsGuiSurface>>,
+}
+
+pub struct ShortcutsGuiSurface {
+    gui: Rc<ShortcutsGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+    trigger_buttons: Vec<Rc<Button>>,
+}
+
+struct StaticButton {
+    gui: Rc<ShortcutsGui>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    Accept,
+    Reject,
+    Assign(usize),
+}
+
+impl ShortcutsGui {
+    pub fn kill(&self, upwards: bool) {
+        self.obj.owner.take();
+        for surface in self.surfaces.lock().drain_values() {
+            surface.overlay.data.kill(false);
+        }
+        if let GlobalShortcutsPhase::Binding(b) = self.binding.session.gs_phase.get() {
+            b.guis.remove(&self.dpy.id);
+            if upwards && b.guis.is_empty() {
+                b.session.kill();
+            }
+        }
+    }
+
+    fn update_buttons(&self) {
+        let capturing = self.capturing.get();
+        for surface in self.surfaces.lock().values() {
+            for (idx, button) in surface.trigger_buttons.iter().enumerate() {
+                *button.text.borrow_mut() = match capturing == Some(idx) {
+                    true => "Press a key combination...".to_string(),
+                    false => self.binding.shortcuts[idx].trigger_description(),
+                };
+            }
+            surface.overlay.data.layout();
+        }
+    }
+}
+
+fn style_button(button: &Button, color: (u8, u8, u8), hover: (u8, u8, u8)) {
+    button.border_color.set(Color::from_gray_srgb(100));
+    button.border.set(2.0);
+    button.padding.set(5.0);
+    button
+        .bg_color
+        .set(Color::from_srgb(color.0, color.1, color.2));
+    button
+        .bg_hover_color
+        .set(Color::from_srgb(hover.0, hover.1, hover.2));
+}
+
+fn create_gui(gui: &Rc<ShortcutsGui>) -> (Rc<dyn GuiElement>, Vec<Rc<Button>>) {
+    let app = &gui.binding.session.app;
+    let text = if app.is_empty() {
+        format!("An application wants to register global shortcuts")
+    } else {
+        format!("`{}` wants to register global shortcuts", app)
+    };
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label];
+    let mut trigger_buttons = vec![];
+    for (idx, shortcut) in gui.binding.shortcuts.iter().enumerate() {
+        let description = Rc::new(Label::default());
+        *description.text.borrow_mut() = match shortcut.description.is_empty() {
+            true => shortcut.id.clone(),
+            false => shortcut.description.clone(),
+        };
+        let button = static_button(
+            gui,
+            ButtonRole::Assign(idx),
+            &shortcut.trigger_description(),
+        );
+        style_button(&button, (200, 200, 200), (230, 230, 230));
+        let row = Rc::new(Flow::default());
+        row.orientation.set(Orientation::Horizontal);
+        row.cross_align.set(Align::Center);
+        row.in_margin.set(H_MARGIN);
+        *row.elements.borrow_mut() = vec![description, button.clone()];
+        elements.push(row);
+        trigger_buttons.push(button);
+    }
+    let accept_button = static_button(gui, ButtonRole::Accept, "Accept");
+    let reject_button = static_button(gui, ButtonRole::Reject, "Reject");
+    style_button(&accept_button, (170, 200, 170), (170, 255, 170));
+    style_button(&reject_button, (200, 170, 170), (255, 170, 170));
+    elements.push(accept_button);
+    elements.push(reject_button);
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    *flow.elements.borrow_mut() = elements;
+    (flow, trigger_buttons)
+}
+
+impl OverlayWindowOwner for ShortcutsGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl ShortcutsGui {
+    pub fn new(
+        binding: &Rc<BindingShortcuts>,
+        dpy: &Rc<PortalDisplay>,
+        obj: &Rc<UsrJayGlobalShortcuts>,
+    ) -> Rc<Self> {
+        let gui = Rc::new(ShortcutsGui {
+            binding: binding.clone(),
+            dpy: dpy.clone(),
+            obj: obj.clone(),
+            capturing: Default::default(),
+            surfaces: Default::default(),
+        });
+        obj.owner.set(Some(gui.clone()));
+        for output in dpy.outputs.lock().values() {
+            let (element, trigger_buttons) = create_gui(&gui);
+            let sgs = Rc::new(ShortcutsGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new(output),
+                trigger_buttons,
+            });
+            sgs.overlay.data.content.set(Some(element));
+            gui.dpy
+                .windows
+                .set(sgs.overlay.data.surface.id, sgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, sgs);
+        }
+        gui
+    }
+}
+
+impl UsrJayGlobalShortcutsOwner for ShortcutsGui {
+    fn trigger_captured(&self, mods: u32, keysym: u32) {
+        let Some(idx) = self.capturing.take() else {
+            return;
+        };
+        if mods != 0 || keysym != syms::Escape.0 {
+            self.binding.shortcuts[idx]
+                .trigger
+                .set(Some(Trigger { mods, keysym }));
+        }
+        for gui in self.binding.guis.lock().values() {
+            gui.update_buttons();
+        }
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, _seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        match self.role {
+            ButtonRole::Accept => {
+                log::info!("User has accepted the global shortcuts request");
+                self.gui.binding.accept();
+            }
+            ButtonRole::Reject => {
+                log::info!("User has rejected the global shortcuts request");
+                self.gui.binding.session.kill();
+            }
+            ButtonRole::Assign(idx) => {
+                self.gui.capturing.set(Some(idx));
+                self.gui.obj.capture_trigger();
+                self.gui.update_buttons();
+            }
+        }
+    }
+}
+
+fn static_button(gui: &Rc<ShortcutsGui>, role: ButtonRole, text: &str) -> Rc<Button> {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        gui: gui.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    button
+}
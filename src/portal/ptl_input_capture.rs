@@ -0,0 +1,547 @@
+mod input_capture_gui;
+
+use {
+    crate::{
+        dbus::{
+            DbusObject, DictEntry, DynamicType, PendingReply,
+            prelude::{ObjectPath, Variant},
+        },
+        fixed::Fixed,
+        ifs::jay_compositor::CREATE_INPUT_CAPTURE_SINCE,
+        portal::{
+            PORTAL_SUCCESS, PortalState,
+            ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::GlobalShortcutsPhase,
+            ptl_input_capture::input_capture_gui::SelectionGui,
+            ptl_remote_desktop::{DeviceTypes, KEYBOARD, POINTER, RemoteDesktopPhase},
+            ptl_screencast::{EMBEDDED, ScreencastPhase},
+            ptl_session::PortalSession,
+        },
+        rect::Rect,
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            hash_map_ext::HashMapExt,
+            numcell::NumCell,
+        },
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::{
+                input_capture::{
+                    Activated, ConnectToEIS, ConnectToEISReply, CreateSession, CreateSessionReply,
+                    Deactivated, Disable, DisableReply, Disabled, Enable, EnableReply, GetZones,
+                    GetZonesReply, Release, ReleaseReply, SetPointerBarriers,
+                    SetPointerBarriersReply, ZonesChanged,
+                },
+                session::CloseReply as SessionCloseReply,
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_input_capture::{UsrJayInputCapture, UsrJayInputCaptureOwner},
+    },
+    std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        ops::Deref,
+        rc::Rc,
+    },
+    uapi::OwnedFd,
+};
+
+const SUPPORTED_CAPABILITIES: DeviceTypes = DeviceTypes(KEYBOARD.0 | POINTER.0);
+
+#[derive(Clone)]
+pub enum InputCapturePhase {
+    Init,
+    Selecting(Rc<SelectingInputCapture>),
+    Running(Rc<RunningInputCapture>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for InputCapturePhase {}
+
+pub struct SelectingInputCapture {
+    pub session: Rc<PortalSession>,
+    pub _request_obj: DbusObject,
+    pub reply: Cell<Option<PendingReply<CreateSessionReply<'static>>>>,
+    pub capabilities: DeviceTypes,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<SelectionGui>>,
+}
+
+pub struct RunningInputCapture {
+    pub session: Rc<PortalSession>,
+    pub dpy: Rc<PortalDisplay>,
+    pub obj: Rc<UsrJayInputCapture>,
+    pub reply: Cell<Option<PendingReply<CreateSessionReply<'static>>>>,
+    pub capabilities: DeviceTypes,
+    pub zones: RefCell<Vec<Rect>>,
+    pub zone_set: NumCell<u32>,
+    pub ei_fd: Cell<Option<Rc<OwnedFd>>>,
+}
+
+impl SelectingInputCapture {
+    pub fn kill(&self) {
+        for gui in self.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        if let Some(reply) = self.reply.take() {
+            reply.err("Session has been terminated");
+        }
+    }
+
+    pub fn accept(&self, dpy: &Rc<PortalDisplay>) {
+        let obj = dpy.jc.create_input_capture();
+        obj.connect_ei(&self.session.app);
+        let running = Rc::new(RunningInputCapture {
+            session: self.session.clone(),
+            dpy: dpy.clone(),
+            obj,
+            reply: Cell::new(self.reply.take()),
+            capabilities: self.capabilities,
+            zones: Default::default(),
+            zone_set: Default::default(),
+            ei_fd: Default::default(),
+        });
+        running.obj.owner.set(Some(running.clone()));
+        self.session
+            .ic_phase
+            .set(InputCapturePhase::Running(running.clone()));
+        dpy.sessions.set(
+            self.session.session_obj.path().to_owned(),
+            self.session.clone(),
+        );
+    }
+}
+
+impl RunningInputCapture {
+    pub fn kill(&self) {
+        self.obj.con.remove_obj(self.obj.deref());
+        self.dpy.sessions.remove(self.session.session_obj.path());
+        if let Some(reply) = self.reply.take() {
+            reply.err("Session has been terminated");
+        }
+    }
+
+    fn session_handle(&self) -> ObjectPath<'_> {
+        ObjectPath(self.session.session_obj.path().into())
+    }
+
+    fn barrier_is_valid(&self, x1: i32, y1: i32, x2: i32, y2: i32) -> bool {
+        let zones = self.zones.borrow();
+        if x1 == x2 {
+            let (min, max) = (y1.min(y2), y1.max(y2));
+            zones
+                .iter()
+                .any(|z| (x1 == z.x1() || x1 == z.x2()) && min >= z.y1() && max < z.y2())
+        } else if y1 == y2 {
+            let (min, max) = (x1.min(x2), x1.max(x2));
+            zones
+                .iter()
+                .any(|z| (y1 == z.y1() || y1 == z.y2()) && min >= z.x1() && max < z.x2())
+        } else {
+            false
+        }
+    }
+}
+
+fn cursor_position(x: Fixed, y: Fixed) -> Variant<'static> {
+    Variant::Struct(vec![Variant::F64(x.to_f64()), Variant::F64(y.to_f64())])
+}
+
+impl UsrJayInputCaptureOwner for RunningInputCapture {
+    fn zones(&self, zones: Vec<Rect>) {
+        *self.zones.borrow_mut() = zones;
+        let zone_set = self.zone_set.fetch_add(1) + 1;
+        if zone_set == 1 {
+            return;
+        }
+        self.session.state.dbus.emit_signal(
+            "/org/freedesktop/portal/desktop",
+            &ZonesChanged {
+                session_handle: self.session_handle(),
+                options: Cow::Owned(vec![DictEntry {
+                    key: "zone_set".into(),
+                    value: Variant::U32(zone_set),
+                }]),
+            },
+        );
+    }
+
+    fn ei_created(&self, fd: &Rc<OwnedFd>) {
+        self.ei_fd.set(Some(fd.clone()));
+        if let Some(reply) = self.reply.take() {
+            reply.ok(&CreateSessionReply {
+                response: PORTAL_SUCCESS,
+                results: Cow::Owned(vec![DictEntry {
+                    key: "capabilities".into(),
+                    value: Variant::U32(self.capabilities.0),
+                }]),
+            });
+        }
+    }
+
+    fn ei_failed(&self, reason: &str) {
+        log::error!("Could not create EI client: {}", reason);
+        if let Some(reply) = self.reply.take() {
+            reply.err(reason);
+        }
+        self.session.kill();
+    }
+
+    fn activated(&self, activation_id: u32, barrier_id: u32, x: Fixed, y: Fixed) {
+        self.session.state.dbus.emit_signal(
+            "/org/freedesktop/portal/desktop",
+            &Activated {
+                session_handle: self.session_handle(),
+                options: Cow::Owned(vec![
+                    DictEntry {
+                        key: "activation_id".into(),
+                        value: Variant::U32(activation_id),
+                    },
+                    DictEntry {
+                        key: "cursor_position".into(),
+                        value: cursor_position(x, y),
+                    },
+                    DictEntry {
+                        key: "barrier_id".into(),
+                        value: Variant::U32(barrier_id),
+                    },
+                ]),
+            },
+        );
+    }
+
+    fn deactivated(&self, activation_id: u32, x: Fixed, y: Fixed) {
+        self.session.state.dbus.emit_signal(
+            "/org/freedesktop/portal/desktop",
+            &Deactivated {
+                session_handle: self.session_handle(),
+                options: Cow::Owned(vec![
+                    DictEntry {
+                        key: "activation_id".into(),
+                        value: Variant::U32(activation_id),
+                    },
+                    DictEntry {
+                        key: "cursor_position".into(),
+                        value: cursor_position(x, y),
+                    },
+                ]),
+            },
+        );
+    }
+
+    fn disabled(&self) {
+        self.session.state.dbus.emit_signal(
+            "/org/freedesktop/portal/desktop",
+            &Disabled {
+                session_handle: self.session_handle(),
+                options: Default::default(),
+            },
+        );
+    }
+}
+
+impl PortalSession {
+    fn running_input_capture<T>(&self, reply: &PendingReply<T>) -> Option<Rc<RunningInputCapture>> {
+        match self.ic_phase.get() {
+            InputCapturePhase::Running(r) => Some(r),
+            _ => {
+                reply.err("Input capture session is not running");
+                None
+            }
+        }
+    }
+
+    fn dbus_get_zones(
+        self: &Rc<Self>,
+        _req: GetZones<'_>,
+        reply: PendingReply<GetZonesReply<'static>>,
+    ) {
+        let Some(running) = self.running_input_capture(&reply) else {
+            return;
+        };
+        let zone_type = DynamicType::Struct(vec![
+            DynamicType::U32,
+            DynamicType::U32,
+            DynamicType::I32,
+            DynamicType::I32,
+        ]);
+        let zones = running
+            .zones
+            .borrow()
+            .iter()
+            .map(|z| {
+                Variant::Struct(vec![
+                    Variant::U32(z.width() as u32),
+                    Variant::U32(z.height() as u32),
+                    Variant::I32(z.x1()),
+                    Variant::I32(z.y1()),
+                ])
+            })
+            .collect();
+        reply.ok(&GetZonesReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Owned(vec![
+                DictEntry {
+                    key: "zones".into(),
+                    value: Variant::Array(zone_type, zones),
+                },
+                DictEntry {
+                    key: "zone_set".into(),
+                    value: Variant::U32(running.zone_set.get()),
+                },
+            ]),
+        });
+    }
+
+    fn dbus_set_pointer_barriers(
+        self: &Rc<Self>,
+        req: SetPointerBarriers<'_>,
+        reply: PendingReply<SetPointerBarriersReply<'static>>,
+    ) {
+        let Some(running) = self.running_input_capture(&reply) else {
+            return;
+        };
+        let zone_set_valid = req.zone_set == running.zone_set.get();
+        let mut failed = vec![];
+        running.obj.clear_barriers();
+        for barrier in req.barriers.iter() {
+            let mut id = None;
+            let mut position = None;
+            for prop in barrier.iter() {
+                match (&*prop.key, &prop.value) {
+                    ("barrier_id", Variant::U32(v)) => id = Some(*v),
+                    ("position", Variant::Struct(v)) => {
+                        if let [
+                            Variant::I32(x1),
+                            Variant::I32(y1),
+                            Variant::I32(x2),
+                            Variant::I32(y2),
+                        ] = &v[..]
+                        {
+                            position = Some((*x1, *y1, *x2, *y2));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let Some(id) = id else {
+                continue;
+            };
+            match position {
+                Some((x1, y1, x2, y2))
+                    if zone_set_valid && running.barrier_is_valid(x1, y1, x2, y2) =>
+                {
+                    running.obj.add_barrier(id, x1, y1, x2, y2);
+                }
+                _ => failed.push(Variant::U32(id)),
+            }
+        }
+        reply.ok(&SetPointerBarriersReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Owned(vec![DictEntry {
+                key: "failed_barriers".into(),
+                value: Variant::Array(DynamicType::U32, failed),
+            }]),
+        });
+    }
+
+    fn dbus_enable(self: &Rc<Self>, _req: Enable<'_>, reply: PendingReply<EnableReply>) {
+        if let Some(running) = self.running_input_capture(&reply) {
+            running.obj.enable();
+            reply.ok(&EnableReply);
+        }
+    }
+
+    fn dbus_disable(self: &Rc<Self>, _req: Disable<'_>, reply: PendingReply<DisableReply>) {
+        if let Some(running) = self.running_input_capture(&reply) {
+            running.obj.disable();
+            reply.ok(&DisableReply);
+        }
+    }
+
+    fn dbus_release(self: &Rc<Self>, req: Release<'_>, reply: PendingReply<ReleaseReply>) {
+        let Some(running) = self.running_input_capture(&reply) else {
+            return;
+        };
+        let mut activation_id = None;
+        let mut warp = None;
+        for opt in req.options.iter() {
+            match (&*opt.key, &opt.value) {
+                ("activation_id", Variant::U32(v)) => activation_id = Some(*v),
+                ("cursor_position", Variant::Struct(v)) => {
+                    if let [Variant::F64(x), Variant::F64(y)] = &v[..] {
+                        warp = Some((Fixed::from_f64(*x), Fixed::from_f64(*y)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        match activation_id {
+            Some(id) => {
+                running.obj.release(id, warp);
+                reply.ok(&ReleaseReply);
+            }
+            None => reply.err("Release requires an activation id"),
+        }
+    }
+
+    fn dbus_connect_to_eis_ic(
+        self: &Rc<Self>,
+        _req: ConnectToEIS<'_>,
+        reply: PendingReply<ConnectToEISReply>,
+    ) {
+        let Some(running) = self.running_input_capture(&reply) else {
+            return;
+        };
+        let Some(fd) = running.ei_fd.take() else {
+            reply.err("EI file descriptor has already been consumed");
+            return;
+        };
+        reply.ok(&ConnectToEISReply { fd });
+    }
+}
+
+pub(super) fn add_input_capture_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::input_capture::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<GetZones, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_get_zones(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<SetPointerBarriers, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_set_pointer_barriers(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<Enable, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_enable(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<Disable, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_disable(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<Release, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_release(req, pr);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<ConnectToEIS, _>(move |req, pr| {
+        if let Some(s) = get_session(&state, &pr, &req.session_handle.0) {
+            s.dbus_connect_to_eis_ic(req, pr);
+        }
+    });
+    object.set_property::<SupportedCapabilities>(Variant::U32(SUPPORTED_CAPABILITIES.0));
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create input capture session {:#?}", req);
+    if state.sessions.contains(req.session_handle.0.deref()) {
+        reply.err("Session already exists");
+        return;
+    }
+    let mut capabilities = SUPPORTED_CAPABILITIES;
+    for opt in req.options.iter() {
+        if let ("capabilities", Variant::U32(v)) = (&*opt.key, &opt.value) {
+            capabilities = DeviceTypes(*v & SUPPORTED_CAPABILITIES.0);
+        }
+    }
+    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Session path is not unique");
+            return;
+        }
+    };
+    let request_obj = match state.dbus.add_object(req.handle.0.to_string()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Request handle is not unique");
+            return;
+        }
+    };
+    let session = Rc::new(PortalSession {
+        _id: state.id(),
+        state: state.clone(),
+        pw_con: state.pw_con.clone(),
+        app: req.app_id.to_string(),
+        session_obj: obj,
+        sc_phase: CloneCell::new(ScreencastPhase::Init),
+        rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        gs_phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        sc_cursor_mode: Cell::new(EMBEDDED),
+        start_reply: Default::default(),
+    });
+    {
+        use org::freedesktop::impl_::portal::session::*;
+        let ses = session.clone();
+        session.session_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&SessionCloseReply);
+        });
+        session.session_obj.set_property::<version>(Variant::U32(1));
+    }
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        let ses = session.clone();
+        request_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&CloseReply);
+        });
+    }
+    let selecting = Rc::new(SelectingInputCapture {
+        session: session.clone(),
+        _request_obj: request_obj,
+        reply: Cell::new(Some(reply)),
+        capabilities,
+        guis: Default::default(),
+    });
+    session
+        .ic_phase
+        .set(InputCapturePhase::Selecting(selecting.clone()));
+    state
+        .sessions
+        .set(req.session_handle.0.to_string(), session.clone());
+    for dpy in state.displays.lock().values() {
+        if dpy.outputs.len() > 0 && dpy.jc.version >= CREATE_INPUT_CAPTURE_SINCE {
+            selecting.guis.set(dpy.id, SelectionGui::new(&session, dpy));
+        }
+    }
+    if selecting.guis.is_empty() {
+        if let Some(reply) = selecting.reply.take() {
+            reply.err("There are no running displays");
+        }
+        session.kill();
+    }
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<PortalSession>> {
+    let res = state.sessions.get(handle);
+    if res.is_none() {
+        let msg = format!("Input capture session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
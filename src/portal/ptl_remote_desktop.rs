@@ -7,8 +7,10 @@ use {
         portal::{
             PORTAL_SUCCESS, PortalState,
             ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::GlobalShortcutsPhase,
+            ptl_input_capture::InputCapturePhase,
             ptl_remote_desktop::remote_desktop_gui::SelectionGui,
-            ptl_screencast::ScreencastPhase,
+            ptl_screencast::{EMBEDDED, ScreencastPhase},
             ptl_session::{PortalSession, PortalSessionReply},
         },
         utils::{
@@ -268,6 +270,9 @@ fn dbus_create_session(
         session_obj: obj,
         sc_phase: CloneCell::new(ScreencastPhase::Init),
         rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        gs_phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        sc_cursor_mode: Cell::new(EMBEDDED),
         start_reply: Default::default(),
     });
     {
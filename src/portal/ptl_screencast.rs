@@ -4,28 +4,38 @@ use {
     crate::{
         allocator::{AllocatorError, BO_USE_RENDERING, BufferObject, BufferUsage},
         dbus::{DbusObject, DictEntry, PendingReply, prelude::Variant},
-        format::{Format, XRGB8888},
-        ifs::{jay_compositor::GET_TOPLEVEL_SINCE, jay_screencast::CLIENT_BUFFERS_SINCE},
+        format::{ARGB8888, Format, XRGB8888},
+        ifs::{
+            jay_compositor::GET_TOPLEVEL_SINCE,
+            jay_screencast::{
+                CLIENT_BUFFERS_SINCE, CURSOR_MODE_HIDDEN, CURSOR_MODE_METADATA, CURSOR_MODE_SINCE,
+                REGION_SINCE,
+            },
+        },
         pipewire::{
             pw_con::PwCon,
             pw_ifs::pw_client_node::{
-                PwClientNode, PwClientNodeBufferConfig, PwClientNodeOwner, PwClientNodePort,
+                CURSOR_META_MAX_SIZE, PwClientNode, PwClientNodeBufferConfig,
+                PwClientNodeCursorMeta, PwClientNodeOwner, PwClientNodePort,
                 PwClientNodePortSupportedFormat, PwClientNodePortSupportedFormats,
-                SUPPORTED_META_VIDEO_CROP,
+                SUPPORTED_META_CURSOR, SUPPORTED_META_VIDEO_CROP,
             },
             pw_pod::{
                 PwPodRectangle, SPA_DATA_DmaBuf, SPA_MEDIA_SUBTYPE_raw, SPA_MEDIA_TYPE_video,
-                SPA_STATUS_HAVE_DATA, SPA_VIDEO_FORMAT_UNKNOWN, SpaChunkFlags, spa_point,
-                spa_rectangle, spa_region,
+                SPA_STATUS_HAVE_DATA, SPA_VIDEO_FORMAT_UNKNOWN, SpaChunkFlags, spa_meta_bitmap,
+                spa_meta_cursor, spa_point, spa_rectangle, spa_region,
             },
         },
         portal::{
             PORTAL_SUCCESS, PortalState,
             ptl_display::{PortalDisplay, PortalDisplayId, PortalOutput},
+            ptl_global_shortcuts::GlobalShortcutsPhase,
+            ptl_input_capture::InputCapturePhase,
             ptl_remote_desktop::RemoteDesktopPhase,
-            ptl_screencast::screencast_gui::SelectionGui,
+            ptl_screencast::screencast_gui::{SelectionGui, SelectionGuiMode},
             ptl_session::{PortalSession, PortalSessionReply},
         },
+        rect::Rect,
         utils::{
             clonecell::{CloneCell, UnsafeCellCloneSafe},
             copyhashmap::CopyHashMap,
@@ -46,7 +56,8 @@ use {
         },
         wl_usr::usr_ifs::{
             usr_jay_screencast::{
-                UsrJayScreencast, UsrJayScreencastOwner, UsrJayScreencastServerConfig,
+                UsrJayScreencast, UsrJayScreencastCursorImage, UsrJayScreencastOwner,
+                UsrJayScreencastServerConfig,
             },
             usr_jay_select_toplevel::UsrJaySelectToplevel,
             usr_jay_select_workspace::UsrJaySelectWorkspace,
@@ -120,6 +131,7 @@ pub struct StartingScreencast {
 
 pub enum ScreencastTarget {
     Output(Rc<PortalOutput>),
+    Region(Rc<PortalOutput>, Rect),
     Workspace(Rc<PortalOutput>, Rc<UsrJayWorkspace>, bool),
     Toplevel(Rc<UsrJayToplevel>),
 }
@@ -140,6 +152,9 @@ pub struct StartedScreencast {
     pub modifier: Cell<Modifier>,
     pub width: Cell<i32>,
     pub height: Cell<i32>,
+    pub cursor_position: Cell<(bool, i32, i32)>,
+    pub cursor_image: RefCell<Option<UsrJayScreencastCursorImage>>,
+    pub cursor_image_changed: Cell<bool>,
 }
 
 bitflags! {
@@ -162,6 +177,7 @@ impl PwClientNodeOwner for StartingScreencast {
         {
             let output = match &self.target {
                 ScreencastTarget::Output(o) => Some(o),
+                ScreencastTarget::Region(o, _) => Some(o),
                 ScreencastTarget::Workspace(o, _, _) => Some(o),
                 ScreencastTarget::Toplevel(_) => None,
             };
@@ -186,13 +202,25 @@ impl PwClientNodeOwner for StartingScreencast {
         let num_buffers = (jsc_version >= CLIENT_BUFFERS_SINCE).then_some(3);
         let port = self.node.create_port(true, supported_formats, num_buffers);
         port.can_alloc_buffers.set(true);
-        port.supported_metas.set(SUPPORTED_META_VIDEO_CROP);
+        let cursor_mode = match self.dpy.jc.version >= CURSOR_MODE_SINCE {
+            true => self.session.sc_cursor_mode.get(),
+            false => EMBEDDED,
+        };
+        let mut metas = SUPPORTED_META_VIDEO_CROP;
+        if cursor_mode == METADATA {
+            metas |= SUPPORTED_META_CURSOR;
+        }
+        port.supported_metas.set(metas);
         let jsc = self.dpy.jc.create_screencast();
         match &self.target {
             ScreencastTarget::Output(o) => {
                 jsc.set_output(&o.jay);
                 jsc.set_allow_all_workspaces(true);
             }
+            ScreencastTarget::Region(o, rect) => {
+                jsc.set_region(&o.jay, *rect);
+                jsc.set_allow_all_workspaces(true);
+            }
             ScreencastTarget::Workspace(o, ws, _) => {
                 jsc.set_output(&o.jay);
                 jsc.allow_workspace(ws);
@@ -200,9 +228,14 @@ impl PwClientNodeOwner for StartingScreencast {
             ScreencastTarget::Toplevel(t) => jsc.set_toplevel(t),
         }
         jsc.set_use_linear_buffers(true);
+        if cursor_mode == HIDDEN {
+            jsc.set_cursor_mode(CURSOR_MODE_HIDDEN);
+        } else if cursor_mode == METADATA {
+            jsc.set_cursor_mode(CURSOR_MODE_METADATA);
+        }
         jsc.configure();
         match &self.target {
-            ScreencastTarget::Output(_) => {}
+            ScreencastTarget::Output(_) | ScreencastTarget::Region(..) => {}
             ScreencastTarget::Workspace(_, w, true) => {
                 self.dpy.con.remove_obj(&**w);
             }
@@ -227,6 +260,9 @@ impl PwClientNodeOwner for StartingScreencast {
             modifier: Cell::new(LINEAR_MODIFIER),
             width: Cell::new(1),
             height: Cell::new(1),
+            cursor_position: Cell::new((false, 0, 0)),
+            cursor_image: Default::default(),
+            cursor_image_changed: Cell::new(false),
         });
         self.session
             .sc_phase
@@ -377,6 +413,60 @@ impl StartedScreencast {
         )?;
         Ok(buffer)
     }
+
+    fn write_cursor_meta(&self, meta: &PwClientNodeCursorMeta) {
+        let (visible, x, y) = self.cursor_position.get();
+        let cursor = unsafe { meta.cursor.write() };
+        cursor.id = visible as u32;
+        cursor.flags = 0;
+        cursor.position = spa_point { x, y };
+        cursor.bitmap_offset = 0;
+        if !visible {
+            return;
+        }
+        let image = self.cursor_image.borrow();
+        let Some(image) = &*image else {
+            return;
+        };
+        cursor.hotspot = spa_point {
+            x: image.hotspot_x,
+            y: image.hotspot_y,
+        };
+        if !self.cursor_image_changed.get() {
+            return;
+        }
+        let (Some(bitmap), Some(pixels)) = (&meta.bitmap, &meta.pixels) else {
+            return;
+        };
+        self.cursor_image_changed.set(false);
+        if image.width > CURSOR_META_MAX_SIZE || image.height > CURSOR_META_MAX_SIZE {
+            log::warn!(
+                "Cursor image of size {}x{} is too large for cursor metadata",
+                image.width,
+                image.height,
+            );
+            return;
+        }
+        let row_len = image.width as usize * 4;
+        let pixels = unsafe { pixels.bytes_mut() };
+        if pixels.len() < row_len * image.height as usize {
+            return;
+        }
+        let rows = image.data.chunks(image.stride as usize);
+        for (src, dst) in rows.zip(pixels.chunks_mut(row_len)) {
+            dst.copy_from_slice(&src[..row_len]);
+        }
+        *unsafe { bitmap.write() } = spa_meta_bitmap {
+            format: ARGB8888.pipewire,
+            size: spa_rectangle {
+                width: image.width as _,
+                height: image.height as _,
+            },
+            stride: row_len as _,
+            offset: size_of::<spa_meta_bitmap>() as _,
+        };
+        cursor.bitmap_offset = size_of::<spa_meta_cursor>() as _;
+    }
 }
 
 impl SelectingScreencastCore {
@@ -422,6 +512,15 @@ impl PortalSession {
                 return;
             }
         }
+        let cursor_mode = match get_cursor_mode(&req) {
+            Ok(m) => m,
+            Err(e) => {
+                self.kill();
+                reply.err(e);
+                return;
+            }
+        };
+        self.sc_cursor_mode.set(cursor_mode);
         self.sc_phase.set(ScreencastPhase::SourcesSelected(Rc::new(
             SourcesSelectedScreencast {
                 restore_data: Cell::new(get_restore_data(&req)),
@@ -477,7 +576,10 @@ impl PortalSession {
         let guis = CopyHashMap::new();
         for dpy in self.state.displays.lock().values() {
             if dpy.outputs.len() > 0 {
-                guis.set(dpy.id, SelectionGui::new(self, dpy, restore_data.is_some()));
+                guis.set(
+                    dpy.id,
+                    SelectionGui::new(self, dpy, restore_data.is_some(), SelectionGuiMode::Buttons),
+                );
             }
         }
         if guis.is_empty() {
@@ -563,6 +665,27 @@ impl PortalSession {
                 };
                 start(ScreencastTarget::Output(output));
             }
+            RestoreDataType::Region(d) => {
+                if dpy.jc.version < REGION_SINCE {
+                    return Err(RestoreError::Region);
+                }
+                let output = dpy
+                    .outputs
+                    .lock()
+                    .values()
+                    .find(|o| o.wl.name.borrow().as_ref() == Some(&d.name))
+                    .cloned();
+                let Some(output) = output else {
+                    return Err(RestoreError::UnknownOutput);
+                };
+                let Some(rect) = Rect::new_sized(d.x, d.y, d.width, d.height) else {
+                    return Err(RestoreError::InvalidRegion);
+                };
+                if rect.is_empty() {
+                    return Err(RestoreError::InvalidRegion);
+                }
+                start(ScreencastTarget::Region(output, rect));
+            }
             RestoreDataType::Workspace(ws) => {
                 let ws = dpy
                     .workspaces
@@ -662,6 +785,9 @@ impl UsrJayScreencastOwner for StartedScreencast {
             chunk.stride = plane.stride;
             chunk.size = plane.stride * buffer.height as u32;
         }
+        if let Some(cursor) = &pbuffer.meta_cursor {
+            self.write_cursor_meta(cursor);
+        }
         if let Some(crop) = &pbuffer.meta_video_crop {
             unsafe { crop.write() }.region = spa_region {
                 position: spa_point { x: 0, y: 0 },
@@ -706,6 +832,15 @@ impl UsrJayScreencastOwner for StartedScreencast {
         self.node.send_port_update(&self.port, self.fixated.get());
         self.node.send_active(true);
     }
+
+    fn cursor_position(&self, visible: bool, x: i32, y: i32) {
+        self.cursor_position.set((visible, x, y));
+    }
+
+    fn cursor_image(&self, image: UsrJayScreencastCursorImage) {
+        *self.cursor_image.borrow_mut() = Some(image);
+        self.cursor_image_changed.set(true);
+    }
 }
 
 fn init_supported_formats(
@@ -768,7 +903,7 @@ pub(super) fn add_screencast_dbus_members(
         dbus_start(&state, req, pr);
     });
     object.set_property::<AvailableSourceTypes>(Variant::U32(MONITOR.0));
-    object.set_property::<AvailableCursorModes>(Variant::U32(EMBEDDED.0));
+    object.set_property::<AvailableCursorModes>(Variant::U32((HIDDEN | EMBEDDED | METADATA).0));
     object.set_property::<version>(Variant::U32(5));
 }
 
@@ -798,6 +933,9 @@ fn dbus_create_session(
         session_obj: obj,
         sc_phase: CloneCell::new(ScreencastPhase::Init),
         rd_phase: CloneCell::new(RemoteDesktopPhase::Init),
+        gs_phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        ic_phase: CloneCell::new(InputCapturePhase::Init),
+        sc_cursor_mode: Cell::new(EMBEDDED),
         start_reply: Default::default(),
     });
     {
@@ -854,6 +992,13 @@ fn create_restore_data(dpy: &PortalDisplay, rd: &ScreencastTarget) -> Option<Var
             ScreencastTarget::Output(o) => RestoreDataType::Output(RestoreDataOutput {
                 name: o.wl.name.borrow().clone()?,
             }),
+            ScreencastTarget::Region(o, rect) => RestoreDataType::Region(RestoreDataRegion {
+                name: o.wl.name.borrow().clone()?,
+                x: rect.x1(),
+                y: rect.y1(),
+                width: rect.width(),
+                height: rect.height(),
+            }),
             ScreencastTarget::Workspace(_, w, _) => {
                 RestoreDataType::Workspace(RestoreDataWorkspace {
                     name: w.name.borrow().clone()?,
@@ -901,6 +1046,23 @@ pub enum RestoreError {
     UnknownWorkspace,
     #[error("The display does not support toplevel restoration")]
     GetToplevel,
+    #[error("The display does not support region capture")]
+    Region,
+    #[error("The restored region is empty")]
+    InvalidRegion,
+}
+
+fn get_cursor_mode(req: &SelectSources) -> Result<CursorModes, &'static str> {
+    let Some(mode) = req.options.iter().find(|n| n.key == "cursor_mode") else {
+        return Ok(EMBEDDED);
+    };
+    let Variant::U32(mode) = mode.value else {
+        return Err("cursor_mode is not a u32");
+    };
+    match CursorModes(mode) {
+        m if m == HIDDEN || m == EMBEDDED || m == METADATA => Ok(m),
+        _ => Err("Unsupported cursor mode"),
+    }
 }
 
 fn get_restore_data(req: &SelectSources) -> Option<Result<RestoreData, RestoreError>> {
@@ -947,6 +1109,7 @@ pub struct RestoreData {
 #[derive(Serialize, Deserialize)]
 enum RestoreDataType {
     Output(RestoreDataOutput),
+    Region(RestoreDataRegion),
     Workspace(RestoreDataWorkspace),
     Toplevel(RestoreDataToplevel),
 }
@@ -956,6 +1119,15 @@ struct RestoreDataOutput {
     name: String,
 }
 
+#[derive(Serialize, Deserialize)]
+struct RestoreDataRegion {
+    name: String,
+    x: i32,
+    y: i32,
+    width: i32,
+    height: i32,
+}
+
 #[derive(Serialize, Deserialize)]
 struct RestoreDataWorkspace {
     name: String,
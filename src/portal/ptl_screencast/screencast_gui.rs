@@ -1,6 +1,9 @@
 use {
     crate::{
-        ifs::wl_seat::{BTN_LEFT, wl_pointer::PRESSED},
+        ifs::{
+            jay_screencast::REGION_SINCE,
+            wl_seat::{BTN_LEFT, wl_pointer::PRESSED},
+        },
         portal::{
             ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
             ptl_screencast::{
@@ -9,9 +12,10 @@ use {
             },
             ptr_gui::{
                 Align, Button, ButtonOwner, Flow, GuiElement, Label, Orientation, OverlayWindow,
-                OverlayWindowOwner,
+                OverlayWindowOwner, RegionSelector, RegionSelectorOwner,
             },
         },
+        rect::Rect,
         theme::Color,
         utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
         wl_usr::usr_ifs::{
@@ -26,9 +30,16 @@ use {
 const H_MARGIN: f32 = 30.0;
 const V_MARGIN: f32 = 20.0;
 
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum SelectionGuiMode {
+    Buttons,
+    Region,
+}
+
 pub struct SelectionGui {
     screencast_session: Rc<PortalSession>,
     dpy: Rc<PortalDisplay>,
+    mode: SelectionGuiMode,
     surfaces: CopyHashMap<u32, Rc<SelectionGuiSurface>>,
 }
 
@@ -49,6 +60,7 @@ enum ButtonRole {
     Accept,
     SelectWorkspace,
     SelectWindow,
+    SelectRegion,
     Reject,
 }
 
@@ -79,12 +91,14 @@ fn create_accept_gui(surface: &Rc<SelectionGuiSurface>, for_restore: bool) -> Rc
     let accept_button = static_button(surface, ButtonRole::Accept, "Share This Output");
     let workspace_button = static_button(surface, ButtonRole::SelectWorkspace, "Share A Workspace");
     let window_button = static_button(surface, ButtonRole::SelectWindow, "Share A Window");
+    let region_button = static_button(surface, ButtonRole::SelectRegion, "Share A Region");
     let reject_button = static_button(surface, ButtonRole::Reject, "Reject");
     for button in [
         &restore_button,
         &accept_button,
         &workspace_button,
         &window_button,
+        &region_button,
         &reject_button,
     ] {
         button.border_color.set(Color::from_gray_srgb(100));
@@ -95,7 +109,12 @@ fn create_accept_gui(surface: &Rc<SelectionGuiSurface>, for_restore: bool) -> Rc
     restore_button
         .bg_hover_color
         .set(Color::from_srgb(170, 170, 255));
-    for button in [&accept_button, &workspace_button, &window_button] {
+    for button in [
+        &accept_button,
+        &workspace_button,
+        &window_button,
+        &region_button,
+    ] {
         button.bg_color.set(Color::from_srgb(170, 200, 170));
         button.bg_hover_color.set(Color::from_srgb(170, 255, 170));
     }
@@ -119,11 +138,27 @@ fn create_accept_gui(surface: &Rc<SelectionGuiSurface>, for_restore: bool) -> Rc
     if surface.gui.dpy.jc.caps.window_capture.get() {
         elements.push(window_button);
     }
+    if surface.gui.dpy.jc.version >= REGION_SINCE {
+        elements.push(region_button);
+    }
     elements.push(reject_button);
     *flow.elements.borrow_mut() = elements;
     flow
 }
 
+fn create_region_gui(surface: &Rc<SelectionGuiSurface>) -> Rc<dyn GuiElement> {
+    let selector = Rc::new(RegionSelector::default());
+    *selector.label.text.borrow_mut() =
+        "Drag to select a region. Right-click to cancel.".to_string();
+    selector.owner.set(Some(surface.clone()));
+    surface
+        .overlay
+        .data
+        .bg_color
+        .set(Color::from_srgba_straight(0, 0, 0, 64));
+    selector
+}
+
 impl OverlayWindowOwner for SelectionGuiSurface {
     fn kill(&self, upwards: bool) {
         self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
@@ -135,19 +170,32 @@ impl OverlayWindowOwner for SelectionGuiSurface {
 }
 
 impl SelectionGui {
-    pub fn new(ss: &Rc<PortalSession>, dpy: &Rc<PortalDisplay>, for_restore: bool) -> Rc<Self> {
+    pub fn new(
+        ss: &Rc<PortalSession>,
+        dpy: &Rc<PortalDisplay>,
+        for_restore: bool,
+        mode: SelectionGuiMode,
+    ) -> Rc<Self> {
         let gui = Rc::new(SelectionGui {
             screencast_session: ss.clone(),
             dpy: dpy.clone(),
+            mode,
             surfaces: Default::default(),
         });
         for output in dpy.outputs.lock().values() {
+            let overlay = match mode {
+                SelectionGuiMode::Buttons => OverlayWindow::new(output),
+                SelectionGuiMode::Region => OverlayWindow::new_fullscreen(output),
+            };
             let sgs = Rc::new(SelectionGuiSurface {
                 gui: gui.clone(),
                 output: output.clone(),
-                overlay: OverlayWindow::new(output),
+                overlay,
             });
-            let element = create_accept_gui(&sgs, for_restore);
+            let element = match gui.mode {
+                SelectionGuiMode::Buttons => create_accept_gui(&sgs, for_restore),
+                SelectionGuiMode::Region => create_region_gui(&sgs),
+            };
             sgs.overlay.data.content.set(Some(element));
             gui.dpy
                 .windows
@@ -167,7 +215,8 @@ impl ButtonOwner for StaticButton {
             ButtonRole::Restore
             | ButtonRole::Accept
             | ButtonRole::SelectWorkspace
-            | ButtonRole::SelectWindow => {
+            | ButtonRole::SelectWindow
+            | ButtonRole::SelectRegion => {
                 log::info!("User has accepted the request");
                 let selecting = match self.surface.gui.screencast_session.sc_phase.get() {
                     ScreencastPhase::Selecting(selecting) => selecting,
@@ -187,6 +236,18 @@ impl ButtonOwner for StaticButton {
                     selecting
                         .core
                         .starting(dpy, ScreencastTarget::Output(self.surface.output.clone()));
+                } else if self.role == ButtonRole::SelectRegion {
+                    let session = &self.surface.gui.screencast_session;
+                    for dpy in session.state.displays.lock().values() {
+                        if dpy.outputs.len() > 0 && dpy.jc.version >= REGION_SINCE {
+                            let gui =
+                                SelectionGui::new(session, dpy, false, SelectionGuiMode::Region);
+                            selecting.guis.set(dpy.id, gui);
+                        }
+                    }
+                    if selecting.guis.is_empty() {
+                        session.kill();
+                    }
                 } else if self.role == ButtonRole::SelectWorkspace {
                     let selector = dpy.jc.select_workspace(&seat.wl);
                     let selecting = Rc::new(SelectingWorkspaceScreencast {
@@ -224,6 +285,37 @@ impl ButtonOwner for StaticButton {
     }
 }
 
+impl RegionSelectorOwner for SelectionGuiSurface {
+    fn selected(&self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        let rect = Rect::new(
+            x1.round() as i32,
+            y1.round() as i32,
+            x2.round() as i32,
+            y2.round() as i32,
+        );
+        let Some(rect) = rect.filter(|r| !r.is_empty()) else {
+            return;
+        };
+        let selecting = match self.gui.screencast_session.sc_phase.get() {
+            ScreencastPhase::Selecting(selecting) => selecting,
+            _ => return,
+        };
+        for gui in selecting.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        log::info!("User has selected a region");
+        selecting.core.starting(
+            &self.output.dpy,
+            ScreencastTarget::Region(self.output.clone(), rect),
+        );
+    }
+
+    fn cancelled(&self) {
+        log::info!("User has aborted the selection");
+        self.gui.screencast_session.kill();
+    }
+}
+
 impl UsrJaySelectToplevelOwner for SelectingWindowScreencast {
     fn done(&self, tl: Option<Rc<UsrJayToplevel>>) {
         let Some(tl) = tl else {
@@ -0,0 +1,479 @@
+mod screenshot_gui;
+
+use {
+    crate::{
+        cli::{
+            ScreenshotFormat,
+            screenshot::{buf_pixel, buf_region_to_bytes},
+        },
+        dbus::{DbusObject, DictEntry, FALSE, PendingReply, prelude::Variant},
+        ifs::jay_compositor::TAKE_OUTPUT_SCREENSHOT_SINCE,
+        portal::{
+            PORTAL_CANCELLED, PORTAL_SUCCESS, PortalState,
+            ptl_display::{PortalDisplay, PortalDisplayId, PortalOutput},
+            ptl_screenshot::screenshot_gui::{SelectionGui, SelectionGuiMode},
+        },
+        rect::Rect,
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            hash_map_ext::HashMapExt,
+        },
+        video::dmabuf::DmaBuf,
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::screenshot::{
+                PickColor, PickColorReply, Screenshot, ScreenshotReply,
+            },
+        },
+        wl_usr::usr_ifs::{
+            usr_jay_screenshot::{UsrJayScreenshot, UsrJayScreenshotOwner},
+            usr_jay_select_toplevel::{UsrJaySelectToplevel, UsrJaySelectToplevelOwner},
+            usr_jay_toplevel::UsrJayToplevel,
+        },
+    },
+    chrono::Local,
+    std::{
+        borrow::Cow,
+        cell::Cell,
+        fmt::Write,
+        path::{Path, PathBuf},
+        rc::Rc,
+    },
+    uapi::OwnedFd,
+};
+
+shared_ids!(ScreenshotRequestId);
+pub struct ScreenshotRequest {
+    pub id: ScreenshotRequestId,
+    pub state: Rc<PortalState>,
+    pub app: String,
+    pub kind: ScreenshotKind,
+    pub request_obj: Cell<Option<DbusObject>>,
+    pub reply: Cell<Option<ScreenshotRequestReply>>,
+    pub phase: CloneCell<ScreenshotPhase>,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum ScreenshotKind {
+    Screenshot,
+    PickColor,
+}
+
+pub enum ScreenshotRequestReply {
+    Screenshot(PendingReply<ScreenshotReply<'static>>),
+    PickColor(PendingReply<PickColorReply<'static>>),
+}
+
+#[derive(Clone)]
+pub enum ScreenshotPhase {
+    Init,
+    Selecting(Rc<SelectingScreenshot>),
+    SelectingWindow(Rc<SelectingWindowScreenshot>),
+    Capturing(Rc<CapturingScreenshot>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for ScreenshotPhase {}
+
+pub struct SelectingScreenshot {
+    pub guis: CopyHashMap<PortalDisplayId, Rc<SelectionGui>>,
+}
+
+pub struct SelectingWindowScreenshot {
+    pub request: Rc<ScreenshotRequest>,
+    pub dpy: Rc<PortalDisplay>,
+    pub selector: Rc<UsrJaySelectToplevel>,
+}
+
+pub struct CapturingScreenshot {
+    pub request: Rc<ScreenshotRequest>,
+    pub dpy: Rc<PortalDisplay>,
+    pub screenshot: Rc<UsrJayScreenshot>,
+    pub target: CaptureTarget,
+}
+
+pub enum CaptureTarget {
+    Full,
+    Region {
+        logical_width: i32,
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+    },
+    Point {
+        logical_width: i32,
+        x: f32,
+        y: f32,
+    },
+}
+
+impl ScreenshotRequest {
+    pub(super) fn kill(&self) {
+        self.reply_cancelled();
+        self.terminate();
+    }
+
+    fn terminate(&self) {
+        self.state.screenshots.remove(&self.id);
+        self.request_obj.take();
+        match self.phase.set(ScreenshotPhase::Terminated) {
+            ScreenshotPhase::Init => {}
+            ScreenshotPhase::Terminated => {}
+            ScreenshotPhase::Selecting(s) => {
+                for gui in s.guis.lock().drain_values() {
+                    gui.kill(false);
+                }
+            }
+            ScreenshotPhase::SelectingWindow(s) => {
+                s.dpy.con.remove_obj(&*s.selector);
+            }
+            ScreenshotPhase::Capturing(s) => {
+                if s.screenshot.owner.take().is_some() {
+                    s.dpy.con.remove_obj(&*s.screenshot);
+                }
+            }
+        }
+    }
+
+    pub(super) fn display_removed(&self, dpy: &PortalDisplay) {
+        let alive = match self.phase.get() {
+            ScreenshotPhase::Init | ScreenshotPhase::Terminated => true,
+            ScreenshotPhase::Selecting(s) => {
+                if let Some(gui) = s.guis.remove(&dpy.id) {
+                    gui.kill(false);
+                }
+                s.guis.is_not_empty()
+            }
+            ScreenshotPhase::SelectingWindow(s) => s.dpy.id != dpy.id,
+            ScreenshotPhase::Capturing(s) => s.dpy.id != dpy.id,
+        };
+        if !alive {
+            self.kill();
+        }
+    }
+
+    fn reply_cancelled(&self) {
+        if let Some(reply) = self.reply.take() {
+            match reply {
+                ScreenshotRequestReply::Screenshot(r) => r.ok(&ScreenshotReply {
+                    response: PORTAL_CANCELLED,
+                    results: Default::default(),
+                }),
+                ScreenshotRequestReply::PickColor(r) => r.ok(&PickColorReply {
+                    response: PORTAL_CANCELLED,
+                    results: Default::default(),
+                }),
+            }
+        }
+    }
+
+    fn reply_err(&self, err: &str) {
+        if let Some(reply) = self.reply.take() {
+            match reply {
+                ScreenshotRequestReply::Screenshot(r) => r.err(err),
+                ScreenshotRequestReply::PickColor(r) => r.err(err),
+            }
+        }
+        self.terminate();
+    }
+
+    fn reply_ok(&self, results: &[DictEntry<Cow<'static, str>, Variant<'static>>]) {
+        if let Some(reply) = self.reply.take() {
+            match reply {
+                ScreenshotRequestReply::Screenshot(r) => r.ok(&ScreenshotReply {
+                    response: PORTAL_SUCCESS,
+                    results: Cow::Borrowed(results),
+                }),
+                ScreenshotRequestReply::PickColor(r) => r.ok(&PickColorReply {
+                    response: PORTAL_SUCCESS,
+                    results: Cow::Borrowed(results),
+                }),
+            }
+        }
+        self.terminate();
+    }
+
+    fn start_selection(self: &Rc<Self>, mode: SelectionGuiMode) {
+        let selecting = Rc::new(SelectingScreenshot {
+            guis: Default::default(),
+        });
+        for dpy in self.state.displays.lock().values() {
+            if dpy.outputs.len() > 0 && dpy.jc.version >= TAKE_OUTPUT_SCREENSHOT_SINCE {
+                selecting
+                    .guis
+                    .set(dpy.id, SelectionGui::new(self, dpy, mode));
+            }
+        }
+        if selecting.guis.is_empty() {
+            self.reply_err("There are no running displays");
+            return;
+        }
+        self.phase.set(ScreenshotPhase::Selecting(selecting));
+    }
+
+    fn select_window(self: &Rc<Self>, dpy: &Rc<PortalDisplay>, selector: Rc<UsrJaySelectToplevel>) {
+        let selecting = Rc::new(SelectingWindowScreenshot {
+            request: self.clone(),
+            dpy: dpy.clone(),
+            selector: selector.clone(),
+        });
+        selector.owner.set(Some(selecting.clone()));
+        self.phase.set(ScreenshotPhase::SelectingWindow(selecting));
+    }
+
+    fn capture_output(self: &Rc<Self>, output: &Rc<PortalOutput>, target: CaptureTarget) {
+        let screenshot = output.dpy.jc.take_output_screenshot(&output.wl, false);
+        self.capture(&output.dpy, screenshot, target);
+    }
+
+    fn capture(
+        self: &Rc<Self>,
+        dpy: &Rc<PortalDisplay>,
+        screenshot: Rc<UsrJayScreenshot>,
+        target: CaptureTarget,
+    ) {
+        let capturing = Rc::new(CapturingScreenshot {
+            request: self.clone(),
+            dpy: dpy.clone(),
+            screenshot: screenshot.clone(),
+            target,
+        });
+        screenshot.owner.set(Some(capturing.clone()));
+        self.phase.set(ScreenshotPhase::Capturing(capturing));
+    }
+}
+
+impl UsrJaySelectToplevelOwner for SelectingWindowScreenshot {
+    fn done(&self, tl: Option<Rc<UsrJayToplevel>>) {
+        let Some(tl) = tl else {
+            log::info!("User has aborted the selection");
+            self.request.kill();
+            return;
+        };
+        let toplevel_id = tl.toplevel_id.borrow_mut().take();
+        self.dpy.con.remove_obj(&*tl);
+        let Some(toplevel_id) = toplevel_id else {
+            self.request
+                .reply_err("Compositor did not send a toplevel id");
+            return;
+        };
+        let screenshot = self.dpy.jc.take_toplevel_screenshot(&toplevel_id, false);
+        self.request
+            .capture(&self.dpy, screenshot, CaptureTarget::Full);
+    }
+}
+
+impl UsrJayScreenshotOwner for CapturingScreenshot {
+    fn done(&self, drm_dev: Option<Rc<OwnedFd>>, buf: DmaBuf) {
+        let res = match self.target {
+            CaptureTarget::Full => self.save(drm_dev.as_ref(), &buf, None),
+            CaptureTarget::Region {
+                logical_width,
+                x1,
+                y1,
+                x2,
+                y2,
+            } => {
+                let scale = buf.width as f32 / logical_width as f32;
+                let rect = Rect::new(
+                    (x1 * scale).round() as i32,
+                    (y1 * scale).round() as i32,
+                    (x2 * scale).round() as i32,
+                    (y2 * scale).round() as i32,
+                );
+                self.save(drm_dev.as_ref(), &buf, rect.filter(|r| !r.is_empty()))
+            }
+            CaptureTarget::Point {
+                logical_width,
+                x,
+                y,
+            } => {
+                let scale = buf.width as f32 / logical_width as f32;
+                let x = (x * scale) as i32;
+                let y = (y * scale) as i32;
+                match buf_pixel(drm_dev.as_ref(), &buf, x, y) {
+                    Ok(c) => {
+                        let [r, g, b] = c.map(|c| Variant::F64(c as f64 / 255.0));
+                        Ok(vec![DictEntry {
+                            key: "color".into(),
+                            value: Variant::Struct(vec![r, g, b]),
+                        }])
+                    }
+                    Err(e) => Err(ErrorFmt(e).to_string()),
+                }
+            }
+        };
+        match res {
+            Ok(results) => self.request.reply_ok(&results),
+            Err(e) => {
+                log::error!("Could not take a screenshot: {}", e);
+                self.request.reply_err(&e);
+            }
+        }
+    }
+
+    fn error(&self, msg: &str) {
+        log::error!("Could not take a screenshot: {}", msg);
+        self.request.reply_err(msg);
+    }
+}
+
+impl CapturingScreenshot {
+    fn save(
+        &self,
+        drm_dev: Option<&Rc<OwnedFd>>,
+        buf: &DmaBuf,
+        region: Option<Rect>,
+    ) -> Result<Vec<DictEntry<Cow<'static, str>, Variant<'static>>>, String> {
+        let data = buf_region_to_bytes(drm_dev, buf, region, ScreenshotFormat::Png)
+            .map_err(|e| ErrorFmt(e).to_string())?;
+        let dir = dirs::picture_dir()
+            .filter(|d| d.is_dir())
+            .unwrap_or_else(|| PathBuf::from(&self.request.state.xrd));
+        let name = Local::now()
+            .format("Screenshot_%Y-%m-%d_%H-%M-%S.png")
+            .to_string();
+        let path = dir.join(name);
+        if let Err(e) = std::fs::write(&path, data) {
+            return Err(format!(
+                "Could not write `{}`: {}",
+                path.display(),
+                ErrorFmt(e)
+            ));
+        }
+        log::info!("Saved screenshot to {}", path.display());
+        Ok(vec![DictEntry {
+            key: "uri".into(),
+            value: Variant::String(file_uri(&path).into()),
+        }])
+    }
+}
+
+fn file_uri(path: &Path) -> String {
+    let mut uri = "file://".to_string();
+    for &b in path.as_os_str().as_encoded_bytes() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(b as char)
+            }
+            _ => {
+                let _ = write!(uri, "%{:02X}", b);
+            }
+        }
+    }
+    uri
+}
+
+pub(super) fn add_screenshot_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::screenshot::*;
+    let state = state_.clone();
+    object.add_method::<Screenshot, _>(move |req, pr| {
+        dbus_screenshot(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<PickColor, _>(move |req, pr| {
+        dbus_pick_color(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(2));
+}
+
+fn create_request(
+    state: &Rc<PortalState>,
+    handle: &str,
+    app_id: &str,
+    kind: ScreenshotKind,
+    reply: ScreenshotRequestReply,
+) -> Option<Rc<ScreenshotRequest>> {
+    let request_obj = match state.dbus.add_object(handle.to_string()) {
+        Ok(r) => r,
+        Err(_) => {
+            match reply {
+                ScreenshotRequestReply::Screenshot(r) => r.err("Request handle is not unique"),
+                ScreenshotRequestReply::PickColor(r) => r.err("Request handle is not unique"),
+            }
+            return None;
+        }
+    };
+    let request = Rc::new(ScreenshotRequest {
+        id: state.id(),
+        state: state.clone(),
+        app: app_id.to_string(),
+        kind,
+        request_obj: Cell::new(None),
+        reply: Cell::new(Some(reply)),
+        phase: CloneCell::new(ScreenshotPhase::Init),
+    });
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        request_obj.add_method::<Close, _>({
+            let request = request.clone();
+            move |_, pr| {
+                request.kill();
+                pr.ok(&CloseReply);
+            }
+        });
+    }
+    request.request_obj.set(Some(request_obj));
+    state.screenshots.set(request.id, request.clone());
+    Some(request)
+}
+
+fn dbus_screenshot(
+    state: &Rc<PortalState>,
+    req: Screenshot,
+    reply: PendingReply<ScreenshotReply<'static>>,
+) {
+    log::info!("Screenshot {:#?}", req);
+    let interactive = req
+        .options
+        .iter()
+        .find(|o| o.key == "interactive")
+        .map(|o| matches!(o.value, Variant::Bool(b) if b != FALSE))
+        .unwrap_or(false);
+    let Some(request) = create_request(
+        state,
+        &req.handle.0,
+        &req.app_id,
+        ScreenshotKind::Screenshot,
+        ScreenshotRequestReply::Screenshot(reply),
+    ) else {
+        return;
+    };
+    if interactive {
+        request.start_selection(SelectionGuiMode::Buttons);
+        return;
+    }
+    let dpy = state
+        .displays
+        .lock()
+        .values()
+        .find(|d| d.outputs.len() > 0)
+        .cloned();
+    let Some(dpy) = dpy else {
+        request.reply_err("There are no running displays");
+        return;
+    };
+    let screenshot = dpy.jc.take_screenshot(false);
+    request.capture(&dpy, screenshot, CaptureTarget::Full);
+}
+
+fn dbus_pick_color(
+    state: &Rc<PortalState>,
+    req: PickColor,
+    reply: PendingReply<PickColorReply<'static>>,
+) {
+    log::info!("PickColor {:#?}", req);
+    let Some(request) = create_request(
+        state,
+        &req.handle.0,
+        &req.app_id,
+        ScreenshotKind::PickColor,
+        ScreenshotRequestReply::PickColor(reply),
+    ) else {
+        return;
+    };
+    request.start_selection(SelectionGuiMode::Point);
+}
@@ -0,0 +1,265 @@
+use {
+    crate::{
+        ifs::wl_seat::{BTN_LEFT, wl_pointer::PRESSED},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_screenshot::{CaptureTarget, ScreenshotKind, ScreenshotPhase, ScreenshotRequest},
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, Label, Orientation, OverlayWindow,
+                OverlayWindowOwner, RegionSelector, RegionSelectorOwner,
+            },
+        },
+        theme::Color,
+        utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+    },
+    std::rc::Rc,
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub enum SelectionGuiMode {
+    Buttons,
+    Region,
+    Point,
+}
+
+pub struct SelectionGui {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    mode: SelectionGuiMode,
+    surfaces: CopyHashMap<u32, Rc<SelectionGuiSurface>>,
+}
+
+pub struct SelectionGuiSurface {
+    gui: Rc<SelectionGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct StaticButton {
+    surface: Rc<SelectionGuiSurface>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    Output,
+    Window,
+    Region,
+    Reject,
+}
+
+impl SelectionGui {
+    pub fn kill(&self, upwards: bool) {
+        for surface in self.surfaces.lock().drain_values() {
+            surface.overlay.data.kill(false);
+        }
+        if let ScreenshotPhase::Selecting(s) = self.request.phase.get() {
+            s.guis.remove(&self.dpy.id);
+            if upwards && s.guis.is_empty() {
+                self.request.kill();
+            }
+        }
+    }
+}
+
+fn create_accept_gui(surface: &Rc<SelectionGuiSurface>) -> Rc<dyn GuiElement> {
+    let app = &surface.gui.request.app;
+    let text = if app.is_empty() {
+        format!("An application wants to take a screenshot")
+    } else {
+        format!("`{}` wants to take a screenshot", app)
+    };
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    let output_button = static_button(surface, ButtonRole::Output, "Capture This Output");
+    let window_button = static_button(surface, ButtonRole::Window, "Capture A Window");
+    let region_button = static_button(surface, ButtonRole::Region, "Capture A Region");
+    let reject_button = static_button(surface, ButtonRole::Reject, "Reject");
+    for button in [
+        &output_button,
+        &window_button,
+        &region_button,
+        &reject_button,
+    ] {
+        button.border_color.set(Color::from_gray_srgb(100));
+        button.border.set(2.0);
+        button.padding.set(5.0);
+    }
+    for button in [&output_button, &window_button, &region_button] {
+        button.bg_color.set(Color::from_srgb(170, 200, 170));
+        button.bg_hover_color.set(Color::from_srgb(170, 255, 170));
+    }
+    reject_button.bg_color.set(Color::from_srgb(200, 170, 170));
+    reject_button
+        .bg_hover_color
+        .set(Color::from_srgb(255, 170, 170));
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    *flow.elements.borrow_mut() = vec![
+        label,
+        output_button,
+        window_button,
+        region_button,
+        reject_button,
+    ];
+    flow
+}
+
+fn create_region_gui(surface: &Rc<SelectionGuiSurface>) -> Rc<dyn GuiElement> {
+    let selector = Rc::new(RegionSelector::default());
+    let text = match surface.gui.mode {
+        SelectionGuiMode::Point => "Click to pick a color. Right-click to cancel.",
+        _ => "Drag to select a region. Right-click to cancel.",
+    };
+    *selector.label.text.borrow_mut() = text.to_string();
+    selector
+        .single_point
+        .set(surface.gui.mode == SelectionGuiMode::Point);
+    selector.owner.set(Some(surface.clone()));
+    surface
+        .overlay
+        .data
+        .bg_color
+        .set(Color::from_srgba_straight(0, 0, 0, 64));
+    selector
+}
+
+impl OverlayWindowOwner for SelectionGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl SelectionGui {
+    pub fn new(
+        request: &Rc<ScreenshotRequest>,
+        dpy: &Rc<PortalDisplay>,
+        mode: SelectionGuiMode,
+    ) -> Rc<Self> {
+        let gui = Rc::new(SelectionGui {
+            request: request.clone(),
+            dpy: dpy.clone(),
+            mode,
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let overlay = match mode {
+                SelectionGuiMode::Buttons => OverlayWindow::new(output),
+                SelectionGuiMode::Region | SelectionGuiMode::Point => {
+                    OverlayWindow::new_fullscreen(output)
+                }
+            };
+            let sgs = Rc::new(SelectionGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay,
+            });
+            let element = match mode {
+                SelectionGuiMode::Buttons => create_accept_gui(&sgs),
+                SelectionGuiMode::Region | SelectionGuiMode::Point => create_region_gui(&sgs),
+            };
+            sgs.overlay.data.content.set(Some(element));
+            gui.dpy
+                .windows
+                .set(sgs.overlay.data.surface.id, sgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, sgs);
+        }
+        gui
+    }
+}
+
+impl SelectionGuiSurface {
+    fn stop_selecting(&self) -> bool {
+        let selecting = match self.gui.request.phase.get() {
+            ScreenshotPhase::Selecting(selecting) => selecting,
+            _ => return false,
+        };
+        for gui in selecting.guis.lock().drain_values() {
+            gui.kill(false);
+        }
+        true
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        let request = &self.surface.gui.request;
+        if self.role == ButtonRole::Reject {
+            log::info!("User has rejected the screenshot request");
+            request.kill();
+            return;
+        }
+        log::info!("User has accepted the request");
+        if !self.surface.stop_selecting() {
+            return;
+        }
+        match self.role {
+            ButtonRole::Output => {
+                request.capture_output(&self.surface.output, CaptureTarget::Full);
+            }
+            ButtonRole::Window => {
+                let dpy = &self.surface.output.dpy;
+                let selector = dpy.jc.select_toplevel(&seat.wl);
+                request.select_window(dpy, selector);
+            }
+            ButtonRole::Region => {
+                request.start_selection(SelectionGuiMode::Region);
+            }
+            ButtonRole::Reject => {}
+        }
+    }
+}
+
+impl RegionSelectorOwner for SelectionGuiSurface {
+    fn selected(&self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        let logical_width = self.overlay.data.width.get();
+        if !self.stop_selecting() {
+            return;
+        }
+        let request = &self.gui.request;
+        let target = match request.kind {
+            ScreenshotKind::PickColor => CaptureTarget::Point {
+                logical_width,
+                x: x1,
+                y: y1,
+            },
+            ScreenshotKind::Screenshot => CaptureTarget::Region {
+                logical_width,
+                x1,
+                y1,
+                x2,
+                y2,
+            },
+        };
+        request.capture_output(&self.output, target);
+    }
+
+    fn cancelled(&self) {
+        log::info!("User has aborted the selection");
+        self.gui.request.kill();
+    }
+}
+
+fn static_button(surface: &Rc<SelectionGuiSurface>, role: ButtonRole, text: &str) -> Rc<Button> {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        surface: surface.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    button
+}
@@ -4,8 +4,10 @@ use {
         pipewire::pw_con::PwCon,
         portal::{
             PORTAL_SUCCESS, PortalState,
+            ptl_global_shortcuts::GlobalShortcutsPhase,
+            ptl_input_capture::InputCapturePhase,
             ptl_remote_desktop::{DeviceTypes, RemoteDesktopPhase},
-            ptl_screencast::{ScreencastPhase, ScreencastTarget},
+            ptl_screencast::{CursorModes, ScreencastPhase, ScreencastTarget},
         },
         utils::{clonecell::CloneCell, hash_map_ext::HashMapExt},
         wire_dbus::org::freedesktop::impl_::portal::{
@@ -25,6 +27,9 @@ pub struct PortalSession {
     pub session_obj: DbusObject,
     pub sc_phase: CloneCell<ScreencastPhase>,
     pub rd_phase: CloneCell<RemoteDesktopPhase>,
+    pub gs_phase: CloneCell<GlobalShortcutsPhase>,
+    pub ic_phase: CloneCell<InputCapturePhase>,
+    pub sc_cursor_mode: Cell<CursorModes>,
     pub start_reply: Cell<Option<PortalSessionReply>>,
 }
 
@@ -56,6 +61,18 @@ impl PortalSession {
                 s.dpy.sessions.remove(self.session_obj.path());
             }
         }
+        match self.gs_phase.set(GlobalShortcutsPhase::Terminated) {
+            GlobalShortcutsPhase::Init => {}
+            GlobalShortcutsPhase::Terminated => {}
+            GlobalShortcutsPhase::Binding(s) => s.kill(),
+            GlobalShortcutsPhase::Bound(s) => s.kill(),
+        }
+        match self.ic_phase.set(InputCapturePhase::Terminated) {
+            InputCapturePhase::Init => {}
+            InputCapturePhase::Terminated => {}
+            InputCapturePhase::Selecting(s) => s.kill(),
+            InputCapturePhase::Running(s) => s.kill(),
+        }
         match self.sc_phase.set(ScreencastPhase::Terminated) {
             ScreencastPhase::Init => {}
             ScreencastPhase::SourcesSelected(_) => {}
@@ -75,7 +92,7 @@ impl PortalSession {
                 s.node.con.destroy_obj(s.node.deref());
                 s.dpy.sessions.remove(self.session_obj.path());
                 match &s.target {
-                    ScreencastTarget::Output(_) => {}
+                    ScreencastTarget::Output(_) | ScreencastTarget::Region(..) => {}
                     ScreencastTarget::Workspace(_, w, true) => {
                         s.dpy.con.remove_obj(&**w);
                     }
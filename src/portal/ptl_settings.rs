@@ -0,0 +1,117 @@
+use {
+    crate::{
+        dbus::{DbusObject, DictEntry, PendingReply, prelude::Variant},
+        portal::{PortalState, ptl_display::PortalDisplay},
+        wire_dbus::org::freedesktop::impl_::portal::settings::{
+            Read, ReadAll, ReadAllReply, ReadReply, SettingChanged,
+        },
+        wl_usr::usr_ifs::usr_jay_portal_settings::{
+            PortalSettingsValues, UsrJayPortalSettingsOwner,
+        },
+    },
+    std::{borrow::Cow, rc::Rc},
+};
+
+const NAMESPACE: &str = "org.freedesktop.appearance";
+
+const KEYS: [&str; 4] = ["color-scheme", "accent-color", "contrast", "reduced-motion"];
+
+fn value(values: &PortalSettingsValues, key: &str) -> Option<Variant<'static>> {
+    let v = match key {
+        "color-scheme" => Variant::U32(values.color_scheme),
+        "accent-color" => {
+            // Values outside of [0, 1] tell applications that no accent color is set.
+            let [r, g, b] = values.accent_color.unwrap_or([-1.0; 3]);
+            Variant::Struct(vec![Variant::F64(r), Variant::F64(g), Variant::F64(b)])
+        }
+        "contrast" => Variant::U32(values.contrast),
+        "reduced-motion" => Variant::U32(values.reduced_motion as u32),
+        _ => return None,
+    };
+    Some(v)
+}
+
+fn namespace_matches(pattern: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => NAMESPACE.starts_with(prefix),
+        None => pattern.is_empty() || pattern == NAMESPACE,
+    }
+}
+
+impl UsrJayPortalSettingsOwner for PortalDisplay {
+    fn settings(&self, values: PortalSettingsValues) {
+        let old = self.state.settings.replace(values);
+        let changed = [
+            ("color-scheme", old.color_scheme != values.color_scheme),
+            ("accent-color", old.accent_color != values.accent_color),
+            ("contrast", old.contrast != values.contrast),
+            (
+                "reduced-motion",
+                old.reduced_motion != values.reduced_motion,
+            ),
+        ];
+        for (key, changed) in changed {
+            if changed && let Some(value) = value(&values, key) {
+                self.state.dbus.emit_signal(
+                    "/org/freedesktop/portal/desktop",
+                    &SettingChanged {
+                        namespace: NAMESPACE.into(),
+                        key: key.into(),
+                        value,
+                    },
+                );
+            }
+        }
+    }
+}
+
+pub(super) fn add_settings_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use crate::wire_dbus::org::freedesktop::impl_::portal::settings::*;
+    let state = state_.clone();
+    object.add_method::<ReadAll, _>(move |req, pr| {
+        dbus_read_all(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<Read, _>(move |req, pr| {
+        dbus_read(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_read_all(
+    state: &Rc<PortalState>,
+    req: ReadAll,
+    reply: PendingReply<ReadAllReply<'static>>,
+) {
+    let mut res = vec![];
+    if req.namespaces.is_empty() || req.namespaces.iter().any(|n| namespace_matches(n)) {
+        let values = state.settings.get();
+        let settings = KEYS
+            .into_iter()
+            .filter_map(|key| {
+                value(&values, key).map(|value| DictEntry {
+                    key: key.into(),
+                    value,
+                })
+            })
+            .collect::<Vec<_>>();
+        res.push(DictEntry {
+            key: NAMESPACE.into(),
+            value: Cow::Owned(settings),
+        });
+    }
+    reply.ok(&ReadAllReply {
+        value: Cow::Owned(res),
+    });
+}
+
+fn dbus_read(state: &Rc<PortalState>, req: Read, reply: PendingReply<ReadReply<'static>>) {
+    if req.namespace != NAMESPACE {
+        reply.err("Requested setting not found");
+        return;
+    }
+    match value(&state.settings.get(), &req.key) {
+        Some(value) => reply.ok(&ReadReply { value }),
+        None => reply.err("Requested setting not found"),
+    }
+}
@@ -9,7 +9,11 @@ use {
         gfx_api::{
             AcquireSync, GfxContext, GfxFramebuffer, GfxTexture, ReleaseSync, needs_render_usage,
         },
-        ifs::zwlr_layer_shell_v1::OVERLAY,
+        ifs::{
+            wl_seat::{BTN_LEFT, BTN_RIGHT, wl_pointer::PRESSED},
+            wl_surface::zwlr_layer_surface_v1::{BOTTOM, LEFT, RIGHT, TOP},
+            zwlr_layer_shell_v1::OVERLAY,
+        },
         portal::{
             ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
             ptl_text::{self, TextMeasurement},
@@ -79,6 +83,13 @@ pub trait GuiElement {
         false
     }
 
+    fn motion(&self, seat: &PortalSeat, x: f32, y: f32) -> bool {
+        let _ = seat;
+        let _ = x;
+        let _ = y;
+        false
+    }
+
     fn destroy(&self) {}
 }
 
@@ -474,10 +485,137 @@ impl GuiElement for Flow {
     }
 }
 
+pub struct RegionSelector {
+    pub data: GuiElementData,
+    pub label: Rc<Label>,
+    pub single_point: Cell<bool>,
+    pub border_color: Cell<Color>,
+    pub start: Cell<Option<(f32, f32)>>,
+    pub pos: Cell<(f32, f32)>,
+    pub owner: CloneCell<Option<Rc<dyn RegionSelectorOwner>>>,
+}
+
+pub trait RegionSelectorOwner {
+    fn selected(&self, x1: f32, y1: f32, x2: f32, y2: f32);
+    fn cancelled(&self);
+}
+
+impl Default for RegionSelector {
+    fn default() -> Self {
+        Self {
+            data: Default::default(),
+            label: Default::default(),
+            single_point: Cell::new(false),
+            border_color: Cell::new(Color::from_gray_srgb(255)),
+            start: Default::default(),
+            pos: Default::default(),
+            owner: Default::default(),
+        }
+    }
+}
+
+impl RegionSelector {
+    fn selection(&self) -> Option<(f32, f32, f32, f32)> {
+        let (x1, y1) = self.start.get()?;
+        let (x2, y2) = self.pos.get();
+        Some((x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)))
+    }
+}
+
+impl GuiElement for RegionSelector {
+    fn data(&self) -> &GuiElementData {
+        &self.data
+    }
+
+    fn layout(
+        &self,
+        ctx: &Rc<dyn GfxContext>,
+        scale: f32,
+        max_width: f32,
+        max_height: f32,
+    ) -> (f32, f32) {
+        let (width, height) = self.label.layout(ctx, scale, max_width, max_height);
+        self.label.data.width.set(width);
+        self.label.data.height.set(height);
+        self.label.data.x.set(((max_width - width) / 2.0).round());
+        self.label.data.y.set(height);
+        (max_width, max_height)
+    }
+
+    fn render_at(&self, color_manager: &ColorManager, r: &mut RendererBase, x: f32, y: f32) {
+        self.label.render_at(
+            color_manager,
+            r,
+            x + self.label.data.x.get(),
+            y + self.label.data.y.get(),
+        );
+        let Some((x1, y1, x2, y2)) = self.selection() else {
+            return;
+        };
+        let (x1, y1, x2, y2) = (x + x1, y + y1, x + x2, y + y2);
+        let rects = [
+            (x1 - 1.0, y1 - 1.0, x2 + 1.0, y1),
+            (x1 - 1.0, y2, x2 + 1.0, y2 + 1.0),
+            (x1 - 1.0, y1, x1, y2),
+            (x2, y1, x2 + 1.0, y2),
+        ];
+        let srgb = &color_manager.srgb_gamma22().linear;
+        r.fill_boxes_f(&rects, &self.border_color.get(), srgb);
+    }
+
+    fn child_at(&self, _x: f32, _y: f32) -> Option<Rc<dyn GuiElement>> {
+        None
+    }
+
+    fn hover_cursor(&self) -> KnownCursor {
+        KnownCursor::Crosshair
+    }
+
+    fn motion(&self, _seat: &PortalSeat, x: f32, y: f32) -> bool {
+        self.pos.set((x, y));
+        self.start.get().is_some()
+    }
+
+    fn button(&self, _seat: &PortalSeat, button: u32, state: u32) {
+        let Some(owner) = self.owner.get() else {
+            return;
+        };
+        if button == BTN_RIGHT {
+            if state == PRESSED {
+                owner.cancelled();
+            }
+            return;
+        }
+        if button != BTN_LEFT {
+            return;
+        }
+        let (x, y) = self.pos.get();
+        if self.single_point.get() {
+            if state == PRESSED {
+                owner.selected(x, y, x, y);
+            }
+            return;
+        }
+        if state == PRESSED {
+            self.start.set(Some((x, y)));
+            return;
+        }
+        if let Some((x1, y1, x2, y2)) = self.selection() {
+            self.start.set(None);
+            owner.selected(x1, y1, x2, y2);
+        }
+    }
+
+    fn destroy(&self) {
+        self.owner.take();
+    }
+}
+
 pub struct OverlayWindow {
     pub layer_surface: Rc<UsrWlrLayerSurface>,
     pub data: Rc<WindowData>,
     pub owner: CloneCell<Option<Rc<dyn OverlayWindowOwner>>>,
+    pub fullscreen: bool,
 }
 
 pub trait OverlayWindowOwner {
@@ -500,6 +638,8 @@ pub struct WindowData {
     pending_bufs: CopyHashMap<ZwpLinuxBufferParamsV1Id, Rc<GuiBufferPending>>,
     pub width: Cell<i32>,
     pub height: Cell<i32>,
+    pub fixed_size: Cell<Option<(i32, i32)>>,
+    pub bg_color: Cell<Color>,
     pub owner: CloneCell<Option<Rc<dyn WindowDataOwner>>>,
     pub seats: CopyHashMap<u32, Rc<GuiWindowSeatState>>,
 }
@@ -519,8 +659,10 @@ pub trait WindowDataOwner {
 
 impl WindowDataOwner for OverlayWindow {
     fn post_layout(&self) {
-        self.layer_surface
-            .set_size(self.data.width.get(), self.data.height.get());
+        if !self.fullscreen {
+            self.layer_surface
+                .set_size(self.data.width.get(), self.data.height.get());
+        }
         self.data.surface.commit();
     }
 
@@ -538,16 +680,32 @@ const NUM_BUFFERS: usize = 2;
 
 impl OverlayWindow {
     pub fn new(output: &Rc<PortalOutput>) -> Rc<Self> {
+        Self::create(output, false)
+    }
+
+    pub fn new_fullscreen(output: &Rc<PortalOutput>) -> Rc<Self> {
+        Self::create(output, true)
+    }
+
+    fn create(output: &Rc<PortalOutput>, fullscreen: bool) -> Rc<Self> {
         let data = WindowData::new(&output.dpy);
         let layer_surface = output
             .dpy
             .ls
             .get_layer_surface(&data.surface, &output.wl, OVERLAY);
-        layer_surface.set_size(1, 1);
+        if fullscreen {
+            data.fixed_size.set(Some((1, 1)));
+            layer_surface.set_size(0, 0);
+            layer_surface.set_anchor(TOP | BOTTOM | LEFT | RIGHT);
+            layer_surface.set_exclusive_zone(-1);
+        } else {
+            layer_surface.set_size(1, 1);
+        }
         let slf = Rc::new(Self {
             layer_surface,
             data,
             owner: Default::default(),
+            fullscreen,
         });
         slf.data.owner.set(Some(slf.clone()));
         slf.layer_surface.owner.set(Some(slf.clone()));
@@ -574,6 +732,8 @@ impl WindowData {
             pending_bufs: Default::default(),
             width: Cell::new(0),
             height: Cell::new(0),
+            fixed_size: Default::default(),
+            bg_color: Cell::new(Color::from_gray_srgb(0)),
             owner: Default::default(),
             render_trigger: Default::default(),
             render_task: Cell::new(None),
@@ -604,8 +764,13 @@ impl WindowData {
             Some(c) => c,
             _ => return,
         };
-        let (mut width, mut height) =
-            content.layout(&ctx.ctx.ctx, scale, f32::INFINITY, f32::INFINITY);
+        let (mut width, mut height) = match self.fixed_size.get() {
+            Some((width, height)) => {
+                content.layout(&ctx.ctx.ctx, scale, width as f32, height as f32);
+                (width as f32, height as f32)
+            }
+            _ => content.layout(&ctx.ctx.ctx, scale, f32::INFINITY, f32::INFINITY),
+        };
         content.data().width.set(width);
         content.data().height.set(height);
         width = width.max(1.0);
@@ -646,7 +811,7 @@ impl WindowData {
             ReleaseSync::Implicit,
             self.dpy.state.color_manager.srgb_gamma22(),
             self.scale.get(),
-            Some(&Color::from_gray_srgb(0)),
+            Some(&self.bg_color.get()),
             &self.dpy.state.color_manager.srgb_gamma22().linear,
             None,
             self.dpy.state.color_manager.srgb_linear(),
@@ -834,7 +999,11 @@ impl WindowData {
             }
         }
 
-        if element_changed {
+        if let Some(e) = &new_element
+            && e.motion(pseat, x, y)
+        {
+            self.schedule_render();
+        } else if element_changed {
             self.schedule_render();
         }
 
@@ -899,7 +1068,14 @@ impl UsrWpFractionalScaleOwner for WindowData {
 }
 
 impl UsrWlrLayerSurfaceOwner for OverlayWindow {
-    fn configure(&self, _ev: &Configure) {
+    fn configure(&self, ev: &Configure) {
+        if self.fullscreen && ev.width > 0 && ev.height > 0 {
+            let size = (ev.width as i32, ev.height as i32);
+            if self.data.fixed_size.replace(Some(size)) != Some(size) {
+                self.data.layout();
+                self.data.allocate_buffers();
+            }
+        }
         self.data.schedule_render();
     }
 
@@ -220,7 +220,7 @@ where
         dx * dx + dy * dy
     }
 
-    #[expect(dead_code)]
+    #[cfg_attr(not(feature = "it"), expect(dead_code))]
     pub fn contains_rect<U>(&self, rect: &Rect<U>) -> bool
     where
         U: Tag,
@@ -1,12 +1,16 @@
 use {
     crate::{
-        gfx_api::{AcquireSync, GfxApiOpt, ReleaseSync, SampleRect},
+        fixed::Fixed,
+        gfx_api::{AcquireSync, FramebufferRect, GfxApiOpt, ReleaseSync, SampleRect},
         icons::{IconState, SizedIcons},
-        ifs::wl_surface::{
-            SurfaceBuffer, WlSurface,
-            x_surface::xwindow::Xwindow,
-            xdg_surface::{XdgSurface, xdg_toplevel::XdgToplevel},
-            zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+        ifs::{
+            wl_seat::window_switcher::WindowSwitcher,
+            wl_surface::{
+                SurfaceBuffer, WlSurface,
+                x_surface::xwindow::Xwindow,
+                xdg_surface::{XdgSurface, xdg_toplevel::XdgToplevel},
+                zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+            },
         },
         rect::Rect,
         renderer::renderer_base::RendererBase,
@@ -14,8 +18,8 @@ use {
         state::State,
         theme::Color,
         tree::{
-            ContainerNode, DisplayNode, FloatNode, OutputNode, PlaceholderNode, ToplevelData,
-            ToplevelNodeBase, WorkspaceNode,
+            ContainerNode, DisplayNode, FloatNode, Node, OutputNode, Overview, PlaceholderNode,
+            ScrollingNode, ToplevelData, ToplevelNodeBase, WorkspaceNode,
         },
     },
     std::{ops::Deref, rc::Rc, slice},
@@ -23,6 +27,10 @@ use {
 
 pub mod renderer_base;
 
+/// The magnification of the color picker lens. This is odd so that the magnified pixel
+/// below the cursor is centered on the cursor.
+const COLOR_PICKER_ZOOM: i32 = 9;
+
 pub struct Renderer<'a> {
     pub base: RendererBase<'a>,
     pub state: &'a State,
@@ -64,6 +72,8 @@ impl Renderer<'_> {
             return;
         }
         let opos = output.global.pos.get();
+        let (output_x, output_y) = (x, y);
+        let overview = output.overview.get();
         macro_rules! render_layer {
             ($layer:expr) => {
                 for ls in $layer.iter() {
@@ -74,7 +84,9 @@ impl Renderer<'_> {
             };
         }
         let mut fullscreen = None;
-        if let Some(ws) = output.workspace.get() {
+        if overview.is_none()
+            && let Some(ws) = output.workspace.get()
+        {
             fullscreen = ws.fullscreen.get();
         }
         let theme = &self.state.theme;
@@ -168,7 +180,9 @@ impl Renderer<'_> {
                 }
                 y += th + 1;
             }
-            if let Some(ws) = output.workspace.get() {
+            if let Some(overview) = &overview {
+                self.render_overview(output, overview, output_x, output_y);
+            } else if let Some(ws) = output.workspace.get() {
                 self.render_workspace(&ws, x, y);
             }
         }
@@ -186,13 +200,16 @@ impl Renderer<'_> {
                 }
             };
         }
-        render_stacked!(self.state.root.stacked);
+        if overview.is_none() {
+            render_stacked!(self.state.root.stacked);
+        }
         if fullscreen.is_none() {
             render_layer!(output.layers[2]);
         }
         render_layer!(output.layers[3]);
         render_stacked!(self.state.root.stacked_above_layers);
-        if let Some(ws) = output.workspace.get()
+        if overview.is_none()
+            && let Some(ws) = output.workspace.get()
             && ws.render_highlight.get() > 0
         {
             let color = self.state.theme.colors.highlight.get();
@@ -200,12 +217,169 @@ impl Renderer<'_> {
             self.base.ops.push(GfxApiOpt::Sync);
             self.base.fill_boxes(&[bounds], &color, srgb);
         }
+        if let Some(switcher) = output.window_switcher.get() {
+            self.base.ops.push(GfxApiOpt::Sync);
+            self.render_window_switcher(output, &switcher, output_x, output_y);
+        }
     }
 
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
         if let Some(node) = workspace.container.get() {
             self.render_container(&node, x, y)
         }
+        if let Some(node) = workspace.scrolling.get() {
+            self.render_scrolling(&node, x, y)
+        }
+    }
+
+    pub fn render_overview(&mut self, output: &OutputNode, overview: &Overview, x: i32, y: i32) {
+        let opos = output.global.pos.get();
+        let (dx, dy) = (x - opos.x1(), y - opos.y1());
+        let colors = &self.state.theme.colors;
+        let bw = self.state.theme.sizes.border_width.get().max(1);
+        let srgb_srgb = self.state.color_manager.srgb_gamma22();
+        let srgb = &srgb_srgb.linear;
+        let active = output.workspace.get().map(|ws| ws.id);
+        let selected = overview.selected.get().map(|s| s.node_id());
+        let layout = output.overview_layout();
+        for ows in &layout {
+            let rect = ows.rect.move_(dx, dy);
+            let color = match Some(ows.workspace.id) == active {
+                true => colors.focused_title_background.get(),
+                false => colors.unfocused_title_background.get(),
+            };
+            self.base.fill_boxes(&outline(&rect, bw), &color, srgb);
+            let prect = self.base.scale_rect(rect);
+            if !prect.is_empty()
+                && let Some((tex, sync)) = overview.thumbnail(&ows.workspace)
+            {
+                self.base.render_texture(
+                    &tex,
+                    None,
+                    prect.x1(),
+                    prect.y1(),
+                    None,
+                    Some((prect.width(), prect.height())),
+                    self.base.scale,
+                    None,
+                    None,
+                    sync,
+                    ReleaseSync::None,
+                    true,
+                    srgb_srgb,
+                );
+            }
+            for window in &ows.windows {
+                if Some(window.window.node_id()) == selected {
+                    let color = colors.focused_title_background.get();
+                    let rect = window.rect.move_(dx, dy);
+                    self.base.fill_boxes(&outline(&rect, bw), &color, srgb);
+                }
+            }
+        }
+        if let Some(drag) = overview.drag(&layout) {
+            if let Some(target) = drag.target {
+                self.render_highlight(&target.move_(dx, dy));
+            }
+            self.render_highlight(&drag.window.move_(dx, dy));
+        }
+    }
+
+    pub fn render_window_switcher(
+        &mut self,
+        output: &OutputNode,
+        switcher: &WindowSwitcher,
+        x: i32,
+        y: i32,
+    ) {
+        let opos = output.global.pos.get();
+        let (dx, dy) = (x - opos.x1(), y - opos.y1());
+        let colors = &self.state.theme.colors;
+        let bw = self.state.theme.sizes.border_width.get().max(1);
+        let srgb_srgb = self.state.color_manager.srgb_gamma22();
+        let srgb = &srgb_srgb.linear;
+        let layout = switcher.layout();
+        let panel = layout.panel.move_(dx, dy);
+        self.base.fill_boxes(
+            slice::from_ref(&panel),
+            &colors.unfocused_title_background.get(),
+            srgb,
+        );
+        self.base
+            .fill_boxes(&outline(&panel, bw), &colors.border.get(), srgb);
+        let selected = switcher.selected.get();
+        for row in &layout.rows {
+            let entry = &switcher.entries[row.entry];
+            if Some(row.entry) == selected {
+                let rect = row.rect.move_(dx, dy);
+                self.base.fill_boxes(
+                    slice::from_ref(&rect),
+                    &colors.focused_title_background.get(),
+                    srgb,
+                );
+            }
+            if let Some(thumbnail) = row.thumbnail {
+                let rect = thumbnail.move_(dx, dy);
+                let prect = self.base.scale_rect(rect);
+                if !prect.is_empty()
+                    && let Some((tex, sync)) = switcher.thumbnail(entry)
+                {
+                    self.base.render_texture(
+                        &tex,
+                        None,
+                        prect.x1(),
+                        prect.y1(),
+                        None,
+                        Some((prect.width(), prect.height())),
+                        self.base.scale,
+                        None,
+                        None,
+                        sync,
+                        ReleaseSync::None,
+                        true,
+                        srgb_srgb,
+                    );
+                }
+            }
+            if let Some(title) = &entry.title
+                && let Some(texture) = title.texture()
+            {
+                let rect = row.title.move_(dx, dy);
+                let bounds = self.base.scale_rect(rect);
+                let (x, y) = self.base.scale_point(rect.x1(), rect.y1());
+                self.base.render_texture(
+                    &texture,
+                    None,
+                    x,
+                    y,
+                    None,
+                    None,
+                    self.base.scale,
+                    Some(&bounds),
+                    None,
+                    AcquireSync::None,
+                    ReleaseSync::None,
+                    false,
+                    srgb_srgb,
+                );
+            }
+        }
+    }
+
+    pub fn render_scrolling(&mut self, scrolling: &ScrollingNode, x: i32, y: i32) {
+        let pos = scrolling.node_absolute_position();
+        for column in scrolling.columns.iter() {
+            let body = column.node.node_absolute_position();
+            if !body.intersects(&pos) {
+                continue;
+            }
+            column.node.node_render(
+                self,
+                x + body.x1() - pos.x1(),
+                y + body.y1() - pos.y1(),
+                None,
+            );
+        }
     }
 
     pub fn render_placeholder(
@@ -381,6 +555,55 @@ impl Renderer<'_> {
         );
     }
 
+    pub fn render_color_picker_lens(&mut self, scene: usize, lens: &Rect, x: Fixed, y: Fixed) {
+        let cd = &self.state.color_manager.srgb_gamma22().linear;
+        let border = self.state.theme.colors.border.get();
+        let base = &mut self.base;
+        let lens = base.scale_rect(*lens);
+        let (x, y) = base.scale_point_f(x.to_f64() as f32, y.to_f64() as f32);
+        let (x, y) = (x.floor() as i32, y.floor() as i32);
+        let fb_rect = |rect: &Rect| {
+            FramebufferRect::new(
+                rect.x1() as f32,
+                rect.y1() as f32,
+                rect.x2() as f32,
+                rect.y2() as f32,
+                base.transform,
+                base.fb_width,
+                base.fb_height,
+            )
+        };
+        let clip = fb_rect(&lens);
+        let center = fb_rect(&Rect::new_sized_unchecked(x, y, 1, 1));
+        base.ops.push(GfxApiOpt::Sync);
+        base.fill_scaled_boxes(slice::from_ref(&lens), &Color::SOLID_BLACK, None, cd);
+        base.copy_magnified(
+            0..scene,
+            &clip,
+            COLOR_PICKER_ZOOM as f32,
+            (center.x1 + center.x2) / 2.0,
+            (center.y1 + center.y2) / 2.0,
+        );
+        base.ops.push(GfxApiOpt::Sync);
+        let half = COLOR_PICKER_ZOOM / 2;
+        let pixel =
+            Rect::new_sized_unchecked(x - half, y - half, COLOR_PICKER_ZOOM, COLOR_PICKER_ZOOM);
+        let grow = |rect: &Rect, n: i32| {
+            Rect::new_unchecked(rect.x1() - n, rect.y1() - n, rect.x2() + n, rect.y2() + n)
+        };
+        // The border of the lens is drawn inside of the lens so that it stays within the
+        // damaged area.
+        for (outer, rect) in [(lens, grow(&lens, -2)), (grow(&pixel, 1), pixel)] {
+            let boxes = [
+                Rect::new_unchecked(outer.x1(), outer.y1(), outer.x2(), rect.y1()),
+                Rect::new_unchecked(outer.x1(), rect.y2(), outer.x2(), outer.y2()),
+                Rect::new_unchecked(outer.x1(), rect.y1(), rect.x1(), rect.y2()),
+                Rect::new_unchecked(rect.x2(), rect.y1(), outer.x2(), rect.y2()),
+            ];
+            base.fill_scaled_boxes(&boxes, &border, None, cd);
+        }
+    }
+
     pub fn render_surface(&mut self, surface: &WlSurface, x: i32, y: i32, bounds: Option<&Rect>) {
         let (x, y) = self.base.scale_point(x, y);
         self.render_surface_scaled(surface, x, y, None, bounds, false);
@@ -617,3 +840,13 @@ impl Renderer<'_> {
         region.contains_rect2(&bounds, |r| self.base.scale_rect(*r))
     }
 }
+
+fn outline(rect: &Rect, width: i32) -> [Rect; 4] {
+    let (x1, y1, x2, y2) = (rect.x1(), rect.y1(), rect.x2(), rect.y2());
+    [
+        Rect::new_unchecked(x1 - width, y1 - width, x2 + width, y1),
+        Rect::new_unchecked(x1 - width, y2, x2 + width, y2 + width),
+        Rect::new_unchecked(x1 - width, y1, x1, y2),
+        Rect::new_unchecked(x2, y1, x2 + width, y2),
+    ]
+}
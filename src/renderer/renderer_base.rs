@@ -11,7 +11,7 @@ use {
         utils::transform_ext::TransformExt,
     },
     jay_config::video::Transform,
-    std::rc::Rc,
+    std::{ops::Range, rc::Rc},
 };
 
 pub struct RendererBase<'a> {
@@ -227,6 +227,100 @@ impl RendererBase<'_> {
             cd: cd.clone(),
         }));
     }
+
+    /// Appends copies of the ops in `range`, magnified by `zoom` around the point `(x, y)`
+    /// and clipped to `clip`. The coordinates are normalized device coordinates.
+    ///
+    /// The copies do not perform any synchronization. The original ops must be part of
+    /// the same render pass.
+    pub fn copy_magnified(
+        &mut self,
+        range: Range<usize>,
+        clip: &FramebufferRect,
+        zoom: f32,
+        x: f32,
+        y: f32,
+    ) {
+        for idx in range {
+            let opt = match &self.ops[idx] {
+                GfxApiOpt::Sync => continue,
+                GfxApiOpt::FillRect(fr) => {
+                    let Some((rect, _)) = magnify_rect(&fr.rect, clip, zoom, x, y) else {
+                        continue;
+                    };
+                    GfxApiOpt::FillRect(FillRect {
+                        rect,
+                        color: fr.color,
+                        alpha: fr.alpha,
+                        cd: fr.cd.clone(),
+                    })
+                }
+                GfxApiOpt::CopyTexture(ct) => {
+                    let Some((target, [x1, y1, x2, y2])) =
+                        magnify_rect(&ct.target, clip, zoom, x, y)
+                    else {
+                        continue;
+                    };
+                    let s = &ct.source;
+                    let (width, height) = (s.x2 - s.x1, s.y2 - s.y1);
+                    GfxApiOpt::CopyTexture(CopyTexture {
+                        tex: ct.tex.clone(),
+                        source: SampleRect {
+                            x1: s.x1 + x1 * width,
+                            y1: s.y1 + y1 * height,
+                            x2: s.x1 + x2 * width,
+                            y2: s.y1 + y2 * height,
+                            buffer_transform: s.buffer_transform,
+                        },
+                        target,
+                        buffer_resv: None,
+                        acquire_sync: AcquireSync::Unnecessary,
+                        release_sync: ReleaseSync::None,
+                        alpha: ct.alpha,
+                        opaque: ct.opaque,
+                        cd: ct.cd.clone(),
+                    })
+                }
+            };
+            self.ops.push(opt);
+        }
+    }
+}
+
+/// Magnifies and clips `rect`. Returns the new rect and the visible part of the
+/// magnified rect, relative to its size.
+fn magnify_rect(
+    rect: &FramebufferRect,
+    clip: &FramebufferRect,
+    zoom: f32,
+    x: f32,
+    y: f32,
+) -> Option<(FramebufferRect, [f32; 4])> {
+    let x1 = (rect.x1 - x) * zoom + x;
+    let x2 = (rect.x2 - x) * zoom + x;
+    let y1 = (rect.y1 - y) * zoom + y;
+    let y2 = (rect.y2 - y) * zoom + y;
+    let cx1 = x1.max(clip.x1);
+    let cx2 = x2.min(clip.x2);
+    let cy1 = y1.max(clip.y1);
+    let cy2 = y2.min(clip.y2);
+    if cx1 >= cx2 || cy1 >= cy2 {
+        return None;
+    }
+    let rel = [
+        (cx1 - x1) / (x2 - x1),
+        (cy1 - y1) / (y2 - y1),
+        (cx2 - x1) / (x2 - x1),
+        (cy2 - y1) / (y2 - y1),
+    ];
+    let rect = FramebufferRect {
+        x1: cx1,
+        x2: cx2,
+        y1: cy1,
+        y2: cy2,
+        output_transform: rect.output_transform,
+    };
+    Some((rect, rel))
 }
 
 #[inline]
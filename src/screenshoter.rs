@@ -1,10 +1,13 @@
 use {
     crate::{
         allocator::{AllocatorError, BO_USE_RENDERING, BufferObject, BufferUsage},
-        format::XRGB8888,
+        cmm::cmm_description::ColorDescription,
+        format::{ABGR16161616F, Format, XRGB8888},
         gfx_api::{AcquireSync, GfxError, ReleaseSync, needs_render_usage},
+        rect::Rect,
         scale::Scale,
         state::State,
+        tree::{Node, OutputNode, ToplevelNode},
         video::drm::DrmError,
     },
     indexmap::IndexMap,
@@ -26,10 +29,16 @@ pub enum ScreenshooterError {
     RenderError(#[from] GfxError),
     #[error(transparent)]
     DrmError(#[from] DrmError),
-    #[error("Render context does not support XRGB8888")]
-    XRGB8888,
-    #[error("Render context supports no modifiers for XRGB8888 rendering")]
-    Modifiers,
+    #[error("Render context does not support {0}")]
+    UnsupportedFormat(&'static str),
+    #[error("Render context supports no modifiers for {0} rendering")]
+    Modifiers(&'static str),
+    #[error("Screenshots cannot be taken in format {0}")]
+    NoColorDescription(&'static str),
+    #[error("Window is not visible")]
+    InvisibleWindow,
+    #[error("The region does not intersect any output")]
+    EmptyRegion,
 }
 
 pub struct Screenshot {
@@ -37,21 +46,118 @@ pub struct Screenshot {
     pub bo: Rc<dyn BufferObject>,
 }
 
+/// Returns the color description that screenshots in the format are rendered in.
+///
+/// 8-bit formats use sRGB. Float formats use scRGB so that HDR content is not clipped.
+pub fn screenshot_color_description<'a>(
+    state: &'a State,
+    format: &Format,
+) -> Option<&'a Rc<ColorDescription>> {
+    if format.drm == XRGB8888.drm {
+        Some(state.color_manager.srgb_gamma22())
+    } else if format.drm == ABGR16161616F.drm {
+        Some(state.color_manager.windows_scrgb())
+    } else {
+        None
+    }
+}
+
 pub fn take_screenshot(
     state: &State,
     include_cursor: bool,
+    format: &'static Format,
 ) -> Result<Screenshot, ScreenshooterError> {
-    let ctx = match state.render_ctx.get() {
-        Some(ctx) => ctx,
-        _ => return Err(ScreenshooterError::NoRenderContext),
-    };
     let extents = state.root.extents.get();
     if extents.is_empty() {
         return Err(ScreenshooterError::EmptyDisplay);
     }
+    render_screenshot(
+        state,
+        state.root.deref(),
+        extents,
+        Scale::from_int(1),
+        include_cursor,
+        format,
+    )
+}
+
+pub fn take_output_screenshot(
+    state: &State,
+    output: &OutputNode,
+    include_cursor: bool,
+    format: &'static Format,
+) -> Result<Screenshot, ScreenshooterError> {
+    let pos = output.global.pos.get();
+    if pos.is_empty() {
+        return Err(ScreenshooterError::EmptyDisplay);
+    }
+    let scale = output.global.persistent.scale.get();
+    render_screenshot(state, output, pos, scale, include_cursor, format)
+}
+
+pub fn take_region_screenshot(
+    state: &State,
+    rect: Rect,
+    include_cursor: bool,
+    format: &'static Format,
+) -> Result<Screenshot, ScreenshooterError> {
+    let rect = rect.intersect(state.root.extents.get());
+    let mut scale = None;
+    for output in state.root.outputs.lock().values() {
+        if output.global.pos.get().intersects(&rect) {
+            let s = output.global.persistent.scale.get();
+            scale = Some(scale.map_or(s, |scale: Scale| scale.max(s)));
+        }
+    }
+    let Some(scale) = scale else {
+        return Err(ScreenshooterError::EmptyRegion);
+    };
+    render_screenshot(
+        state,
+        state.root.deref(),
+        rect,
+        scale,
+        include_cursor,
+        format,
+    )
+}
+
+pub fn take_toplevel_screenshot(
+    state: &State,
+    tl: &dyn ToplevelNode,
+    include_cursor: bool,
+    format: &'static Format,
+) -> Result<Screenshot, ScreenshooterError> {
+    let data = tl.tl_data();
+    let pos = tl.node_absolute_position();
+    if !data.visible.get() || pos.is_empty() {
+        return Err(ScreenshooterError::InvisibleWindow);
+    }
+    let scale = match data.workspace.get() {
+        None => Scale::default(),
+        Some(w) => w.output.get().global.persistent.scale.get(),
+    };
+    render_screenshot(state, tl, pos, scale, include_cursor, format)
+}
+
+fn render_screenshot(
+    state: &State,
+    node: &dyn Node,
+    rect: Rect,
+    scale: Scale,
+    include_cursor: bool,
+    format: &'static Format,
+) -> Result<Screenshot, ScreenshooterError> {
+    let Some(cd) = screenshot_color_description(state, format) else {
+        return Err(ScreenshooterError::NoColorDescription(format.name));
+    };
+    let ctx = match state.render_ctx.get() {
+        Some(ctx) => ctx,
+        _ => return Err(ScreenshooterError::NoRenderContext),
+    };
     let formats = ctx.formats();
-    let modifiers: IndexMap<_, _> = match formats.get(&XRGB8888.drm) {
-        None => return Err(ScreenshooterError::XRGB8888),
+    let modifiers: IndexMap<_, _> = match formats.get(&format.drm) {
+        None => return Err(ScreenshooterError::UnsupportedFormat(format.name)),
         Some(f) => f
             .write_modifiers
             .iter()
@@ -59,7 +165,7 @@ pub fn take_screenshot(
             .collect(),
     };
     if modifiers.is_empty() {
-        return Err(ScreenshooterError::Modifiers);
+        return Err(ScreenshooterError::Modifiers(format.name));
     }
     let mut usage = BO_USE_RENDERING;
     if !needs_render_usage(modifiers.values().copied()) {
@@ -67,23 +173,17 @@ pub fn take_screenshot(
     }
     let modifiers: Vec<_> = modifiers.keys().copied().copied().collect();
     let allocator = ctx.allocator();
-    let bo = allocator.create_bo(
-        &state.dma_buf_ids,
-        extents.width(),
-        extents.height(),
-        XRGB8888,
-        &modifiers,
-        usage,
-    )?;
+    let [width, height] = scale.pixel_size([rect.width(), rect.height()]);
+    let bo = allocator.create_bo(&state.dma_buf_ids, width, height, format, &modifiers, usage)?;
     let fb = ctx.clone().dmabuf_fb(bo.dmabuf())?;
     fb.render_node(
         AcquireSync::Unnecessary,
         ReleaseSync::Implicit,
-        state.color_manager.srgb_gamma22(),
-        state.root.deref(),
+        cd,
+        node,
         state,
-        Some(state.root.extents.get()),
-        Scale::from_int(1),
+        Some(rect),
+        scale,
         include_cursor,
         true,
         false,
@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::{
         acceptor::Acceptor,
@@ -48,6 +51,9 @@ use {
                 DataOfferIds, DataSourceIds, data_control::DataControlDeviceIds,
                 x_data_device::XIpcDeviceIds,
             },
+            jay_global_shortcuts::JayGlobalShortcuts,
+            jay_input_capture::JayInputCapture,
+            jay_portal_settings::PortalSettings,
             jay_render_ctx::JayRenderCtx,
             jay_screencast::JayScreencast,
             jay_seat_events::JaySeatEvents,
@@ -57,7 +63,11 @@ use {
             wl_seat::{
                 PhysicalKeyboardId, PhysicalKeyboardIds, PositionHintRequest, SeatIds,
                 WlSeatGlobal,
-                tablet::{TabletIds, TabletInit, TabletPadIds, TabletPadInit, TabletToolIds},
+                hot_corner::HotCorner,
+                tablet::{
+                    PressureCurve, TabletIds, TabletInit, TabletOutputArea, TabletPadIds,
+                    TabletPadInit, TabletToolIds,
+                },
             },
             wl_surface::{
                 NoneSurfaceExt,
@@ -94,10 +104,10 @@ use {
         theme::{Color, Theme},
         time::Time,
         tree::{
-            ContainerNode, ContainerSplit, Direction, DisplayNode, FindTreeUsecase, FloatNode,
-            FoundNode, LatchListener, Node, NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode,
-            TearingMode, ToplevelData, ToplevelNode, ToplevelNodeBase, VrrMode, WorkspaceNode,
-            generic_node_visitor,
+            ContainerNode, Direction, DisplayNode, FindTreeUsecase, FloatNode, FoundNode,
+            LatchListener, Node, NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode,
+            ScrollingNode, TearingMode, ToplevelData, ToplevelNode, ToplevelNodeBase, VrrMode,
+            WorkspaceDefinition, WorkspaceNode, generic_node_visitor, place_float,
         },
         utils::{
             activation_token::ActivationToken,
@@ -111,6 +121,7 @@ use {
             hash_map_ext::HashMapExt,
             linkedlist::LinkedList,
             numcell::NumCell,
+            pid_info::get_ppid,
             queue::AsyncQueue,
             refcounted::RefCounted,
             run_toplevel::RunToplevel,
@@ -126,9 +137,9 @@ use {
         },
         wheel::Wheel,
         wire::{
-            ExtForeignToplevelListV1Id, ExtIdleNotificationV1Id, JayHeadManagerSessionV1Id,
-            JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId, ZwlrForeignToplevelManagerV1Id,
-            ZwpLinuxDmabufFeedbackV1Id,
+            ExtForeignToplevelListV1Id, ExtIdleNotificationV1Id, JayGlobalShortcutsId,
+            JayHeadManagerSessionV1Id, JayInputCaptureId, JayRenderCtxId, JaySeatEventsId,
+            JayWorkspaceWatcherId, ZwlrForeignToplevelManagerV1Id, ZwpLinuxDmabufFeedbackV1Id,
         },
         xwayland::{self, XWaylandEvent},
     },
@@ -136,9 +147,10 @@ use {
     bstr::ByteSlice,
     jay_config::{
         PciId,
+        input::TabletPadElement,
         video::{GfxApi, Transform},
-        window::TileState,
-        workspace::WorkspaceDisplayOrder,
+        window::{FloatPlacement, TileState},
+        workspace::{WorkspaceDisplayOrder, WorkspaceLayout},
     },
     std::{
         cell::{Cell, RefCell},
@@ -150,7 +162,7 @@ use {
         time::Duration,
     },
     thiserror::Error,
-    uapi::OwnedFd,
+    uapi::{OwnedFd, c},
 };
 
 pub struct State {
@@ -176,6 +188,7 @@ pub struct State {
     pub input_device_ids: InputDeviceIds,
     pub node_ids: NodeIds,
     pub root: Rc<DisplayNode>,
+    pub sticky_floats: LinkedList<Rc<FloatNode>>,
     pub workspaces: CopyHashMap<String, Rc<WorkspaceNode>>,
     pub dummy_output: CloneCell<Option<Rc<OutputNode>>>,
     pub backend_events: AsyncQueue<BackendEvent>,
@@ -191,6 +204,8 @@ pub struct State {
     pub pending_container_render_title: AsyncQueue<Rc<ContainerNode>>,
     pub pending_output_render_data: AsyncQueue<Rc<OutputNode>>,
     pub pending_float_layout: AsyncQueue<Rc<FloatNode>>,
+    pub pending_workspace_layout: AsyncQueue<Rc<WorkspaceNode>>,
+    pub pending_scrolling_layout: AsyncQueue<Rc<ScrollingNode>>,
     pub pending_float_titles: AsyncQueue<Rc<FloatNode>>,
     pub pending_input_popup_positioning: AsyncQueue<Rc<ZwpInputPopupSurfaceV2>>,
     pub pending_toplevel_screencasts: AsyncQueue<Rc<JayScreencast>>,
@@ -220,6 +235,8 @@ pub struct State {
     pub scales: RefCounted<Scale>,
     pub cursor_sizes: RefCounted<u32>,
     pub hardware_tick_cursor: AsyncQueue<Option<Rc<dyn Cursor>>>,
+    /// Incremented whenever the image of the hardware cursor might have changed.
+    pub hardware_cursor_image_serial: NumCell<u64>,
     pub testers: RefCell<AHashMap<(ClientId, JaySeatEventsId), Rc<JaySeatEvents>>>,
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
@@ -273,6 +290,12 @@ pub struct State {
     pub float_above_fullscreen: Cell<bool>,
     pub icons: Icons,
     pub show_pin_icon: Cell<bool>,
+    pub float_placement: Cell<FloatPlacement>,
+    pub float_snap_threshold: Cell<i32>,
+    pub portal_settings: PortalSettings,
+    pub global_shortcuts: CopyHashMap<(ClientId, JayGlobalShortcutsId), Rc<JayGlobalShortcuts>>,
+    pub global_shortcut_capture: CloneCell<Option<Rc<JayGlobalShortcuts>>>,
+    pub input_captures: CopyHashMap<(ClientId, JayInputCaptureId), Rc<JayInputCapture>>,
     pub cl_matcher_manager: ClMatcherManager,
     pub tl_matcher_manager: TlMatcherManager,
     pub caps_thread: Option<PrCapsThread>,
@@ -284,6 +307,11 @@ pub struct State {
         CopyHashMap<(ClientId, JayHeadManagerSessionV1Id), Rc<JayHeadManagerSessionV1>>,
     pub head_managers_async: AsyncQueue<HeadManagerEvent>,
     pub show_bar: Cell<bool>,
+    pub magnifier_in_screencasts: Cell<bool>,
+    pub hot_corners: CopyHashMap<u64, Rc<HotCorner>>,
+    pub default_workspace_layout: Cell<WorkspaceLayout>,
+    pub workspace_layouts: CopyHashMap<String, WorkspaceLayout>,
+    pub workspace_definitions: CopyHashMap<String, Rc<WorkspaceDefinition>>,
     pub enable_primary_selection: Cell<bool>,
     pub xdg_surface_configure_events: AsyncQueue<XdgSurfaceConfigureEvent>,
     pub workspace_display_order: Cell<WorkspaceDisplayOrder>,
@@ -395,6 +423,10 @@ pub struct DeviceHandlerData {
     pub output: CloneCell<Option<Rc<OutputGlobalOpt>>>,
     pub tablet_init: Option<Box<TabletInit>>,
     pub tablet_pad_init: Option<Box<TabletPadInit>>,
+    pub tablet_output_area: Cell<TabletOutputArea>,
+    pub tablet_pressure_curve: Cell<Option<PressureCurve>>,
+    pub tablet_tool_buttons: CopyHashMap<u32, u32>,
+    pub tablet_pad_bindings: CopyHashMap<TabletPadElement, ()>,
     pub is_touch: bool,
     pub is_kb: bool,
     pub mods_listener: EventListener<dyn LedsListener>,
@@ -791,13 +823,96 @@ impl State {
         self.focus_after_map(node, seat.as_deref());
     }
 
+    pub fn map_new_tiled(self: &Rc<Self>, node: Rc<dyn ToplevelNode>) {
+        if self.swallow(&node) {
+            self.focus_after_map(node, self.seat_queue.last().as_deref());
+        } else {
+            self.map_tiled(node);
+        }
+    }
+
+    fn swallow(&self, node: &Rc<dyn ToplevelNode>) -> bool {
+        let Some(config) = self.config.get() else {
+            return false;
+        };
+        let data = node.tl_data();
+        if !config.swallowable(data) {
+            return false;
+        }
+        let Some(pid) = data.pid() else {
+            return false;
+        };
+        let mut candidates = AHashMap::<c::pid_t, Vec<Rc<dyn ToplevelNode>>>::new();
+        for tl in self.toplevels.lock().values() {
+            let Some(tl) = tl.upgrade() else {
+                continue;
+            };
+            let tl_data = tl.tl_data();
+            if tl_data.node_id == data.node_id || tl_data.is_fullscreen.get() {
+                continue;
+            }
+            let Some(parent) = tl_data.parent.get() else {
+                continue;
+            };
+            if parent.node_into_container().is_none() {
+                continue;
+            }
+            let Some(tl_pid) = tl_data.pid() else {
+                continue;
+            };
+            if tl_pid != pid && config.swallow(tl_data) {
+                candidates.entry(tl_pid).or_default().push(tl);
+            }
+        }
+        if candidates.is_empty() {
+            return false;
+        }
+        const MAX_DEPTH: usize = 32;
+        let mut swallower = None;
+        let mut ancestor = pid;
+        for _ in 0..MAX_DEPTH {
+            match get_ppid(ancestor) {
+                Some(ppid) if ppid > 1 => ancestor = ppid,
+                _ => break,
+            }
+            if let Some(candidates) = candidates.remove(&ancestor) {
+                let focused = candidates
+                    .iter()
+                    .position(|c| c.tl_data().seat_foci.is_not_empty());
+                swallower = candidates.into_iter().nth(focused.unwrap_or(0));
+                break;
+            }
+        }
+        let Some(swallower) = swallower else {
+            return false;
+        };
+        let swallower_data = swallower.tl_data();
+        let Some(parent) = swallower_data.parent.take() else {
+            return false;
+        };
+        let mut seats = vec![];
+        swallower_data
+            .seat_state
+            .for_each_kb_focus(|s| seats.push(s));
+        parent.cnode_replace_child(&*swallower, node.clone());
+        swallower.tl_set_visible(false);
+        swallower_data.swallowed_by.set(Some(Rc::downgrade(node)));
+        data.swallowed.set(Some(swallower.clone()));
+        for seat in seats {
+            node.clone().node_do_focus(&seat, Direction::Unspecified);
+        }
+        true
+    }
+
     fn do_map_tiled(self: &Rc<Self>, seat: Option<&Rc<WlSeatGlobal>>, node: Rc<dyn ToplevelNode>) {
         let ws = self.ensure_map_workspace(seat);
         self.map_tiled_on(node, &ws);
     }
 
     pub fn map_tiled_on(self: &Rc<Self>, node: Rc<dyn ToplevelNode>, ws: &Rc<WorkspaceNode>) {
-        if let Some(c) = ws.container.get() {
+        if ws.layout.get() == WorkspaceLayout::Scrolling {
+            ws.ensure_scrolling().add_window(node);
+        } else if let Some(c) = ws.container.get() {
             let la = c.clone().tl_last_active_child();
             let lap = la
                 .tl_data()
@@ -810,11 +925,18 @@ impl State {
                 c.append_child(node);
             }
         } else {
-            let container = ContainerNode::new(self, ws, node, ContainerSplit::Horizontal);
+            let container = ContainerNode::new(self, ws, node, ws.split.get());
             ws.set_container(&container);
         }
     }
 
+    /// Moves all sticky floats to the active workspace of the output.
+    pub fn move_sticky_floats(&self, output: &Rc<OutputNode>) {
+        for float in self.sticky_floats.iter() {
+            float.move_to_output(output);
+        }
+    }
+
     pub fn map_floating(
         self: &Rc<Self>,
         node: Rc<dyn ToplevelNode>,
@@ -850,7 +972,8 @@ impl State {
             } else {
                 height = output_rect.height();
             }
-            Rect::new_sized(x1, y1, width, height).unwrap()
+            let centered = Rect::new_sized(x1, y1, width, height).unwrap();
+            place_float(self, workspace, centered)
         };
         FloatNode::new(self, workspace, position, node.clone());
         self.focus_after_map(node, self.seat_queue.last().as_deref());
@@ -908,7 +1031,9 @@ impl State {
         let ws = match self.workspaces.get(name) {
             Some(ws) => ws,
             _ => {
-                let output = seat.get_output();
+                let output = self
+                    .preferred_workspace_output(name)
+                    .unwrap_or_else(|| seat.get_output());
                 if output.is_dummy {
                     log::warn!("Not showing workspace because seat is on dummy output");
                     return;
@@ -919,6 +1044,55 @@ impl State {
         self.show_workspace2(Some(seat), &ws.output.get(), &ws);
     }
 
+    pub fn workspace_definition(&self, name: &str) -> Rc<WorkspaceDefinition> {
+        match self.workspace_definitions.get(name) {
+            Some(d) => d,
+            _ => {
+                let d = Rc::new(WorkspaceDefinition::default());
+                self.workspace_definitions.set(name.to_string(), d.clone());
+                d
+            }
+        }
+    }
+
+    pub fn is_persistent_workspace(&self, name: &str) -> bool {
+        self.workspace_definitions
+            .get(name)
+            .is_some_and(|d| d.persistent.get())
+    }
+
+    pub fn preferred_workspace_output(&self, name: &str) -> Option<Rc<OutputNode>> {
+        let definition = self.workspace_definitions.get(name)?;
+        let outputs = self.root.outputs.lock();
+        for connector in &*definition.outputs.borrow() {
+            for output in outputs.values() {
+                if *output.global.connector.name == *connector {
+                    return Some(output.clone());
+                }
+            }
+        }
+        None
+    }
+
+    pub fn create_persistent_workspaces(&self) {
+        let Some(fallback) = self.root.outputs.lock().values().next().cloned() else {
+            return;
+        };
+        let mut names = vec![];
+        for (name, definition) in self.workspace_definitions.lock().iter() {
+            if definition.persistent.get() && !self.workspaces.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names.sort();
+        for name in names {
+            let output = self
+                .preferred_workspace_output(&name)
+                .unwrap_or_else(|| fallback.clone());
+            output.create_workspace(&name);
+        }
+    }
+
     pub fn float_map_ws(&self) -> Rc<WorkspaceNode> {
         if let Some(seat) = self.seat_queue.last() {
             let output = seat.get_output();
@@ -941,6 +1115,20 @@ impl State {
         }
     }
 
+    pub fn key_sequences_changed(&self) {
+        for output in self.root.outputs.lock().values() {
+            output.schedule_update_render_data();
+        }
+    }
+
+    pub fn pending_key_sequence(&self) -> Option<Rc<String>> {
+        self.globals
+            .seats
+            .lock()
+            .values()
+            .find_map(|seat| seat.key_sequence_prefix())
+    }
+
     pub fn input_occurred(&self) {
         if !self.idle.input.replace(true) {
             self.idle.change.trigger();
@@ -990,7 +1178,13 @@ impl State {
         }
         self.damage_visualizer.add(rect);
         for output in self.root.outputs.lock().values() {
+            if !cursor && let Some(switcher) = output.window_switcher.get() {
+                switcher.damage_thumbnails(&rect);
+            }
             if output.global.pos.get().intersects(&rect) {
+                if !cursor && let Some(overview) = output.overview.get() {
+                    overview.damage_thumbnails();
+                }
                 if skip_hc && output.hardware_cursor.is_some() {
                     continue;
                 }
@@ -1053,6 +1247,8 @@ impl State {
         self.pending_container_render_title.clear();
         self.pending_output_render_data.clear();
         self.pending_float_layout.clear();
+        self.pending_workspace_layout.clear();
+        self.pending_scrolling_layout.clear();
         self.pending_float_titles.clear();
         self.pending_input_popup_positioning.clear();
         self.pending_toplevel_screencasts.clear();
@@ -1060,6 +1256,10 @@ impl State {
         self.pending_placeholder_render_textures.clear();
         self.render_ctx_watchers.clear();
         self.workspace_watchers.clear();
+        self.portal_settings.watchers.clear();
+        self.global_shortcuts.clear();
+        self.global_shortcut_capture.take();
+        self.input_captures.clear();
         self.toplevel_lists.clear();
         self.security_context_acceptors.clear();
         self.slow_clients.clear();
@@ -1110,6 +1310,9 @@ impl State {
     }
 
     pub fn damage_hardware_cursors(&self, render: bool) {
+        if render {
+            self.hardware_cursor_image_serial.fetch_add(1);
+        }
         for output in self.root.outputs.lock().values() {
             if let Some(hc) = output.hardware_cursor.get() {
                 if render {
@@ -1165,6 +1368,8 @@ impl State {
         blend_buffer: Option<&Rc<dyn GfxBlendBuffer>>,
         blend_cd: &Rc<ColorDescription>,
     ) -> Result<Option<SyncFile>, GfxError> {
+        output.update_overview_thumbnails();
+        output.update_window_switcher_thumbnails();
         let sync_file = fb.render_output(
             acquire_sync,
             release_sync,
@@ -1246,9 +1451,8 @@ impl State {
             && let Some(cursor_user) = cursor_user_group.active()
             && let Some(cursor) = cursor_user.get()
         {
-            let (mut x, mut y) = cursor_user.position();
-            x = x + x_off - Fixed::from_int(position.x1());
-            y = y + y_off - Fixed::from_int(position.y1());
+            let (x, y) =
+                screencopy_cursor_position(cursor_user.position(), position, x_off, y_off, scale);
             cursor.render(&mut renderer, x, y);
         }
         target.render(
@@ -1414,6 +1618,9 @@ impl State {
         for seat in self.globals.seats.lock().values() {
             seat.output_extents_changed();
         }
+        for ic in self.input_captures.lock().values() {
+            ic.zones_changed();
+        }
     }
 
     pub fn update_ei_acceptor(self: &Rc<Self>) {
@@ -1567,3 +1774,20 @@ pub enum ShmScreencopyError {
     #[error("Could not read pixels from texture")]
     ReadPixels(#[source] GfxError),
 }
+
+/// Returns the position of the cursor within a screencopy target.
+///
+/// `position` is the logical position of the captured area. The offsets are the
+/// position of the captured texture within the target in physical pixels.
+fn screencopy_cursor_position(
+    (x, y): (Fixed, Fixed),
+    position: Rect,
+    x_off: i32,
+    y_off: i32,
+    scale: Scale,
+) -> (Fixed, Fixed) {
+    let scale = scale.to_f64();
+    let x = x - Fixed::from_int(position.x1()) + Fixed::from_f64(x_off as f64 / scale);
+    let y = y - Fixed::from_int(position.y1()) + Fixed::from_f64(y_off as f64 / scale);
+    (x, y)
+}
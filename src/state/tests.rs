@@ -0,0 +1,21 @@
+use crate::{fixed::Fixed, rect::Rect, scale::Scale, state::screencopy_cursor_position};
+
+#[test]
+fn screencopy_cursor_unscaled() {
+    let position = Rect::new_sized_unchecked(100, 50, 800, 600);
+    let pos = (Fixed::from_int(150), Fixed::from_int(70));
+    let (x, y) = screencopy_cursor_position(pos, position, 0, 0, Scale::from_int(1));
+    assert_eq!((x, y), (Fixed::from_int(50), Fixed::from_int(20)));
+    let (x, y) = screencopy_cursor_position(pos, position, -20, 10, Scale::from_int(1));
+    assert_eq!((x, y), (Fixed::from_int(30), Fixed::from_int(30)));
+}
+
+#[test]
+fn screencopy_cursor_scaled() {
+    let position = Rect::new_sized_unchecked(100, 50, 800, 600);
+    let pos = (Fixed::from_int(150), Fixed::from_int(70));
+    let (x, y) = screencopy_cursor_position(pos, position, -40, 20, Scale::from_int(2));
+    assert_eq!((x, y), (Fixed::from_int(30), Fixed::from_int(30)));
+    let (x, y) = screencopy_cursor_position(pos, position, -30, 30, Scale::from_f64(1.5));
+    assert_eq!((x, y), (Fixed::from_int(30), Fixed::from_int(40)));
+}
@@ -258,6 +258,11 @@ impl ConnectorHandler {
             ext_workspace_groups: Default::default(),
             pinned: Default::default(),
             tearing: Default::default(),
+            zoom: Cell::new(1.0),
+            unmagnified: Default::default(),
+            cursorless: Default::default(),
+            overview: Default::default(),
+            window_switcher: Default::default(),
         });
         on.update_visible();
         on.update_rects();
@@ -297,7 +302,11 @@ impl ConnectorHandler {
                 if ws.is_dummy {
                     continue;
                 }
-                if ws.desired_output.get() == global.output_id {
+                let preferred = self
+                    .state
+                    .preferred_workspace_output(&ws.name.borrow())
+                    .is_some_and(|o| o.id == on.id);
+                if preferred || ws.desired_output.get() == global.output_id {
                     ws_to_move.push_back(ws.clone());
                 }
             }
@@ -314,6 +323,7 @@ impl ConnectorHandler {
             };
             move_ws_to_output(&ws, &on, config);
         }
+        self.state.create_persistent_workspaces();
         if let Some(config) = self.state.config.get() {
             config.connector_connected(self.id);
         }
@@ -396,6 +406,10 @@ impl ConnectorHandler {
                 source_is_destroyed: true,
                 before: None,
             };
+            let target = self
+                .state
+                .preferred_workspace_output(&ws.name.borrow())
+                .unwrap_or_else(|| target.clone());
             move_ws_to_output(&ws, &target, config);
         }
         for group in on.ext_workspace_groups.lock().drain_values() {
@@ -29,6 +29,10 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn InputDevice>) {
         output: Default::default(),
         tablet_init: dev.tablet_info(),
         tablet_pad_init: dev.tablet_pad_info(),
+        tablet_output_area: Default::default(),
+        tablet_pressure_curve: Default::default(),
+        tablet_tool_buttons: Default::default(),
+        tablet_pad_bindings: Default::default(),
         is_touch: dev.has_capability(InputDeviceCapability::Touch),
         is_kb: dev.has_capability(InputDeviceCapability::Keyboard),
         mods_listener: EventListener::new(slf.clone()),
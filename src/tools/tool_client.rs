@@ -6,6 +6,7 @@ use {
         io_uring::{IoUring, IoUringError},
         logger::Logger,
         object::{ObjectId, WL_DISPLAY_ID},
+        rect::Rect,
         utils::{
             asyncevent::AsyncEvent,
             bitfield::Bitfield,
@@ -23,10 +24,12 @@ use {
         },
         wheel::{Wheel, WheelError},
         wire::{
-            JayCompositor, JayCompositorId, JayDamageTracking, JayDamageTrackingId, JayToplevelId,
-            JayWorkspaceId, WlCallbackId, WlRegistryId, WlSeatId, jay_compositor,
+            ExtDataControlManagerV1, JayCompositor, JayCompositorId, JayDamageTracking,
+            JayDamageTrackingId, JayToplevelId, JayWorkspaceId, WlCallbackId, WlOutput, WlOutputId,
+            WlRegistryId, WlSeat, WlSeatId, ZwpLinuxDmabufV1, ZxdgOutputManagerV1,
+            ZxdgOutputManagerV1Id, ZxdgOutputV1Id, jay_compositor, jay_select_region,
             jay_select_toplevel, jay_select_workspace, jay_toplevel, wl_callback, wl_display,
-            wl_registry,
+            wl_output, wl_registry, zxdg_output_manager_v1, zxdg_output_v1,
         },
     },
     ahash::AHashMap,
@@ -76,7 +79,7 @@ pub enum ToolClientError {
 pub struct ToolClient {
     pub _logger: Arc<Logger>,
     pub ring: Rc<IoUring>,
-    pub _wheel: Rc<Wheel>,
+    pub wheel: Rc<Wheel>,
     pub eng: Rc<AsyncEngine>,
     obj_ids: RefCell<Bitfield>,
     handlers: RefCell<
@@ -178,7 +181,7 @@ impl ToolClient {
         let slf = Rc::new(Self {
             _logger: logger,
             ring,
-            _wheel: wheel,
+            wheel,
             eng,
             obj_ids: RefCell::new(obj_ids),
             handlers: Default::default(),
@@ -293,6 +296,11 @@ impl ToolClient {
         struct S {
             jay_compositor: Cell<Option<(u32, u32)>>,
             jay_damage_tracking: Cell<Option<u32>>,
+            ext_data_control_manager: Cell<Option<u32>>,
+            wl_seat: Cell<Option<(u32, u32)>>,
+            wl_outputs: RefCell<Vec<(u32, u32)>>,
+            zxdg_output_manager: Cell<Option<u32>>,
+            zwp_linux_dmabuf: Cell<Option<(u32, u32)>>,
         }
         let s = Rc::new(S::default());
         let registry: WlRegistryId = self.id();
@@ -305,6 +313,18 @@ impl ToolClient {
                 s.jay_compositor.set(Some((g.name, g.version)));
             } else if g.interface == JayDamageTracking.name() {
                 s.jay_damage_tracking.set(Some(g.name));
+            } else if g.interface == ExtDataControlManagerV1.name() {
+                s.ext_data_control_manager.set(Some(g.name));
+            } else if g.interface == WlSeat.name() {
+                if s.wl_seat.get().is_none() {
+                    s.wl_seat.set(Some((g.name, g.version)));
+                }
+            } else if g.interface == WlOutput.name() {
+                s.wl_outputs.borrow_mut().push((g.name, g.version));
+            } else if g.interface == ZxdgOutputManagerV1.name() {
+                s.zxdg_output_manager.set(Some(g.name));
+            } else if g.interface == ZwpLinuxDmabufV1.name() {
+                s.zwp_linux_dmabuf.set(Some((g.name, g.version)));
             }
         });
         self.round_trip().await;
@@ -320,6 +340,11 @@ impl ToolClient {
             registry,
             jay_compositor: get!(jay_compositor, JayCompositor),
             jay_damage_tracking: s.jay_damage_tracking.get(),
+            ext_data_control_manager: s.ext_data_control_manager.get(),
+            wl_seat: s.wl_seat.get(),
+            wl_outputs: s.wl_outputs.take(),
+            zxdg_output_manager: s.zxdg_output_manager.get(),
+            zwp_linux_dmabuf: s.zwp_linux_dmabuf.get(),
         });
         self.singletons.set(Some(res.clone()));
         res
@@ -335,7 +360,7 @@ impl ToolClient {
             self_id: s.registry,
             name: s.jay_compositor.0,
             interface: JayCompositor.name(),
-            version: s.jay_compositor.1.min(21),
+            version: s.jay_compositor.1.min(31),
             id: id.into(),
         });
         self.jay_compositor.set(Some(id));
@@ -410,6 +435,143 @@ impl ToolClient {
         toplevel.get()
     }
 
+    pub async fn select_toplevel_id(self: &Rc<Self>) -> Option<String> {
+        let id = self.id();
+        self.send(jay_compositor::SelectToplevel {
+            self_id: self.jay_compositor().await,
+            id,
+            seat: WlSeatId::NONE,
+        });
+        let ae = Rc::new(AsyncEvent::default());
+        let toplevel_id = Rc::new(RefCell::new(None));
+        jay_select_toplevel::Done::handle(
+            self,
+            id,
+            (self.clone(), ae.clone(), toplevel_id.clone()),
+            |(tc, ae, toplevel_id), event| {
+                if event.id.is_some() {
+                    jay_toplevel::Id::handle(tc, event.id, toplevel_id.clone(), |tl_id, event| {
+                        *tl_id.borrow_mut() = Some(event.id.to_string());
+                    });
+                    jay_toplevel::Done::handle(tc, event.id, ae.clone(), |ae, _event| {
+                        ae.trigger();
+                    });
+                } else {
+                    ae.trigger();
+                }
+            },
+        );
+        ae.triggered().await;
+        toplevel_id.take()
+    }
+
+    pub async fn select_region(self: &Rc<Self>) -> Option<Rect> {
+        let id = self.id();
+        self.send(jay_compositor::SelectRegion {
+            self_id: self.jay_compositor().await,
+            id,
+            seat: WlSeatId::NONE,
+        });
+        let ae = Rc::new(AsyncEvent::default());
+        let rect = Rc::new(Cell::new(None));
+        jay_select_region::Cancelled::handle(self, id, ae.clone(), |ae, _event| {
+            ae.trigger();
+        });
+        jay_select_region::Selected::handle(
+            self,
+            id,
+            (ae.clone(), rect.clone()),
+            |(ae, rect), event| {
+                rect.set(Rect::new_sized(event.x, event.y, event.width, event.height));
+                ae.trigger();
+            },
+        );
+        ae.triggered().await;
+        rect.get()
+    }
+
+    pub async fn get_toplevel(self: &Rc<Self>, toplevel_id: &str) -> JayToplevelId {
+        let id = self.id();
+        self.send(jay_compositor::GetToplevel {
+            self_id: self.jay_compositor().await,
+            id,
+            toplevel_id,
+        });
+        let ae = Rc::new(AsyncEvent::default());
+        let toplevel = Rc::new(Cell::new(JayToplevelId::NONE));
+        jay_select_toplevel::Done::handle(
+            self,
+            id,
+            (ae.clone(), toplevel.clone()),
+            |(ae, toplevel), event| {
+                toplevel.set(event.id);
+                ae.trigger();
+            },
+        );
+        ae.triggered().await;
+        toplevel.get()
+    }
+
+    pub async fn outputs(self: &Rc<Self>) -> Vec<ToolOutput> {
+        let s = self.singletons().await;
+        let xdg_output_manager = s.zxdg_output_manager.map(|name| {
+            let id: ZxdgOutputManagerV1Id = self.id();
+            self.send(wl_registry::Bind {
+                self_id: s.registry,
+                name,
+                interface: ZxdgOutputManagerV1.name(),
+                version: 1,
+                id: id.into(),
+            });
+            id
+        });
+        let mut outputs = vec![];
+        for &(name, version) in &s.wl_outputs {
+            let id: WlOutputId = self.id();
+            self.send(wl_registry::Bind {
+                self_id: s.registry,
+                name,
+                interface: WlOutput.name(),
+                version: version.min(4),
+                id: id.into(),
+            });
+            let output_name = Rc::new(RefCell::new(String::new()));
+            wl_output::Name::handle(self, id, output_name.clone(), |output_name, event| {
+                *output_name.borrow_mut() = event.name.to_string();
+            });
+            let pos = Rc::new(Cell::new((0, 0)));
+            let size = Rc::new(Cell::new((0, 0)));
+            if let Some(manager) = xdg_output_manager {
+                let xdg_output: ZxdgOutputV1Id = self.id();
+                self.send(zxdg_output_manager_v1::GetXdgOutput {
+                    self_id: manager,
+                    id: xdg_output,
+                    output: id,
+                });
+                zxdg_output_v1::LogicalPosition::handle(self, xdg_output, pos.clone(), |p, ev| {
+                    p.set((ev.x, ev.y));
+                });
+                zxdg_output_v1::LogicalSize::handle(self, xdg_output, size.clone(), |s, ev| {
+                    s.set((ev.width, ev.height));
+                });
+            }
+            outputs.push((id, output_name, pos, size));
+        }
+        self.round_trip().await;
+        outputs
+            .into_iter()
+            .map(|(id, name, pos, size)| {
+                let (x, y) = pos.get();
+                let (width, height) = size.get();
+                ToolOutput {
+                    id,
+                    name: name.take(),
+                    rect: Rect::new_sized(x, y, width, height).unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+
     pub async fn select_toplevel_client(self: &Rc<Self>) -> u64 {
         let id = self.id();
         self.send(jay_compositor::SelectToplevel {
@@ -447,9 +609,21 @@ impl ToolClient {
 }
 
 pub struct Singletons {
-    registry: WlRegistryId,
+    pub registry: WlRegistryId,
     pub jay_compositor: (u32, u32),
     pub jay_damage_tracking: Option<u32>,
+    pub ext_data_control_manager: Option<u32>,
+    pub wl_seat: Option<(u32, u32)>,
+    pub wl_outputs: Vec<(u32, u32)>,
+    pub zxdg_output_manager: Option<u32>,
+    pub zwp_linux_dmabuf: Option<(u32, u32)>,
+}
+
+pub struct ToolOutput {
+    pub id: WlOutputId,
+    pub name: String,
+    /// The logical position and size of the output in compositor coordinates.
+    pub rect: Rect,
 }
 
 pub const NONE_FUTURE: Option<Pending<()>> = None;
@@ -28,16 +28,19 @@ use {
     },
 };
 pub use {
-    container::*, containing::*, display::*, float::*, output::*, placeholder::*, stacked::*,
-    toplevel::*, walker::*, workspace::*,
+    container::*, containing::*, display::*, float::*, layout::*, output::*, overview::*,
+    placeholder::*, scrolling::*, stacked::*, toplevel::*, walker::*, workspace::*,
 };
 
 mod container;
 mod containing;
 mod display;
 mod float;
+mod layout;
 mod output;
+mod overview;
 mod placeholder;
+mod scrolling;
 mod stacked;
 mod toplevel;
 mod walker;
@@ -630,6 +633,10 @@ pub trait Node: 'static {
         None
     }
 
+    fn node_into_scrolling(self: Rc<Self>) -> Option<Rc<ScrollingNode>> {
+        None
+    }
+
     fn node_into_surface(self: Rc<Self>) -> Option<Rc<WlSurface>> {
         None
     }
@@ -18,9 +18,10 @@ use {
         text::TextTexture,
         tree::{
             ContainingNode, Direction, FindTreeResult, FindTreeUsecase, FloatNode, FoundNode, Node,
-            NodeId, NodeLayerLink, NodeLocation, OutputNode, TddType, TileDragDestination,
-            ToplevelData, ToplevelNode, ToplevelNodeBase, ToplevelType, WorkspaceNode,
-            default_tile_drag_bounds, toplevel_set_floating, walker::NodeVisitor,
+            NodeId, NodeLayerLink, NodeLocation, OutputNode, PlaceholderNode, ScrollingNode,
+            TddType, TileDragDestination, ToplevelData, ToplevelNode, ToplevelNodeBase,
+            ToplevelType, WorkspaceNode, default_tile_drag_bounds, toplevel_parent_container,
+            toplevel_set_floating, walker::NodeVisitor,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -276,6 +277,24 @@ impl ContainerNode {
         self.add_child_x(prev, new, |prev, new| self.add_child_before_(prev, new));
     }
 
+    /// Exchanges `child` with `other`, which can be a child of any container.
+    ///
+    /// Both nodes take over the position and size of the other node. The shape of the tree
+    /// is not changed.
+    pub fn swap_child(self: &Rc<Self>, child: Rc<dyn ToplevelNode>, other: Rc<dyn ToplevelNode>) {
+        let Some(other_parent) = toplevel_parent_container(&*other) else {
+            return;
+        };
+        if !self.child_nodes.borrow().contains_key(&child.node_id()) {
+            return;
+        }
+        let placeholder = Rc::new_cyclic(|weak| PlaceholderNode::new_empty(&self.state, weak));
+        self.clone()
+            .cnode_replace_child(&*child, placeholder.clone());
+        other_parent.cnode_replace_child(&*other, child);
+        self.clone().cnode_replace_child(&*placeholder, other);
+    }
+
     fn add_child_x<F>(self: &Rc<Self>, prev: &dyn Node, new: Rc<dyn ToplevelNode>, f: F)
     where
         F: FnOnce(&NodeRef<ContainerChild>, Rc<dyn ToplevelNode>),
@@ -359,6 +378,24 @@ impl ContainerNode {
         // log::info!("add_child");
         self.schedule_layout();
         self.cancel_seat_ops();
+        self.workspace.get().schedule_layout();
+    }
+
+    pub fn num_children(&self) -> usize {
+        self.num_children.get()
+    }
+
+    pub fn set_child_factors(self: &Rc<Self>, factors: &[f64]) {
+        let mut changed = false;
+        let mut sum_factors = 0.0;
+        for (child, &factor) in self.children.iter().zip(factors) {
+            changed |= (child.factor.replace(factor) - factor).abs() > f64::EPSILON;
+            sum_factors += factor;
+        }
+        self.sum_factors.set(sum_factors);
+        if changed {
+            self.schedule_layout();
+        }
     }
 
     fn cancel_seat_ops(&self) {
@@ -957,6 +994,21 @@ impl ContainerNode {
             .and_then(|p| p.node_into_container())
     }
 
+    fn parent_scrolling(&self) -> Option<Rc<ScrollingNode>> {
+        self.toplevel_data
+            .parent
+            .get()
+            .and_then(|p| p.node_into_scrolling())
+    }
+
+    fn move_focus_from_self(&self, seat: &Rc<WlSeatGlobal>, direction: Direction) {
+        if let Some(c) = self.parent_container() {
+            c.move_focus_from_child(seat, self, direction);
+        } else if let Some(s) = self.parent_scrolling() {
+            s.move_focus_from_column(seat, self, direction);
+        }
+    }
+
     pub fn move_focus_from_child(
         self: Rc<Self>,
         seat: &Rc<WlSeatGlobal>,
@@ -979,9 +1031,7 @@ impl ContainerNode {
             }
         };
         if !in_line {
-            if let Some(c) = self.parent_container() {
-                c.move_focus_from_child(seat, self.deref(), direction);
-            }
+            self.move_focus_from_self(seat, direction);
             return;
         }
         let prev = match direction {
@@ -998,9 +1048,7 @@ impl ContainerNode {
         let sibling = match sibling {
             Some(s) => s,
             None => {
-                if let Some(c) = self.parent_container() {
-                    c.move_focus_from_child(seat, self.deref(), direction);
-                }
+                self.move_focus_from_self(seat, direction);
                 return;
             }
         };
@@ -1015,6 +1063,11 @@ impl ContainerNode {
     pub fn move_child(self: Rc<Self>, child: Rc<dyn ToplevelNode>, direction: Direction) {
         // CASE 1: This is the only child of the container. Replace the container by the child.
         if self.num_children.get() == 1 {
+            if let Some(scrolling) = self.parent_scrolling() {
+                // The container is a column of a scrolling workspace. Move the column instead.
+                scrolling.move_column(self.deref(), direction);
+                return;
+            }
             if let Some(parent) = self.toplevel_data.parent.get()
                 && !self.toplevel_data.is_fullscreen.get()
                 && parent.cnode_accepts_child(&*child)
@@ -1073,7 +1126,15 @@ impl ContainerNode {
         }
         let parent = match parent_opt {
             Some(p) => p,
-            _ => return,
+            _ => {
+                if split == ContainerSplit::Horizontal
+                    && let Some(scrolling) = neighbor.parent_scrolling()
+                {
+                    self.cnode_remove_child2(&*child, true);
+                    scrolling.add_window_next_to(&*neighbor, child, !prev);
+                }
+                return;
+            }
         };
         self.cnode_remove_child2(&*child, true);
         match prev {
@@ -1911,6 +1972,7 @@ impl ContainingNode for ContainerNode {
             node.to_ref()
         };
         let num_children = self.num_children.fetch_sub(1) - 1;
+        self.workspace.get().schedule_layout();
         if num_children == 0 {
             self.tl_destroy();
             return;
@@ -2141,6 +2203,14 @@ impl ContainingNode for ContainerNode {
         self.tl_set_pinned(false, pinned);
     }
 
+    fn cnode_sticky(&self) -> bool {
+        self.tl_sticky()
+    }
+
+    fn cnode_set_sticky(self: Rc<Self>, sticky: bool) {
+        self.tl_set_sticky(false, sticky);
+    }
+
     fn cnode_get_float(self: Rc<Self>) -> Option<Rc<FloatNode>> {
         self.tl_data().float.get()
     }
@@ -38,6 +38,12 @@ pub trait ContainingNode: Node {
     fn cnode_set_pinned(self: Rc<Self>, pinned: bool) {
         let _ = pinned;
     }
+    fn cnode_sticky(&self) -> bool {
+        false
+    }
+    fn cnode_set_sticky(self: Rc<Self>, sticky: bool) {
+        let _ = sticky;
+    }
     fn cnode_get_float(self: Rc<Self>) -> Option<Rc<FloatNode>> {
         None
     }
@@ -26,6 +26,7 @@ use {
     },
     ahash::AHashMap,
     arrayvec::ArrayVec,
+    jay_config::window::{FloatPlacement, FloatSnap},
     std::{
         cell::{Cell, RefCell},
         fmt::{Debug, Formatter},
@@ -44,6 +45,7 @@ pub struct FloatNode {
     pub display_link: RefCell<Option<LinkedNode<Rc<dyn StackedNode>>>>,
     pub workspace_link: Cell<Option<LinkedNode<Rc<dyn StackedNode>>>>,
     pub pinned_link: RefCell<Option<LinkedNode<Rc<dyn PinnedNode>>>>,
+    pub sticky_link: RefCell<Option<LinkedNode<Rc<FloatNode>>>>,
     pub workspace: CloneCell<Rc<WorkspaceNode>>,
     pub location: Cell<NodeLocation>,
     pub child: CloneCell<Option<Rc<dyn ToplevelNode>>>,
@@ -109,6 +111,108 @@ pub async fn float_titles(state: Rc<State>) {
     }
 }
 
+/// Computes the position of a new float according to the configured placement.
+///
+/// `centered` is the position of the float when centered on the output.
+pub fn place_float(state: &State, ws: &WorkspaceNode, centered: Rect) -> Rect {
+    let output = ws.output.get();
+    if output.is_dummy {
+        return centered;
+    }
+    let area = output.workspace_rect.get();
+    let (width, height) = centered.size();
+    match state.float_placement.get() {
+        FloatPlacement::Center => centered,
+        FloatPlacement::UnderCursor => {
+            let Some(seat) = state.seat_queue.last() else {
+                return centered;
+            };
+            let (x, y) = seat.pointer_cursor().position_int();
+            if !area.contains(x, y) {
+                return centered;
+            }
+            clamp_float(area, x - width / 2, y - height / 2, width, height)
+        }
+        FloatPlacement::Smart => {
+            let floats: Vec<_> = ws
+                .stacked
+                .iter()
+                .filter_map(|n| n.deref().clone().node_into_float())
+                .map(|f| f.position.get())
+                .collect();
+            let overlap = |rect: Rect| {
+                floats
+                    .iter()
+                    .map(|f| {
+                        let i = rect.intersect(*f);
+                        i.width() as i64 * i.height() as i64
+                    })
+                    .sum::<i64>()
+            };
+            let mut xs = vec![area.x1(), area.x2() - width];
+            let mut ys = vec![area.y1(), area.y2() - height];
+            for f in &floats {
+                xs.extend([f.x1() - width, f.x2()]);
+                ys.extend([f.y1() - height, f.y2()]);
+            }
+            let mut best = (overlap(centered), centered);
+            for &y in &ys {
+                for &x in &xs {
+                    if x < area.x1() || x + width > area.x2() {
+                        continue;
+                    }
+                    if y < area.y1() || y + height > area.y2() {
+                        continue;
+                    }
+                    let rect = Rect::new_sized_unchecked(x, y, width, height);
+                    let overlap = overlap(rect);
+                    if overlap < best.0 {
+                        best = (overlap, rect);
+                    }
+                }
+            }
+            best.1
+        }
+        FloatPlacement::Cascade => {
+            let theme = &state.theme;
+            let offset = theme.sizes.border_width.get() + theme.sizes.title_height.get() + 1;
+            let last = ws
+                .stacked
+                .rev_iter()
+                .find_map(|n| n.deref().clone().node_into_float());
+            let (mut x, mut y) = match last {
+                Some(f) => {
+                    let pos = f.position.get();
+                    (pos.x1() + offset, pos.y1() + offset)
+                }
+                None => (area.x1() + offset, area.y1() + offset),
+            };
+            if x + width > area.x2() || y + height > area.y2() {
+                x = area.x1();
+                y = area.y1();
+            }
+            clamp_float(area, x, y, width, height)
+        }
+    }
+}
+
+fn clamp_float(area: Rect, x: i32, y: i32, width: i32, height: i32) -> Rect {
+    let x = x.min(area.x2() - width).max(area.x1());
+    let y = y.min(area.y2() - height).max(area.y1());
+    Rect::new_sized_unchecked(x, y, width, height)
+}
+
+fn snap_delta(delta: &mut i32, edges: [i32; 2], targets: [i32; 2]) {
+    for edge in edges {
+        for target in targets {
+            let d = target - edge;
+            if d.abs() < delta.abs() {
+                *delta = d;
+            }
+        }
+    }
+}
+
 impl FloatNode {
     pub fn new(
         state: &Rc<State>,
@@ -124,6 +228,7 @@ impl FloatNode {
             display_link: RefCell::new(None),
             workspace_link: Cell::new(None),
             pinned_link: RefCell::new(None),
+            sticky_link: RefCell::new(None),
             workspace: CloneCell::new(ws.clone()),
             location: Cell::new(ws.location()),
             child: CloneCell::new(Some(child.clone())),
@@ -152,6 +257,9 @@ impl FloatNode {
         if child.tl_data().pinned.get() {
             floater.toggle_pinned();
         }
+        if child.tl_data().sticky.get() {
+            floater.toggle_sticky();
+        }
         floater
     }
 
@@ -311,10 +419,11 @@ impl FloatNode {
                 OpType::Move => {
                     let dx = x - seat_state.dist_hor;
                     let dy = y - seat_state.dist_ver;
-                    x1 += dx;
-                    y1 += dy;
-                    x2 += dx;
-                    y2 += dy;
+                    let (sx, sy) = self.snap_offset(pos.move_(dx, dy));
+                    x1 += dx + sx;
+                    y1 += dy + sy;
+                    x2 += dx + sx;
+                    y2 += dy + sy;
                 }
                 OpType::ResizeLeft => {
                     x1 += x - seat_state.dist_hor;
@@ -413,6 +522,76 @@ impl FloatNode {
         }
     }
 
+    /// Returns the offset that snaps the float at `pos` to a nearby edge of the output or
+    /// of another float.
+    fn snap_offset(&self, pos: Rect) -> (i32, i32) {
+        let threshold = self.state.float_snap_threshold.get();
+        if threshold <= 0 {
+            return (0, 0);
+        }
+        let ws = self.workspace.get();
+        let output = ws.output.get();
+        let mut dx = threshold + 1;
+        let mut dy = threshold + 1;
+        if !output.is_dummy {
+            let area = output.workspace_rect.get();
+            snap_delta(&mut dx, [pos.x1(), pos.x2()], [area.x1(), area.x2()]);
+            snap_delta(&mut dy, [pos.y1(), pos.y2()], [area.y1(), area.y2()]);
+        }
+        for node in ws.stacked.iter() {
+            let Some(float) = node.deref().clone().node_into_float() else {
+                continue;
+            };
+            if float.id == self.id || !float.visible.get() {
+                continue;
+            }
+            let other = float.position.get();
+            if pos.y1() <= other.y2() + threshold && other.y1() <= pos.y2() + threshold {
+                snap_delta(&mut dx, [pos.x1(), pos.x2()], [other.x1(), other.x2()]);
+            }
+            if pos.x1() <= other.x2() + threshold && other.x1() <= pos.x2() + threshold {
+                snap_delta(&mut dy, [pos.y1(), pos.y2()], [other.y1(), other.y2()]);
+            }
+        }
+        if dx.abs() > threshold {
+            dx = 0;
+        }
+        if dy.abs() > threshold {
+            dy = 0;
+        }
+        (dx, dy)
+    }
+
+    /// Moves and resizes the float to cover a region of its output.
+    pub fn snap(self: &Rc<Self>, snap: FloatSnap) {
+        let output = self.workspace.get().output.get();
+        if output.is_dummy {
+            return;
+        }
+        let area = output.workspace_rect.get();
+        let xm = area.x1() + area.width() / 2;
+        let ym = area.y1() + area.height() / 2;
+        let (x1, x2) = match snap {
+            FloatSnap::Left | FloatSnap::TopLeft | FloatSnap::BottomLeft => (area.x1(), xm),
+            FloatSnap::Right | FloatSnap::TopRight | FloatSnap::BottomRight => (xm, area.x2()),
+            FloatSnap::Top | FloatSnap::Bottom => (area.x1(), area.x2()),
+        };
+        let (y1, y2) = match snap {
+            FloatSnap::Top | FloatSnap::TopLeft | FloatSnap::TopRight => (area.y1(), ym),
+            FloatSnap::Bottom | FloatSnap::BottomLeft | FloatSnap::BottomRight => (ym, area.y2()),
+            FloatSnap::Left | FloatSnap::Right => (area.y1(), area.y2()),
+        };
+        let pos = self.position.get();
+        let new_pos = Rect::new_unchecked(x1, y1, x2, y2);
+        self.position.set(new_pos);
+        if self.visible.get() {
+            self.state.damage(pos);
+            self.state.damage(new_pos);
+        }
+        self.schedule_layout();
+        self.state.tree_changed();
+    }
+
     fn set_workspace_(
         self: &Rc<Self>,
         ws: &Rc<WorkspaceNode>,
@@ -545,9 +724,64 @@ impl FloatNode {
         if let Some(tl) = self.child.get() {
             tl.tl_data().pinned.set(pl.is_some());
         }
+        if pl.is_none() && self.sticky_link.take().is_some() {
+            if let Some(tl) = self.child.get() {
+                tl.tl_data().sticky.set(false);
+            }
+        }
         self.schedule_render_titles();
     }
 
+    fn toggle_sticky(self: &Rc<Self>) {
+        let sticky = {
+            let sl = &mut *self.sticky_link.borrow_mut();
+            *sl = match sl.is_some() {
+                true => None,
+                false => Some(self.state.sticky_floats.add_last(self.clone())),
+            };
+            sl.is_some()
+        };
+        if sticky && self.pinned_link.borrow().is_none() {
+            self.toggle_pinned();
+        }
+        if let Some(tl) = self.child.get() {
+            tl.tl_data().sticky.set(sticky);
+        }
+    }
+
+    /// Moves a sticky float to the active workspace of the output.
+    ///
+    /// The float keeps its position relative to the output.
+    pub fn move_to_output(self: &Rc<Self>, output: &Rc<OutputNode>) {
+        if output.is_dummy {
+            return;
+        }
+        let Some(ws) = output.workspace.get() else {
+            return;
+        };
+        let old_ws = self.workspace.get();
+        if old_ws.id == ws.id {
+            return;
+        }
+        let old_output = old_ws.output.get();
+        let old_opos = old_output.global.pos.get();
+        let opos = output.global.pos.get();
+        let pos = self.position.get();
+        let new_pos = pos.move_(opos.x1() - old_opos.x1(), opos.y1() - old_opos.y1());
+        self.set_workspace_(&ws, true, true);
+        if let Some(tl) = self.child.get() {
+            tl.tl_workspace_output_changed(&old_output, output);
+        }
+        self.position.set(new_pos);
+        self.state.damage(pos);
+        if self.visible.get() {
+            self.state.damage(new_pos);
+        }
+        self.after_ws_move(output);
+        self.schedule_layout();
+        self.state.tree_changed();
+    }
+
     fn button(
         self: Rc<Self>,
         id: CursorType,
@@ -910,6 +1144,7 @@ impl ContainingNode for FloatNode {
         self.display_link.borrow_mut().take();
         self.workspace_link.set(None);
         self.pinned_link.take();
+        self.sticky_link.take();
         if self.visible.get() {
             self.state.damage(self.position.get());
         }
@@ -1000,6 +1235,17 @@ impl ContainingNode for FloatNode {
         self.toggle_pinned();
     }
 
+    fn cnode_sticky(&self) -> bool {
+        self.sticky_link.borrow().is_some()
+    }
+
+    fn cnode_set_sticky(self: Rc<Self>, sticky: bool) {
+        if self.sticky_link.borrow().is_some() == sticky {
+            return;
+        }
+        self.toggle_sticky();
+    }
+
     fn cnode_get_float(self: Rc<Self>) -> Option<Rc<FloatNode>> {
         Some(self)
     }
@@ -0,0 +1,287 @@
+use {
+    crate::{
+        state::State,
+        tree::{ContainerNode, ContainerSplit, ScrollingNode, ToplevelNode, WorkspaceNode},
+    },
+    jay_config::workspace::WorkspaceLayout,
+    std::rc::Rc,
+};
+
+pub const MIN_MASTER_RATIO: f64 = 0.1;
+pub const MAX_MASTER_RATIO: f64 = 0.9;
+
+enum LayoutTree {
+    Leaf(Rc<dyn ToplevelNode>),
+    Split(ContainerSplit, Vec<(f64, LayoutTree)>),
+}
+
+impl LayoutTree {
+    fn equal(split: ContainerSplit, mut nodes: Vec<LayoutTree>) -> Self {
+        if nodes.len() == 1 {
+            return nodes.pop().unwrap();
+        }
+        let factor = 1.0 / nodes.len() as f64;
+        Self::Split(split, nodes.into_iter().map(|n| (factor, n)).collect())
+    }
+
+    fn leaves(split: ContainerSplit, leaves: &[Rc<dyn ToplevelNode>]) -> Self {
+        Self::equal(
+            split,
+            leaves.iter().map(|l| LayoutTree::Leaf(l.clone())).collect(),
+        )
+    }
+}
+
+pub async fn workspace_layout(state: Rc<State>) {
+    loop {
+        let ws = state.pending_workspace_layout.pop().await;
+        if ws.layout_scheduled.get() {
+            ws.perform_layout();
+            ws.layout_scheduled.set(false);
+        }
+    }
+}
+
+impl WorkspaceNode {
+    pub fn set_layout(self: &Rc<Self>, layout: WorkspaceLayout) {
+        if self.layout.replace(layout) != layout {
+            self.schedule_layout();
+        }
+    }
+
+    pub fn set_master_count(self: &Rc<Self>, count: u32) {
+        if self.master_count.replace(count) != count {
+            self.schedule_layout();
+        }
+    }
+
+    pub fn set_master_ratio(self: &Rc<Self>, ratio: f64) {
+        let ratio = ratio.clamp(MIN_MASTER_RATIO, MAX_MASTER_RATIO);
+        if self.master_ratio.replace(ratio) != ratio {
+            self.schedule_layout();
+        }
+    }
+
+    /// Returns the tiled windows of this workspace.
+    pub fn tiled_leaves(&self) -> Vec<Rc<dyn ToplevelNode>> {
+        let mut leaves = vec![];
+        if let Some(root) = self.container.get() {
+            collect_leaves(&root, &mut leaves);
+        }
+        if let Some(scrolling) = self.scrolling.get() {
+            collect_scrolling_leaves(&scrolling, &mut leaves);
+        }
+        leaves
+    }
+
+    pub fn schedule_layout(self: &Rc<Self>) {
+        if self.is_dummy
+            || (self.layout.get() == WorkspaceLayout::Manual && self.scrolling.is_none())
+        {
+            return;
+        }
+        if !self.layout_scheduled.replace(true) {
+            self.state.pending_workspace_layout.push(self.clone());
+        }
+    }
+
+    fn perform_layout(self: &Rc<Self>) {
+        if self.layout.get() == WorkspaceLayout::Scrolling {
+            self.scroll();
+            return;
+        }
+        if self.scrolling.is_some() {
+            self.unscroll();
+        }
+        let Some(root) = self.container.get() else {
+            return;
+        };
+        let mut leaves = vec![];
+        collect_leaves(&root, &mut leaves);
+        if leaves.is_empty() {
+            return;
+        }
+        let tree = match self.layout.get() {
+            WorkspaceLayout::Manual | WorkspaceLayout::Scrolling => return,
+            WorkspaceLayout::MasterStack => {
+                master_stack(&leaves, self.master_count.get(), self.master_ratio.get())
+            }
+            WorkspaceLayout::Dwindle => dwindle(&leaves, ContainerSplit::Horizontal),
+            WorkspaceLayout::Grid => grid(&leaves),
+        };
+        let tree = match tree {
+            LayoutTree::Leaf(_) => LayoutTree::Split(ContainerSplit::Horizontal, vec![(1.0, tree)]),
+            _ => tree,
+        };
+        if apply_factors(&(root as Rc<dyn ToplevelNode>), &tree) {
+            return;
+        }
+        for leaf in &leaves {
+            if let Some(parent) = leaf.tl_data().parent.get() {
+                parent.cnode_remove_child2(&**leaf, true);
+            }
+        }
+        let root = self.build(tree);
+        match root.node_into_container() {
+            Some(root) => self.set_container(&root),
+            _ => log::error!("Root of the workspace layout is not a container"),
+        }
+    }
+
+    /// Moves all tiled windows into columns of the scrolling node.
+    fn scroll(self: &Rc<Self>) {
+        let Some(root) = self.container.get() else {
+            return;
+        };
+        let mut leaves = vec![];
+        collect_leaves(&root, &mut leaves);
+        let scrolling = self.ensure_scrolling();
+        for leaf in leaves {
+            if let Some(parent) = leaf.tl_data().parent.get() {
+                parent.cnode_remove_child2(&*leaf, true);
+            }
+            scrolling.append_window(leaf);
+        }
+    }
+
+    /// Moves all windows out of the scrolling node into the container tree.
+    fn unscroll(self: &Rc<Self>) {
+        let Some(scrolling) = self.scrolling.get() else {
+            return;
+        };
+        let mut leaves = vec![];
+        collect_scrolling_leaves(&scrolling, &mut leaves);
+        for leaf in leaves {
+            if let Some(parent) = leaf.tl_data().parent.get() {
+                parent.cnode_remove_child2(&*leaf, true);
+            }
+            match self.container.get() {
+                Some(c) => c.append_child(leaf),
+                _ => {
+                    let c = ContainerNode::new(&self.state, self, leaf, self.split.get());
+                    self.set_container(&c);
+                }
+            }
+        }
+        self.remove_scrolling();
+    }
+
+    fn build(self: &Rc<Self>, tree: LayoutTree) -> Rc<dyn ToplevelNode> {
+        let (split, children) = match tree {
+            LayoutTree::Leaf(node) => return node,
+            LayoutTree::Split(split, children) => (split, children),
+        };
+        let mut factors = vec![];
+        let mut container: Option<Rc<ContainerNode>> = None;
+        for (factor, child) in children {
+            factors.push(factor);
+            let child = self.build(child);
+            match &container {
+                None => {
+                    container = Some(ContainerNode::new(&self.state, self, child, split));
+                }
+                Some(c) => c.append_child(child),
+            }
+        }
+        let container = container.unwrap();
+        container.set_child_factors(&factors);
+        container
+    }
+}
+
+fn collect_leaves(container: &ContainerNode, leaves: &mut Vec<Rc<dyn ToplevelNode>>) {
+    for child in container.children.iter() {
+        match child.node.clone().node_into_container() {
+            Some(c) => collect_leaves(&c, leaves),
+            _ => leaves.push(child.node.clone()),
+        }
+    }
+}
+
+fn collect_scrolling_leaves(scrolling: &ScrollingNode, leaves: &mut Vec<Rc<dyn ToplevelNode>>) {
+    for column in scrolling.columns.iter() {
+        match column.node.clone().node_into_container() {
+            Some(c) => collect_leaves(&c, leaves),
+            _ => leaves.push(column.node.clone()),
+        }
+    }
+}
+
+fn apply_factors(node: &Rc<dyn ToplevelNode>, tree: &LayoutTree) -> bool {
+    fn matches(node: &Rc<dyn ToplevelNode>, tree: &LayoutTree) -> bool {
+        match tree {
+            LayoutTree::Leaf(leaf) => leaf.node_id() == node.node_id(),
+            LayoutTree::Split(split, children) => {
+                let Some(c) = node.clone().node_into_container() else {
+                    return false;
+                };
+                c.split.get() == *split
+                    && c.mono_child.is_none()
+                    && c.num_children() == children.len()
+                    && c.children
+                        .iter()
+                        .zip(children)
+                        .all(|(c, (_, t))| matches(&c.node, t))
+            }
+        }
+    }
+    fn apply(node: &Rc<dyn ToplevelNode>, tree: &LayoutTree) {
+        let LayoutTree::Split(_, children) = tree else {
+            return;
+        };
+        let Some(c) = node.clone().node_into_container() else {
+            return;
+        };
+        let factors: Vec<_> = children.iter().map(|(f, _)| *f).collect();
+        c.set_child_factors(&factors);
+        for (c, (_, t)) in c.children.iter().zip(children) {
+            apply(&c.node, t);
+        }
+    }
+    if !matches(node, tree) {
+        return false;
+    }
+    apply(node, tree);
+    true
+}
+
+fn master_stack(leaves: &[Rc<dyn ToplevelNode>], count: u32, ratio: f64) -> LayoutTree {
+    let count = (count as usize).min(leaves.len());
+    if count == 0 || count == leaves.len() {
+        return LayoutTree::leaves(ContainerSplit::Vertical, leaves);
+    }
+    let (master, stack) = leaves.split_at(count);
+    LayoutTree::Split(
+        ContainerSplit::Horizontal,
+        vec![
+            (ratio, LayoutTree::leaves(ContainerSplit::Vertical, master)),
+            (
+                1.0 - ratio,
+                LayoutTree::leaves(ContainerSplit::Vertical, stack),
+            ),
+        ],
+    )
+}
+
+fn dwindle(leaves: &[Rc<dyn ToplevelNode>], split: ContainerSplit) -> LayoutTree {
+    match leaves {
+        [] => LayoutTree::Split(split, vec![]),
+        [leaf] => LayoutTree::Leaf(leaf.clone()),
+        [first, rest @ ..] => LayoutTree::Split(
+            split,
+            vec![
+                (0.5, LayoutTree::Leaf(first.clone())),
+                (0.5, dwindle(rest, split.other())),
+            ],
+        ),
+    }
+}
+
+fn grid(leaves: &[Rc<dyn ToplevelNode>]) -> LayoutTree {
+    let columns = (leaves.len() as f64).sqrt().ceil().max(1.0) as usize;
+    let rows = leaves
+        .chunks(columns)
+        .map(|row| LayoutTree::leaves(ContainerSplit::Horizontal, row))
+        .collect();
+    LayoutTree::equal(ContainerSplit::Vertical, rows)
+}
@@ -1,5 +1,6 @@
 use {
     crate::{
+        allocator::{AllocatorError, BO_USE_RENDERING, BufferObject},
         backend::{
             BackendColorSpace, BackendConnectorState, BackendEotfs, HardwareCursor, KeyState, Mode,
         },
@@ -7,7 +8,11 @@ use {
         cmm::cmm_description::ColorDescription,
         cursor::KnownCursor,
         fixed::Fixed,
-        gfx_api::{AcquireSync, BufferResv, GfxTexture, ReleaseSync},
+        format::XRGB8888,
+        gfx_api::{
+            AcquireSync, BufferResv, GfxError, GfxFramebuffer, GfxRenderPass, GfxTexture,
+            ReleaseSync,
+        },
         ifs::{
             ext_image_copy::ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
             jay_output::JayOutput,
@@ -17,6 +22,7 @@ use {
             wl_seat::{
                 BTN_LEFT, NodeSeatState, SeatId, WlSeatGlobal, collect_kb_foci2,
                 tablet::{TabletTool, TabletToolChanges, TabletToolId},
+                window_switcher::WindowSwitcher,
                 wl_pointer::PendingScroll,
             },
             wl_surface::{
@@ -35,6 +41,7 @@ use {
             zwlr_layer_shell_v1::{BACKGROUND, BOTTOM, OVERLAY, TOP},
             zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
         },
+        keyboard::KeyboardState,
         output_schedule::OutputSchedule,
         rect::Rect,
         renderer::Renderer,
@@ -42,9 +49,10 @@ use {
         state::State,
         text::TextTexture,
         tree::{
-            Direction, FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, NodeLayerLink,
-            NodeLocation, PinnedNode, StackedNode, TddType, TileDragDestination,
-            WorkspaceDragDestination, WorkspaceNode, WorkspaceNodeId, walker::NodeVisitor,
+            ContainerSplit, Direction, FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId,
+            NodeLayerLink, NodeLocation, Overview, PinnedNode, StackedNode, TddType,
+            TileDragDestination, WorkspaceDragDestination, WorkspaceNode, WorkspaceNodeId,
+            walker::NodeVisitor,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -75,6 +83,7 @@ use {
         ops::{BitOrAssign, Deref},
         rc::Rc,
     },
+    thiserror::Error,
 };
 
 tree_id!(OutputNodeId);
@@ -118,8 +127,83 @@ pub struct OutputNode {
     pub ext_workspace_groups: CopyHashMap<WorkspaceManagerId, Rc<ExtWorkspaceGroupHandleV1>>,
     pub pinned: LinkedList<Rc<dyn PinnedNode>>,
     pub tearing: Cell<bool>,
+    pub zoom: Cell<f64>,
+    pub unmagnified: CloneCell<Option<Rc<OffscreenBuffer>>>,
+    pub cursorless: CloneCell<Option<Rc<OffscreenBuffer>>>,
+    pub overview: CloneCell<Option<Rc<Overview>>>,
+    pub window_switcher: CloneCell<Option<Rc<WindowSwitcher>>>,
+}
+
+/// A buffer that parts of the scene can be rendered into and then sampled from.
+pub struct OffscreenBuffer {
+    _bo: Rc<dyn BufferObject>,
+    pub fb: Rc<dyn GfxFramebuffer>,
+    pub tex: Rc<dyn GfxTexture>,
+}
+
+impl OffscreenBuffer {
+    pub fn new(state: &State, width: i32, height: i32) -> Result<Self, OffscreenBufferError> {
+        let Some(ctx) = state.render_ctx.get() else {
+            return Err(OffscreenBufferError::NoRenderContext);
+        };
+        let formats = ctx.formats();
+        let modifiers = match formats.get(&XRGB8888.drm) {
+            Some(f) if !f.write_modifiers.is_empty() => {
+                f.write_modifiers.keys().copied().collect::<Vec<_>>()
+            }
+            _ => return Err(OffscreenBufferError::XRGB8888Writing),
+        };
+        let bo = ctx.allocator().create_bo(
+            &state.dma_buf_ids,
+            width,
+            height,
+            XRGB8888,
+            &modifiers,
+            BO_USE_RENDERING,
+        )?;
+        let img = ctx.clone().dmabuf_img(bo.dmabuf())?;
+        Ok(Self {
+            fb: img.clone().to_framebuffer()?,
+            tex: img.to_texture()?,
+            _bo: bo,
+        })
+    }
+
+    /// Renders the logical area `extents` into the buffer.
+    pub fn render(
+        &self,
+        state: &State,
+        extents: Rect,
+        scale: Scale,
+        f: impl FnOnce(&mut Renderer<'_>),
+    ) -> Result<AcquireSync, GfxError> {
+        let (width, height) = self.fb.physical_size();
+        let mut ops = vec![];
+        let mut renderer = Renderer {
+            base: self.fb.renderer_base(&mut ops, scale, Transform::None),
+            state,
+            logical_extents: extents.at_point(0, 0),
+            pixel_extents: Rect::new_sized_unchecked(0, 0, width, height),
+            icons: state.icons.get(state, scale),
+        };
+        f(&mut renderer);
+        let cd = state.color_manager.srgb_gamma22();
+        let sf = self.fb.render(
+            AcquireSync::Unnecessary,
+            ReleaseSync::Explicit,
+            cd,
+            &ops,
+            Some(&state.theme.colors.background.get()),
+            &cd.linear,
+            None,
+            cd,
+        )?;
+        Ok(AcquireSync::from_sync_file(sf))
+    }
 }
 
+const MAX_ZOOM: f64 = 32.0;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum BeforeLatchResult {
     None,
@@ -268,6 +352,9 @@ impl OutputNode {
 
     pub fn remove_screencast(&self, sc: &JayScreencast) {
         self.screencasts.remove(&(sc.client.id, sc.id));
+        if self.screencasts.is_empty() {
+            self.cursorless.take();
+        }
         self.screencast_changed();
     }
 
@@ -277,6 +364,162 @@ impl OutputNode {
         }
     }
 
+    pub fn is_magnified(&self) -> bool {
+        self.zoom.get() > 1.0
+    }
+
+    /// Returns whether a color picker lens is visible on this output. The lens shows
+    /// content from outside of the damaged area.
+    pub fn has_color_picker_lens(&self) -> bool {
+        let pos = self.global.pos.get();
+        self.state
+            .globals
+            .lock_seats()
+            .values()
+            .any(|s| s.color_picker_lens().is_some_and(|l| l.intersects(&pos)))
+    }
+
+    /// Re-renders the damaged thumbnails of the window switcher shown on this output.
+    pub fn update_window_switcher_thumbnails(&self) {
+        if let Some(switcher) = self.window_switcher.get() {
+            switcher.update_thumbnails();
+        }
+    }
+
+    pub fn set_zoom(&self, zoom: f64) {
+        let mut zoom = zoom.clamp(1.0, MAX_ZOOM);
+        if zoom < 1.0 + 1e-6 {
+            zoom = 1.0;
+        }
+        if self.zoom.replace(zoom) == zoom {
+            return;
+        }
+        if zoom == 1.0 {
+            self.unmagnified.take();
+        }
+        self.state.damage(self.global.pos.get());
+    }
+
+    fn magnifier_center(&self) -> (f64, f64) {
+        let pos = self.global.pos.get();
+        for seat in self.state.globals.lock_seats().values() {
+            let (x, y) = seat.pointer_cursor().position();
+            if pos.contains(x.round_down(), y.round_down()) {
+                return (x.to_f64(), y.to_f64());
+            }
+        }
+        let (x, y) = pos.center();
+        (x as f64, y as f64)
+    }
+
+    /// Applies the magnifier to a render pass of this output.
+    ///
+    /// Returns whether the pass was modified. Magnified passes must be rendered without
+    /// damage tracking.
+    pub fn magnify_render_pass(&self, pass: &mut GfxRenderPass) -> bool {
+        if !self.is_magnified() {
+            return false;
+        }
+        let pos = self.global.pos.get();
+        if pos.is_empty() {
+            return false;
+        }
+        let (x, y) = self.magnifier_center();
+        let x = 2.0 * (x - pos.x1() as f64) / pos.width() as f64 - 1.0;
+        let y = 2.0 * (y - pos.y1() as f64) / pos.height() as f64 - 1.0;
+        pass.magnify(self.zoom.get() as f32, x as f32, y as f32);
+        true
+    }
+
+    fn offscreen_buffer(
+        &self,
+        cell: &CloneCell<Option<Rc<OffscreenBuffer>>>,
+        purpose: &str,
+    ) -> Option<Rc<OffscreenBuffer>> {
+        let (width, height) = self.global.pixel_size();
+        if let Some(buffer) = cell.get()
+            && buffer.fb.physical_size() == (width, height)
+        {
+            return Some(buffer);
+        }
+        match OffscreenBuffer::new(&self.state, width, height) {
+            Ok(buffer) => {
+                let buffer = Rc::new(buffer);
+                cell.set(Some(buffer.clone()));
+                Some(buffer)
+            }
+            Err(e) => {
+                log::error!(
+                    "Could not allocate buffer for {} screencasts: {}",
+                    purpose,
+                    ErrorFmt(e)
+                );
+                None
+            }
+        }
+    }
+
+    fn render_unmagnified(
+        &self,
+        cd: &Rc<ColorDescription>,
+        render_hardware_cursor: bool,
+    ) -> Option<(Rc<dyn GfxTexture>, AcquireSync)> {
+        let buffer = self.offscreen_buffer(&self.unmagnified, "unmagnified")?;
+        let res = buffer.fb.render_node(
+            AcquireSync::Unnecessary,
+            ReleaseSync::Explicit,
+            cd,
+            self,
+            &self.state,
+            Some(self.global.pos.get()),
+            self.global.persistent.scale.get(),
+            true,
+            !render_hardware_cursor,
+            self.has_fullscreen(),
+            true,
+            self.global.persistent.transform.get(),
+            None,
+            cd,
+        );
+        match res {
+            Ok(sf) => Some((buffer.tex.clone(), AcquireSync::from_sync_file(sf))),
+            Err(e) => {
+                log::error!("Could not render unmagnified output: {}", ErrorFmt(e));
+                None
+            }
+        }
+    }
+
+    fn render_cursorless(
+        &self,
+        cd: &Rc<ColorDescription>,
+    ) -> Option<(Rc<dyn GfxTexture>, AcquireSync)> {
+        let buffer = self.offscreen_buffer(&self.cursorless, "cursorless")?;
+        let res = buffer.fb.render_node(
+            AcquireSync::Unnecessary,
+            ReleaseSync::Explicit,
+            cd,
+            self,
+            &self.state,
+            Some(self.global.pos.get()),
+            self.global.persistent.scale.get(),
+            false,
+            false,
+            self.has_fullscreen(),
+            true,
+            self.global.persistent.transform.get(),
+            None,
+            cd,
+        );
+        match res {
+            Ok(sf) => Some((buffer.tex.clone(), AcquireSync::from_sync_file(sf))),
+            Err(e) => {
+                log::error!("Could not render output without cursor: {}", ErrorFmt(e));
+                None
+            }
+        }
+    }
+
     pub fn perform_screencopies(
         &self,
         tex: &Rc<dyn GfxTexture>,
@@ -294,6 +537,20 @@ impl OutputNode {
                 return;
             }
         }
+        let unmagnified;
+        let (tex, resv, acquire_sync, release_sync) = if self.is_magnified()
+            && !self.state.magnifier_in_screencasts.get()
+            && self.has_screencopies()
+        {
+            let Some((tex, acquire_sync)) = self.render_unmagnified(cd, render_hardware_cursor)
+            else {
+                return;
+            };
+            unmagnified = (tex, acquire_sync);
+            (&unmagnified.0, None, &unmagnified.1, ReleaseSync::None)
+        } else {
+            (tex, resv, acquire_sync, release_sync)
+        };
         self.perform_wlr_screencopies(
             tex,
             cd,
@@ -305,19 +562,39 @@ impl OutputNode {
             y_off,
             size,
         );
+        let mut cursorless = None;
         for sc in self.screencasts.lock().values() {
-            sc.copy_texture(
-                self,
-                tex,
-                cd,
-                resv,
-                acquire_sync,
-                release_sync,
-                render_hardware_cursor,
-                x_off,
-                y_off,
-                size,
-            );
+            if render_hardware_cursor || sc.includes_cursor() {
+                sc.copy_texture(
+                    self,
+                    tex,
+                    cd,
+                    resv,
+                    acquire_sync,
+                    release_sync,
+                    render_hardware_cursor,
+                    x_off,
+                    y_off,
+                    size,
+                );
+                continue;
+            }
+            // The software cursor is part of the texture.
+            let cursorless = cursorless.get_or_insert_with(|| self.render_cursorless(cd));
+            if let Some((tex, acquire_sync)) = cursorless {
+                sc.copy_texture(
+                    self,
+                    tex,
+                    cd,
+                    None,
+                    acquire_sync,
+                    ReleaseSync::None,
+                    false,
+                    0,
+                    0,
+                    None,
+                );
+            }
         }
         for sc in self.ext_copy_sessions.lock().values() {
             sc.copy_texture(
@@ -335,6 +612,12 @@ impl OutputNode {
         }
     }
 
+    fn has_screencopies(&self) -> bool {
+        self.screencopies.is_not_empty()
+            || self.screencasts.is_not_empty()
+            || self.ext_copy_sessions.is_not_empty()
+    }
+
     pub fn perform_wlr_screencopies(
         &self,
         tex: &Rc<dyn GfxTexture>,
@@ -451,6 +734,7 @@ impl OutputNode {
         self.lock_surface.take();
         self.jay_outputs.clear();
         self.screencasts.clear();
+        self.cursorless.take();
         self.screencopies.clear();
         self.ext_copy_sessions.clear();
         self.ext_workspace_groups.clear();
@@ -491,6 +775,9 @@ impl OutputNode {
             }
         }
         self.schedule_update_render_data();
+        for sc in self.screencasts.lock().values() {
+            sc.output_scale_changed();
+        }
         self.global
             .connector
             .head_managers
@@ -539,7 +826,7 @@ impl OutputNode {
                 on_completed.clone(),
                 Some(texture_height),
                 &font,
-                &ws.name,
+                &ws.name.borrow(),
                 tc,
                 false,
                 scale,
@@ -550,7 +837,13 @@ impl OutputNode {
             tex_x: 0,
             tex: TextTexture::new(&self.state.cpu_worker, &ctx),
         });
-        let status = self.status.get();
+        let mut status = self.status.get();
+        if let Some(prefix) = self.state.pending_key_sequence() {
+            status = match status.is_empty() {
+                true => prefix,
+                false => Rc::new(format!("{prefix} | {status}")),
+            };
+        }
         let tc = self.state.theme.colors.bar_text.get();
         tex.tex.schedule_render_fitting(
             on_completed.clone(),
@@ -692,7 +985,7 @@ impl OutputNode {
             for pinned in self.pinned.iter() {
                 pinned.deref().clone().set_workspace(ws, false);
             }
-            if old.is_empty() {
+            if old.is_empty() && !self.state.is_persistent_workspace(&old.name.borrow()) {
                 for jw in old.jay_workspaces.lock().values() {
                     jw.send_destroyed();
                     jw.workspace.set(None);
@@ -701,7 +994,7 @@ impl OutputNode {
                     wh.handle_destroyed();
                 }
                 old.clear();
-                self.state.workspaces.remove(&old.name);
+                self.state.workspaces.remove(&*old.name.borrow());
             } else {
                 old.set_visible(false);
                 old.flush_jay_workspaces();
@@ -724,7 +1017,7 @@ impl OutputNode {
     pub fn find_workspace_insertion_point(&self, name: &str) -> Option<NodeRef<Rc<WorkspaceNode>>> {
         if self.state.workspace_display_order.get() == WorkspaceDisplayOrder::Sorted {
             for existing_ws in self.workspaces.iter() {
-                if name < existing_ws.name.as_str() {
+                if name < existing_ws.name.borrow().as_str() {
                     return Some(existing_ws);
                 }
             }
@@ -733,6 +1026,7 @@ impl OutputNode {
     }
 
     pub fn create_workspace(self: &Rc<Self>, name: &str) -> Rc<WorkspaceNode> {
+        let definition = self.state.workspace_definitions.get(name);
         let ws = Rc::new(WorkspaceNode {
             id: self.state.node_ids.next(),
             state: self.state.clone(),
@@ -741,9 +1035,10 @@ impl OutputNode {
             output_id: Cell::new(self.id),
             position: Cell::new(Default::default()),
             container: Default::default(),
+            scrolling: Default::default(),
             stacked: Default::default(),
             seat_state: Default::default(),
-            name: name.to_string(),
+            name: RefCell::new(name.to_string()),
             output_link: Default::default(),
             visible: Cell::new(false),
             fullscreen: Default::default(),
@@ -757,6 +1052,32 @@ impl OutputNode {
             render_highlight: Default::default(),
             ext_workspaces: Default::default(),
             opt: Default::default(),
+            layout: Cell::new(
+                self.state
+                    .workspace_layouts
+                    .get(name)
+                    .unwrap_or(self.state.default_workspace_layout.get()),
+            ),
+            master_count: Cell::new(
+                definition
+                    .as_ref()
+                    .map(|d| d.master_count.get())
+                    .unwrap_or(1),
+            ),
+            master_ratio: Cell::new(
+                definition
+                    .as_ref()
+                    .map(|d| d.master_ratio.get())
+                    .unwrap_or(0.5),
+            ),
+            layout_scheduled: Cell::new(false),
+            split: Cell::new(
+                definition
+                    .as_ref()
+                    .map(|d| d.split.get())
+                    .unwrap_or(ContainerSplit::Horizontal),
+            ),
+            gap: Cell::new(definition.as_ref().map(|d| d.gap.get()).unwrap_or(0)),
         });
         ws.opt.set(Some(ws.clone()));
         ws.update_has_captures();
@@ -1302,6 +1623,9 @@ impl OutputNode {
         if !rect.contains(x_abs, y_abs) {
             return None;
         }
+        if let Some(scrolling) = ws.scrolling.get() {
+            return scrolling.tile_drag_destination(source, rect, x_abs, y_abs);
+        }
         let Some(c) = ws.container.get() else {
             return Some(TileDragDestination {
                 highlight: rect,
@@ -1604,8 +1928,9 @@ impl Node for OutputNode {
                 return res;
             }
         }
+        let overview = self.overview.is_some();
         let mut fullscreen = None;
-        if let Some(ws) = self.workspace.get() {
+        if !overview && let Some(ws) = self.workspace.get() {
             fullscreen = ws.fullscreen.get();
         }
         {
@@ -1618,6 +1943,9 @@ impl Node for OutputNode {
                 return res;
             }
         }
+        if overview {
+            return FindTreeResult::AcceptsInput;
+        }
         {
             let res = self.find_stacked_at(&self.state.root.stacked, x, y, tree, usecase);
             if res.accepts_input() {
@@ -1680,6 +2008,32 @@ impl Node for OutputNode {
         renderer.render_output(self, x, y);
     }
 
+    fn node_on_key(
+        &self,
+        seat: &WlSeatGlobal,
+        _time_usec: u64,
+        key: u32,
+        state: KeyState,
+        _kb_state: &KeyboardState,
+    ) {
+        if state != KeyState::Pressed {
+            return;
+        }
+        let Some(overview) = self.overview.get() else {
+            return;
+        };
+        let id = self.global.connector.connector.id();
+        let Some(slf) = self.state.root.outputs.get(&id) else {
+            return;
+        };
+        let seats = self.state.globals.lock_seats();
+        let Some(seat) = seats.values().find(|s| s.id() == seat.id()).cloned() else {
+            return;
+        };
+        drop(seats);
+        slf.overview_key(&overview, &seat, key);
+    }
+
     fn node_on_button(
         self: Rc<Self>,
         seat: &Rc<WlSeatGlobal>,
@@ -1688,6 +2042,10 @@ impl Node for OutputNode {
         state: KeyState,
         _serial: u64,
     ) {
+        if let Some(overview) = self.overview.get() {
+            self.overview_button(&overview, seat, button, state);
+            return;
+        }
         if button != BTN_LEFT {
             return;
         }
@@ -1743,6 +2101,10 @@ impl Node for OutputNode {
 
     fn node_on_pointer_motion(self: Rc<Self>, seat: &Rc<WlSeatGlobal>, x: Fixed, y: Fixed) {
         self.pointer_move(PointerType::Seat(seat.id()), x, y);
+        if let Some(overview) = self.overview.get() {
+            self.overview_pointer_motion(&overview, seat);
+            return;
+        }
         if let Some((down_x, down_y)) = self.pointer_down.get(&seat.id()) {
             if self
                 .state
@@ -1925,3 +2287,15 @@ impl TearingMode {
         }
     }
 }
+
+#[derive(Debug, Error)]
+pub enum OffscreenBufferError {
+    #[error("There is no render context")]
+    NoRenderContext,
+    #[error("Render context does not support XRGB8888 format for rendering")]
+    XRGB8888Writing,
+    #[error(transparent)]
+    Allocator(#[from] AllocatorError),
+    #[error(transparent)]
+    Gfx(#[from] GfxError),
+}
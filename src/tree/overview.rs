@@ -0,0 +1,556 @@
+use {
+    crate::{
+        backend::KeyState,
+        gfx_api::{AcquireSync, GfxTexture},
+        ifs::wl_seat::{BTN_LEFT, SeatId, WlSeatGlobal},
+        rect::Rect,
+        scale::Scale,
+        state::State,
+        tree::{
+            Direction, Node, OffscreenBuffer, OutputNode, PointerType, ToplevelNode, ToplevelType,
+            WorkspaceNode, WorkspaceNodeId, toplevel_set_workspace,
+        },
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        ops::Deref,
+        rc::Rc,
+    },
+};
+
+/// The space between the thumbnails and around the edges of the output.
+const GAP: i32 = 20;
+
+const KEY_ESC: u32 = 1;
+const KEY_TAB: u32 = 15;
+const KEY_ENTER: u32 = 28;
+const KEY_H: u32 = 35;
+const KEY_J: u32 = 36;
+const KEY_K: u32 = 37;
+const KEY_L: u32 = 38;
+const KEY_SPACE: u32 = 57;
+const KEY_KPENTER: u32 = 96;
+const KEY_UP: u32 = 103;
+const KEY_LEFT: u32 = 105;
+const KEY_RIGHT: u32 = 106;
+const KEY_DOWN: u32 = 108;
+
+/// The state of an output that shows all of its workspaces at once.
+pub struct Overview {
+    state: Rc<State>,
+    pub selected: CloneCell<Option<Rc<dyn ToplevelNode>>>,
+    press: RefCell<Option<OverviewPress>>,
+    thumbnails: CopyHashMap<WorkspaceNodeId, Rc<Thumbnail>>,
+}
+
+struct Thumbnail {
+    buffer: OffscreenBuffer,
+    sync: RefCell<AcquireSync>,
+    damaged: Cell<bool>,
+}
+
+struct OverviewPress {
+    seat: SeatId,
+    down: (i32, i32),
+    pos: (i32, i32),
+    workspace: Option<Rc<WorkspaceNode>>,
+    window: Option<Rc<dyn ToplevelNode>>,
+    dragging: bool,
+}
+
+pub struct OverviewWorkspace {
+    pub workspace: Rc<WorkspaceNode>,
+    pub rect: Rect,
+    pub windows: Vec<OverviewWindow>,
+}
+
+pub struct OverviewWindow {
+    pub window: Rc<dyn ToplevelNode>,
+    pub rect: Rect,
+}
+
+pub struct OverviewDrag {
+    pub window: Rect,
+    pub target: Option<Rect>,
+}
+
+impl Overview {
+    /// Returns the last rendered thumbnail of a workspace.
+    pub fn thumbnail(&self, ws: &WorkspaceNode) -> Option<(Rc<dyn GfxTexture>, AcquireSync)> {
+        let thumbnail = self.thumbnails.get(&ws.id)?;
+        let sync = thumbnail.sync.borrow().clone();
+        Some((thumbnail.buffer.tex.clone(), sync))
+    }
+
+    /// Marks all thumbnails as out of date.
+    pub fn damage_thumbnails(&self) {
+        for thumbnail in self.thumbnails.lock().values() {
+            thumbnail.damaged.set(true);
+        }
+    }
+
+    #[cfg(feature = "it")]
+    pub fn thumbnails_damaged(&self) -> Vec<bool> {
+        let thumbnails = self.thumbnails.lock();
+        thumbnails.values().map(|t| t.damaged.get()).collect()
+    }
+
+    /// Renders a workspace into a buffer of the given size unless the existing
+    /// thumbnail is still up to date.
+    fn update_thumbnail(&self, ws: &WorkspaceNode, width: i32, height: i32, scale: Scale) {
+        let thumbnail = match self.thumbnails.get(&ws.id) {
+            Some(t) if t.buffer.fb.physical_size() == (width, height) => {
+                if !t.damaged.get() {
+                    return;
+                }
+                t
+            }
+            _ => match OffscreenBuffer::new(&self.state, width, height) {
+                Ok(buffer) => Rc::new(Thumbnail {
+                    buffer,
+                    sync: RefCell::new(AcquireSync::Unnecessary),
+                    damaged: Cell::new(true),
+                }),
+                Err(e) => {
+                    log::error!("Could not allocate overview thumbnail: {}", ErrorFmt(e));
+                    self.thumbnails.remove(&ws.id);
+                    return;
+                }
+            },
+        };
+        let pos = ws.position.get();
+        let res = thumbnail
+            .buffer
+            .render(&self.state, pos, scale, |renderer| {
+                match ws.fullscreen.get() {
+                    Some(fs) => {
+                        let fpos = fs.node_absolute_position();
+                        fs.node_render(renderer, fpos.x1() - pos.x1(), fpos.y1() - pos.y1(), None);
+                    }
+                    None => {
+                        renderer.render_workspace(ws, 0, 0);
+                        for stacked in ws.stacked.iter() {
+                            let spos = stacked.node_absolute_position();
+                            stacked.node_render(
+                                renderer,
+                                spos.x1() - pos.x1(),
+                                spos.y1() - pos.y1(),
+                                None,
+                            );
+                        }
+                    }
+                }
+            });
+        match res {
+            Ok(sync) => {
+                *thumbnail.sync.borrow_mut() = sync;
+                thumbnail.damaged.set(false);
+                self.thumbnails.set(ws.id, thumbnail);
+            }
+            Err(e) => {
+                log::error!("Could not render overview thumbnail: {}", ErrorFmt(e));
+                self.thumbnails.remove(&ws.id);
+            }
+        }
+    }
+
+    /// Returns the dragged window and the workspace it would be dropped on.
+    pub fn drag(&self, layout: &[OverviewWorkspace]) -> Option<OverviewDrag> {
+        let press = self.press.borrow();
+        let press = press.as_ref()?;
+        if !press.dragging {
+            return None;
+        }
+        let window = press.window.as_ref()?;
+        let (x, y) = press.pos;
+        let mut drag = OverviewDrag {
+            window: Rect::new_empty(x, y),
+            target: None,
+        };
+        for ws in layout {
+            if let Some(w) = ws
+                .windows
+                .iter()
+                .find(|w| w.window.node_id() == window.node_id())
+            {
+                let (dx, dy) = (x - press.down.0, y - press.down.1);
+                drag.window = w.rect.move_(dx, dy);
+            }
+            if ws.rect.contains(x, y) {
+                drag.target = Some(ws.rect);
+            }
+        }
+        Some(drag)
+    }
+}
+
+impl OutputNode {
+    pub fn toggle_overview(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>) {
+        match self.overview.is_some() {
+            true => self.close_overview(seat),
+            false => self.open_overview(seat),
+        }
+    }
+
+    pub fn open_overview(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>) {
+        if self.overview.is_some() || self.state.lock.locked.get() {
+            return;
+        }
+        let overview = Rc::new(Overview {
+            state: self.state.clone(),
+            selected: Default::default(),
+            press: Default::default(),
+            thumbnails: Default::default(),
+        });
+        if let Some(ws) = self.workspace.get() {
+            let focus = seat.get_keyboard_node();
+            overview.selected.set(
+                self.overview_layout()
+                    .into_iter()
+                    .filter(|o| o.workspace.id == ws.id)
+                    .flat_map(|o| o.windows)
+                    .find(|w| focus.node_id() == w.window.node_id())
+                    .map(|w| w.window),
+            );
+        }
+        self.overview.set(Some(overview));
+        seat.focus_node(self.clone());
+        self.state.damage(self.global.pos.get());
+    }
+
+    pub fn close_overview(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>) {
+        if self.overview.take().is_none() {
+            return;
+        }
+        self.state.damage(self.global.pos.get());
+        if seat.get_keyboard_node().node_id() == self.node_id()
+            && let Some(ws) = self.workspace.get()
+        {
+            ws.node_do_focus(seat, Direction::Unspecified);
+        }
+    }
+
+    /// Computes the positions of the workspace thumbnails in global coordinates.
+    pub fn overview_layout(&self) -> Vec<OverviewWorkspace> {
+        let area = self.workspace_rect.get();
+        let workspaces: Vec<_> = self.workspaces.iter().map(|w| w.deref().clone()).collect();
+        if workspaces.is_empty() || area.is_empty() {
+            return vec![];
+        }
+        let num = workspaces.len() as i32;
+        let mut cols = 1;
+        while cols * cols < num {
+            cols += 1;
+        }
+        let rows = (num + cols - 1) / cols;
+        let cell_width = (area.width() - (cols + 1) * GAP) / cols;
+        let cell_height = (area.height() - (rows + 1) * GAP) / rows;
+        if cell_width <= 0 || cell_height <= 0 {
+            return vec![];
+        }
+        let scale = (cell_width as f64 / area.width() as f64)
+            .min(cell_height as f64 / area.height() as f64);
+        let width = (area.width() as f64 * scale).round() as i32;
+        let height = (area.height() as f64 * scale).round() as i32;
+        let mut res = vec![];
+        for (idx, workspace) in workspaces.into_iter().enumerate() {
+            let (col, row) = (idx as i32 % cols, idx as i32 / cols);
+            let x1 = area.x1() + GAP + col * (cell_width + GAP) + (cell_width - width) / 2;
+            let y1 = area.y1() + GAP + row * (cell_height + GAP) + (cell_height - height) / 2;
+            let rect = Rect::new_sized_unchecked(x1, y1, width, height);
+            let wpos = workspace.position.get();
+            let map = |r: Rect| {
+                let x1 = x1 + ((r.x1() - wpos.x1()) as f64 * scale).round() as i32;
+                let y1 = y1 + ((r.y1() - wpos.y1()) as f64 * scale).round() as i32;
+                let x2 = x1 + (r.width() as f64 * scale).round() as i32;
+                let y2 = y1 + (r.height() as f64 * scale).round() as i32;
+                Rect::new(x1, y1, x2, y2).unwrap_or(rect)
+            };
+            let windows = overview_windows(&self.state, &workspace)
+                .into_iter()
+                .map(|window| OverviewWindow {
+                    rect: map(window.node_absolute_position()),
+                    window,
+                })
+                .collect();
+            res.push(OverviewWorkspace {
+                workspace,
+                rect,
+                windows,
+            });
+        }
+        res
+    }
+
+    /// Re-renders the thumbnails of damaged workspaces.
+    ///
+    /// This is only called before the output is presented. Captures of the output reuse
+    /// the existing thumbnails.
+    pub fn update_overview_thumbnails(&self) {
+        let Some(overview) = self.overview.get() else {
+            return;
+        };
+        let layout = self.overview_layout();
+        {
+            let mut thumbnails = overview.thumbnails.lock();
+            thumbnails.retain(|id, _| layout.iter().any(|ws| ws.workspace.id == *id));
+        }
+        let scale = self.global.persistent.scale.get();
+        for ows in &layout {
+            let [width, height] = scale.pixel_size([ows.rect.width(), ows.rect.height()]);
+            if width <= 0 || height <= 0 {
+                continue;
+            }
+            let wpos = ows.workspace.position.get();
+            let tscale = Scale::from_f64(
+                scale.to_f64() * ows.rect.width() as f64 / wpos.width().max(1) as f64,
+            );
+            overview.update_thumbnail(&ows.workspace, width, height, tscale);
+        }
+    }
+
+    /// Damages the overview without invalidating its thumbnails.
+    fn damage_overview(&self) {
+        self.global.add_damage_area(&self.global.pos.get());
+        self.global.connector.damage();
+    }
+
+    fn overview_find(
+        &self,
+        x: i32,
+        y: i32,
+    ) -> (Option<Rc<WorkspaceNode>>, Option<Rc<dyn ToplevelNode>>) {
+        for ws in self.overview_layout() {
+            if !ws.rect.contains(x, y) {
+                continue;
+            }
+            let window = ws
+                .windows
+                .into_iter()
+                .rev()
+                .find(|w| w.rect.contains(x, y))
+                .map(|w| w.window);
+            return (Some(ws.workspace), window);
+        }
+        (None, None)
+    }
+
+    fn overview_activate(
+        self: &Rc<Self>,
+        seat: &Rc<WlSeatGlobal>,
+        ws: &Rc<WorkspaceNode>,
+        window: Option<Rc<dyn ToplevelNode>>,
+    ) {
+        self.overview.take();
+        self.state.damage(self.global.pos.get());
+        self.state.show_workspace2(Some(seat), self, ws);
+        match window {
+            Some(tl) => seat.focus_toplevel(tl),
+            None => ws.clone().node_do_focus(seat, Direction::Unspecified),
+        }
+    }
+
+    pub fn overview_button(
+        self: &Rc<Self>,
+        overview: &Overview,
+        seat: &Rc<WlSeatGlobal>,
+        button: u32,
+        state: KeyState,
+    ) {
+        if button != BTN_LEFT {
+            return;
+        }
+        if state == KeyState::Pressed {
+            let Some(pos) = self.overview_pointer_position(seat) else {
+                return;
+            };
+            let (workspace, window) = self.overview_find(pos.0, pos.1);
+            *overview.press.borrow_mut() = Some(OverviewPress {
+                seat: seat.id(),
+                down: pos,
+                pos,
+                workspace,
+                window,
+                dragging: false,
+            });
+            return;
+        }
+        let Some(press) = overview.press.borrow_mut().take() else {
+            return;
+        };
+        if press.seat != seat.id() {
+            return;
+        }
+        if press.dragging {
+            self.state.damage(self.global.pos.get());
+            let (target, _) = self.overview_find(press.pos.0, press.pos.1);
+            if let Some(target) = target
+                && let Some(window) = press.window
+            {
+                toplevel_set_workspace(&self.state, window, &target);
+            }
+            return;
+        }
+        match &press.workspace {
+            Some(ws) => self.overview_activate(seat, ws, press.window),
+            None => self.close_overview(seat),
+        }
+    }
+
+    pub fn overview_pointer_motion(self: &Rc<Self>, overview: &Overview, seat: &Rc<WlSeatGlobal>) {
+        let Some(pos) = self.overview_pointer_position(seat) else {
+            return;
+        };
+        let mut press = overview.press.borrow_mut();
+        let Some(press) = &mut *press else {
+            return;
+        };
+        if press.seat != seat.id() {
+            return;
+        }
+        press.pos = pos;
+        if !press.dragging
+            && press.window.is_some()
+            && self.state.ui_drag_threshold_reached(pos, press.down)
+        {
+            press.dragging = true;
+        }
+        if press.dragging {
+            self.damage_overview();
+        }
+    }
+
+    fn overview_pointer_position(&self, seat: &WlSeatGlobal) -> Option<(i32, i32)> {
+        let (x, y) = self.pointer_positions.get(&PointerType::Seat(seat.id()))?;
+        let pos = self.global.pos.get();
+        Some((x + pos.x1(), y + pos.y1()))
+    }
+
+    pub fn overview_key(self: &Rc<Self>, overview: &Overview, seat: &Rc<WlSeatGlobal>, key: u32) {
+        let direction = match key {
+            KEY_ESC => {
+                self.close_overview(seat);
+                return;
+            }
+            KEY_ENTER | KEY_KPENTER | KEY_SPACE => {
+                let layout = self.overview_layout();
+                if let Some(selected) = overview.selected.get()
+                    && let Some(ws) = layout.iter().find(|ws| {
+                        ws.windows
+                            .iter()
+                            .any(|w| w.window.node_id() == selected.node_id())
+                    })
+                {
+                    self.overview_activate(seat, &ws.workspace, Some(selected));
+                } else if let Some(ws) = self.workspace.get() {
+                    self.overview_activate(seat, &ws, None);
+                }
+                return;
+            }
+            KEY_TAB => Direction::Unspecified,
+            KEY_LEFT | KEY_H => Direction::Left,
+            KEY_DOWN | KEY_J => Direction::Down,
+            KEY_UP | KEY_K => Direction::Up,
+            KEY_RIGHT | KEY_L => Direction::Right,
+            _ => return,
+        };
+        let windows: Vec<_> = self
+            .overview_layout()
+            .into_iter()
+            .flat_map(|ws| ws.windows)
+            .collect();
+        let current = overview.selected.get().and_then(|s| {
+            windows
+                .iter()
+                .position(|w| w.window.node_id() == s.node_id())
+        });
+        let next = match current {
+            None => (!windows.is_empty()).then_some(0),
+            Some(idx) if direction == Direction::Unspecified => Some((idx + 1) % windows.len()),
+            Some(idx) => window_in_direction(&windows, idx, direction),
+        };
+        if let Some(next) = next {
+            overview.selected.set(Some(windows[next].window.clone()));
+            self.damage_overview();
+        }
+    }
+}
+
+fn overview_windows(state: &State, ws: &WorkspaceNode) -> Vec<Rc<dyn ToplevelNode>> {
+    let is_window = |tl: &Rc<dyn ToplevelNode>| {
+        matches!(
+            tl.tl_data().kind,
+            ToplevelType::XdgToplevel(_) | ToplevelType::XWindow(_)
+        )
+    };
+    if let Some(fs) = ws.fullscreen.get() {
+        return vec![fs];
+    }
+    let mut windows: Vec<_> = ws
+        .tiled_leaves()
+        .into_iter()
+        .filter(|tl| is_window(tl) && !is_hidden_mono_child(tl))
+        .collect();
+    for tl in state.toplevels.lock().values() {
+        let Some(tl) = tl.upgrade() else {
+            continue;
+        };
+        let data = tl.tl_data();
+        if data.parent_is_float.get()
+            && data.workspace.get().map(|w| w.id) == Some(ws.id)
+            && is_window(&tl)
+        {
+            windows.push(tl);
+        }
+    }
+    windows
+}
+
+fn is_hidden_mono_child(tl: &Rc<dyn ToplevelNode>) -> bool {
+    let mut node = tl.clone();
+    while let Some(parent) = node
+        .tl_data()
+        .parent
+        .get()
+        .and_then(|p| p.node_into_container())
+    {
+        if let Some(mc) = parent.mono_child.get()
+            && mc.node.node_id() != node.node_id()
+        {
+            return true;
+        }
+        node = parent;
+    }
+    false
+}
+
+fn window_in_direction(
+    windows: &[OverviewWindow],
+    current: usize,
+    direction: Direction,
+) -> Option<usize> {
+    let (cx, cy) = windows[current].rect.center();
+    let mut best = None;
+    for (idx, window) in windows.iter().enumerate() {
+        if idx == current {
+            continue;
+        }
+        let (x, y) = window.rect.center();
+        let (primary, secondary) = match direction {
+            Direction::Left => (cx - x, y - cy),
+            Direction::Right => (x - cx, y - cy),
+            Direction::Up => (cy - y, x - cx),
+            Direction::Down => (y - cy, x - cx),
+            Direction::Unspecified => return None,
+        };
+        if primary <= 0 {
+            continue;
+        }
+        let distance = primary as i64 + 2 * (secondary as i64).abs();
+        if best.is_none_or(|(_, d)| distance < d) {
+            best = Some((idx, distance));
+        }
+    }
+    best.map(|(idx, _)| idx)
+}
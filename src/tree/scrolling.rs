@@ -0,0 +1,690 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        cursor::KnownCursor,
+        ifs::wl_seat::{NodeSeatState, WlSeatGlobal, collect_kb_foci},
+        rect::Rect,
+        renderer::Renderer,
+        state::State,
+        tree::{
+            ContainerNode, ContainerSplit, ContainingNode, Direction, FindTreeResult,
+            FindTreeUsecase, FoundNode, Node, NodeId, NodeLayerLink, NodeLocation, OutputNode,
+            TddType, TileDragDestination, ToplevelNode, WorkspaceNode, walker::NodeVisitor,
+        },
+        utils::{
+            clonecell::CloneCell,
+            errorfmt::ErrorFmt,
+            linkedlist::{LinkedList, LinkedNode, NodeRef},
+            threshold_counter::ThresholdCounter,
+        },
+    },
+    ahash::AHashMap,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+tree_id!(ScrollingNodeId);
+
+const COLUMN_WIDTHS: [f64; 3] = [1.0 / 3.0, 1.0 / 2.0, 2.0 / 3.0];
+const DEFAULT_COLUMN_WIDTH: f64 = 1.0 / 2.0;
+const MIN_COLUMN_WIDTH: f64 = 0.1;
+const MAX_COLUMN_WIDTH: f64 = 1.0;
+
+const ANIMATION_USEC: u64 = 150_000;
+const ANIMATION_TICK_MS: u64 = 8;
+
+pub struct ScrollingColumn {
+    pub node: Rc<dyn ToplevelNode>,
+    pub width: Cell<f64>,
+    /// The position of the column relative to the start of the strip.
+    pub body: Cell<Rect>,
+    attention_requested: Cell<bool>,
+}
+
+impl ScrollingColumn {
+    fn new(node: Rc<dyn ToplevelNode>, width: f64) -> Self {
+        Self {
+            node,
+            width: Cell::new(width),
+            body: Default::default(),
+            attention_requested: Cell::new(false),
+        }
+    }
+}
+
+/// An infinite horizontal strip of columns.
+///
+/// Only the part of the strip inside the viewport is shown. The viewport follows the
+/// active column.
+pub struct ScrollingNode {
+    pub id: ScrollingNodeId,
+    state: Rc<State>,
+    workspace: Rc<WorkspaceNode>,
+    pub columns: LinkedList<ScrollingColumn>,
+    child_nodes: RefCell<AHashMap<NodeId, LinkedNode<ScrollingColumn>>>,
+    active_column: CloneCell<Option<NodeRef<ScrollingColumn>>>,
+    pending_reveal: CloneCell<Option<NodeRef<ScrollingColumn>>>,
+    seat_state: NodeSeatState,
+    visible: Cell<bool>,
+    position: Cell<Rect>,
+    strip_width: Cell<i32>,
+    viewport: Cell<f64>,
+    viewport_target: Cell<f64>,
+    animation_start: Cell<(u64, f64)>,
+    animation: Cell<Option<SpawnedFuture<()>>>,
+    animating: Cell<bool>,
+    layout_scheduled: Cell<bool>,
+    attention_requests: ThresholdCounter,
+}
+
+pub async fn scrolling_layout(state: Rc<State>) {
+    loop {
+        let node = state.pending_scrolling_layout.pop().await;
+        if node.layout_scheduled.get() {
+            node.perform_layout();
+        }
+    }
+}
+
+impl ScrollingNode {
+    pub fn new(state: &Rc<State>, workspace: &Rc<WorkspaceNode>) -> Rc<Self> {
+        Rc::new(Self {
+            id: state.node_ids.next(),
+            state: state.clone(),
+            workspace: workspace.clone(),
+            columns: Default::default(),
+            child_nodes: Default::default(),
+            active_column: Default::default(),
+            pending_reveal: Default::default(),
+            seat_state: Default::default(),
+            visible: Cell::new(workspace.container_visible()),
+            position: Cell::new(workspace.tiling_rect()),
+            strip_width: Cell::new(0),
+            viewport: Cell::new(0.0),
+            viewport_target: Cell::new(0.0),
+            animation_start: Cell::new((0, 0.0)),
+            animation: Default::default(),
+            animating: Cell::new(false),
+            layout_scheduled: Cell::new(false),
+            attention_requests: Default::default(),
+        })
+    }
+
+    pub fn clear(&self) {
+        self.animation.take();
+        self.animating.set(false);
+        self.active_column.take();
+        self.pending_reveal.take();
+        self.child_nodes.borrow_mut().clear();
+    }
+
+    /// Returns the horizontal offset of the viewport within the strip.
+    fn viewport_offset(&self) -> i32 {
+        self.viewport.get().round() as i32
+    }
+
+    pub fn active_column(&self) -> Option<Rc<dyn ToplevelNode>> {
+        self.active_column.get().map(|c| c.node.clone())
+    }
+
+    /// Adds a new column to the right of the active column.
+    pub fn add_window(self: &Rc<Self>, node: Rc<dyn ToplevelNode>) {
+        let column = ScrollingColumn::new(self.create_column(node), DEFAULT_COLUMN_WIDTH);
+        let link = match self.active_column.get() {
+            Some(active) => active.append(column),
+            _ => self.columns.add_last(column),
+        };
+        self.insert_column(link);
+    }
+
+    /// Adds a new column at the end of the strip.
+    pub fn append_window(self: &Rc<Self>, node: Rc<dyn ToplevelNode>) {
+        let column = ScrollingColumn::new(self.create_column(node), DEFAULT_COLUMN_WIDTH);
+        let link = self.columns.add_last(column);
+        self.insert_column(link);
+    }
+
+    /// Adds a new column directly to the left or right of an existing column.
+    pub fn add_window_next_to(
+        self: &Rc<Self>,
+        column: &dyn Node,
+        node: Rc<dyn ToplevelNode>,
+        right: bool,
+    ) {
+        let Some(link) = self.link(column) else {
+            return;
+        };
+        let column = ScrollingColumn::new(self.create_column(node), DEFAULT_COLUMN_WIDTH);
+        let link = match right {
+            true => link.append(column),
+            false => link.prepend(column),
+        };
+        self.insert_column(link);
+    }
+
+    fn create_column(&self, node: Rc<dyn ToplevelNode>) -> Rc<dyn ToplevelNode> {
+        if node.node_is_container() {
+            return node;
+        }
+        ContainerNode::new(&self.state, &self.workspace, node, ContainerSplit::Vertical)
+    }
+
+    fn insert_column(self: &Rc<Self>, link: LinkedNode<ScrollingColumn>) {
+        let node = link.node.clone();
+        let link_ref = link.to_ref();
+        self.child_nodes.borrow_mut().insert(node.node_id(), link);
+        node.tl_set_parent(self.clone());
+        node.tl_set_visible(self.visible.get());
+        if node.tl_data().wants_attention.get() {
+            link_ref.attention_requested.set(true);
+            self.mod_attention_requests(true);
+        }
+        if self.active_column.is_none() || !collect_kb_foci(node).is_empty() {
+            self.activate_column(&link_ref);
+        }
+        self.schedule_layout();
+    }
+
+    fn link(&self, column: &dyn Node) -> Option<NodeRef<ScrollingColumn>> {
+        self.child_nodes
+            .borrow()
+            .get(&column.node_id())
+            .map(|l| l.to_ref())
+    }
+
+    fn activate_column(self: &Rc<Self>, column: &NodeRef<ScrollingColumn>) {
+        self.active_column.set(Some(column.clone()));
+        self.reveal_column(column);
+    }
+
+    fn reveal_column(self: &Rc<Self>, column: &NodeRef<ScrollingColumn>) {
+        self.pending_reveal.set(Some(column.clone()));
+        self.schedule_layout();
+    }
+
+    pub fn move_focus_from_column(
+        self: &Rc<Self>,
+        seat: &Rc<WlSeatGlobal>,
+        column: &dyn Node,
+        direction: Direction,
+    ) {
+        let Some(link) = self.link(column) else {
+            return;
+        };
+        let neighbor = match direction {
+            Direction::Left => link.prev(),
+            Direction::Right => link.next(),
+            _ => return,
+        };
+        if let Some(neighbor) = neighbor {
+            neighbor.node.clone().node_do_focus(seat, direction);
+        }
+    }
+
+    /// Swaps a column with its left or right neighbor.
+    pub fn move_column(self: &Rc<Self>, column: &dyn Node, direction: Direction) {
+        let Some(link) = self.link(column) else {
+            return;
+        };
+        match direction {
+            Direction::Left => match link.prev() {
+                Some(prev) => prev.prepend_existing(&link),
+                _ => return,
+            },
+            Direction::Right => match link.next() {
+                Some(next) => next.append_existing(&link),
+                _ => return,
+            },
+            _ => return,
+        }
+        self.reveal_column(&link);
+    }
+
+    /// Moves the first window of the column to the right of `column` to the bottom of
+    /// `column`.
+    pub fn consume_into_column(self: &Rc<Self>, column: &dyn Node) {
+        let Some(link) = self.link(column) else {
+            return;
+        };
+        let Some(target) = link.node.clone().node_into_container() else {
+            return;
+        };
+        let Some(source) = link
+            .next()
+            .and_then(|n| n.node.clone().node_into_container())
+        else {
+            return;
+        };
+        let Some(child) = source.children.first().map(|c| c.node.clone()) else {
+            return;
+        };
+        source.cnode_remove_child2(&*child, true);
+        target.append_child(child);
+    }
+
+    /// Moves a window out of `column` into a new column to the right of `column`.
+    pub fn expel_from_column(self: &Rc<Self>, column: &dyn Node, child: Rc<dyn ToplevelNode>) {
+        let Some(link) = self.link(column) else {
+            return;
+        };
+        let Some(container) = link.node.clone().node_into_container() else {
+            return;
+        };
+        if container.num_children() < 2 || child.node_id() == container.node_id() {
+            return;
+        }
+        container.cnode_remove_child2(&*child, true);
+        self.add_window_next_to(column, child, true);
+    }
+
+    /// Switches the width of a column to the next preset.
+    pub fn cycle_column_width(self: &Rc<Self>, column: &dyn Node) {
+        let Some(link) = self.link(column) else {
+            return;
+        };
+        let current = link.width.get();
+        let width = COLUMN_WIDTHS
+            .iter()
+            .copied()
+            .find(|w| *w > current + 0.01)
+            .unwrap_or(COLUMN_WIDTHS[0]);
+        link.width.set(width);
+        self.reveal_column(&link);
+    }
+
+    pub fn tile_drag_destination(
+        &self,
+        source: NodeId,
+        abs_bounds: Rect,
+        abs_x: i32,
+        abs_y: i32,
+    ) -> Option<TileDragDestination> {
+        for column in self.columns.iter() {
+            let body = column.node.node_absolute_position();
+            if !body.contains(abs_x, abs_y) {
+                continue;
+            }
+            if let Some(c) = column.node.clone().node_into_container() {
+                let bounds = body.intersect(abs_bounds);
+                return c.tile_drag_destination(source, bounds, abs_x, abs_y);
+            }
+            return None;
+        }
+        Some(TileDragDestination {
+            highlight: abs_bounds,
+            ty: TddType::MoveToWorkspace {
+                workspace: self.workspace.clone(),
+            },
+        })
+    }
+
+    pub fn on_spaces_changed(self: &Rc<Self>) {
+        self.schedule_layout();
+    }
+
+    pub fn change_extents(self: &Rc<Self>, rect: &Rect) {
+        let prev = self.position.replace(*rect);
+        if prev.size() != rect.size() {
+            self.perform_layout();
+        } else {
+            self.apply_positions();
+        }
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.visible.set(visible);
+        for column in self.columns.iter() {
+            column.node.tl_set_visible(visible);
+        }
+    }
+
+    fn schedule_layout(self: &Rc<Self>) {
+        if !self.layout_scheduled.replace(true) {
+            self.state.pending_scrolling_layout.push(self.clone());
+        }
+    }
+
+    fn perform_layout(self: &Rc<Self>) {
+        self.layout_scheduled.set(false);
+        let pos = self.position.get();
+        let gap = self.state.theme.sizes.border_width.get();
+        let total = (pos.width() + gap) as f64;
+        let mut x = 0;
+        for column in self.columns.iter() {
+            let width = ((column.width.get() * total).round() as i32 - gap).max(1);
+            column
+                .body
+                .set(Rect::new_sized_unchecked(x, 0, width, pos.height()));
+            x += width + gap;
+        }
+        self.strip_width.set((x - gap).max(0));
+        let mut target = self.viewport_target.get();
+        if let Some(column) = self.pending_reveal.take() {
+            let body = column.body.get();
+            if body.x2() as f64 > target + pos.width() as f64 {
+                target = (body.x2() - pos.width()) as f64;
+            }
+            if (body.x1() as f64) < target {
+                target = body.x1() as f64;
+            }
+        }
+        self.scroll_to(target);
+        self.apply_positions();
+        self.state.tree_changed();
+    }
+
+    fn max_viewport(&self) -> f64 {
+        (self.strip_width.get() - self.position.get().width()).max(0) as f64
+    }
+
+    fn scroll_to(self: &Rc<Self>, target: f64) {
+        let target = target.clamp(0.0, self.max_viewport());
+        self.viewport_target.set(target);
+        if self.viewport.get() == target {
+            return;
+        }
+        if !self.visible.get() {
+            self.animation.take();
+            self.animating.set(false);
+            self.viewport.set(target);
+            self.apply_positions();
+            return;
+        }
+        self.animation_start
+            .set((self.state.now_usec(), self.viewport.get()));
+        if self.animating.replace(true) {
+            return;
+        }
+        let slf = self.clone();
+        let future = self.state.eng.spawn("scrolling animation", async move {
+            loop {
+                if let Err(e) = slf.state.wheel.timeout(ANIMATION_TICK_MS).await {
+                    log::error!("Could not wait for animation tick: {}", ErrorFmt(e));
+                    slf.animation_start.set((0, slf.viewport_target.get()));
+                }
+                if !slf.animation_step() {
+                    return;
+                }
+            }
+        });
+        self.animation.set(Some(future));
+    }
+
+    fn animation_step(&self) -> bool {
+        let (start, from) = self.animation_start.get();
+        let target = self.viewport_target.get();
+        let elapsed = self.state.now_usec().saturating_sub(start);
+        let t = (elapsed as f64 / ANIMATION_USEC as f64).min(1.0);
+        let t = 1.0 - (1.0 - t).powi(3);
+        self.viewport.set(from + (target - from) * t);
+        self.apply_positions();
+        if t >= 1.0 {
+            self.animating.set(false);
+            return false;
+        }
+        true
+    }
+
+    fn apply_positions(&self) {
+        let pos = self.position.get();
+        let offset = self.viewport_offset();
+        for column in self.columns.iter() {
+            let body = column.body.get().move_(pos.x1() - offset, pos.y1());
+            column.node.clone().tl_change_extents(&body);
+        }
+        if self.visible.get() {
+            self.state.damage(pos);
+        }
+    }
+
+    pub fn swipe_begin(&self) {
+        self.animation.take();
+        self.animating.set(false);
+        self.viewport_target.set(self.viewport.get());
+    }
+
+    pub fn swipe_update(&self, dx: f64) {
+        let viewport = (self.viewport.get() - dx).clamp(0.0, self.max_viewport());
+        self.viewport.set(viewport);
+        self.viewport_target.set(viewport);
+        self.apply_positions();
+    }
+
+    /// Snaps the viewport to the column closest to its left edge and focuses that
+    /// column.
+    pub fn swipe_end(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>, cancelled: bool) {
+        let viewport = self.viewport.get();
+        let closest = self
+            .columns
+            .iter()
+            .min_by_key(|c| (c.body.get().x1() as f64 - viewport).abs() as i64);
+        let Some(closest) = closest else {
+            return;
+        };
+        self.scroll_to(closest.body.get().x1() as f64);
+        if !cancelled {
+            closest
+                .node
+                .clone()
+                .node_do_focus(seat, Direction::Unspecified);
+        }
+    }
+
+    fn mod_attention_requests(&self, set: bool) {
+        if self.attention_requests.adj(set) {
+            self.workspace
+                .clone()
+                .cnode_child_attention_request_changed(self, set);
+        }
+    }
+}
+
+/// Returns the scrolling node, column, and child of the column that contain `node`.
+pub fn scrolling_column(
+    node: Rc<dyn ToplevelNode>,
+) -> Option<(
+    Rc<ScrollingNode>,
+    Rc<dyn ToplevelNode>,
+    Rc<dyn ToplevelNode>,
+)> {
+    let mut child = node.clone();
+    let mut column = node;
+    loop {
+        let parent = column.tl_data().parent.get()?;
+        if let Some(scrolling) = parent.clone().node_into_scrolling() {
+            return Some((scrolling, column, child));
+        }
+        child = column;
+        column = parent.node_into_toplevel()?;
+    }
+}
+
+impl Node for ScrollingNode {
+    fn node_id(&self) -> NodeId {
+        self.id.into()
+    }
+
+    fn node_seat_state(&self) -> &NodeSeatState {
+        &self.seat_state
+    }
+
+    fn node_visit(self: Rc<Self>, visitor: &mut dyn NodeVisitor) {
+        visitor.visit_scrolling(&self);
+    }
+
+    fn node_visit_children(&self, visitor: &mut dyn NodeVisitor) {
+        for column in self.columns.iter() {
+            column.node.clone().node_visit(visitor);
+        }
+    }
+
+    fn node_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    fn node_absolute_position(&self) -> Rect {
+        self.position.get()
+    }
+
+    fn node_output(&self) -> Option<Rc<OutputNode>> {
+        Some(self.workspace.output.get())
+    }
+
+    fn node_location(&self) -> Option<NodeLocation> {
+        Some(self.workspace.location())
+    }
+
+    fn node_layer(&self) -> NodeLayerLink {
+        NodeLayerLink::Tiled
+    }
+
+    fn node_do_focus(self: Rc<Self>, seat: &Rc<WlSeatGlobal>, direction: Direction) {
+        let column = match self.active_column.get() {
+            Some(c) => Some(c),
+            _ => self.columns.first(),
+        };
+        if let Some(column) = column {
+            column.node.clone().node_do_focus(seat, direction);
+        }
+    }
+
+    fn node_find_tree_at(
+        &self,
+        x: i32,
+        y: i32,
+        tree: &mut Vec<FoundNode>,
+        usecase: FindTreeUsecase,
+    ) -> FindTreeResult {
+        let offset = self.viewport_offset();
+        for column in self.columns.iter() {
+            let body = column.body.get().move_(-offset, 0);
+            if body.contains(x, y) {
+                let (x, y) = body.translate(x, y);
+                tree.push(FoundNode {
+                    node: column.node.clone(),
+                    x,
+                    y,
+                });
+                column.node.node_find_tree_at(x, y, tree, usecase);
+                break;
+            }
+        }
+        FindTreeResult::AcceptsInput
+    }
+
+    fn node_child_active_changed(self: Rc<Self>, child: &dyn Node, active: bool, _depth: u32) {
+        if active && let Some(link) = self.link(child) {
+            self.activate_column(&link);
+        }
+    }
+
+    fn node_render(&self, renderer: &mut Renderer, x: i32, y: i32, _bounds: Option<&Rect>) {
+        renderer.render_scrolling(self, x, y);
+    }
+
+    fn node_make_visible(self: Rc<Self>) {
+        self.workspace.clone().node_make_visible();
+    }
+
+    fn node_on_pointer_focus(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.pointer_cursor().set_known(KnownCursor::Default);
+    }
+
+    fn node_into_containing_node(self: Rc<Self>) -> Option<Rc<dyn ContainingNode>> {
+        Some(self)
+    }
+
+    fn node_into_scrolling(self: Rc<Self>) -> Option<Rc<ScrollingNode>> {
+        Some(self)
+    }
+}
+
+impl ContainingNode for ScrollingNode {
+    fn cnode_replace_child(self: Rc<Self>, old: &dyn Node, new: Rc<dyn ToplevelNode>) {
+        let Some(link) = self.child_nodes.borrow_mut().remove(&old.node_id()) else {
+            log::error!("Trying to replace a node that isn't a column of this node");
+            return;
+        };
+        if link.attention_requested.get() {
+            self.mod_attention_requests(false);
+        }
+        let new_link = link.append(ScrollingColumn::new(new, link.width.get()));
+        new_link.body.set(link.body.get());
+        if let Some(active) = self.active_column.get()
+            && active.node.node_id() == old.node_id()
+        {
+            self.active_column.set(Some(new_link.to_ref()));
+        }
+        drop(link);
+        self.insert_column(new_link);
+    }
+
+    fn cnode_remove_child2(self: Rc<Self>, child: &dyn Node, _preserve_focus: bool) {
+        let Some(link) = self.child_nodes.borrow_mut().remove(&child.node_id()) else {
+            return;
+        };
+        if link.attention_requested.get() {
+            self.mod_attention_requests(false);
+        }
+        if let Some(active) = self.active_column.get()
+            && active.node.node_id() == child.node_id()
+        {
+            let new = link.prev().or_else(|| link.next());
+            self.active_column.set(new.clone());
+            self.pending_reveal.set(new);
+        }
+        drop(link);
+        if self.columns.is_empty() {
+            self.workspace.remove_scrolling();
+            return;
+        }
+        self.schedule_layout();
+    }
+
+    fn cnode_accepts_child(&self, node: &dyn Node) -> bool {
+        node.node_is_container()
+    }
+
+    fn cnode_child_attention_request_changed(self: Rc<Self>, child: &dyn Node, set: bool) {
+        let Some(link) = self.link(child) else {
+            return;
+        };
+        if link.attention_requested.replace(set) != set {
+            self.mod_attention_requests(set);
+        }
+    }
+
+    fn cnode_workspace(self: Rc<Self>) -> Rc<WorkspaceNode> {
+        self.workspace.clone()
+    }
+
+    fn cnode_make_visible(self: Rc<Self>, child: &dyn Node) {
+        self.workspace.clone().node_make_visible();
+        if let Some(link) = self.link(child) {
+            self.reveal_column(&link);
+        }
+    }
+
+    fn cnode_resize_child(
+        self: Rc<Self>,
+        child: &dyn Node,
+        new_x1: Option<i32>,
+        _new_y1: Option<i32>,
+        new_x2: Option<i32>,
+        _new_y2: Option<i32>,
+    ) {
+        let Some(link) = self.link(child) else {
+            return;
+        };
+        let pos = link.node.node_absolute_position();
+        let x1 = new_x1.unwrap_or(pos.x1());
+        let x2 = new_x2.unwrap_or(pos.x2());
+        let gap = self.state.theme.sizes.border_width.get();
+        let total = (self.position.get().width() + gap).max(1) as f64;
+        let width = ((x2 - x1 + gap) as f64 / total).clamp(MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH);
+        link.width.set(width);
+        self.schedule_layout();
+    }
+}
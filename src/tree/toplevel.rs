@@ -52,6 +52,7 @@ use {
         ops::Deref,
         rc::{Rc, Weak},
     },
+    uapi::c,
 };
 
 tree_id!(ToplevelNodeId);
@@ -69,6 +70,8 @@ pub trait ToplevelNode: ToplevelNodeBase {
     fn tl_destroy(&self);
     fn tl_pinned(&self) -> bool;
     fn tl_set_pinned(&self, self_pinned: bool, pinned: bool);
+    fn tl_sticky(&self) -> bool;
+    fn tl_set_sticky(&self, self_sticky: bool, sticky: bool);
     fn tl_set_float(&self, float: Option<&Rc<FloatNode>>);
     fn tl_mark_ancestor_fullscreen(&self, fullscreen: bool);
     fn tl_mark_fullscreen(&self, fullscreen: bool);
@@ -228,6 +231,24 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
         parent.cnode_set_pinned(pinned);
     }
 
+    fn tl_sticky(&self) -> bool {
+        let Some(parent) = self.tl_data().parent.get() else {
+            return false;
+        };
+        parent.cnode_sticky()
+    }
+
+    fn tl_set_sticky(&self, self_sticky: bool, sticky: bool) {
+        let data = self.tl_data();
+        if self_sticky {
+            data.sticky.set(sticky);
+        }
+        let Some(parent) = data.parent.get() else {
+            return;
+        };
+        parent.cnode_set_sticky(sticky);
+    }
+
     fn tl_set_float(&self, float: Option<&Rc<FloatNode>>) {
         self.tl_data().float.set(float.cloned());
         self.tl_push_float(float);
@@ -375,6 +396,7 @@ pub struct ToplevelData {
     pub float_width: Cell<i32>,
     pub float_height: Cell<i32>,
     pub pinned: Cell<bool>,
+    pub sticky: Cell<bool>,
     pub is_fullscreen: Cell<bool>,
     pub self_or_ancestor_is_fullscreen: Cell<bool>,
     pub fullscrceen_data: RefCell<Option<FullscreenedData>>,
@@ -404,6 +426,8 @@ pub struct ToplevelData {
     pub just_mapped_scheduled: Cell<bool>,
     pub seat_foci: CopyHashMap<SeatId, ()>,
     pub content_type: Cell<Option<ContentType>>,
+    pub swallowed: CloneCell<Option<Rc<dyn ToplevelNode>>>,
+    pub swallowed_by: CloneCell<Option<Weak<dyn ToplevelNode>>>,
 }
 
 impl ToplevelData {
@@ -431,6 +455,7 @@ impl ToplevelData {
             float_width: Default::default(),
             float_height: Default::default(),
             pinned: Cell::new(false),
+            sticky: Cell::new(false),
             is_fullscreen: Default::default(),
             self_or_ancestor_is_fullscreen: Default::default(),
             fullscrceen_data: Default::default(),
@@ -457,7 +482,16 @@ impl ToplevelData {
             just_mapped_scheduled: Cell::new(false),
             seat_foci: Default::default(),
             content_type: Default::default(),
+            swallowed: Default::default(),
+            swallowed_by: Default::default(),
+        }
+    }
+
+    pub fn pid(&self) -> Option<c::pid_t> {
+        if let ToplevelType::XWindow(w) = &self.kind {
+            return w.info.pid.get().map(|pid| pid as c::pid_t);
         }
+        self.client.as_ref().map(|c| c.pid_info.pid)
     }
 
     pub fn active(&self) -> bool {
@@ -548,12 +582,38 @@ impl ToplevelData {
         if let Some(fd) = self.fullscrceen_data.borrow_mut().take() {
             fd.placeholder.tl_destroy();
         }
+        if let Some(swallower) = self.swallowed_by.take().and_then(|s| s.upgrade()) {
+            swallower.tl_data().swallowed.take();
+        }
+        let swallowed = self.swallowed.take();
+        if let Some(swallowed) = &swallowed {
+            swallowed.tl_data().swallowed_by.take();
+        }
+        let mut seats = vec![];
+        if swallowed.is_some() {
+            self.seat_state.for_each_kb_focus(|s| seats.push(s));
+        }
         if let Some(parent) = self.parent.take() {
-            parent.cnode_remove_child(node);
+            match &swallowed {
+                Some(swallowed) if parent.clone().node_into_container().is_some() => {
+                    parent.cnode_replace_child(node, swallowed.clone());
+                }
+                _ => parent.cnode_remove_child(node),
+            }
         }
         self.float.take();
         self.workspace.take();
         self.seat_state.destroy_node(node);
+        if let Some(swallowed) = swallowed {
+            if swallowed.tl_data().parent.is_none() {
+                self.state.map_tiled(swallowed.clone());
+            }
+            for seat in seats {
+                swallowed
+                    .clone()
+                    .node_do_focus(&seat, Direction::Unspecified);
+            }
+        }
     }
 
     pub fn broadcast(&self, toplevel: Rc<dyn ToplevelNode>) {
@@ -754,6 +814,9 @@ impl ToplevelData {
             if let Some(container) = ws.container.get() {
                 kb_foci = collect_kb_foci(container);
             }
+            if let Some(scrolling) = ws.scrolling.get() {
+                collect_kb_foci2(scrolling, &mut kb_foci);
+            }
             for stacked in ws.stacked.iter() {
                 collect_kb_foci2(stacked.deref().clone(), &mut kb_foci);
             }
@@ -973,6 +1036,93 @@ pub fn toplevel_parent_container(tl: &dyn ToplevelNode) -> Option<Rc<ContainerNo
     None
 }
 
+fn toplevel_is_ancestor(ancestor: &dyn Node, tl: &dyn ToplevelNode) -> bool {
+    let mut parent = tl.tl_data().parent.get();
+    while let Some(p) = parent {
+        if p.node_id() == ancestor.node_id() {
+            return true;
+        }
+        parent = p
+            .node_into_toplevel()
+            .and_then(|tl| tl.tl_data().parent.get());
+    }
+    false
+}
+
+fn toplevels_can_be_rearranged(a: &dyn ToplevelNode, b: &dyn ToplevelNode) -> bool {
+    a.node_id() != b.node_id()
+        && !a.tl_data().is_fullscreen.get()
+        && !b.tl_data().is_fullscreen.get()
+        && !toplevel_is_ancestor(a, b)
+        && !toplevel_is_ancestor(b, a)
+}
+
+/// Returns the tiled window next to `tl` in the given direction.
+pub fn toplevel_in_direction(
+    tl: &dyn ToplevelNode,
+    direction: Direction,
+) -> Option<Rc<dyn ToplevelNode>> {
+    let ws = tl.tl_data().workspace.get()?;
+    let pos = tl.node_absolute_position();
+    let (cx, cy) = pos.center();
+    let overlaps_x = |r: &Rect| r.x1() < pos.x2() && r.x2() > pos.x1();
+    let overlaps_y = |r: &Rect| r.y1() < pos.y2() && r.y2() > pos.y1();
+    let mut best = None;
+    let mut best_distance = (i32::MAX, i32::MAX);
+    for leaf in ws.tiled_leaves() {
+        if leaf.node_id() == tl.node_id() {
+            continue;
+        }
+        let r = leaf.node_absolute_position();
+        let (x, y) = r.center();
+        let (distance, overlaps, offset) = match direction {
+            Direction::Left => (pos.x1() - r.x2(), overlaps_y(&r), (y - cy).abs()),
+            Direction::Right => (r.x1() - pos.x2(), overlaps_y(&r), (y - cy).abs()),
+            Direction::Up => (pos.y1() - r.y2(), overlaps_x(&r), (x - cx).abs()),
+            Direction::Down => (r.y1() - pos.y2(), overlaps_x(&r), (x - cx).abs()),
+            Direction::Unspecified => return None,
+        };
+        if distance < 0 || !overlaps {
+            continue;
+        }
+        if (distance, offset) < best_distance {
+            best_distance = (distance, offset);
+            best = Some(leaf);
+        }
+    }
+    best
+}
+
+pub fn toplevel_swap(a: Rc<dyn ToplevelNode>, b: Rc<dyn ToplevelNode>) {
+    if !toplevels_can_be_rearranged(&*a, &*b) {
+        return;
+    }
+    let Some(parent) = toplevel_parent_container(&*a) else {
+        return;
+    };
+    let workspaces = [a.tl_data().workspace.get(), b.tl_data().workspace.get()];
+    parent.swap_child(a, b);
+    for ws in workspaces.into_iter().flatten() {
+        ws.schedule_layout();
+    }
+}
+
+pub fn toplevel_move_next_to(tl: Rc<dyn ToplevelNode>, target: Rc<dyn ToplevelNode>) {
+    if !toplevels_can_be_rearranged(&*tl, &*target) {
+        return;
+    }
+    let Some(parent) = toplevel_parent_container(&*tl) else {
+        return;
+    };
+    if toplevel_parent_container(&*target).is_none() {
+        return;
+    }
+    parent.cnode_remove_child2(&*tl, true);
+    if let Some(target_parent) = toplevel_parent_container(&*target) {
+        target_parent.add_child_after(&*target, tl);
+    }
+}
+
 pub fn toplevel_create_split(state: &Rc<State>, tl: Rc<dyn ToplevelNode>, axis: ContainerSplit) {
     if tl.tl_data().is_fullscreen.get() {
         return;
@@ -1,6 +1,7 @@
 use {
     crate::{
         client::ClientId,
+        criteria::tlm::TL_CHANGED_WORKSPACE,
         cursor::KnownCursor,
         fixed::Fixed,
         ifs::{
@@ -22,7 +23,8 @@ use {
         tree::{
             ContainingNode, Direction, FindTreeResult, FindTreeUsecase, FloatNode, FoundNode, Node,
             NodeId, NodeLayerLink, NodeLocation, NodeVisitorBase, OutputNode, OutputNodeId,
-            PlaceholderNode, StackedNode, ToplevelNode, container::ContainerNode,
+            PlaceholderNode, ScrollingNode, StackedNode, ToplevelNode, ToplevelNodeBase,
+            container::{ContainerNode, ContainerSplit},
             walker::NodeVisitor,
         },
         utils::{
@@ -35,13 +37,14 @@ use {
         },
         wire::JayWorkspaceId,
     },
-    jay_config::workspace::WorkspaceDisplayOrder,
+    jay_config::workspace::{WorkspaceDisplayOrder, WorkspaceLayout},
     std::{
         cell::{Cell, RefCell},
         fmt::Debug,
         ops::Deref,
         rc::Rc,
     },
+    thiserror::Error,
 };
 
 tree_id!(WorkspaceNodeId);
@@ -54,9 +57,10 @@ pub struct WorkspaceNode {
     pub output_id: Cell<OutputNodeId>,
     pub position: Cell<Rect>,
     pub container: CloneCell<Option<Rc<ContainerNode>>>,
+    pub scrolling: CloneCell<Option<Rc<ScrollingNode>>>,
     pub stacked: LinkedList<Rc<dyn StackedNode>>,
     pub seat_state: NodeSeatState,
-    pub name: String,
+    pub name: RefCell<String>,
     pub output_link: RefCell<Option<LinkedNode<Rc<WorkspaceNode>>>>,
     pub visible: Cell<bool>,
     pub fullscreen: CloneCell<Option<Rc<dyn ToplevelNode>>>,
@@ -70,11 +74,42 @@ pub struct WorkspaceNode {
     pub render_highlight: NumCell<u32>,
     pub ext_workspaces: CopyHashMap<WorkspaceManagerId, Rc<ExtWorkspaceHandleV1>>,
     pub opt: Rc<Opt<WorkspaceNode>>,
+    pub layout: Cell<WorkspaceLayout>,
+    pub master_count: Cell<u32>,
+    pub master_ratio: Cell<f64>,
+    pub layout_scheduled: Cell<bool>,
+    pub split: Cell<ContainerSplit>,
+    pub gap: Cell<i32>,
+}
+
+pub struct WorkspaceDefinition {
+    pub persistent: Cell<bool>,
+    pub outputs: RefCell<Vec<String>>,
+    pub split: Cell<ContainerSplit>,
+    pub gap: Cell<i32>,
+    pub master_count: Cell<u32>,
+    pub master_ratio: Cell<f64>,
+}
+
+impl Default for WorkspaceDefinition {
+    fn default() -> Self {
+        Self {
+            persistent: Cell::new(false),
+            outputs: Default::default(),
+            split: Cell::new(ContainerSplit::Horizontal),
+            gap: Cell::new(0),
+            master_count: Cell::new(1),
+            master_ratio: Cell::new(0.5),
+        }
+    }
 }
 
 impl WorkspaceNode {
     pub fn clear(&self) {
         self.container.set(None);
+        if let Some(scrolling) = self.scrolling.take() {
+            scrolling.clear();
+        }
         *self.output_link.borrow_mut() = None;
         self.fullscreen.set(None);
         self.jay_workspaces.clear();
@@ -167,16 +202,36 @@ impl WorkspaceNode {
             self.discard_child_properties(&*prev);
         }
         self.pull_child_properties(&**container);
-        let pos = self.position.get();
+        let pos = self.tiling_rect();
         container.clone().tl_change_extents(&pos);
         container.tl_set_parent(self.clone());
         container.tl_set_visible(self.container_visible());
         self.container.set(Some(container.clone()));
         self.state.damage(self.position.get());
+        self.schedule_layout();
+    }
+
+    pub fn ensure_scrolling(self: &Rc<Self>) -> Rc<ScrollingNode> {
+        if let Some(scrolling) = self.scrolling.get() {
+            return scrolling;
+        }
+        let scrolling = ScrollingNode::new(&self.state, self);
+        self.scrolling.set(Some(scrolling.clone()));
+        scrolling
+    }
+
+    pub fn remove_scrolling(&self) {
+        if let Some(scrolling) = self.scrolling.take() {
+            scrolling.clear();
+            self.state.damage(self.position.get());
+        }
     }
 
     pub fn is_empty(&self) -> bool {
-        self.stacked.is_empty() && self.fullscreen.is_none() && self.container.is_none()
+        self.stacked.is_empty()
+            && self.fullscreen.is_none()
+            && self.container.is_none()
+            && self.scrolling.is_none()
     }
 
     pub fn container_visible(&self) -> bool {
@@ -189,8 +244,31 @@ impl WorkspaceNode {
 
     pub fn change_extents(&self, rect: &Rect) {
         self.position.set(*rect);
+        let rect = self.tiling_rect();
         if let Some(c) = self.container.get() {
-            c.tl_change_extents(rect);
+            c.tl_change_extents(&rect);
+        }
+        if let Some(s) = self.scrolling.get() {
+            s.change_extents(&rect);
+        }
+    }
+
+    pub fn tiling_rect(&self) -> Rect {
+        let pos = self.position.get();
+        let gap = self.gap.get();
+        Rect::new_sized(
+            pos.x1() + gap,
+            pos.y1() + gap,
+            pos.width() - 2 * gap,
+            pos.height() - 2 * gap,
+        )
+        .unwrap_or(pos)
+    }
+
+    pub fn set_gap(&self, gap: i32) {
+        if self.gap.replace(gap) != gap {
+            self.change_extents(&self.position.get());
+            self.state.damage(self.position.get());
         }
     }
 
@@ -217,6 +295,9 @@ impl WorkspaceNode {
         if let Some(container) = self.container.get() {
             container.tl_set_visible(self.container_visible());
         }
+        if let Some(scrolling) = self.scrolling.get() {
+            scrolling.set_visible(self.container_visible());
+        }
         for stacked in self.stacked.iter() {
             if stacked.stacked_needs_set_visible() {
                 stacked.stacked_set_visible(self.float_visible());
@@ -283,6 +364,82 @@ impl WorkspaceNode {
     pub fn location(&self) -> NodeLocation {
         NodeLocation::Workspace(self.output_id.get(), self.id)
     }
+
+    pub fn rename(self: &Rc<Self>, name: &str) -> Result<(), WorkspaceRenameError> {
+        if self.is_dummy {
+            return Err(WorkspaceRenameError::Dummy);
+        }
+        if name.is_empty() {
+            return Err(WorkspaceRenameError::Empty);
+        }
+        if *self.name.borrow() == name {
+            return Ok(());
+        }
+        if self.state.workspaces.contains(name) {
+            return Err(WorkspaceRenameError::Exists(name.to_string()));
+        }
+        let old = self.name.replace(name.to_string());
+        self.state.workspaces.remove(&old);
+        self.state.workspaces.set(name.to_string(), self.clone());
+        if let Some(layout) = self.state.workspace_layouts.remove(&old) {
+            self.state.workspace_layouts.set(name.to_string(), layout);
+        }
+        if let Some(definition) = self.state.workspace_definitions.remove(&old) {
+            self.state
+                .workspace_definitions
+                .set(name.to_string(), definition);
+        }
+        if let Some(config) = self.state.config.get() {
+            config.workspace_renamed(&old, name);
+        }
+        struct WorkspaceChanged;
+        impl NodeVisitorBase for WorkspaceChanged {
+            fn visit_container(&mut self, node: &Rc<ContainerNode>) {
+                node.tl_data().property_changed(TL_CHANGED_WORKSPACE);
+                node.node_visit_children(self);
+            }
+
+            fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
+                node.tl_data().property_changed(TL_CHANGED_WORKSPACE);
+                node.node_visit_children(self);
+            }
+
+            fn visit_xwindow(&mut self, node: &Rc<Xwindow>) {
+                node.tl_data().property_changed(TL_CHANGED_WORKSPACE);
+                node.node_visit_children(self);
+            }
+
+            fn visit_placeholder(&mut self, node: &Rc<PlaceholderNode>) {
+                node.tl_data().property_changed(TL_CHANGED_WORKSPACE);
+                node.node_visit_children(self);
+            }
+        }
+        self.node_visit_children(&mut WorkspaceChanged);
+        for stacked in self.stacked.iter() {
+            stacked.deref().clone().node_visit(&mut WorkspaceChanged);
+        }
+        for jw in self.jay_workspaces.lock().values() {
+            jw.send_name(self);
+            jw.send_done();
+        }
+        for wh in self.ext_workspaces.lock().values() {
+            wh.handle_name_changed(name);
+        }
+        let output = self.output.get();
+        output.handle_workspace_display_order_update();
+        self.state.damage(output.global.pos.get());
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WorkspaceRenameError {
+    #[error("Workspace names must not be empty")]
+    Empty,
+    #[error("A workspace with the name {0} already exists")]
+    Exists(String),
+    #[error("The dummy workspace cannot be renamed")]
+    Dummy,
 }
 
 impl Node for WorkspaceNode {
@@ -302,6 +459,9 @@ impl Node for WorkspaceNode {
         if let Some(c) = self.container.get() {
             visitor.visit_container(&c);
         }
+        if let Some(s) = self.scrolling.get() {
+            visitor.visit_scrolling(&s);
+        }
         if let Some(fs) = self.fullscreen.get() {
             fs.node_visit(visitor);
         }
@@ -334,6 +494,8 @@ impl Node for WorkspaceNode {
             && let Some(last) = seat.get_last_focus_on_workspace(&self)
         {
             seat.focus_node(last);
+        } else if let Some(scrolling) = self.scrolling.get() {
+            scrolling.node_do_focus(seat, direction);
         } else if let Some(container) = self.container.get() {
             container.node_do_focus(seat, direction);
         } else if let Some(float) = self
@@ -354,6 +516,14 @@ impl Node for WorkspaceNode {
         tree: &mut Vec<FoundNode>,
         usecase: FindTreeUsecase,
     ) -> FindTreeResult {
+        if let Some(n) = self.scrolling.get() {
+            tree.push(FoundNode {
+                node: n.clone(),
+                x,
+                y,
+            });
+            return n.node_find_tree_at(x, y, tree, usecase);
+        }
         if let Some(n) = self.container.get() {
             tree.push(FoundNode {
                 node: n.clone(),
@@ -494,7 +664,7 @@ pub fn move_ws_to_output(
     ws.set_output(&target);
     let before = if target.state.workspace_display_order.get() == WorkspaceDisplayOrder::Sorted {
         target
-            .find_workspace_insertion_point(&ws.name)
+            .find_workspace_insertion_point(&ws.name.borrow())
             .map(|nr| nr.deref().clone())
     } else {
         config.before
@@ -6,7 +6,7 @@ use {
             ptr_ext::{MutPtrExt, PtrExt},
         },
     },
-    jay_config::{keyboard::mods::Modifiers, window::Window},
+    jay_config::{keyboard::mods::Modifiers, window::Window, workspace::WorkspaceLayout},
     std::{
         cell::UnsafeCell,
         fmt::{Debug, Formatter},
@@ -104,3 +104,5 @@ unsafe impl UnsafeCellCloneSafe for Modifiers {}
 unsafe impl UnsafeCellCloneSafe for NodeId {}
 
 unsafe impl UnsafeCellCloneSafe for Window {}
+
+unsafe impl UnsafeCellCloneSafe for WorkspaceLayout {}
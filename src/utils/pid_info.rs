@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod tests;
+
 use {
     crate::utils::{errorfmt::ErrorFmt, oserror::OsError},
     bstr::ByteSlice,
@@ -40,6 +43,21 @@ pub fn get_pid_info(uid: c::uid_t, pid: c::pid_t) -> PidInfo {
     }
 }
 
+pub fn get_ppid(pid: c::pid_t) -> Option<c::pid_t> {
+    let stat = std::fs::read(format!("/proc/{}/stat", pid)).ok()?;
+    parse_ppid(&stat)
+}
+
+fn parse_ppid(stat: &[u8]) -> Option<c::pid_t> {
+    // The comm field is enclosed in parentheses and can itself contain parentheses.
+    let (_, rest) = stat.rsplit_once_str(")")?;
+    let mut fields = rest
+        .split(u8::is_ascii_whitespace)
+        .filter(|f| !f.is_empty());
+    let _state = fields.next()?;
+    fields.next()?.to_str().ok()?.parse().ok()
+}
+
 pub fn get_socket_creds(socket: &OwnedFd) -> Option<(c::uid_t, c::pid_t)> {
     let mut cred = c::ucred {
         pid: 0,
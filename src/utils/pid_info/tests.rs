@@ -0,0 +1,27 @@
+use crate::utils::pid_info::{get_ppid, parse_ppid};
+
+#[test]
+fn parse() {
+    assert_eq!(parse_ppid(b"1234 (bash) S 42 1234 1234 0 -1"), Some(42));
+    assert_eq!(parse_ppid(b"1234 (a) b (c)) R 7 1234 1234 0 -1"), Some(7));
+    assert_eq!(parse_ppid(b"1234 (a b) S\n"), None);
+    assert_eq!(parse_ppid(b"1234 (a) S x"), None);
+    assert_eq!(parse_ppid(b"1234 bash S 42"), None);
+}
+
+#[test]
+fn own_process() {
+    assert_eq!(get_ppid(uapi::getpid()), Some(uapi::getppid()));
+}
+
+#[test]
+fn child_process() {
+    let mut child = std::process::Command::new("sleep")
+        .arg("10")
+        .spawn()
+        .unwrap();
+    let ppid = get_ppid(child.id() as _);
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert_eq!(ppid, Some(uapi::getpid()));
+}
@@ -7,9 +7,12 @@ use {
         wl_usr::{
             UsrCon,
             usr_ifs::{
-                usr_jay_ei_session_builder::UsrJayEiSessionBuilder, usr_jay_output::UsrJayOutput,
-                usr_jay_pointer::UsrJayPointer, usr_jay_render_ctx::UsrJayRenderCtx,
-                usr_jay_screencast::UsrJayScreencast,
+                usr_jay_ei_session_builder::UsrJayEiSessionBuilder,
+                usr_jay_global_shortcuts::UsrJayGlobalShortcuts,
+                usr_jay_input_capture::UsrJayInputCapture, usr_jay_output::UsrJayOutput,
+                usr_jay_pointer::UsrJayPointer, usr_jay_portal_settings::UsrJayPortalSettings,
+                usr_jay_render_ctx::UsrJayRenderCtx, usr_jay_screencast::UsrJayScreencast,
+                usr_jay_screenshot::UsrJayScreenshot,
                 usr_jay_select_toplevel::UsrJaySelectToplevel,
                 usr_jay_select_workspace::UsrJaySelectWorkspace,
                 usr_jay_workspace_watcher::UsrJayWorkspaceWatcher, usr_wl_output::UsrWlOutput,
@@ -111,6 +114,53 @@ impl UsrJayCompositor {
         ww
     }
 
+    pub fn get_portal_settings(&self) -> Rc<UsrJayPortalSettings> {
+        let ps = Rc::new(UsrJayPortalSettings {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+            pending: Default::default(),
+        });
+        self.con.request(GetPortalSettings {
+            self_id: self.id,
+            id: ps.id,
+        });
+        self.con.add_object(ps.clone());
+        ps
+    }
+
+    pub fn get_global_shortcuts(&self) -> Rc<UsrJayGlobalShortcuts> {
+        let gs = Rc::new(UsrJayGlobalShortcuts {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+        });
+        self.con.request(GetGlobalShortcuts {
+            self_id: self.id,
+            id: gs.id,
+        });
+        self.con.add_object(gs.clone());
+        gs
+    }
+
+    pub fn create_input_capture(&self) -> Rc<UsrJayInputCapture> {
+        let ic = Rc::new(UsrJayInputCapture {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+            pending_zones: Default::default(),
+        });
+        self.con.request(CreateInputCapture {
+            self_id: self.id,
+            id: ic.id,
+        });
+        self.con.add_object(ic.clone());
+        ic
+    }
+
     pub fn get_pointer(&self, seat: &UsrWlSeat) -> Rc<UsrJayPointer> {
         let jp = Rc::new(UsrJayPointer {
             id: self.con.id(),
@@ -174,6 +224,59 @@ impl UsrJayCompositor {
         sc
     }
 
+    pub fn take_screenshot(&self, include_cursor: bool) -> Rc<UsrJayScreenshot> {
+        let ss = self.screenshot();
+        self.con.request(TakeScreenshot2 {
+            self_id: self.id,
+            id: ss.id,
+            include_cursor: include_cursor as _,
+        });
+        ss
+    }
+
+    pub fn take_output_screenshot(
+        &self,
+        output: &UsrWlOutput,
+        include_cursor: bool,
+    ) -> Rc<UsrJayScreenshot> {
+        let ss = self.screenshot();
+        self.con.request(TakeOutputScreenshot {
+            self_id: self.id,
+            id: ss.id,
+            output: output.id,
+            include_cursor: include_cursor as _,
+        });
+        ss
+    }
+
+    pub fn take_toplevel_screenshot(
+        &self,
+        toplevel_id: &str,
+        include_cursor: bool,
+    ) -> Rc<UsrJayScreenshot> {
+        let ss = self.screenshot();
+        self.con.request(TakeToplevelScreenshot {
+            self_id: self.id,
+            id: ss.id,
+            toplevel_id,
+            include_cursor: include_cursor as _,
+        });
+        ss
+    }
+
+    fn screenshot(&self) -> Rc<UsrJayScreenshot> {
+        let ss = Rc::new(UsrJayScreenshot {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            version: self.version,
+            drm_dev: Default::default(),
+            pending_planes: Default::default(),
+        });
+        self.con.add_object(ss.clone());
+        ss
+    }
+
     pub fn create_ei_session(&self) -> Rc<UsrJayEiSessionBuilder> {
         let obj = Rc::new(UsrJayEiSessionBuilder {
             id: self.con.id(),
@@ -217,6 +320,14 @@ impl JayCompositorEventHandler for UsrJayCompositor {
         }
         Ok(())
     }
+
+    fn rename_workspace_failed(
+        &self,
+        _ev: RenameWorkspaceFailed<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
 usr_object_base! {
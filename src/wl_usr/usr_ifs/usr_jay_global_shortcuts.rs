@@ -0,0 +1,88 @@
+use {
+    crate::{
+        object::Version,
+        utils::clonecell::CloneCell,
+        wire::{JayGlobalShortcutsId, jay_global_shortcuts::*},
+        wl_usr::{UsrCon, usr_object::UsrObject},
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct UsrJayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayGlobalShortcutsOwner>>>,
+    pub version: Version,
+}
+
+pub trait UsrJayGlobalShortcutsOwner {
+    fn activated(&self, id: u32, time_usec: u64) {
+        let _ = id;
+        let _ = time_usec;
+    }
+
+    fn deactivated(&self, id: u32, time_usec: u64) {
+        let _ = id;
+        let _ = time_usec;
+    }
+
+    fn trigger_captured(&self, mods: u32, keysym: u32) {
+        let _ = mods;
+        let _ = keysym;
+    }
+}
+
+impl UsrJayGlobalShortcuts {
+    pub fn bind(&self, id: u32, mods: u32, keysym: u32) {
+        self.con.request(Bind {
+            self_id: self.id,
+            id,
+            mods,
+            keysym,
+        });
+    }
+
+    pub fn capture_trigger(&self) {
+        self.con.request(CaptureTrigger { self_id: self.id });
+    }
+}
+
+impl JayGlobalShortcutsEventHandler for UsrJayGlobalShortcuts {
+    type Error = Infallible;
+
+    fn activated(&self, ev: Activated, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.activated(ev.id, ev.time_usec);
+        }
+        Ok(())
+    }
+
+    fn deactivated(&self, ev: Deactivated, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.deactivated(ev.id, ev.time_usec);
+        }
+        Ok(())
+    }
+
+    fn trigger_captured(&self, ev: TriggerCaptured, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.trigger_captured(ev.mods, ev.keysym);
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrJayGlobalShortcuts = JayGlobalShortcuts;
+    version = self.version;
+}
+
+impl UsrObject for UsrJayGlobalShortcuts {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
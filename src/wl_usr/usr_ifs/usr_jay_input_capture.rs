@@ -0,0 +1,161 @@
+use {
+    crate::{
+        fixed::Fixed,
+        object::Version,
+        rect::Rect,
+        utils::clonecell::CloneCell,
+        wire::{JayInputCaptureId, jay_input_capture::*},
+        wl_usr::{UsrCon, usr_object::UsrObject},
+    },
+    std::{cell::RefCell, convert::Infallible, mem, rc::Rc},
+    uapi::OwnedFd,
+};
+
+pub struct UsrJayInputCapture {
+    pub id: JayInputCaptureId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayInputCaptureOwner>>>,
+    pub version: Version,
+    pub pending_zones: RefCell<Vec<Rect>>,
+}
+
+pub trait UsrJayInputCaptureOwner {
+    fn zones(&self, zones: Vec<Rect>) {
+        let _ = zones;
+    }
+
+    fn ei_created(&self, fd: &Rc<OwnedFd>) {
+        let _ = fd;
+    }
+
+    fn ei_failed(&self, reason: &str) {
+        let _ = reason;
+    }
+
+    fn activated(&self, activation_id: u32, barrier_id: u32, x: Fixed, y: Fixed) {
+        let _ = activation_id;
+        let _ = barrier_id;
+        let _ = x;
+        let _ = y;
+    }
+
+    fn deactivated(&self, activation_id: u32, x: Fixed, y: Fixed) {
+        let _ = activation_id;
+        let _ = x;
+        let _ = y;
+    }
+
+    fn disabled(&self) {}
+}
+
+impl UsrJayInputCapture {
+    pub fn connect_ei(&self, app_id: &str) {
+        self.con.request(ConnectEi {
+            self_id: self.id,
+            app_id,
+        });
+    }
+
+    pub fn add_barrier(&self, id: u32, x1: i32, y1: i32, x2: i32, y2: i32) {
+        self.con.request(AddBarrier {
+            self_id: self.id,
+            id,
+            x1,
+            y1,
+            x2,
+            y2,
+        });
+    }
+
+    pub fn clear_barriers(&self) {
+        self.con.request(ClearBarriers { self_id: self.id });
+    }
+
+    pub fn enable(&self) {
+        self.con.request(Enable { self_id: self.id });
+    }
+
+    pub fn disable(&self) {
+        self.con.request(Disable { self_id: self.id });
+    }
+
+    pub fn release(&self, activation_id: u32, warp: Option<(Fixed, Fixed)>) {
+        let (x, y) = warp.unwrap_or_default();
+        self.con.request(Release {
+            self_id: self.id,
+            activation_id,
+            warp: warp.is_some() as u32,
+            x,
+            y,
+        });
+    }
+}
+
+impl JayInputCaptureEventHandler for UsrJayInputCapture {
+    type Error = Infallible;
+
+    fn zone(&self, ev: Zone, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(rect) = Rect::new_sized(ev.x, ev.y, ev.width, ev.height) {
+            self.pending_zones.borrow_mut().push(rect);
+        }
+        Ok(())
+    }
+
+    fn zones_done(&self, _ev: ZonesDone, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let zones = mem::take(&mut *self.pending_zones.borrow_mut());
+        if let Some(owner) = self.owner.get() {
+            owner.zones(zones);
+        }
+        Ok(())
+    }
+
+    fn ei_created(&self, ev: EiCreated, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.ei_created(&ev.fd);
+        }
+        Ok(())
+    }
+
+    fn ei_failed(&self, ev: EiFailed<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.ei_failed(ev.reason);
+        }
+        Ok(())
+    }
+
+    fn activated(&self, ev: Activated, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.activated(ev.activation_id, ev.barrier_id, ev.x, ev.y);
+        }
+        Ok(())
+    }
+
+    fn deactivated(&self, ev: Deactivated, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.deactivated(ev.activation_id, ev.x, ev.y);
+        }
+        Ok(())
+    }
+
+    fn disabled(&self, _ev: Disabled, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.disabled();
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrJayInputCapture = JayInputCapture;
+    version = self.version;
+}
+
+impl UsrObject for UsrJayInputCapture {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
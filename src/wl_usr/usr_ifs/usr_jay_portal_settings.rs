@@ -0,0 +1,88 @@
+use {
+    crate::{
+        object::Version,
+        utils::clonecell::CloneCell,
+        wire::{JayPortalSettingsId, jay_portal_settings::*},
+        wl_usr::{UsrCon, usr_object::UsrObject},
+    },
+    std::{cell::Cell, convert::Infallible, rc::Rc},
+};
+
+pub struct UsrJayPortalSettings {
+    pub id: JayPortalSettingsId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayPortalSettingsOwner>>>,
+    pub version: Version,
+    pub pending: Cell<PortalSettingsValues>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct PortalSettingsValues {
+    pub color_scheme: u32,
+    pub accent_color: Option<[f64; 3]>,
+    pub contrast: u32,
+    pub reduced_motion: bool,
+}
+
+pub trait UsrJayPortalSettingsOwner {
+    fn settings(&self, values: PortalSettingsValues);
+}
+
+impl UsrJayPortalSettings {
+    fn update(&self, f: impl FnOnce(&mut PortalSettingsValues)) {
+        let mut values = self.pending.get();
+        f(&mut values);
+        self.pending.set(values);
+    }
+}
+
+impl JayPortalSettingsEventHandler for UsrJayPortalSettings {
+    type Error = Infallible;
+
+    fn color_scheme(&self, ev: ColorScheme, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.update(|v| v.color_scheme = ev.scheme);
+        Ok(())
+    }
+
+    fn accent_color(&self, ev: AccentColor, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.update(|v| v.accent_color = Some([ev.r, ev.g, ev.b]));
+        Ok(())
+    }
+
+    fn no_accent_color(&self, _ev: NoAccentColor, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.update(|v| v.accent_color = None);
+        Ok(())
+    }
+
+    fn contrast(&self, ev: Contrast, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.update(|v| v.contrast = ev.contrast);
+        Ok(())
+    }
+
+    fn reduced_motion(&self, ev: ReducedMotion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.update(|v| v.reduced_motion = ev.reduced != 0);
+        Ok(())
+    }
+
+    fn done(&self, _ev: Done, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.settings(self.pending.get());
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrJayPortalSettings = JayPortalSettings;
+    version = self.version;
+}
+
+impl UsrObject for UsrJayPortalSettings {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
@@ -2,7 +2,8 @@ use {
     crate::{
         format::formats,
         object::Version,
-        utils::clonecell::CloneCell,
+        rect::Rect,
+        utils::{clonecell::CloneCell, mmap::mmap, oserror::OsError, ptr_ext::PtrExt},
         video::dmabuf::{DmaBuf, DmaBufPlane, PlaneVec},
         wire::{JayScreencastId, jay_screencast::*},
         wl_usr::{
@@ -16,6 +17,7 @@ use {
     },
     std::{cell::RefCell, mem, ops::DerefMut, rc::Rc},
     thiserror::Error,
+    uapi::c,
 };
 
 pub struct UsrJayScreencast {
@@ -41,6 +43,15 @@ pub struct UsrJayScreencastServerConfig {
     pub height: i32,
 }
 
+pub struct UsrJayScreencastCursorImage {
+    pub width: i32,
+    pub height: i32,
+    pub stride: i32,
+    pub hotspot_x: i32,
+    pub hotspot_y: i32,
+    pub data: Vec<u8>,
+}
+
 pub trait UsrJayScreencastOwner {
     fn buffers(&self, buffers: Vec<DmaBuf>) {
         let _ = buffers;
@@ -57,6 +68,16 @@ pub trait UsrJayScreencastOwner {
     fn config(&self, config: UsrJayScreencastServerConfig) {
         let _ = config;
     }
+
+    fn cursor_position(&self, visible: bool, x: i32, y: i32) {
+        let _ = visible;
+        let _ = x;
+        let _ = y;
+    }
+
+    fn cursor_image(&self, image: UsrJayScreencastCursorImage) {
+        let _ = image;
+    }
 }
 
 impl UsrJayScreencast {
@@ -67,6 +88,17 @@ impl UsrJayScreencast {
         });
     }
 
+    pub fn set_region(&self, output: &UsrJayOutput, rect: Rect) {
+        self.con.request(SetRegion {
+            self_id: self.id,
+            output: output.id,
+            x: rect.x1(),
+            y: rect.y1(),
+            width: rect.width(),
+            height: rect.height(),
+        });
+    }
+
     pub fn set_toplevel(&self, tl: &UsrJayToplevel) {
         self.con.request(SetToplevel {
             self_id: self.id,
@@ -129,6 +161,13 @@ impl UsrJayScreencast {
             buffer: buffer.id,
         });
     }
+
+    pub fn set_cursor_mode(&self, mode: u32) {
+        self.con.request(SetCursorMode {
+            self_id: self.id,
+            mode,
+        });
+    }
 }
 
 impl JayScreencastEventHandler for UsrJayScreencast {
@@ -247,6 +286,38 @@ impl JayScreencastEventHandler for UsrJayScreencast {
         self.pending_config.borrow_mut().height = ev.height;
         Ok(())
     }
+
+    fn cursor_position(&self, ev: CursorPosition, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(owner) = self.owner.get() {
+            owner.cursor_position(ev.visible != 0, ev.x, ev.y);
+        }
+        Ok(())
+    }
+
+    fn cursor_image(&self, ev: CursorImage, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if ev.width < 0 || ev.height < 0 || ev.stride < ev.width * 4 {
+            return Err(UsrJayScreencastError::InvalidCursorImage);
+        }
+        let len = ev.stride as usize * ev.height as usize;
+        let data = if len == 0 {
+            vec![]
+        } else {
+            let map = mmap(len, c::PROT_READ, c::MAP_PRIVATE, ev.fd.raw(), 0)
+                .map_err(UsrJayScreencastError::MapCursorImage)?;
+            unsafe { map.ptr.deref() }.to_vec()
+        };
+        if let Some(owner) = self.owner.get() {
+            owner.cursor_image(UsrJayScreencastCursorImage {
+                width: ev.width,
+                height: ev.height,
+                stride: ev.stride,
+                hotspot_x: ev.hotspot_x,
+                hotspot_y: ev.hotspot_y,
+                data,
+            });
+        }
+        Ok(())
+    }
 }
 
 usr_object_base! {
@@ -268,4 +339,8 @@ impl UsrObject for UsrJayScreencast {
 pub enum UsrJayScreencastError {
     #[error("The server sent an unknown format {0}")]
     UnknownFormat(u32),
+    #[error("The server sent an invalid cursor image")]
+    InvalidCursorImage,
+    #[error("Could not map the cursor image")]
+    MapCursorImage(#[source] OsError),
 }
@@ -0,0 +1,114 @@
+use {
+    crate::{
+        format::XRGB8888,
+        object::Version,
+        utils::clonecell::CloneCell,
+        video::dmabuf::{DmaBuf, DmaBufPlane, PlaneVec},
+        wire::{JayScreenshotId, jay_screenshot::*},
+        wl_usr::{UsrCon, usr_object::UsrObject},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        convert::Infallible,
+        mem,
+        ops::DerefMut,
+        rc::Rc,
+    },
+    uapi::OwnedFd,
+};
+
+pub struct UsrJayScreenshot {
+    pub id: JayScreenshotId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayScreenshotOwner>>>,
+    pub version: Version,
+    pub drm_dev: Cell<Option<Rc<OwnedFd>>>,
+    pub pending_planes: RefCell<PlaneVec<DmaBufPlane>>,
+}
+
+pub trait UsrJayScreenshotOwner {
+    fn done(&self, drm_dev: Option<Rc<OwnedFd>>, buf: DmaBuf);
+    fn error(&self, msg: &str);
+}
+
+impl UsrJayScreenshot {
+    fn send(&self, res: Result<(Option<Rc<OwnedFd>>, DmaBuf), &str>) {
+        if let Some(owner) = self.owner.take() {
+            match res {
+                Ok((drm_dev, buf)) => owner.done(drm_dev, buf),
+                Err(msg) => owner.error(msg),
+            }
+        }
+        self.con.remove_obj(self);
+    }
+}
+
+impl JayScreenshotEventHandler for UsrJayScreenshot {
+    type Error = Infallible;
+
+    fn dmabuf(&self, ev: Dmabuf, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mut planes = PlaneVec::new();
+        planes.push(DmaBufPlane {
+            offset: ev.offset,
+            stride: ev.stride,
+            fd: ev.fd,
+        });
+        let buf = DmaBuf {
+            id: self.con.dma_buf_ids.next(),
+            width: ev.width as _,
+            height: ev.height as _,
+            format: XRGB8888,
+            modifier: ev.modifier,
+            planes,
+        };
+        self.send(Ok((Some(ev.drm_dev), buf)));
+        Ok(())
+    }
+
+    fn error(&self, ev: Error<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.send(Err(ev.msg));
+        Ok(())
+    }
+
+    fn drm_dev(&self, ev: DrmDev, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.drm_dev.set(Some(ev.drm_dev));
+        Ok(())
+    }
+
+    fn plane(&self, ev: Plane, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.pending_planes.borrow_mut().push(DmaBufPlane {
+            offset: ev.offset,
+            stride: ev.stride,
+            fd: ev.fd,
+        });
+        Ok(())
+    }
+
+    fn dmabuf2(&self, ev: Dmabuf2, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let buf = DmaBuf {
+            id: self.con.dma_buf_ids.next(),
+            width: ev.width,
+            height: ev.height,
+            format: XRGB8888,
+            modifier: ev.modifier,
+            planes: mem::take(self.pending_planes.borrow_mut().deref_mut()),
+        };
+        self.send(Ok((self.drm_dev.take(), buf)));
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    self = UsrJayScreenshot = JayScreenshot;
+    version = self.version;
+}
+
+impl UsrObject for UsrJayScreenshot {
+    fn destroy(&self) {
+        // nothing
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
@@ -6,6 +6,8 @@ mod keysyms;
 mod parser;
 mod parsers;
 mod spanned;
+#[cfg(test)]
+mod tests;
 mod value;
 
 pub use crate::config::parsers::input_mode::InputMode;
@@ -18,6 +20,12 @@ use {
                 config::{ConfigParser, ConfigParserError},
                 float::Float,
                 focus_history::FocusHistory,
+                hot_corner::HotCorner,
+                magnifier::Magnifier,
+                mouse_keys::MouseKeys,
+                overview::Overview,
+                portal::Portal,
+                workspace::ConfigWorkspace,
             },
         },
         toml::{self},
@@ -26,16 +34,17 @@ use {
     jay_config::{
         Axis, Direction, Workspace,
         input::{
-            LayerDirection, SwitchEvent, Timeline, acceleration::AccelProfile,
+            LayerDirection, MouseButton, SwitchEvent, Timeline, acceleration::AccelProfile,
             clickmethod::ClickMethod,
         },
         keyboard::{Keymap, ModifiedKeySym, mods::Modifiers, syms::KeySym},
         logging::LogLevel,
+        portal::ColorScheme,
         status::MessageFormat,
         theme::Color,
         video::{BlendSpace, ColorSpace, Eotf, Format, GfxApi, TearingMode, Transform, VrrMode},
-        window::{ContentType, TileState, WindowType},
-        workspace::WorkspaceDisplayOrder,
+        window::{ContentType, FloatSnap, TileState, WindowType},
+        workspace::{WorkspaceDisplayOrder, WorkspaceLayout},
         xwayland::XScalingMode,
     },
     std::{
@@ -75,6 +84,9 @@ pub enum SimpleCommand {
     ToggleFloatAboveFullscreen,
     SetFloatPinned(bool),
     ToggleFloatPinned,
+    SetFloatSticky(bool),
+    ToggleFloatSticky,
+    SnapFloat(FloatSnap),
     KillClient,
     ShowBar(bool),
     ToggleBar,
@@ -84,6 +96,24 @@ pub enum SimpleCommand {
     CreateMark,
     JumpToMark,
     PopMode(bool),
+    MouseKeysMotion(&'static [Direction]),
+    MouseKeysSelectButton(MouseButton),
+    MouseKeysClick(bool),
+    MouseKeysPress(bool),
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+    MasterCount(i32),
+    MasterRatio(f64),
+    CycleColumnWidth,
+    ConsumeIntoColumn,
+    ExpelFromColumn,
+    Swap(Direction),
+    SwapWithMark,
+    MoveToMark,
+    ToggleOverview,
+    ReleaseInputCapture,
+    WindowSwitcher(Timeline),
 }
 
 #[derive(Debug, Clone)]
@@ -170,10 +200,21 @@ pub enum Action {
     CreateMark(u32),
     JumpToMark(u32),
     CopyMark(u32, u32),
+    SwapWithMark(u32),
+    MoveToMark(u32),
     SetMode {
         name: String,
         latch: bool,
     },
+    SetLayout {
+        layout: WorkspaceLayout,
+    },
+    RenameWorkspace {
+        name: String,
+    },
+    SetColorScheme {
+        scheme: ColorScheme,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -273,6 +314,8 @@ pub struct WindowRule {
     pub latch: Option<Action>,
     pub auto_focus: Option<bool>,
     pub initial_tile_state: Option<TileState>,
+    pub swallow: Option<bool>,
+    pub swallowable: Option<bool>,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -397,6 +440,39 @@ pub struct Input {
     pub calibration_matrix: Option<[[f32; 3]; 2]>,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct TabletOutputArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigTablet {
+    pub match_: InputMatch,
+    pub output_area: Option<TabletOutputArea>,
+    pub pressure_curve: Option<[f64; 4]>,
+    pub left_handed: Option<bool>,
+    pub stylus_buttons: Vec<(u32, MouseButton)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PadAxisActions {
+    pub increase: Option<Action>,
+    pub decrease: Option<Action>,
+    pub step: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigTabletPad {
+    pub match_: InputMatch,
+    pub buttons: AHashMap<u32, Action>,
+    pub rings: AHashMap<u32, PadAxisActions>,
+    pub strips: AHashMap<u32, PadAxisActions>,
+    pub dials: AHashMap<u32, PadAxisActions>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Exec {
     pub prog: String,
@@ -460,6 +536,13 @@ pub struct Shortcut {
     pub keysym: ModifiedKeySym,
     pub action: Action,
     pub latch: Option<Action>,
+    pub sequence: Option<KeySequence>,
+}
+
+#[derive(Debug, Clone)]
+pub struct KeySequence {
+    pub keys: Vec<String>,
+    pub keysyms: Vec<ModifiedKeySym>,
 }
 
 #[derive(Debug, Clone)]
@@ -512,6 +595,17 @@ pub struct Config {
     pub middle_click_paste: Option<bool>,
     pub input_modes: AHashMap<String, InputMode>,
     pub workspace_display_order: Option<WorkspaceDisplayOrder>,
+    pub mouse_keys: Option<MouseKeys>,
+    pub magnifier: Option<Magnifier>,
+    pub tablets: Vec<ConfigTablet>,
+    pub tablet_pads: Vec<ConfigTabletPad>,
+    pub key_sequence_timeout: Duration,
+    pub hot_corners: Vec<HotCorner>,
+    pub default_workspace_layout: Option<WorkspaceLayout>,
+    pub workspace_layouts: AHashMap<String, WorkspaceLayout>,
+    pub workspaces: Vec<ConfigWorkspace>,
+    pub portal: Option<Portal>,
+    pub overview: Option<Overview>,
 }
 
 #[derive(Debug, Error)]
@@ -24,6 +24,7 @@ pub mod float;
 pub mod focus_history;
 mod format;
 mod gfx_api;
+pub mod hot_corner;
 mod idle;
 mod input;
 mod input_match;
@@ -31,14 +32,19 @@ pub mod input_mode;
 pub mod keymap;
 mod libei;
 mod log_level;
+pub mod magnifier;
 pub mod mark_id;
 mod mode;
 pub mod modified_keysym;
+pub mod mouse_keys;
 mod output;
 mod output_match;
+pub mod overview;
+pub mod portal;
 mod repeat_rate;
 pub mod shortcuts;
 mod status;
+pub mod tablet;
 mod tearing;
 mod theme;
 mod tile_state;
@@ -47,7 +53,9 @@ mod vrr;
 mod window_match;
 mod window_rule;
 mod window_type;
+pub mod workspace;
 mod workspace_display_order;
+mod workspace_layout;
 mod xwayland;
 
 #[derive(Debug, Error)]
@@ -20,9 +20,11 @@ use {
                 mark_id::{MarkIdParser, MarkIdParserError},
                 output::{OutputParser, OutputParserError},
                 output_match::{OutputMatchParser, OutputMatchParserError},
+                portal::{ColorSchemeParser, ColorSchemeParserError},
                 repeat_rate::{RepeatRateParser, RepeatRateParserError},
                 status::{StatusParser, StatusParserError},
                 theme::{ThemeParser, ThemeParserError},
+                workspace_layout::{WorkspaceLayoutParser, WorkspaceLayoutParserError},
             },
             spanned::SpannedErrorExt,
         },
@@ -35,7 +37,8 @@ use {
     jay_config::{
         Axis::{Horizontal, Vertical},
         get_workspace,
-        input::{LayerDirection, Timeline},
+        input::{LayerDirection, MouseButton, Timeline},
+        window::FloatSnap,
     },
     thiserror::Error,
 };
@@ -72,6 +75,10 @@ pub enum ActionParserError {
     SetLogLevel(#[source] LogLevelParserError),
     #[error("Could not parse a set-gfx-api action")]
     GfxApi(#[source] GfxApiParserError),
+    #[error("Could not parse a set-layout action")]
+    Layout(#[source] WorkspaceLayoutParserError),
+    #[error("Could not parse a set-color-scheme action")]
+    ColorScheme(#[source] ColorSchemeParserError),
     #[error("Could not parse a configure-drm-device action")]
     DrmDevice(#[source] DrmDeviceParserError),
     #[error("Could not parse a set-render-device action")]
@@ -88,6 +95,10 @@ pub enum ActionParserError {
     JumpToMark(#[source] MarkIdParserError),
     #[error("Could not parse a copy-mark action")]
     CopyMark(#[source] MarkIdParserError),
+    #[error("Could not parse a swap-with-mark action")]
+    SwapWithMark(#[source] MarkIdParserError),
+    #[error("Could not parse a move-to-mark action")]
+    MoveToMark(#[source] MarkIdParserError),
 }
 
 pub struct ActionParser<'a>(pub &'a Context<'a>);
@@ -140,6 +151,17 @@ impl ActionParser<'_> {
             "pin-float" => SetFloatPinned(true),
             "unpin-float" => SetFloatPinned(false),
             "toggle-float-pinned" => ToggleFloatPinned,
+            "stick-float" => SetFloatSticky(true),
+            "unstick-float" => SetFloatSticky(false),
+            "toggle-float-sticky" => ToggleFloatSticky,
+            "snap-float-left" => SnapFloat(FloatSnap::Left),
+            "snap-float-right" => SnapFloat(FloatSnap::Right),
+            "snap-float-top" => SnapFloat(FloatSnap::Top),
+            "snap-float-bottom" => SnapFloat(FloatSnap::Bottom),
+            "snap-float-top-left" => SnapFloat(FloatSnap::TopLeft),
+            "snap-float-top-right" => SnapFloat(FloatSnap::TopRight),
+            "snap-float-bottom-left" => SnapFloat(FloatSnap::BottomLeft),
+            "snap-float-bottom-right" => SnapFloat(FloatSnap::BottomRight),
             "kill-client" => KillClient,
             "show-bar" => ShowBar(true),
             "hide-bar" => ShowBar(false),
@@ -153,6 +175,41 @@ impl ActionParser<'_> {
             "jump-to-mark" => JumpToMark,
             "clear-modes" => PopMode(false),
             "pop-mode" => PopMode(true),
+            "mouse-keys-left" => MouseKeysMotion(&[Left]),
+            "mouse-keys-down" => MouseKeysMotion(&[Down]),
+            "mouse-keys-up" => MouseKeysMotion(&[Up]),
+            "mouse-keys-right" => MouseKeysMotion(&[Right]),
+            "mouse-keys-up-left" => MouseKeysMotion(&[Up, Left]),
+            "mouse-keys-up-right" => MouseKeysMotion(&[Up, Right]),
+            "mouse-keys-down-left" => MouseKeysMotion(&[Down, Left]),
+            "mouse-keys-down-right" => MouseKeysMotion(&[Down, Right]),
+            "mouse-keys-select-left" => MouseKeysSelectButton(MouseButton::Left),
+            "mouse-keys-select-middle" => MouseKeysSelectButton(MouseButton::Middle),
+            "mouse-keys-select-right" => MouseKeysSelectButton(MouseButton::Right),
+            "mouse-keys-click" => MouseKeysClick(false),
+            "mouse-keys-double-click" => MouseKeysClick(true),
+            "mouse-keys-press" => MouseKeysPress(true),
+            "mouse-keys-release" => MouseKeysPress(false),
+            "zoom-in" => ZoomIn,
+            "zoom-out" => ZoomOut,
+            "zoom-reset" => ZoomReset,
+            "increase-master-count" => MasterCount(1),
+            "decrease-master-count" => MasterCount(-1),
+            "increase-master-ratio" => MasterRatio(0.05),
+            "decrease-master-ratio" => MasterRatio(-0.05),
+            "cycle-column-width" => CycleColumnWidth,
+            "consume-into-column" => ConsumeIntoColumn,
+            "expel-from-column" => ExpelFromColumn,
+            "swap-left" => Swap(Left),
+            "swap-down" => Swap(Down),
+            "swap-up" => Swap(Up),
+            "swap-right" => Swap(Right),
+            "swap-with-mark" => SwapWithMark,
+            "move-to-mark" => MoveToMark,
+            "overview" => ToggleOverview,
+            "release-input-capture" => ReleaseInputCapture,
+            "window-switcher-prev" => WindowSwitcher(Timeline::Older),
+            "window-switcher-next" => WindowSwitcher(Timeline::Newer),
             _ => {
                 return Err(
                     ActionParserError::UnknownSimpleAction(string.to_string()).spanned(span)
@@ -307,6 +364,27 @@ impl ActionParser<'_> {
         Ok(Action::SetLogLevel { level })
     }
 
+    fn parse_set_layout(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let layout = ext
+            .extract(val("layout"))?
+            .parse_map(&mut WorkspaceLayoutParser)
+            .map_spanned_err(ActionParserError::Layout)?;
+        Ok(Action::SetLayout { layout })
+    }
+
+    fn parse_rename_workspace(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let name = ext.extract(str("name"))?.value.to_string();
+        Ok(Action::RenameWorkspace { name })
+    }
+
+    fn parse_set_color_scheme(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let scheme = ext
+            .extract(val("scheme"))?
+            .parse_map(&mut ColorSchemeParser)
+            .map_spanned_err(ActionParserError::ColorScheme)?;
+        Ok(Action::SetColorScheme { scheme })
+    }
+
     fn parse_set_gfx_api(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
         let api = ext
             .extract(val("api"))?
@@ -406,6 +484,32 @@ impl ActionParser<'_> {
         Ok(Action::JumpToMark(id))
     }
 
+    fn parse_swap_with_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (id,) = ext.extract((opt(val("id")),))?;
+        let Some(id) = id else {
+            return Ok(Action::SimpleCommand {
+                cmd: SimpleCommand::SwapWithMark,
+            });
+        };
+        let id = id
+            .parse(&mut MarkIdParser(self.0))
+            .map_spanned_err(ActionParserError::SwapWithMark)?;
+        Ok(Action::SwapWithMark(id))
+    }
+
+    fn parse_move_to_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (id,) = ext.extract((opt(val("id")),))?;
+        let Some(id) = id else {
+            return Ok(Action::SimpleCommand {
+                cmd: SimpleCommand::MoveToMark,
+            });
+        };
+        let id = id
+            .parse(&mut MarkIdParser(self.0))
+            .map_spanned_err(ActionParserError::MoveToMark)?;
+        Ok(Action::MoveToMark(id))
+    }
+
     fn parse_copy_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
         let (src, dst) = ext.extract((val("src"), val("dst")))?;
         let src = src
@@ -489,8 +593,13 @@ impl Parser for ActionParser<'_> {
             "create-mark" => self.parse_create_mark(&mut ext),
             "jump-to-mark" => self.parse_jump_to_mark(&mut ext),
             "copy-mark" => self.parse_copy_mark(&mut ext),
+            "swap-with-mark" => self.parse_swap_with_mark(&mut ext),
+            "move-to-mark" => self.parse_move_to_mark(&mut ext),
             "push-mode" => self.parse_push_mode(&mut ext),
             "latch-mode" => self.parse_latch_mode(&mut ext),
+            "set-layout" => self.parse_set_layout(&mut ext),
+            "rename-workspace" => self.parse_rename_workspace(&mut ext),
+            "set-color-scheme" => self.parse_set_color_scheme(&mut ext),
             v => {
                 ext.ignore_unused();
                 return Err(ActionParserError::UnknownType(v.to_string()).spanned(ty.span));
@@ -18,25 +18,33 @@ use {
                 float::FloatParser,
                 focus_history::FocusHistoryParser,
                 gfx_api::GfxApiParser,
+                hot_corner::HotCornersParser,
                 idle::IdleParser,
                 input::InputsParser,
                 input_mode::InputModesParser,
                 keymap::KeymapParser,
                 libei::LibeiParser,
                 log_level::LogLevelParser,
+                magnifier::MagnifierParser,
+                mouse_keys::MouseKeysParser,
                 output::OutputsParser,
+                overview::OverviewParser,
+                portal::PortalParser,
                 repeat_rate::RepeatRateParser,
                 shortcuts::{
                     ComplexShortcutsParser, ShortcutsParser, ShortcutsParserError,
                     parse_modified_keysym_str,
                 },
                 status::StatusParser,
+                tablet::{TabletPadsParser, TabletsParser},
                 tearing::TearingParser,
                 theme::ThemeParser,
                 ui_drag::UiDragParser,
                 vrr::VrrParser,
                 window_rule::WindowRulesParser,
+                workspace::WorkspacesParser,
                 workspace_display_order::WorkspaceDisplayOrderParser,
+                workspace_layout::{WorkspaceLayoutParser, WorkspaceLayoutsParser},
                 xwayland::XwaylandParser,
             },
             spanned::SpannedErrorExt,
@@ -48,7 +56,7 @@ use {
     },
     ahash::AHashMap,
     indexmap::IndexMap,
-    std::collections::HashSet,
+    std::{collections::HashSet, time::Duration},
     thiserror::Error,
 };
 
@@ -139,7 +147,19 @@ impl Parser for ConfigParser<'_> {
                 show_bar,
                 focus_history_val,
             ),
-            (middle_click_paste, input_modes_val, workspace_display_order_val),
+            (
+                middle_click_paste,
+                input_modes_val,
+                workspace_display_order_val,
+                mouse_keys_val,
+                magnifier_val,
+                tablets_val,
+                tablet_pads_val,
+                key_sequence_timeout_val,
+                hot_corners_val,
+                default_workspace_layout_val,
+            ),
+            (workspace_layouts_val, workspaces_val, portal_val, overview_val),
         ) = ext.extract((
             (
                 opt(val("keymap")),
@@ -193,6 +213,19 @@ impl Parser for ConfigParser<'_> {
                 recover(opt(bol("middle-click-paste"))),
                 opt(val("modes")),
                 opt(val("workspace-display-order")),
+                opt(val("mouse-keys")),
+                opt(val("magnifier")),
+                opt(val("tablets")),
+                opt(val("tablet-pads")),
+                recover(opt(int("key-sequence-timeout-ms"))),
+                opt(val("hot-corners")),
+                opt(val("default-workspace-layout")),
+            ),
+            (
+                opt(val("workspace-layouts")),
+                opt(val("workspaces")),
+                opt(val("portal")),
+                opt(val("overview")),
             ),
         ))?;
         let mut keymap = None;
@@ -503,6 +536,116 @@ impl Parser for ConfigParser<'_> {
                 }
             }
         }
+        let mut mouse_keys = None;
+        if let Some(value) = mouse_keys_val {
+            match value.parse(&mut MouseKeysParser(self.0)) {
+                Ok(v) => mouse_keys = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the mouse-keys settings: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        let mut magnifier = None;
+        if let Some(value) = magnifier_val {
+            match value.parse(&mut MagnifierParser(self.0)) {
+                Ok(v) => magnifier = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the magnifier settings: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        let mut tablets = vec![];
+        if let Some(value) = tablets_val {
+            match value.parse(&mut TabletsParser(self.0)) {
+                Ok(v) => tablets = v,
+                Err(e) => {
+                    log::warn!("Could not parse the tablets: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut tablet_pads = vec![];
+        if let Some(value) = tablet_pads_val {
+            match value.parse(&mut TabletPadsParser(self.0)) {
+                Ok(v) => tablet_pads = v,
+                Err(e) => {
+                    log::warn!("Could not parse the tablet pads: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut key_sequence_timeout = Duration::from_secs(1);
+        if let Some(value) = key_sequence_timeout_val {
+            if value.value < 0 {
+                log::warn!(
+                    "Key sequence timeout should not be negative: {}",
+                    self.0.error3(value.span)
+                );
+            } else {
+                key_sequence_timeout = Duration::from_millis(value.value as u64);
+            }
+        }
+        let mut hot_corners = vec![];
+        if let Some(value) = hot_corners_val {
+            match value.parse(&mut HotCornersParser(self.0)) {
+                Ok(v) => hot_corners = v,
+                Err(e) => {
+                    log::warn!("Could not parse the hot corners: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut default_workspace_layout = None;
+        if let Some(value) = default_workspace_layout_val {
+            match value.parse(&mut WorkspaceLayoutParser) {
+                Ok(v) => default_workspace_layout = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the default workspace layout: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        let mut workspace_layouts = AHashMap::new();
+        if let Some(value) = workspace_layouts_val {
+            match value.parse(&mut WorkspaceLayoutsParser(self.0)) {
+                Ok(v) => workspace_layouts = v,
+                Err(e) => {
+                    log::warn!("Could not parse the workspace layouts: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut workspaces = vec![];
+        if let Some(value) = workspaces_val {
+            match value.parse(&mut WorkspacesParser(self.0)) {
+                Ok(v) => workspaces = v,
+                Err(e) => {
+                    log::warn!("Could not parse the workspaces: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut portal = None;
+        if let Some(value) = portal_val {
+            match value.parse(&mut PortalParser(self.0)) {
+                Ok(v) => portal = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the portal settings: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut overview = None;
+        if let Some(value) = overview_val {
+            match value.parse(&mut OverviewParser(self.0)) {
+                Ok(v) => overview = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the overview settings: {}", self.0.error(e));
+                }
+            }
+        }
         Ok(Config {
             keymap,
             repeat_rate,
@@ -546,6 +689,17 @@ impl Parser for ConfigParser<'_> {
             middle_click_paste: middle_click_paste.despan(),
             input_modes,
             workspace_display_order,
+            mouse_keys,
+            magnifier,
+            tablets,
+            tablet_pads,
+            key_sequence_timeout,
+            hot_corners,
+            default_workspace_layout,
+            workspace_layouts,
+            workspaces,
+            portal,
+            overview,
         })
     }
 }
@@ -2,15 +2,16 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{Extractor, ExtractorError, bol, opt, recover},
+            extractor::{Extractor, ExtractorError, bol, int, opt, recover, val},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
         },
         toml::{
-            toml_span::{DespanExt, Span, Spanned},
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
             toml_value::Value,
         },
     },
     indexmap::IndexMap,
+    jay_config::window::FloatPlacement,
     thiserror::Error,
 };
 
@@ -27,6 +28,8 @@ pub struct FloatParser<'a>(pub &'a Context<'a>);
 #[derive(Debug, Clone)]
 pub struct Float {
     pub show_pin_icon: Option<bool>,
+    pub placement: Option<FloatPlacement>,
+    pub snap_threshold: Option<i32>,
 }
 
 impl Parser for FloatParser<'_> {
@@ -40,9 +43,53 @@ impl Parser for FloatParser<'_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.0, span, table);
-        let (show_pin_icon,) = ext.extract((recover(opt(bol("show-pin-icon"))),))?;
+        let (show_pin_icon, placement, snap_threshold) = ext.extract((
+            recover(opt(bol("show-pin-icon"))),
+            opt(val("placement")),
+            recover(opt(int("snap-threshold"))),
+        ))?;
+        let placement = placement.and_then(|p| match p.parse(&mut FloatPlacementParser) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                log::error!("Could not parse float placement: {}", self.0.error(e));
+                None
+            }
+        });
         Ok(Float {
             show_pin_icon: show_pin_icon.despan(),
+            placement,
+            snap_threshold: snap_threshold.despan().map(|v| v as i32),
         })
     }
 }
+
+#[derive(Debug, Error)]
+pub enum FloatPlacementParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown placement {0}")]
+    UnknownPlacement(String),
+}
+
+struct FloatPlacementParser;
+
+impl Parser for FloatPlacementParser {
+    type Value = FloatPlacement;
+    type Error = FloatPlacementParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let placement = match string {
+            "center" => FloatPlacement::Center,
+            "under-cursor" => FloatPlacement::UnderCursor,
+            "smart" => FloatPlacement::Smart,
+            "cascade" => FloatPlacement::Cascade,
+            _ => {
+                return Err(
+                    FloatPlacementParserError::UnknownPlacement(string.to_string()).spanned(span),
+                );
+            }
+        };
+        Ok(placement)
+    }
+}
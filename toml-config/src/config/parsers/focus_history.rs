@@ -28,6 +28,7 @@ pub struct FocusHistoryParser<'a>(pub &'a Context<'a>);
 pub struct FocusHistory {
     pub only_visible: Option<bool>,
     pub same_workspace: Option<bool>,
+    pub switcher_thumbnails: Option<bool>,
 }
 
 impl Parser for FocusHistoryParser<'_> {
@@ -41,13 +42,15 @@ impl Parser for FocusHistoryParser<'_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.0, span, table);
-        let (only_visible, same_workspace) = ext.extract((
+        let (only_visible, same_workspace, switcher_thumbnails) = ext.extract((
             recover(opt(bol("only-visible"))),
             recover(opt(bol("same-workspace"))),
+            recover(opt(bol("switcher-thumbnails"))),
         ))?;
         Ok(FocusHistory {
             only_visible: only_visible.despan(),
             same_workspace: same_workspace.despan(),
+            switcher_thumbnails: switcher_thumbnails.despan(),
         })
     }
 }
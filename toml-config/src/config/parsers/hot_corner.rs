@@ -0,0 +1,142 @@
+use {
+    crate::{
+        config::{
+            Action, OutputMatch,
+            context::Context,
+            extractor::{Extractor, ExtractorError, fltorint, int, opt, recover, str, val},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::{
+                action::{ActionParser, ActionParserError},
+                output_match::{OutputMatchParser, OutputMatchParserError},
+            },
+            spanned::SpannedErrorExt,
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::input::ScreenEdge,
+    std::time::Duration,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum HotCornerParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error(transparent)]
+    Match(#[from] OutputMatchParserError),
+    #[error("Could not parse the action")]
+    Action(#[source] ActionParserError),
+    #[error("Unknown corner {0}")]
+    UnknownCorner(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct HotCorner {
+    pub corner: ScreenEdge,
+    pub match_: Option<OutputMatch>,
+    pub dwell: Duration,
+    pub pressure: f64,
+    pub action: Action,
+}
+
+pub struct HotCornerParser<'a>(pub &'a Context<'a>);
+
+impl Parser for HotCornerParser<'_> {
+    type Value = HotCorner;
+    type Error = HotCornerParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (corner, match_val, dwell_ms, pressure, action_val) = ext.extract((
+            str("corner"),
+            opt(val("match")),
+            recover(opt(int("dwell-ms"))),
+            recover(opt(fltorint("pressure"))),
+            val("action"),
+        ))?;
+        let corner = match corner.value {
+            "top-left" => ScreenEdge::TopLeft,
+            "top" => ScreenEdge::Top,
+            "top-right" => ScreenEdge::TopRight,
+            "right" => ScreenEdge::Right,
+            "bottom-right" => ScreenEdge::BottomRight,
+            "bottom" => ScreenEdge::Bottom,
+            "bottom-left" => ScreenEdge::BottomLeft,
+            "left" => ScreenEdge::Left,
+            _ => {
+                return Err(
+                    HotCornerParserError::UnknownCorner(corner.value.to_string())
+                        .spanned(corner.span),
+                );
+            }
+        };
+        let match_ = match match_val {
+            None => None,
+            Some(m) => Some(m.parse_map(&mut OutputMatchParser(self.0))?),
+        };
+        let mut dwell = Duration::ZERO;
+        if let Some(value) = dwell_ms {
+            if value.value < 0 {
+                log::warn!(
+                    "Dwell time should not be negative: {}",
+                    self.0.error3(value.span)
+                );
+            } else {
+                dwell = Duration::from_millis(value.value as u64);
+            }
+        }
+        let mut pressure_px = 0.0;
+        if let Some(value) = pressure {
+            if value.value < 0.0 {
+                log::warn!(
+                    "Pressure should not be negative: {}",
+                    self.0.error3(value.span)
+                );
+            } else {
+                pressure_px = value.value;
+            }
+        }
+        let action = action_val
+            .parse(&mut ActionParser(self.0))
+            .map_spanned_err(HotCornerParserError::Action)?;
+        Ok(HotCorner {
+            corner,
+            match_,
+            dwell,
+            pressure: pressure_px,
+            action,
+        })
+    }
+}
+
+pub struct HotCornersParser<'a>(pub &'a Context<'a>);
+
+impl Parser for HotCornersParser<'_> {
+    type Value = Vec<HotCorner>;
+    type Error = HotCornerParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut HotCornerParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse hot corner: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
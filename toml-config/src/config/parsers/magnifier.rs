@@ -0,0 +1,67 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{Extractor, ExtractorError, bol, fltorint, opt, recover, str},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::modified_keysym::ModifiersParser,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::keyboard::mods::Modifiers,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum MagnifierParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct MagnifierParser<'a>(pub &'a Context<'a>);
+
+#[derive(Debug, Clone)]
+pub struct Magnifier {
+    pub step: Option<f64>,
+    pub scroll_modifiers: Option<Modifiers>,
+    pub show_in_screencasts: Option<bool>,
+}
+
+impl Parser for MagnifierParser<'_> {
+    type Value = Magnifier;
+    type Error = MagnifierParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (step, scroll_modifiers_str, show_in_screencasts) = ext.extract((
+            recover(opt(fltorint("step"))),
+            recover(opt(str("scroll-modifiers"))),
+            recover(opt(bol("show-in-screencasts"))),
+        ))?;
+        let mut scroll_modifiers = None;
+        if let Some(mods) = scroll_modifiers_str {
+            match ModifiersParser.parse_string(mods.span, mods.value) {
+                Ok(m) => scroll_modifiers = Some(m),
+                Err(e) => {
+                    log::warn!("Could not parse scroll modifiers: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(Magnifier {
+            step: step.despan(),
+            scroll_modifiers,
+            show_in_screencasts: show_in_screencasts.despan(),
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{Extractor, ExtractorError, fltorint, n64, opt, recover},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum MouseKeysParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct MouseKeysParser<'a>(pub &'a Context<'a>);
+
+#[derive(Debug, Clone)]
+pub struct MouseKeys {
+    pub initial_speed: Option<f64>,
+    pub max_speed: Option<f64>,
+    pub time_to_max_ms: Option<u64>,
+}
+
+impl Parser for MouseKeysParser<'_> {
+    type Value = MouseKeys;
+    type Error = MouseKeysParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (initial_speed, max_speed, time_to_max_ms) = ext.extract((
+            recover(opt(fltorint("initial-speed"))),
+            recover(opt(fltorint("max-speed"))),
+            recover(opt(n64("time-to-max-ms"))),
+        ))?;
+        Ok(MouseKeys {
+            initial_speed: initial_speed.despan(),
+            max_speed: max_speed.despan(),
+            time_to_max_ms: time_to_max_ms.despan(),
+        })
+    }
+}
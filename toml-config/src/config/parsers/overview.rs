@@ -0,0 +1,60 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{Extractor, ExtractorError, bol, n32, opt, recover},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum OverviewParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct OverviewParser<'a>(pub &'a Context<'a>);
+
+#[derive(Debug, Clone)]
+pub struct Overview {
+    pub swipe: Option<bool>,
+    pub swipe_fingers: Option<u32>,
+}
+
+impl Parser for OverviewParser<'_> {
+    type Value = Overview;
+    type Error = OverviewParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (swipe, swipe_fingers) = ext.extract((
+            recover(opt(bol("swipe"))),
+            recover(opt(n32("swipe-fingers"))),
+        ))?;
+        let mut swipe_fingers = swipe_fingers.despan();
+        if let Some(fingers) = swipe_fingers
+            && fingers < 3
+        {
+            log::warn!("Swipe gestures need at least 3 fingers but {fingers} were requested");
+            swipe_fingers = None;
+        }
+        Ok(Overview {
+            swipe: swipe.despan(),
+            swipe_fingers,
+        })
+    }
+}
@@ -0,0 +1,167 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{Extractor, ExtractorError, bol, opt, recover, val},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::color::ColorParser,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::{
+        portal::{ColorScheme, Contrast},
+        theme::Color,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum PortalParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct PortalParser<'a>(pub &'a Context<'a>);
+
+#[derive(Debug, Clone, Default)]
+pub struct Portal {
+    pub settings: Option<PortalSettings>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PortalSettings {
+    pub color_scheme: Option<ColorScheme>,
+    pub accent_color: Option<Color>,
+    pub contrast: Option<Contrast>,
+    pub reduced_motion: Option<bool>,
+}
+
+impl Parser for PortalParser<'_> {
+    type Value = Portal;
+    type Error = PortalParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let settings_val = ext.extract(opt(val("settings")))?;
+        let settings =
+            settings_val.and_then(|v| match v.parse(&mut PortalSettingsParser(self.0)) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the portal settings: {}", self.0.error(e));
+                    None
+                }
+            });
+        Ok(Portal { settings })
+    }
+}
+
+struct PortalSettingsParser<'a>(&'a Context<'a>);
+
+impl Parser for PortalSettingsParser<'_> {
+    type Value = PortalSettings;
+    type Error = PortalParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (color_scheme, accent_color, contrast, reduced_motion) = ext.extract((
+            opt(val("color-scheme")),
+            opt(val("accent-color")),
+            opt(val("contrast")),
+            recover(opt(bol("reduced-motion"))),
+        ))?;
+        let color_scheme = color_scheme.and_then(|v| match v.parse(&mut ColorSchemeParser) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                log::warn!("Could not parse the color scheme: {}", self.0.error(e));
+                None
+            }
+        });
+        let accent_color = accent_color.and_then(|v| match v.parse(&mut ColorParser) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                log::warn!("Could not parse the accent color: {}", self.0.error(e));
+                None
+            }
+        });
+        let contrast = contrast.and_then(|v| match v.parse(&mut ContrastParser) {
+            Ok(v) => Some(v),
+            Err(e) => {
+                log::warn!("Could not parse the contrast: {}", self.0.error(e));
+                None
+            }
+        });
+        Ok(PortalSettings {
+            color_scheme,
+            accent_color,
+            contrast,
+            reduced_motion: reduced_motion.despan(),
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ColorSchemeParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown color scheme {0}")]
+    Unknown(String),
+}
+
+pub struct ColorSchemeParser;
+
+impl Parser for ColorSchemeParser {
+    type Value = ColorScheme;
+    type Error = ColorSchemeParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let scheme = match string {
+            "default" => ColorScheme::DEFAULT,
+            "prefer-dark" => ColorScheme::PREFER_DARK,
+            "prefer-light" => ColorScheme::PREFER_LIGHT,
+            _ => return Err(ColorSchemeParserError::Unknown(string.to_string()).spanned(span)),
+        };
+        Ok(scheme)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ContrastParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown contrast {0}")]
+    Unknown(String),
+}
+
+struct ContrastParser;
+
+impl Parser for ContrastParser {
+    type Value = Contrast;
+    type Error = ContrastParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let contrast = match string {
+            "normal" => Contrast::NORMAL,
+            "high" => Contrast::HIGH,
+            _ => return Err(ContrastParserError::Unknown(string.to_string()).spanned(span)),
+        };
+        Ok(contrast)
+    }
+}
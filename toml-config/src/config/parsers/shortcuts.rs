@@ -1,7 +1,7 @@
 use {
     crate::{
         config::{
-            Action, Shortcut, SimpleCommand,
+            Action, KeySequence, Shortcut, SimpleCommand,
             context::Context,
             extractor::{Extractor, ExtractorError, opt, str, val},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
@@ -55,19 +55,22 @@ impl Parser for ShortcutsParser<'_, '_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         for (key, value) in table.iter() {
-            let Some(keysym) = parse_modified_keysym(self.cx, key) else {
+            let Some((keysym, sequence)) = parse_shortcut_key(self.cx, key) else {
                 continue;
             };
             let Some(action) = parse_action(self.cx, &key.value, value) else {
                 continue;
             };
-            let spanned = keysym.spanned(key.span);
-            log_used(self.cx, self.used_keys, spanned);
+            if sequence.is_none() {
+                let spanned = keysym.spanned(key.span);
+                log_used(self.cx, self.used_keys, spanned);
+            }
             self.shortcuts.push(Shortcut {
                 mask: Modifiers(!0),
                 keysym,
                 action,
                 latch: None,
+                sequence,
             });
         }
         Ok(())
@@ -91,11 +94,13 @@ impl Parser for ComplexShortcutsParser<'_, '_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         for (key, value) in table.iter() {
-            let Some(keysym) = parse_modified_keysym(self.cx, key) else {
+            let Some((keysym, sequence)) = parse_shortcut_key(self.cx, key) else {
                 continue;
             };
+            let is_sequence = sequence.is_some();
             let shortcut = match value.parse(&mut ComplexShortcutParser {
                 keysym,
+                sequence,
                 cx: self.cx,
             }) {
                 Ok(v) => v,
@@ -108,8 +113,10 @@ impl Parser for ComplexShortcutsParser<'_, '_> {
                     continue;
                 }
             };
-            let spanned = keysym.spanned(key.span);
-            log_used(self.cx, self.used_keys, spanned);
+            if !is_sequence {
+                let spanned = keysym.spanned(key.span);
+                log_used(self.cx, self.used_keys, spanned);
+            }
             self.shortcuts.push(shortcut);
         }
         Ok(())
@@ -118,6 +125,7 @@ impl Parser for ComplexShortcutsParser<'_, '_> {
 
 struct ComplexShortcutParser<'a> {
     pub keysym: ModifiedKeySym,
+    pub sequence: Option<KeySequence>,
     pub cx: &'a Context<'a>,
 }
 
@@ -160,6 +168,7 @@ impl Parser for ComplexShortcutParser<'_> {
             keysym: self.keysym,
             action,
             latch,
+            sequence: self.sequence.take(),
         })
     }
 }
@@ -174,8 +183,24 @@ fn parse_action(cx: &Context<'_>, key: &str, value: &Spanned<Value>) -> Option<A
     }
 }
 
-fn parse_modified_keysym(cx: &Context<'_>, key: &Spanned<String>) -> Option<ModifiedKeySym> {
-    parse_modified_keysym_str(cx, key.span, &key.value)
+fn parse_shortcut_key(
+    cx: &Context<'_>,
+    key: &Spanned<String>,
+) -> Option<(ModifiedKeySym, Option<KeySequence>)> {
+    let keys: Vec<_> = key.value.split_whitespace().collect();
+    if keys.len() < 2 {
+        let keysym = parse_modified_keysym_str(cx, key.span, &key.value)?;
+        return Some((keysym, None));
+    }
+    let mut keysyms = vec![];
+    for k in &keys {
+        keysyms.push(parse_modified_keysym_str(cx, key.span, k)?);
+    }
+    let sequence = KeySequence {
+        keys: keys.iter().map(|k| k.to_string()).collect(),
+        keysyms,
+    };
+    Some((sequence.keysyms[0], Some(sequence)))
 }
 
 pub fn parse_modified_keysym_str(
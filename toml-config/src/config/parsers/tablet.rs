@@ -0,0 +1,448 @@
+use {
+    crate::{
+        config::{
+            Action, ConfigTablet, ConfigTabletPad, PadAxisActions, TabletOutputArea,
+            context::Context,
+            extractor::{Extractor, ExtractorError, arr, bol, fltorint, opt, recover, val},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::{
+                action::ActionParser,
+                input_match::{InputMatchParser, InputMatchParserError},
+            },
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    ahash::AHashMap,
+    indexmap::IndexMap,
+    jay_config::input::MouseButton,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum TabletParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error(transparent)]
+    Match(#[from] InputMatchParserError),
+    #[error("Pressure curve must have exactly four entries")]
+    PressureCurveLength,
+    #[error("Pressure curve entries must be floats")]
+    PressureCurveFloat,
+}
+
+pub struct TabletParser<'a>(pub &'a Context<'a>);
+
+impl Parser for TabletParser<'_> {
+    type Value = ConfigTablet;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (match_val, output_area_val, pressure_curve_val, left_handed, stylus_buttons_val) = ext
+            .extract((
+                val("match"),
+                opt(val("output-area")),
+                recover(opt(arr("pressure-curve"))),
+                recover(opt(bol("left-handed"))),
+                opt(val("stylus-buttons")),
+            ))?;
+        let mut output_area = None;
+        if let Some(value) = output_area_val {
+            match value.parse(&mut TabletOutputAreaParser(self.0)) {
+                Ok(v) => output_area = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the output area: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut pressure_curve = None;
+        if let Some(value) = pressure_curve_val {
+            match parse_pressure_curve(value.span, value.value) {
+                Ok(v) => pressure_curve = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the pressure curve: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut stylus_buttons = vec![];
+        if let Some(value) = stylus_buttons_val {
+            match value.parse(&mut StylusButtonsParser(self.0)) {
+                Ok(v) => stylus_buttons = v,
+                Err(e) => {
+                    log::warn!("Could not parse the stylus buttons: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(ConfigTablet {
+            match_: match_val.parse_map(&mut InputMatchParser(self.0))?,
+            output_area,
+            pressure_curve,
+            left_handed: left_handed.despan(),
+            stylus_buttons,
+        })
+    }
+}
+
+fn parse_pressure_curve(
+    span: Span,
+    array: &[Spanned<Value>],
+) -> Result<[f64; 4], Spanned<TabletParserError>> {
+    if array.len() != 4 {
+        return Err(TabletParserError::PressureCurveLength.spanned(span));
+    }
+    let mut res = [0.0; 4];
+    for (res, v) in res.iter_mut().zip(array) {
+        *res = match v.value {
+            Value::Float(f) => f,
+            Value::Integer(i) => i as _,
+            _ => return Err(TabletParserError::PressureCurveFloat.spanned(v.span)),
+        };
+    }
+    Ok(res)
+}
+
+struct TabletOutputAreaParser<'a>(&'a Context<'a>);
+
+impl Parser for TabletOutputAreaParser<'_> {
+    type Value = TabletOutputArea;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (x, y, width, height) = ext.extract((
+            opt(fltorint("x")),
+            opt(fltorint("y")),
+            opt(fltorint("width")),
+            opt(fltorint("height")),
+        ))?;
+        Ok(TabletOutputArea {
+            x: x.despan().unwrap_or(0.0),
+            y: y.despan().unwrap_or(0.0),
+            width: width.despan().unwrap_or(1.0),
+            height: height.despan().unwrap_or(1.0),
+        })
+    }
+}
+
+struct StylusButtonsParser<'a>(&'a Context<'a>);
+
+impl Parser for StylusButtonsParser<'_> {
+    type Value = Vec<(u32, MouseButton)>;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut res = vec![];
+        for (key, value) in table {
+            let button = match key.value.as_str() {
+                "stylus" => 0x14b,
+                "stylus2" => 0x14c,
+                "stylus3" => 0x149,
+                _ => {
+                    log::warn!(
+                        "Unknown stylus button {}: {}",
+                        key.value,
+                        self.0.error3(key.span)
+                    );
+                    continue;
+                }
+            };
+            let mouse_button = match &value.value {
+                Value::String(s) => match s.as_str() {
+                    "left" => MouseButton::Left,
+                    "middle" => MouseButton::Middle,
+                    "right" => MouseButton::Right,
+                    _ => {
+                        log::warn!("Unknown mouse button {s}: {}", self.0.error3(value.span));
+                        continue;
+                    }
+                },
+                v => {
+                    log::warn!(
+                        "Expected a string but found {}: {}",
+                        v.name(),
+                        self.0.error3(value.span)
+                    );
+                    continue;
+                }
+            };
+            res.push((button, mouse_button));
+        }
+        Ok(res)
+    }
+}
+
+pub struct TabletsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for TabletsParser<'_> {
+    type Value = Vec<ConfigTablet>;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut TabletParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse tablet: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TabletPadParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error(transparent)]
+    Match(#[from] InputMatchParserError),
+}
+
+pub struct TabletPadParser<'a>(pub &'a Context<'a>);
+
+impl Parser for TabletPadParser<'_> {
+    type Value = ConfigTabletPad;
+    type Error = TabletPadParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (match_val, buttons_val, rings_val, strips_val, dials_val) = ext.extract((
+            val("match"),
+            opt(val("buttons")),
+            opt(val("rings")),
+            opt(val("strips")),
+            opt(val("dials")),
+        ))?;
+        let mut buttons = AHashMap::new();
+        if let Some(value) = buttons_val {
+            match value.parse(&mut PadButtonsParser(self.0)) {
+                Ok(v) => buttons = v,
+                Err(e) => {
+                    log::warn!("Could not parse the pad buttons: {}", self.0.error(e));
+                }
+            }
+        }
+        let parse_axes = |value: Option<Spanned<&Value>>, kind: PadAxisKind| {
+            let Some(value) = value else {
+                return AHashMap::new();
+            };
+            match value.parse(&mut PadAxesParser { cx: self.0, kind }) {
+                Ok(v) => v,
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the pad {}: {}",
+                        kind.name(),
+                        self.0.error(e)
+                    );
+                    AHashMap::new()
+                }
+            }
+        };
+        let rings = parse_axes(rings_val, PadAxisKind::Ring);
+        let strips = parse_axes(strips_val, PadAxisKind::Strip);
+        let dials = parse_axes(dials_val, PadAxisKind::Dial);
+        Ok(ConfigTabletPad {
+            match_: match_val.parse_map(&mut InputMatchParser(self.0))?,
+            buttons,
+            rings,
+            strips,
+            dials,
+        })
+    }
+}
+
+fn parse_index(cx: &Context<'_>, key: &Spanned<String>) -> Option<u32> {
+    match key.value.parse() {
+        Ok(idx) => Some(idx),
+        Err(_) => {
+            log::warn!(
+                "Expected a non-negative integer index: {}",
+                cx.error3(key.span)
+            );
+            None
+        }
+    }
+}
+
+fn parse_action(cx: &Context<'_>, value: Spanned<&Value>) -> Option<Action> {
+    match value.parse(&mut ActionParser(cx)) {
+        Ok(a) => Some(a),
+        Err(e) => {
+            log::warn!("Could not parse action: {}", cx.error(e));
+            None
+        }
+    }
+}
+
+struct PadButtonsParser<'a>(&'a Context<'a>);
+
+impl Parser for PadButtonsParser<'_> {
+    type Value = AHashMap<u32, Action>;
+    type Error = TabletPadParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut res = AHashMap::new();
+        for (key, value) in table {
+            let Some(idx) = parse_index(self.0, key) else {
+                continue;
+            };
+            let Some(action) = parse_action(self.0, value.as_ref()) else {
+                continue;
+            };
+            res.insert(idx, action);
+        }
+        Ok(res)
+    }
+}
+
+#[derive(Copy, Clone)]
+enum PadAxisKind {
+    Ring,
+    Strip,
+    Dial,
+}
+
+impl PadAxisKind {
+    fn name(self) -> &'static str {
+        match self {
+            PadAxisKind::Ring => "rings",
+            PadAxisKind::Strip => "strips",
+            PadAxisKind::Dial => "dials",
+        }
+    }
+
+    fn field_names(self) -> (&'static str, &'static str) {
+        match self {
+            PadAxisKind::Ring | PadAxisKind::Dial => ("clockwise", "counter-clockwise"),
+            PadAxisKind::Strip => ("down", "up"),
+        }
+    }
+}
+
+struct PadAxesParser<'a> {
+    cx: &'a Context<'a>,
+    kind: PadAxisKind,
+}
+
+impl Parser for PadAxesParser<'_> {
+    type Value = AHashMap<u32, PadAxisActions>;
+    type Error = TabletPadParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut res = AHashMap::new();
+        for (key, value) in table {
+            let Some(idx) = parse_index(self.cx, key) else {
+                continue;
+            };
+            match value.parse(&mut PadAxisParser {
+                cx: self.cx,
+                kind: self.kind,
+            }) {
+                Ok(v) => {
+                    res.insert(idx, v);
+                }
+                Err(e) => {
+                    log::warn!("Could not parse bindings: {}", self.cx.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+struct PadAxisParser<'a> {
+    cx: &'a Context<'a>,
+    kind: PadAxisKind,
+}
+
+impl Parser for PadAxisParser<'_> {
+    type Value = PadAxisActions;
+    type Error = TabletPadParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let (increase_name, decrease_name) = self.kind.field_names();
+        let mut ext = Extractor::new(self.cx, span, table);
+        let (increase_val, decrease_val, step) = ext.extract((
+            opt(val(increase_name)),
+            opt(val(decrease_name)),
+            recover(opt(fltorint("step"))),
+        ))?;
+        let mut step = step.despan();
+        if let Some(s) = step
+            && (!s.is_finite() || s <= 0.0)
+        {
+            log::warn!("Ignoring non-positive step {s}: {}", self.cx.error3(span));
+            step = None;
+        }
+        Ok(PadAxisActions {
+            increase: increase_val.and_then(|v| parse_action(self.cx, v)),
+            decrease: decrease_val.and_then(|v| parse_action(self.cx, v)),
+            step,
+        })
+    }
+}
+
+pub struct TabletPadsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for TabletPadsParser<'_> {
+    type Value = Vec<ConfigTabletPad>;
+    type Error = TabletPadParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut TabletPadParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse tablet pad: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
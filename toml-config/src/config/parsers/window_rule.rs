@@ -48,15 +48,25 @@ impl Parser for WindowRuleParser<'_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.0, span, table);
-        let (name, match_val, action_val, latch_val, auto_focus, initial_tile_state_val) = ext
-            .extract((
-                opt(str("name")),
-                opt(val("match")),
-                opt(val("action")),
-                opt(val("latch")),
-                recover(opt(bol("auto-focus"))),
-                opt(val("initial-tile-state")),
-            ))?;
+        let (
+            name,
+            match_val,
+            action_val,
+            latch_val,
+            auto_focus,
+            initial_tile_state_val,
+            swallow,
+            swallowable,
+        ) = ext.extract((
+            opt(str("name")),
+            opt(val("match")),
+            opt(val("action")),
+            opt(val("latch")),
+            recover(opt(bol("auto-focus"))),
+            opt(val("initial-tile-state")),
+            recover(opt(bol("swallow"))),
+            recover(opt(bol("swallowable"))),
+        ))?;
         let mut action = None;
         if let Some(value) = action_val {
             action = Some(
@@ -96,6 +106,8 @@ impl Parser for WindowRuleParser<'_> {
             latch,
             auto_focus: auto_focus.despan(),
             initial_tile_state,
+            swallow: swallow.despan(),
+            swallowable: swallowable.despan(),
         })
     }
 }
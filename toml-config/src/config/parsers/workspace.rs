@@ -0,0 +1,127 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{Extractor, ExtractorError, arr, bol, int, opt, recover, str, val},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::{
+                StringParser, StringParserError,
+                workspace_layout::{WorkspaceLayoutParser, WorkspaceLayoutParserError},
+            },
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::{Axis, workspace::WorkspaceLayout},
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WorkspaceParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error(transparent)]
+    String(#[from] StringParserError),
+    #[error(transparent)]
+    Layout(#[from] WorkspaceLayoutParserError),
+    #[error("Unknown split {0}")]
+    UnknownSplit(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigWorkspace {
+    pub name: String,
+    pub outputs: Vec<String>,
+    pub persistent: bool,
+    pub layout: Option<WorkspaceLayout>,
+    pub split: Option<Axis>,
+    pub gap: Option<i32>,
+}
+
+pub struct WorkspaceParser<'a>(pub &'a Context<'a>);
+
+impl Parser for WorkspaceParser<'_> {
+    type Value = ConfigWorkspace;
+    type Error = WorkspaceParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (name, outputs_val, persistent, layout_val, split, gap) = ext.extract((
+            str("name"),
+            opt(arr("outputs")),
+            recover(opt(bol("persistent"))),
+            opt(val("layout")),
+            recover(opt(str("split"))),
+            recover(opt(int("gap"))),
+        ))?;
+        let mut outputs = vec![];
+        if let Some(outputs_val) = outputs_val {
+            for output in outputs_val.value {
+                outputs.push(output.parse_map(&mut StringParser)?);
+            }
+        }
+        let layout = match layout_val {
+            None => None,
+            Some(l) => Some(l.parse_map(&mut WorkspaceLayoutParser)?),
+        };
+        let split = match split {
+            None => None,
+            Some(s) => match s.value {
+                "horizontal" => Some(Axis::Horizontal),
+                "vertical" => Some(Axis::Vertical),
+                _ => {
+                    return Err(
+                        WorkspaceParserError::UnknownSplit(s.value.to_string()).spanned(s.span)
+                    );
+                }
+            },
+        };
+        let mut gap_px = None;
+        if let Some(value) = gap {
+            if value.value < 0 {
+                log::warn!("Gap should not be negative: {}", self.0.error3(value.span));
+            } else {
+                gap_px = Some(value.value as i32);
+            }
+        }
+        Ok(ConfigWorkspace {
+            name: name.value.to_string(),
+            outputs,
+            persistent: persistent.despan().unwrap_or(false),
+            layout,
+            split,
+            gap: gap_px,
+        })
+    }
+}
+
+pub struct WorkspacesParser<'a>(pub &'a Context<'a>);
+
+impl Parser for WorkspacesParser<'_> {
+    type Value = Vec<ConfigWorkspace>;
+    type Error = WorkspaceParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut WorkspaceParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse workspace: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
@@ -0,0 +1,74 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    ahash::AHashMap,
+    indexmap::IndexMap,
+    jay_config::workspace::WorkspaceLayout,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WorkspaceLayoutParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown workspace layout {0}")]
+    Unknown(String),
+}
+
+pub struct WorkspaceLayoutParser;
+
+impl Parser for WorkspaceLayoutParser {
+    type Value = WorkspaceLayout;
+    type Error = WorkspaceLayoutParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        match string {
+            "manual" => Ok(WorkspaceLayout::Manual),
+            "master-stack" => Ok(WorkspaceLayout::MasterStack),
+            "dwindle" => Ok(WorkspaceLayout::Dwindle),
+            "grid" => Ok(WorkspaceLayout::Grid),
+            "scrolling" => Ok(WorkspaceLayout::Scrolling),
+            _ => Err(WorkspaceLayoutParserError::Unknown(string.to_string()).spanned(span)),
+        }
+    }
+}
+
+pub struct WorkspaceLayoutsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for WorkspaceLayoutsParser<'_> {
+    type Value = AHashMap<String, WorkspaceLayout>;
+    type Error = WorkspaceLayoutParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut res = AHashMap::new();
+        for (key, value) in table {
+            match value.parse(&mut WorkspaceLayoutParser) {
+                Ok(v) => {
+                    res.insert(key.value.clone(), v);
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the layout of workspace {}: {}",
+                        key.value,
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        Ok(res)
+    }
+}
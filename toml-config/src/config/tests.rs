@@ -0,0 +1,166 @@
+use {
+    crate::config::{Action, Config, InputMatch, SimpleCommand, parse_config},
+    jay_config::input::MouseButton,
+    std::time::Duration,
+};
+
+fn parse(input: &str) -> Config {
+    parse_config(input.as_bytes(), &Default::default(), |e| {
+        panic!("could not parse config: {e}")
+    })
+    .unwrap()
+}
+
+fn match_name(m: &InputMatch) -> Option<&str> {
+    match m {
+        InputMatch::All { name, .. } => name.as_deref(),
+        InputMatch::Any(_) => None,
+    }
+}
+
+#[test]
+fn tablet() {
+    let config = parse(
+        r#"
+            [[tablets]]
+            match.name = "pen"
+            output-area = { x = 0.5, y = 0, width = 0.5, height = 1 }
+            pressure-curve = [0.3, 0, 0.7, 1]
+            left-handed = true
+            stylus-buttons = { stylus = "right", stylus2 = "middle" }
+        "#,
+    );
+    assert_eq!(config.tablets.len(), 1);
+    let tablet = &config.tablets[0];
+    assert_eq!(match_name(&tablet.match_), Some("pen"));
+    let area = tablet.output_area.unwrap();
+    assert_eq!(
+        (area.x, area.y, area.width, area.height),
+        (0.5, 0.0, 0.5, 1.0)
+    );
+    assert_eq!(tablet.pressure_curve, Some([0.3, 0.0, 0.7, 1.0]));
+    assert_eq!(tablet.left_handed, Some(true));
+    assert_eq!(
+        tablet.stylus_buttons,
+        [(0x14b, MouseButton::Right), (0x14c, MouseButton::Middle)],
+    );
+}
+
+#[test]
+fn tablet_defaults() {
+    let config = parse(
+        r#"
+            [[tablets]]
+            match.name = "pen"
+            output-area = { width = 0.25 }
+        "#,
+    );
+    let tablet = &config.tablets[0];
+    let area = tablet.output_area.unwrap();
+    assert_eq!(
+        (area.x, area.y, area.width, area.height),
+        (0.0, 0.0, 0.25, 1.0)
+    );
+    assert_eq!(tablet.pressure_curve, None);
+    assert_eq!(tablet.left_handed, None);
+    assert!(tablet.stylus_buttons.is_empty());
+}
+
+#[test]
+fn tablet_invalid_fields() {
+    let config = parse(
+        r#"
+            [[tablets]]
+            match.name = "pen"
+            pressure-curve = [0, 1]
+            stylus-buttons = { stylus = "fourth", eraser = "left", stylus3 = "left" }
+
+            [[tablets]]
+            match.name = "pen2"
+            pressure-curve = [0, "a", 1, 1]
+        "#,
+    );
+    assert_eq!(config.tablets.len(), 2);
+    assert_eq!(config.tablets[0].pressure_curve, None);
+    assert_eq!(
+        config.tablets[0].stylus_buttons,
+        [(0x149, MouseButton::Left)]
+    );
+    assert_eq!(config.tablets[1].pressure_curve, None);
+}
+
+#[test]
+fn tablet_pad() {
+    let config = parse(
+        r#"
+            [[tablet-pads]]
+            match.name = "pad"
+            buttons = { 0 = "focus-prev", 1 = "focus-next", x = "focus-left" }
+            rings.0 = { clockwise = "zoom-in", counter-clockwise = "zoom-out", step = 30 }
+            strips.1 = { down = "zoom-out", step = -1 }
+            dials.0 = { clockwise = "zoom-in" }
+        "#,
+    );
+    assert_eq!(config.tablet_pads.len(), 1);
+    let pad = &config.tablet_pads[0];
+    assert_eq!(match_name(&pad.match_), Some("pad"));
+    assert_eq!(pad.buttons.len(), 2);
+    assert!(pad.buttons.contains_key(&0));
+    assert!(pad.buttons.contains_key(&1));
+    let ring = &pad.rings[&0];
+    assert!(matches!(
+        ring.increase,
+        Some(Action::SimpleCommand {
+            cmd: SimpleCommand::ZoomIn
+        })
+    ));
+    assert!(matches!(
+        ring.decrease,
+        Some(Action::SimpleCommand {
+            cmd: SimpleCommand::ZoomOut
+        })
+    ));
+    assert_eq!(ring.step, Some(30.0));
+    let strip = &pad.strips[&1];
+    assert!(strip.increase.is_some());
+    assert!(strip.decrease.is_none());
+    assert_eq!(strip.step, None);
+    let dial = &pad.dials[&0];
+    assert!(dial.increase.is_some());
+    assert_eq!(dial.step, None);
+}
+
+#[test]
+fn key_sequence_timeout() {
+    let config = parse("");
+    assert_eq!(config.key_sequence_timeout, Duration::from_secs(1));
+    let config = parse("key-sequence-timeout-ms = 0");
+    assert_eq!(config.key_sequence_timeout, Duration::ZERO);
+    let config = parse("key-sequence-timeout-ms = 2500");
+    assert_eq!(config.key_sequence_timeout, Duration::from_millis(2500));
+}
+
+#[test]
+fn overview() {
+    let config = parse("");
+    assert!(config.overview.is_none());
+    let config = parse(
+        r#"
+            [overview]
+            swipe = false
+            swipe-fingers = 3
+        "#,
+    );
+    let overview = config.overview.unwrap();
+    assert_eq!(overview.swipe, Some(false));
+    assert_eq!(overview.swipe_fingers, Some(3));
+    let config = parse(
+        r#"
+            [overview]
+            swipe-fingers = 2
+        "#,
+    );
+    let overview = config.overview.unwrap();
+    assert_eq!(overview.swipe, None);
+    assert_eq!(overview.swipe_fingers, None);
+}
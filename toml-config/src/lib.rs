@@ -14,8 +14,9 @@ use {
     crate::{
         config::{
             Action, ClientRule, Config, ConfigConnector, ConfigDrmDevice, ConfigKeymap,
-            ConnectorMatch, DrmDeviceMatch, Exec, Input, InputMatch, Output, OutputMatch,
-            SimpleCommand, Status, Theme, WindowRule, parse_config,
+            ConfigTablet, ConfigTabletPad, ConnectorMatch, DrmDeviceMatch, Exec, Input, InputMatch,
+            Output, OutputMatch, PadAxisActions, SimpleCommand, Status, Theme, WindowRule,
+            parse_config,
         },
         rules::{MatcherTemp, RuleMapper},
         shortcuts::ModeState,
@@ -28,17 +29,21 @@ use {
         exec::{Command, set_env, unset_env},
         get_workspace,
         input::{
-            FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent, capability::CAP_SWITCH,
-            get_seat, input_devices, on_input_device_removed, on_new_input_device,
-            set_libei_socket_enabled,
+            FocusFollowsMouseMode, HotCorner, InputDevice, Seat, SwitchEvent, TabletPadElement,
+            TabletPadEvent,
+            capability::{CAP_SWITCH, CAP_TABLET_PAD, CAP_TABLET_TOOL},
+            create_hot_corner, get_seat, input_devices, on_input_device_removed,
+            on_new_input_device, set_libei_socket_enabled,
         },
         is_reload,
         keyboard::Keymap,
         logging::set_log_level,
-        on_devices_enumerated, on_idle, on_unload, quit, reload, set_color_management_enabled,
-        set_default_workspace_capture, set_explicit_sync_enabled, set_float_above_fullscreen,
-        set_idle, set_idle_grace_period, set_middle_click_paste_enabled, set_show_bar,
-        set_show_float_pin_icon, set_ui_drag_enabled, set_ui_drag_threshold,
+        on_devices_enumerated, on_idle, on_unload,
+        portal::{set_accent_color, set_color_scheme, set_contrast, set_reduced_motion},
+        quit, reload, set_color_management_enabled, set_default_workspace_capture,
+        set_explicit_sync_enabled, set_float_above_fullscreen, set_float_placement,
+        set_float_snap_threshold, set_idle, set_idle_grace_period, set_middle_click_paste_enabled,
+        set_show_bar, set_show_float_pin_icon, set_ui_drag_enabled, set_ui_drag_threshold,
         status::{set_i3bar_separator, set_status, set_status_command, unset_status_command},
         switch_to_vt,
         theme::{reset_colors, reset_font, reset_sizes, set_font},
@@ -47,16 +52,17 @@ use {
             ColorSpace, Connector, DrmDevice, Eotf, connectors, drm_devices,
             on_connector_connected, on_connector_disconnected, on_graphics_initialized,
             on_new_connector, on_new_drm_device, set_direct_scanout_enabled, set_gfx_api,
-            set_tearing_mode, set_vrr_cursor_hz, set_vrr_mode,
+            set_magnifier_in_screencasts, set_tearing_mode, set_vrr_cursor_hz, set_vrr_mode,
         },
         window::Window,
-        workspace::set_workspace_display_order,
+        workspace::{set_default_workspace_layout, set_workspace_display_order},
         xwayland::set_x_scaling_mode,
     },
     run_on_drop::on_drop,
     std::{
         cell::{Cell, RefCell},
         io::ErrorKind,
+        mem,
         path::PathBuf,
         rc::Rc,
         time::Duration,
@@ -185,6 +191,9 @@ impl Action {
                     window_or_seat!(s, s.set_float_pinned(pinned))
                 }
                 SimpleCommand::ToggleFloatPinned => window_or_seat!(s, s.toggle_float_pinned()),
+                SimpleCommand::SetFloatSticky(sticky) => window_or_seat!(s, s.set_sticky(sticky)),
+                SimpleCommand::ToggleFloatSticky => window_or_seat!(s, s.toggle_sticky()),
+                SimpleCommand::SnapFloat(snap) => window_or_seat!(s, s.snap_float(snap)),
                 SimpleCommand::KillClient => client_action!(c, c.kill()),
                 SimpleCommand::ShowBar(show) => b.new(move || set_show_bar(show)),
                 SimpleCommand::ToggleBar => b.new(toggle_show_bar),
@@ -212,6 +221,49 @@ impl Action {
                     let state = state.clone();
                     b.new(move || state.pop_mode(pop))
                 }
+                SimpleCommand::MouseKeysMotion(directions) => b.new(move || {
+                    for &direction in directions {
+                        s.mouse_keys_start_motion(direction);
+                    }
+                    s.latch(move || {
+                        for &direction in directions {
+                            s.mouse_keys_stop_motion(direction);
+                        }
+                    });
+                }),
+                SimpleCommand::MouseKeysSelectButton(button) => {
+                    b.new(move || s.mouse_keys_select_button(button))
+                }
+                SimpleCommand::MouseKeysClick(double) => b.new(move || s.mouse_keys_click(double)),
+                SimpleCommand::MouseKeysPress(true) => b.new(move || s.mouse_keys_press()),
+                SimpleCommand::MouseKeysPress(false) => b.new(move || s.mouse_keys_release()),
+                SimpleCommand::ZoomIn => b.new(move || s.zoom_in()),
+                SimpleCommand::ZoomOut => b.new(move || s.zoom_out()),
+                SimpleCommand::ZoomReset => b.new(move || s.zoom_reset()),
+                SimpleCommand::MasterCount(delta) => b.new(move || {
+                    let ws = s.get_workspace();
+                    if ws.exists() {
+                        let count = ws.master_count().saturating_add_signed(delta);
+                        ws.set_master_count(count);
+                    }
+                }),
+                SimpleCommand::MasterRatio(delta) => b.new(move || {
+                    let ws = s.get_workspace();
+                    if ws.exists() {
+                        ws.set_master_ratio(ws.master_ratio() + delta);
+                    }
+                }),
+                SimpleCommand::CycleColumnWidth => b.new(move || s.cycle_column_width()),
+                SimpleCommand::ConsumeIntoColumn => b.new(move || s.consume_into_column()),
+                SimpleCommand::ExpelFromColumn => b.new(move || s.expel_from_column()),
+                SimpleCommand::Swap(dir) => b.new(move || s.swap(dir)),
+                SimpleCommand::SwapWithMark => b.new(move || s.swap_with_mark(None)),
+                SimpleCommand::MoveToMark => b.new(move || s.move_to_mark(None)),
+                SimpleCommand::ToggleOverview => b.new(move || s.toggle_overview()),
+                SimpleCommand::ReleaseInputCapture => b.new(move || s.release_input_capture()),
+                SimpleCommand::WindowSwitcher(timeline) => {
+                    b.new(move || s.window_switcher(timeline))
+                }
             },
             Action::Multi { actions } => {
                 let actions: Vec<_> = actions.into_iter().map(|a| a.into_fn(state)).collect();
@@ -376,6 +428,8 @@ impl Action {
                 let persistent = state.persistent.clone();
                 b.new(move || persistent.seat.jump_to_mark(Some(m)))
             }
+            Action::SwapWithMark(m) => b.new(move || s.swap_with_mark(Some(m))),
+            Action::MoveToMark(m) => b.new(move || s.move_to_mark(Some(m))),
             Action::CopyMark(s, d) => {
                 let persistent = state.persistent.clone();
                 b.new(move || persistent.seat.copy_mark(s, d))
@@ -392,6 +446,19 @@ impl Action {
                     state.set_mode(new, latch);
                 })
             }
+            Action::SetLayout { layout } => b.new(move || {
+                let ws = s.get_workspace();
+                if ws.exists() {
+                    ws.set_layout(layout);
+                }
+            }),
+            Action::RenameWorkspace { name } => b.new(move || {
+                let ws = s.get_workspace();
+                if ws.exists() {
+                    ws.rename(&name);
+                }
+            }),
+            Action::SetColorScheme { scheme } => b.new(move || set_color_scheme(scheme)),
         }
     }
 }
@@ -637,6 +704,120 @@ impl Input {
     }
 }
 
+impl ConfigTablet {
+    fn apply(&self, c: InputDevice) {
+        if let Some(a) = self.output_area {
+            c.set_tablet_output_area(a.x, a.y, a.width, a.height);
+        }
+        if let Some([x1, y1, x2, y2]) = self.pressure_curve {
+            c.set_tablet_pressure_curve(x1, y1, x2, y2);
+        }
+        if let Some(v) = self.left_handed {
+            c.set_left_handed(v);
+        }
+        for &(button, mouse_button) in &self.stylus_buttons {
+            c.set_tablet_tool_button_mapping(button, Some(mouse_button));
+        }
+    }
+}
+
+struct PadAxisFns {
+    increase: Option<Rc<dyn Fn()>>,
+    decrease: Option<Rc<dyn Fn()>>,
+    step: f64,
+}
+
+impl PadAxisFns {
+    fn new(actions: PadAxisActions, default_step: f64, state: &Rc<State>) -> Rc<Self> {
+        Rc::new(Self {
+            increase: actions.increase.map(|a| a.into_rc_fn(state)),
+            decrease: actions.decrease.map(|a| a.into_rc_fn(state)),
+            step: actions.step.unwrap_or(default_step),
+        })
+    }
+
+    fn handle(&self, acc: &Cell<f64>, delta: f64) {
+        let mut v = acc.get() + delta;
+        while v >= self.step {
+            v -= self.step;
+            if let Some(f) = &self.increase {
+                f();
+            }
+        }
+        while v <= -self.step {
+            v += self.step;
+            if let Some(f) = &self.decrease {
+                f();
+            }
+        }
+        acc.set(v);
+    }
+}
+
+struct TabletPadFns {
+    match_: InputMatch,
+    elements: Vec<(TabletPadElement, TabletPadFn)>,
+}
+
+enum TabletPadFn {
+    Button(Rc<dyn Fn()>),
+    Axis(Rc<PadAxisFns>),
+}
+
+impl TabletPadFns {
+    fn new(pad: ConfigTabletPad, state: &Rc<State>) -> Self {
+        let mut elements = vec![];
+        for (idx, action) in pad.buttons {
+            let f = TabletPadFn::Button(action.into_rc_fn(state));
+            elements.push((TabletPadElement::Button(idx), f));
+        }
+        for (idx, actions) in pad.rings {
+            let f = TabletPadFn::Axis(PadAxisFns::new(actions, 15.0, state));
+            elements.push((TabletPadElement::Ring(idx), f));
+        }
+        for (idx, actions) in pad.strips {
+            let f = TabletPadFn::Axis(PadAxisFns::new(actions, 0.1, state));
+            elements.push((TabletPadElement::Strip(idx), f));
+        }
+        for (idx, actions) in pad.dials {
+            let f = TabletPadFn::Axis(PadAxisFns::new(actions, 1.0, state));
+            elements.push((TabletPadElement::Dial(idx), f));
+        }
+        Self {
+            match_: pad.match_,
+            elements,
+        }
+    }
+
+    fn bind(&self, dev: InputDevice) {
+        for (element, f) in &self.elements {
+            match f {
+                TabletPadFn::Button(f) => {
+                    let f = f.clone();
+                    dev.bind_tablet_pad(*element, move |ev| {
+                        if let TabletPadEvent::Button { pressed: true } = ev {
+                            f();
+                        }
+                    });
+                }
+                TabletPadFn::Axis(axis) => {
+                    let axis = axis.clone();
+                    let acc = Cell::new(0.0);
+                    dev.bind_tablet_pad(*element, move |ev| {
+                        let delta = match ev {
+                            TabletPadEvent::Button { .. } => return,
+                            TabletPadEvent::Ring { degrees } => degrees,
+                            TabletPadEvent::Strip { delta } => delta,
+                            TabletPadEvent::Dial { value120 } => value120 as f64 / 120.0,
+                        };
+                        axis.handle(&acc, delta);
+                    });
+                }
+            }
+        }
+    }
+}
+
 impl OutputMatch {
     fn matches(&self, c: Connector, state: &State) -> bool {
         if !c.connected() {
@@ -797,6 +978,8 @@ struct State {
     action_depth_max: u64,
     action_depth: Cell<u64>,
 
+    key_sequence_timeout: Duration,
+
     client: Cell<Option<Client>>,
 
     window: Cell<Option<Option<Window>>>,
@@ -812,6 +995,8 @@ impl Drop for State {
 
 type SwitchActions = Vec<(InputMatch, AHashMap<SwitchEvent, Box<dyn Fn()>>)>;
 
+type TabletPadActions = Vec<TabletPadFns>;
+
 impl State {
     fn get_keymap(&self, map: &ConfigKeymap) -> Option<Keymap> {
         let map = match map {
@@ -915,6 +1100,28 @@ impl State {
         });
     }
 
+    fn handle_tablet_device(
+        self: &Rc<Self>,
+        dev: InputDevice,
+        tablets: &[ConfigTablet],
+        pads: &TabletPadActions,
+    ) {
+        if dev.has_capability(CAP_TABLET_TOOL) {
+            for tablet in tablets {
+                if tablet.match_.matches(dev, self) {
+                    tablet.apply(dev);
+                }
+            }
+        }
+        if dev.has_capability(CAP_TABLET_PAD) {
+            for pad in pads {
+                if pad.match_.matches(dev, self) {
+                    pad.bind(dev);
+                }
+            }
+        }
+    }
+
     fn add_io_output(&self, c: Connector) {
         let mappings: Vec<_> = self
             .io_maps
@@ -1008,6 +1215,7 @@ struct PersistentState {
     window_rules: Cell<Vec<MatcherTemp<WindowRule>>>,
     mark_names: RefCell<AHashMap<String, u32>>,
     mode_state: ModeState,
+    hot_corners: RefCell<Vec<HotCorner>>,
 }
 
 fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
@@ -1088,6 +1296,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         io_outputs: Default::default(),
         action_depth_max: config.max_action_depth,
         action_depth: Cell::new(0),
+        key_sequence_timeout: config.key_sequence_timeout,
         client: Default::default(),
         window: Default::default(),
     });
@@ -1114,6 +1323,14 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         }
     }
     let switch_actions = Rc::new(switch_actions);
+    let tablets = Rc::new(mem::take(&mut config.tablets));
+    let tablet_pad_actions: Rc<TabletPadActions> = Rc::new(
+        config
+            .tablet_pads
+            .drain(..)
+            .map(|pad| TabletPadFns::new(pad, &state))
+            .collect(),
+    );
     match config.on_graphics_initialized {
         None => on_graphics_initialized(|| ()),
         Some(a) => on_graphics_initialized(a.into_fn(&state)),
@@ -1220,6 +1437,8 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     on_new_input_device({
         let state = state.clone();
         let switch_actions = switch_actions.clone();
+        let tablets = tablets.clone();
+        let tablet_pad_actions = tablet_pad_actions.clone();
         move |c| {
             state.add_io_input(c);
             for input in &config.inputs {
@@ -1228,6 +1447,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
                 }
             }
             state.handle_switch_device(c, &switch_actions);
+            state.handle_tablet_device(c, &tablets, &tablet_pad_actions);
         }
     });
     on_input_device_removed({
@@ -1243,6 +1463,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         state.add_io_input(c);
         state.map_input_to_output(c);
         state.handle_switch_device(c, &switch_actions);
+        state.handle_tablet_device(c, &tablets, &tablet_pad_actions);
     }
     persistent
         .seat
@@ -1285,11 +1506,22 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     {
         set_color_management_enabled(enabled);
     }
-    if let Some(float) = config.float
-        && let Some(show) = float.show_pin_icon
-    {
-        set_show_float_pin_icon(show);
+    if let Some(float) = config.float {
+        if let Some(show) = float.show_pin_icon {
+            set_show_float_pin_icon(show);
+        }
+        if let Some(placement) = float.placement {
+            set_float_placement(placement);
+        }
+        if let Some(threshold) = float.snap_threshold {
+            set_float_snap_threshold(threshold);
+        }
     }
+    let portal_settings = config.portal.and_then(|p| p.settings).unwrap_or_default();
+    set_color_scheme(portal_settings.color_scheme.unwrap_or_default());
+    set_accent_color(portal_settings.accent_color);
+    set_contrast(portal_settings.contrast.unwrap_or_default());
+    set_reduced_motion(portal_settings.reduced_motion.unwrap_or_default());
     if let Some(key) = config.pointer_revert_key {
         persistent.seat.set_pointer_revert_key(key);
     }
@@ -1306,6 +1538,9 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         if let Some(v) = v.same_workspace {
             persistent.seat.focus_history_set_same_workspace(v);
         }
+        if let Some(v) = v.switcher_thumbnails {
+            persistent.seat.window_switcher_set_thumbnails(v);
+        }
     }
     if let Some(v) = config.middle_click_paste {
         set_middle_click_paste_enabled(v);
@@ -1313,6 +1548,71 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     if let Some(v) = config.workspace_display_order {
         set_workspace_display_order(v);
     }
+    if let Some(v) = config.mouse_keys {
+        persistent.seat.set_mouse_keys_acceleration(
+            v.initial_speed.unwrap_or(100.0),
+            v.max_speed.unwrap_or(1200.0),
+            Duration::from_millis(v.time_to_max_ms.unwrap_or(1000)),
+        );
+    }
+    if let Some(layout) = config.default_workspace_layout {
+        set_default_workspace_layout(layout);
+    }
+    for (name, layout) in &config.workspace_layouts {
+        get_workspace(name).set_layout(*layout);
+    }
+    for ws in &config.workspaces {
+        let workspace = get_workspace(&ws.name);
+        let outputs: Vec<_> = ws.outputs.iter().map(|o| o.as_str()).collect();
+        workspace.set_preferred_outputs(&outputs);
+        if let Some(layout) = ws.layout {
+            workspace.set_layout(layout);
+        }
+        if let Some(split) = ws.split {
+            workspace.set_default_split(split);
+        }
+        if let Some(gap) = ws.gap {
+            workspace.set_gap(gap);
+        }
+        workspace.set_persistent(ws.persistent);
+    }
+    for corner in persistent.hot_corners.borrow_mut().drain(..) {
+        corner.remove();
+    }
+    for hc in config.hot_corners {
+        let corner = create_hot_corner(hc.corner, hc.dwell, hc.pressure);
+        let action = hc.action.into_fn(&state);
+        let match_ = hc.match_;
+        let state = state.clone();
+        corner.on_trigger(move |_, connector| {
+            if let Some(match_) = &match_
+                && !match_.matches(connector, &state)
+            {
+                return;
+            }
+            action();
+        });
+        persistent.hot_corners.borrow_mut().push(corner);
+    }
+    if let Some(v) = config.magnifier {
+        if let Some(step) = v.step {
+            persistent.seat.set_zoom_step(step);
+        }
+        persistent
+            .seat
+            .set_zoom_scroll_modifiers(v.scroll_modifiers);
+        if let Some(show) = v.show_in_screencasts {
+            set_magnifier_in_screencasts(show);
+        }
+    }
+    if let Some(v) = config.overview {
+        if let Some(swipe) = v.swipe {
+            persistent.seat.set_overview_swipe_enabled(swipe);
+        }
+        if let Some(fingers) = v.swipe_fingers {
+            persistent.seat.set_overview_swipe_fingers(fingers);
+        }
+    }
 }
 
 fn create_command(exec: &Exec) -> Command {
@@ -1346,6 +1646,7 @@ pub fn configure() {
         window_rules: Default::default(),
         mark_names,
         mode_state: Default::default(),
+        hot_corners: Default::default(),
     });
     {
         let p = persistent.clone();
@@ -1353,6 +1654,7 @@ pub fn configure() {
             p.actions.borrow_mut().clear();
             p.client_rule_mapper.borrow_mut().take();
             p.mode_state.clear();
+            p.hot_corners.borrow_mut().clear();
         });
     }
     load_config(true, &persistent);
@@ -342,6 +342,12 @@ impl Rule for WindowRule {
         if let Some(tile_state) = self.initial_tile_state {
             matcher.set_initial_tile_state(tile_state);
         }
+        if let Some(swallow) = self.swallow {
+            matcher.set_swallow(swallow);
+        }
+        if let Some(swallowable) = self.swallowable {
+            matcher.set_swallowable(swallowable);
+        }
     }
 
     fn gen_matcher(m: Self::Matcher) -> Self::Criterion<'static> {
@@ -4,6 +4,7 @@ use {
         config::{Action, InputMode, Shortcut, SimpleCommand},
     },
     ahash::{AHashMap, AHashSet},
+    indexmap::IndexMap,
     jay_config::keyboard::{ModifiedKeySym, mods::Modifiers},
     std::{
         cell::{Cell, RefCell},
@@ -15,6 +16,7 @@ use {
 #[derive(Default)]
 pub struct ModeState {
     latched: Cell<bool>,
+    sequence_pending: Cell<bool>,
     stack: RefCell<Vec<Rc<ConvertedShortcuts>>>,
     slots: RefCell<AHashMap<String, Rc<ModeSlot>>>,
     diffs: RefCell<AHashMap<[*const ConvertedShortcuts; 2], Rc<Vec<ModeDiff>>>>,
@@ -23,6 +25,7 @@ pub struct ModeState {
 
 impl ModeState {
     pub fn clear(&self) {
+        self.sequence_pending.set(false);
         self.slots.borrow_mut().clear();
         self.stack.borrow_mut().clear();
         self.diffs.borrow_mut().clear();
@@ -80,6 +83,9 @@ impl State {
         modes: &AHashMap<String, InputMode>,
     ) {
         let state = &self.persistent.mode_state;
+        if state.sequence_pending.take() {
+            self.persistent.seat.end_key_sequence();
+        }
         let base = self.convert_shortcuts(shortcuts);
         let stack = &mut *state.stack.borrow_mut();
         stack.clear();
@@ -91,6 +97,7 @@ impl State {
 
     pub fn set_mode(&self, new: &Rc<ConvertedShortcuts>, latch: bool) {
         let state = &self.persistent.mode_state;
+        self.end_key_sequence();
         self.cancel_mode_latch();
         self.apply_shortcuts(new);
         let stack = &mut *state.stack.borrow_mut();
@@ -102,6 +109,7 @@ impl State {
 
     pub fn pop_mode(&self, pop: bool) {
         let state = &self.persistent.mode_state;
+        self.end_key_sequence();
         let stack = &mut *state.stack.borrow_mut();
         if stack.len() < 1 + pop as usize {
             log::error!("Mode stack is empty");
@@ -132,6 +140,39 @@ impl State {
         self.apply_shortcuts(new);
     }
 
+    fn begin_key_sequence(self: &Rc<Self>, prefix: &str, next: &Rc<ConvertedShortcuts>) {
+        let state = &self.persistent.mode_state;
+        self.cancel_mode_latch();
+        state.sequence_pending.set(true);
+        self.apply_shortcuts(next);
+        let slf = self.clone();
+        self.persistent
+            .seat
+            .begin_key_sequence(prefix, self.key_sequence_timeout, move || {
+                slf.key_sequence_cancelled();
+            });
+    }
+
+    fn key_sequence_cancelled(&self) {
+        let state = &self.persistent.mode_state;
+        if !state.sequence_pending.take() {
+            return;
+        }
+        let stack = &*state.stack.borrow();
+        if let Some(top) = stack.last() {
+            self.apply_shortcuts(top);
+        }
+    }
+
+    fn end_key_sequence(&self) {
+        let state = &self.persistent.mode_state;
+        if !state.sequence_pending.get() {
+            return;
+        }
+        self.key_sequence_cancelled();
+        self.persistent.seat.end_key_sequence();
+    }
+
     pub fn convert_modes(
         self: &Rc<Self>,
         base: &ConvertedShortcuts,
@@ -197,12 +238,71 @@ impl State {
         shortcuts: impl IntoIterator<Item = &'a Shortcut>,
         dst: &mut ConvertedShortcuts,
     ) {
+        let mut sequences = IndexMap::<_, Vec<_>>::new();
         for sc in shortcuts {
+            if sc.sequence.is_some() {
+                sequences.entry(sc.keysym).or_default().push(sc);
+                continue;
+            }
+            sequences.shift_remove(&sc.keysym);
             match self.convert_shortcut(sc.clone()) {
                 None => dst.remove(&sc.keysym),
                 Some(cs) => dst.insert(sc.keysym, cs),
             };
         }
+        for (keysym, scs) in sequences {
+            match self.convert_sequence(1, &scs) {
+                None => dst.remove(&keysym),
+                Some(cs) => dst.insert(keysym, cs),
+            };
+        }
+    }
+
+    fn convert_sequence(
+        self: &Rc<Self>,
+        depth: usize,
+        shortcuts: &[&Shortcut],
+    ) -> Option<ConvertedShortcut> {
+        let sequence = |sc: &Shortcut| sc.sequence.as_ref().unwrap().clone();
+        let mut children = IndexMap::<_, Vec<_>>::new();
+        for sc in shortcuts {
+            let seq = sequence(sc);
+            if seq.keysyms.len() == depth {
+                if shortcuts.len() > 1 {
+                    log::warn!(
+                        "Key sequence `{}` is a prefix of another key sequence",
+                        seq.keys.join(" "),
+                    );
+                }
+                let cs = self.convert_shortcut(Shortcut {
+                    sequence: None,
+                    ..(*sc).clone()
+                })?;
+                let slf = self.clone();
+                let f = cs.shortcut;
+                return Some(ConvertedShortcut {
+                    mask: cs.mask,
+                    shortcut: Rc::new(move || {
+                        slf.end_key_sequence();
+                        f();
+                    }),
+                });
+            }
+            children.entry(seq.keysyms[depth]).or_default().push(*sc);
+        }
+        let mut next = ConvertedShortcuts::new();
+        for (keysym, scs) in children {
+            if let Some(cs) = self.convert_sequence(depth + 1, &scs) {
+                next.insert(keysym, cs);
+            }
+        }
+        let next = Rc::new(next);
+        let prefix = sequence(shortcuts[0]).keys[..depth].join(" ");
+        let slf = self.clone();
+        Some(ConvertedShortcut {
+            mask: Modifiers(!0),
+            shortcut: Rc::new(move || slf.begin_key_sequence(&prefix, &next)),
+        })
     }
 
     fn convert_shortcut(self: &Rc<Self>, shortcut: Shortcut) -> Option<ConvertedShortcut> {